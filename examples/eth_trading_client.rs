@@ -1,5 +1,7 @@
 use eth_trading_mcp::config::Config;
-use eth_trading_mcp::{GetBalanceRequest, GetTokenPriceRequest, SwapTokensRequest};
+use eth_trading_mcp::{
+    GetBalanceRequest, GetBestSwapRequest, GetTokenPriceRequest, SwapTokensRequest,
+};
 use rmcp::ServiceExt;
 use rmcp::model::{CallToolRequestParam, ClientCapabilities, ClientInfo, Implementation};
 use rmcp::transport::SseClientTransport;
@@ -22,10 +24,12 @@ const USDC_ADDRESS: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
 /// 4. Calling the get_token_price tool
 /// 5. Calling the swap_tokens tool (V2)
 /// 6. Calling the swap_tokens tool (V3)
-/// 7. Comparing V2 vs V3 swap results
+/// 7. Comparing V2 vs V3 swap results with get_best_swap
 #[tokio::main]
 async fn main() {
-    let config = Config::from_yaml("config/default.yaml").await;
+    let config = Config::from_yaml("config/default.yaml")
+        .await
+        .expect("Failed to load config");
     let uri = format!("http://localhost:{}/trading/sse", config.server.port);
 
     let transport = SseClientTransport::start(uri.as_str())
@@ -73,6 +77,8 @@ async fn main() {
     let get_eth_balance_request = GetBalanceRequest {
         wallet_address: VITALIK_ADDRESS.to_string(),
         token_contract_address: None,
+        summarize: None,
+        rpc_url: None,
     };
 
     let arguments = serde_json::to_value(&get_eth_balance_request)
@@ -99,6 +105,8 @@ async fn main() {
     let get_usdt_balance_request = GetBalanceRequest {
         wallet_address: VITALIK_ADDRESS.to_string(),
         token_contract_address: Some(USDT_ADDRESS.to_string()),
+        summarize: None,
+        rpc_url: None,
     };
 
     let arguments = serde_json::to_value(&get_usdt_balance_request)
@@ -168,11 +176,13 @@ async fn main() {
     println!("=== Simulating token swap ===");
     let swap_tokens_request = SwapTokensRequest {
         from_token: USDT_ADDRESS.to_string(),
-        to_token: "ETH".to_string(),           // Use ETH symbol for WETH
-        amount: "100".to_string(),             // 100 USDT (within balance)
-        slippage_tolerance: "0.5".to_string(), // 0.5% slippage tolerance
+        to_token: "ETH".to_string(), // Use ETH symbol for WETH
+        amount: "100".to_string(),   // 100 USDT (within balance)
+        slippage_tolerance: Some("0.5".to_string()), // 0.5% slippage tolerance
         uniswap_version: Some("v2".to_string()),
         from_address: Some(VITALIK_ADDRESS.to_string()),
+        summarize: Some(true), // Include a one-line summary for display
+        ..Default::default()
     };
 
     let arguments = serde_json::to_value(&swap_tokens_request)
@@ -194,12 +204,13 @@ async fn main() {
     // 7. Simulate a V3 swap
     println!("=== Simulating Uniswap V3 swap ===");
     let swap_v3_request = SwapTokensRequest {
-        from_token: "USDC".to_string(),          // Use USDC symbol
-        to_token: "WETH".to_string(),            // Swap to WETH
-        amount: "1000".to_string(),              // 1000 USDC
-        slippage_tolerance: "0.5".to_string(),   // 0.5% slippage tolerance
-        uniswap_version: Some("v3".to_string()), // Use V3
+        from_token: "USDC".to_string(),              // Use USDC symbol
+        to_token: "WETH".to_string(),                // Swap to WETH
+        amount: "1000".to_string(),                  // 1000 USDC
+        slippage_tolerance: Some("0.5".to_string()), // 0.5% slippage tolerance
+        uniswap_version: Some("v3".to_string()),     // Use V3
         from_address: Some(VITALIK_ADDRESS.to_string()),
+        ..Default::default()
     };
 
     let arguments = serde_json::to_value(&swap_v3_request)
@@ -221,61 +232,33 @@ async fn main() {
         serde_json::to_string_pretty(&swap_v3_result).unwrap()
     );
 
-    // 8. Compare V2 vs V3 for the same swap
+    // 8. Compare V2 vs V3 for the same swap in one call
     println!("=== Comparing V2 vs V3 for USDC -> WETH swap ===");
-
-    // V2 swap
-    let swap_v2_compare = SwapTokensRequest {
+    let best_swap_request = GetBestSwapRequest {
         from_token: "USDC".to_string(),
         to_token: "WETH".to_string(),
         amount: "1000".to_string(),
         slippage_tolerance: "0.5".to_string(),
-        uniswap_version: Some("v2".to_string()),
         from_address: None, // No simulation address for faster response
     };
 
-    let arguments_v2 = serde_json::to_value(&swap_v2_compare)
-        .expect("failed to serialize swap_v2_compare")
+    let arguments = serde_json::to_value(&best_swap_request)
+        .expect("failed to serialize best_swap_request")
         .as_object()
         .cloned();
 
-    let swap_v2_result = client
+    let best_swap_result = client
         .call_tool(CallToolRequestParam {
-            name: "swap_tokens".into(),
-            arguments: arguments_v2,
-        })
-        .await
-        .expect("failed to call `swap_tokens` v2");
-
-    // V3 swap
-    let swap_v3_compare = SwapTokensRequest {
-        from_token: "USDC".to_string(),
-        to_token: "WETH".to_string(),
-        amount: "1000".to_string(),
-        slippage_tolerance: "0.5".to_string(),
-        uniswap_version: Some("v3".to_string()),
-        from_address: None,
-    };
-
-    let arguments_v3 = serde_json::to_value(&swap_v3_compare)
-        .expect("failed to serialize swap_v3_compare")
-        .as_object()
-        .cloned();
-
-    let swap_v3_result_compare = client
-        .call_tool(CallToolRequestParam {
-            name: "swap_tokens".into(),
-            arguments: arguments_v3,
+            name: "get_best_swap".into(),
+            arguments,
         })
         .await
-        .expect("failed to call `swap_tokens` v3");
+        .expect("failed to call `get_best_swap`");
 
-    println!("V2 Result:");
-    println!("{}", serde_json::to_string_pretty(&swap_v2_result).unwrap());
-    println!("\nV3 Result:");
+    println!("Best swap (chosen_version, with both quotes):");
     println!(
         "{}",
-        serde_json::to_string_pretty(&swap_v3_result_compare).unwrap()
+        serde_json::to_string_pretty(&best_swap_result).unwrap()
     );
 
     println!("\n=== All operations completed successfully ===");
@@ -22,7 +22,9 @@ async fn main() {
     let cfg = serde_json::to_value(&additional_parameters)
         .expect("failed to serialize AdditionalParameters");
 
-    let config = Config::from_yaml("config/default.yaml").await;
+    let config = Config::from_yaml("config/default.yaml")
+        .await
+        .expect("Failed to load config");
     let uri = format!("http://localhost:{}/trading/sse", config.server.port);
 
     let transport = SseClientTransport::start(uri.as_str())
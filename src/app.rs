@@ -1,37 +1,369 @@
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
-use axum::Router;
+use alloy::providers::{DynProvider, Provider};
+use axum::extract::State;
 use axum::http::StatusCode;
 use axum::routing::get;
+use axum::{Json, Router, middleware};
 use rmcp::transport::SseServer;
 use rmcp::transport::sse_server::SseServerConfig;
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+use rmcp::transport::streamable_http_server::{StreamableHttpServerConfig, StreamableHttpService};
 use tokio_util::sync::CancellationToken;
 
 use crate::config::Config;
+use crate::middleware::auth::{ApiKey, require_api_key};
+use crate::middleware::connection_limit::{ConnectionLimiter, limit_sse_connections};
+use crate::middleware::cors::cors_layer;
+use crate::middleware::drain::reject_streams_while_draining;
+use crate::middleware::rate_limit::{RateLimiter, rate_limit_by_ip};
 use crate::middleware::trace::http_trace_layer;
+use crate::repository::CircuitBreaker;
 use crate::service::EthereumTradingService;
+use crate::service::price_feed::CoinGeckoPriceFeed;
+use crate::shutdown::Drain;
+use crate::telemetry::{metrics_handler, prometheus_handle};
 
-pub fn build_app(cancellation_token: CancellationToken, config: Config) -> anyhow::Result<Router> {
+/// How long the readiness probe waits for the RPC to return the latest block
+const READINESS_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Config and RPC provider that new MCP sessions are built from
+///
+/// Clones share one slot, so `replace` (on SIGHUP) switches every transport and the readiness
+/// probe to the new settings at once. Sessions already open keep the service they started with.
+#[derive(Clone)]
+pub struct LiveConfig {
+    current: Arc<RwLock<Arc<LiveState>>>,
+}
+
+struct LiveState {
+    config: Config,
+    provider: DynProvider,
+    /// `server.api_key`, or `None` when it is unset or empty
+    api_key: Option<ApiKey>,
+    /// Price feed shared by every session, so its circuit breaker sees all failures
+    price_feed: Option<Arc<CoinGeckoPriceFeed>>,
+}
+
+impl LiveState {
+    fn new(config: Config, provider: DynProvider) -> Arc<Self> {
+        let api_key = config
+            .server
+            .api_key
+            .as_deref()
+            .filter(|key| !key.is_empty())
+            .map(ApiKey::new);
+        let price_feed = CoinGeckoPriceFeed::from_config(&config);
+        Arc::new(Self {
+            config,
+            provider,
+            api_key,
+            price_feed,
+        })
+    }
+}
+
+impl LiveConfig {
+    pub fn new(config: Config, provider: DynProvider) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(LiveState::new(config, provider))),
+        }
+    }
+
+    pub fn config(&self) -> Config {
+        self.current().config.clone()
+    }
+
+    pub fn provider(&self) -> DynProvider {
+        self.current().provider.clone()
+    }
+
+    /// Swaps in a reloaded config together with the provider for its RPC URL
+    pub fn replace(&self, config: Config, provider: DynProvider) {
+        *self.current.write().unwrap() = LiveState::new(config, provider);
+    }
+
+    /// API key the /trading endpoints currently require, if any
+    pub fn api_key(&self) -> Option<ApiKey> {
+        self.current().api_key.clone()
+    }
+
+    /// Service for a new MCP session, built from the current config
+    pub fn service(&self, breaker: &CircuitBreaker) -> EthereumTradingService {
+        let current = self.current();
+        EthereumTradingService::with_provider(
+            &current.config,
+            current.provider.clone(),
+            breaker.clone(),
+        )
+        .with_price_feed(current.price_feed.clone())
+    }
+
+    fn current(&self) -> Arc<LiveState> {
+        self.current.read().unwrap().clone()
+    }
+}
+
+/// Builds the HTTP app; every MCP session shares the live provider (and its WebSocket
+/// connection, if any) and the circuit breaker guarding it
+///
+/// The listener settings (`server.*`, except `api_key`) are read once here; see
+/// [`Config::restart_required_changes`]. Tool calls report to `drain`, which refuses new SSE
+/// streams and tool calls once shutdown has started.
+pub fn build_app(
+    cancellation_token: CancellationToken,
+    live: LiveConfig,
+    breaker: CircuitBreaker,
+    drain: Drain,
+) -> anyhow::Result<Router> {
+    let config = live.config();
     let addr = config.server_uri().parse()?;
+    let transport = config.server.transport;
+    let limiter = ConnectionLimiter::new(config.server.max_connections);
+    let rate_limit = config.server.rate_limit.clone();
+    let cors = cors_layer(&config.server.cors_allowed_origins)?;
+    prometheus_handle();
 
-    let sse_config = SseServerConfig {
-        bind: addr,
-        sse_path: "/sse".to_string(),
-        post_path: "/message".to_string(),
-        ct: cancellation_token,
-        sse_keep_alive: Some(Duration::from_secs(15)),
-    };
+    let mut trading_router = Router::new();
+
+    if transport.serves_sse() {
+        let sse_config = SseServerConfig {
+            bind: addr,
+            sse_path: "/sse".to_string(),
+            post_path: "/message".to_string(),
+            ct: cancellation_token,
+            sse_keep_alive: Some(Duration::from_secs(15)),
+        };
+
+        let (sse_server, sse_router) = SseServer::new(sse_config);
+        let live = live.clone();
+        let breaker = breaker.clone();
+        let drain = drain.clone();
+        sse_server.with_service(move || live.service(&breaker).with_drain(drain.clone()));
+        trading_router = trading_router.merge(sse_router);
+    }
+
+    if transport.serves_streamable_http() {
+        let live = live.clone();
+        let breaker = breaker.clone();
+        let drain = drain.clone();
+        let http_service = StreamableHttpService::new(
+            move || Ok(live.service(&breaker).with_drain(drain.clone())),
+            LocalSessionManager::default().into(),
+            StreamableHttpServerConfig::default(),
+        );
+        trading_router = trading_router.nest_service("/mcp", http_service);
+    }
 
-    let (sse_server, sse_router) = SseServer::new(sse_config);
+    let mut trading_router = trading_router
+        .layer(middleware::from_fn_with_state(
+            limiter.clone(),
+            limit_sse_connections,
+        ))
+        .layer(middleware::from_fn_with_state(
+            drain,
+            reject_streams_while_draining,
+        ));
 
-    let eth_service = move || EthereumTradingService::new(&config);
+    // Added last so unauthorized requests are rejected before taking a connection slot. Always
+    // installed, as a reload may set or rotate the key
+    if live.api_key().is_none() {
+        tracing::warn!("server.api_key is not set; /trading endpoints are unauthenticated");
+    }
+    trading_router = trading_router.layer(middleware::from_fn_with_state(
+        live.clone(),
+        require_api_key,
+    ));
 
-    sse_server.with_service(eth_service);
+    // Outermost, so guessing the API key is rate limited as well
+    if let Some(rate_limit) = rate_limit {
+        let per_second = rate_limit.requests_per_second;
+        let burst = rate_limit.burst.unwrap_or(per_second);
+        trading_router = trading_router.layer(middleware::from_fn_with_state(
+            RateLimiter::new(per_second, burst),
+            rate_limit_by_ip,
+        ));
+    }
+
+    // Preflight requests carry no API key, so CORS answers them before any other layer
+    if let Some(cors) = cors {
+        trading_router = trading_router.layer(cors);
+    }
 
     let app = Router::new()
-        .route("/health", get(|| async move { StatusCode::OK }))
-        .nest("/trading", sse_router)
+        .route("/health", get(health).with_state((limiter, breaker)))
+        .route("/health/ready", get(ready).with_state(live))
+        .route("/metrics", get(metrics_handler))
+        .nest("/trading", trading_router)
         .layer(http_trace_layer());
 
     Ok(app)
 }
+
+async fn health(
+    State((limiter, breaker)): State<(ConnectionLimiter, CircuitBreaker)>,
+) -> Json<serde_json::Value> {
+    Json(serde_json::json!({
+        "status": "ok",
+        "active_connections": limiter.active(),
+        "max_connections": limiter.max(),
+        "rpc_circuit": breaker.state().as_str(),
+        "rpc_consecutive_failures": breaker.consecutive_failures(),
+    }))
+}
+
+/// Readiness probe: 503 unless the RPC returns the latest block within `READINESS_TIMEOUT`
+async fn ready(State(live): State<LiveConfig>) -> (StatusCode, Json<serde_json::Value>) {
+    let provider = live.provider();
+    let error = match tokio::time::timeout(READINESS_TIMEOUT, provider.get_block_number()).await {
+        Ok(Ok(block_number)) => {
+            return (
+                StatusCode::OK,
+                Json(serde_json::json!({
+                    "status": "ready",
+                    "block_number": block_number,
+                })),
+            );
+        }
+        Ok(Err(e)) => e.to_string(),
+        Err(_) => format!(
+            "RPC did not respond within {}s",
+            READINESS_TIMEOUT.as_secs()
+        ),
+    };
+
+    tracing::warn!("Readiness check failed: {error}");
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(serde_json::json!({
+            "status": "unavailable",
+            "error": error,
+        })),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::connect_http_provider;
+
+    async fn test_config() -> Config {
+        Config::from_yaml("config/test.yaml").await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_ready_reports_unreachable_rpc() {
+        // Nothing listens on port 1, so the connection is refused immediately
+        let provider = connect_http_provider("http://127.0.0.1:1");
+        let live = LiveConfig::new(test_config().await, provider);
+
+        let (status, Json(body)) = ready(State(live)).await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "unavailable");
+        assert!(body["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_live_config_replace_is_seen_by_clones() {
+        let config = test_config().await;
+        let live = LiveConfig::new(config.clone(), connect_http_provider(&config.rpc.url));
+        let session_side = live.clone();
+
+        let mut reloaded = config;
+        reloaded.rpc.url = "http://127.0.0.1:1".to_string();
+        reloaded.trading.deadline_secs = 60;
+        live.replace(reloaded.clone(), connect_http_provider(&reloaded.rpc.url));
+
+        assert_eq!(session_side.config().rpc.url, "http://127.0.0.1:1");
+        assert_eq!(session_side.config().trading.deadline_secs, 60);
+
+        // The readiness probe follows the swapped provider
+        let (status, _) = ready(State(session_side)).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_cors_preflight_is_answered_for_allowed_origins() {
+        let mut config = test_config().await;
+        config.server.api_key = Some("secret-key".to_string());
+        config.server.cors_allowed_origins = vec!["https://agent.example.com".to_string()];
+        let live = LiveConfig::new(config, connect_http_provider("http://127.0.0.1:1"));
+        let breaker = CircuitBreaker::new("RPC", 5, Duration::from_secs(30));
+        let app = build_app(CancellationToken::new(), live, breaker, Drain::new()).unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/trading/sse", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let preflight = |origin: &'static str| {
+            reqwest::Client::new()
+                .request(reqwest::Method::OPTIONS, &url)
+                .header("Origin", origin)
+                .header("Access-Control-Request-Method", "GET")
+                .header("Access-Control-Request-Headers", "authorization")
+                .send()
+        };
+
+        // Answered without the API key, which browsers never send on preflight
+        let allowed = preflight("https://agent.example.com").await.unwrap();
+        assert_eq!(allowed.status(), StatusCode::OK);
+        assert_eq!(
+            allowed.headers()["access-control-allow-origin"],
+            "https://agent.example.com"
+        );
+
+        let other = preflight("https://evil.example.com").await.unwrap();
+        assert!(!other.headers().contains_key("access-control-allow-origin"));
+    }
+
+    #[tokio::test]
+    async fn test_reloaded_api_key_applies_to_new_requests() {
+        let mut config = test_config().await;
+        config.server.api_key = Some("old-key".to_string());
+        let live = LiveConfig::new(config.clone(), connect_http_provider("http://127.0.0.1:1"));
+        let breaker = CircuitBreaker::new("RPC", 5, Duration::from_secs(30));
+        let app = build_app(
+            CancellationToken::new(),
+            live.clone(),
+            breaker,
+            Drain::new(),
+        )
+        .unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/trading/sse", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let status = |key: &'static str| {
+            let request = reqwest::Client::new().get(&url).bearer_auth(key).send();
+            async move { request.await.unwrap().status() }
+        };
+
+        assert_eq!(status("old-key").await, StatusCode::OK);
+
+        config.server.api_key = Some("new-key".to_string());
+        live.replace(config, connect_http_provider("http://127.0.0.1:1"));
+
+        assert_eq!(status("old-key").await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status("new-key").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_new_streams_are_rejected_while_draining() {
+        let config = test_config().await;
+        let live = LiveConfig::new(config, connect_http_provider("http://127.0.0.1:1"));
+        let breaker = CircuitBreaker::new("RPC", 5, Duration::from_secs(30));
+        let drain = Drain::new();
+        let app = build_app(CancellationToken::new(), live, breaker, drain.clone()).unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/trading/sse", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        assert_eq!(drain.drain(Duration::ZERO).await, 0);
+        let response = reqwest::get(&url).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
@@ -1,55 +1,502 @@
-use std::{fs, path::Path};
+use std::collections::HashMap;
+use std::{fs, io, path::Path};
 
 use dotenv::dotenv;
 use envsubst::substitute;
 use serde::Deserialize;
+use thiserror::Error;
+
+use crate::repository::{CircuitBreaker, Network};
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
+    /// Network to connect to ("mainnet", "sepolia", "arbitrum", "base" or "optimism"). Defaults to mainnet.
+    #[serde(default)]
+    pub network: Network,
     pub server: ServerConfig,
     pub rpc: RpcConfig,
     pub wallet: WalletConfig,
+    #[serde(default)]
+    pub display: DisplayConfig,
+    /// Optional path to a token-list JSON file merged into the built-in token registry.
+    #[serde(default)]
+    pub token_list_path: Option<String>,
+    /// Decimals for tokens whose `decimals()` is missing or wrong, by symbol or address. Used
+    /// instead of calling the contract.
+    #[serde(default)]
+    pub decimals_override: HashMap<String, u8>,
+    #[serde(default)]
+    pub price_api: PriceApiConfig,
+    #[serde(default)]
+    pub trading: TradingConfig,
+    /// Safety margin added to gas estimates, in percent of the node's estimate. Defaults to 20.
+    #[serde(default = "Config::default_gas_buffer_percent")]
+    pub gas_buffer_percent: u32,
+    /// Where the ETH/USD price behind on-chain USD values comes from. Defaults to the Uniswap V2
+    /// USDC/WETH pair.
+    #[serde(default)]
+    pub eth_price_source: EthPriceSource,
+    #[serde(default)]
+    pub chainlink: ChainlinkConfig,
 }
 
 impl Config {
-    pub async fn from_yaml(path: impl AsRef<Path>) -> Self {
+    pub async fn from_yaml(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
         dotenv().ok();
 
-        let file_content =
-            fs::read_to_string(path).expect("failed to read config file from path: {path}");
+        let path = path.as_ref().display().to_string();
+        let file_content = fs::read_to_string(&path).map_err(|source| match source.kind() {
+            io::ErrorKind::NotFound => ConfigError::NotFound { path: path.clone() },
+            _ => ConfigError::Read {
+                path: path.clone(),
+                source,
+            },
+        })?;
 
         let env_vars: std::collections::HashMap<String, String> = std::env::vars()
             .filter(|(key, _)| key.starts_with("SERVER_") || key.starts_with("WALLET_"))
             .collect();
 
-        let interpolated = substitute(&file_content, &env_vars)
-            .expect("Failed to substitute environment variables in YAML");
+        let interpolated = substitute(&file_content, &env_vars).map_err(|source| {
+            ConfigError::EnvSubstitution {
+                path: path.clone(),
+                source,
+            }
+        })?;
+
+        Self::parse(&interpolated, &path)
+    }
+
+    /// Deserialize YAML, keeping the path of the field that failed for the error
+    fn parse(yaml: &str, path: &str) -> Result<Self, ConfigError> {
+        let deserializer = serde_yaml::Deserializer::from_str(yaml);
+        let config: Self = serde_path_to_error::deserialize(deserializer).map_err(|e| {
+            let field = e.path().to_string();
+            ConfigError::Parse {
+                path: path.to_string(),
+                field,
+                source: e.into_inner(),
+            }
+        })?;
+
+        // Checked here so a bad URL is rejected at startup and on reload instead of panicking
+        // when the provider connects
+        let allowed_urls = config.rpc.allowed_urls.iter().enumerate();
+        let urls = std::iter::once(("rpc.url".to_string(), &config.rpc.url)).chain(
+            allowed_urls.map(|(i, allowed)| (format!("rpc.allowed_urls[{i}].url"), &allowed.url)),
+        );
+        for (field, url) in urls {
+            validate_rpc_url(url).map_err(|reason| ConfigError::InvalidRpcUrl {
+                path: path.to_string(),
+                field,
+                reason,
+            })?;
+        }
 
-        let config: Config =
-            serde_yaml::from_str(&interpolated).expect("Failed to parse YAML configuration");
+        Ok(config)
+    }
 
-        config
+    pub(crate) fn default_gas_buffer_percent() -> u32 {
+        20
     }
 
     pub fn server_uri(&self) -> String {
         format!("{}:{}", self.server.host, self.server.port)
     }
+
+    /// Settings that differ from `previous` but only apply after a restart
+    ///
+    /// The listener, its middleware and the shared circuit breaker are built once at startup;
+    /// everything else is read when an MCP session starts, or per request for the API key, so a
+    /// reload picks it up.
+    pub fn restart_required_changes(&self, previous: &Config) -> Vec<&'static str> {
+        let (server, old) = (&self.server, &previous.server);
+        let rate_limit = |config: &ServerConfig| {
+            config
+                .rate_limit
+                .as_ref()
+                .map(|limit| (limit.requests_per_second, limit.burst))
+        };
+        let breaker = |config: &Config| {
+            let breaker = &config.rpc.circuit_breaker;
+            (breaker.failure_threshold, breaker.cooldown_secs)
+        };
+
+        [
+            ("server.host", server.host != old.host),
+            ("server.port", server.port != old.port),
+            ("server.transport", server.transport != old.transport),
+            (
+                "server.max_connections",
+                server.max_connections != old.max_connections,
+            ),
+            ("server.rate_limit", rate_limit(server) != rate_limit(old)),
+            (
+                "server.cors_allowed_origins",
+                server.cors_allowed_origins != old.cors_allowed_origins,
+            ),
+            ("rpc.circuit_breaker", breaker(self) != breaker(previous)),
+        ]
+        .into_iter()
+        .filter_map(|(name, changed)| changed.then_some(name))
+        .collect()
+    }
+}
+
+/// Checks that `url` is an absolute HTTP(S) or WebSocket URL the provider can connect to
+fn validate_rpc_url(url: &str) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| e.to_string())?;
+    match parsed.scheme() {
+        "http" | "https" | "ws" | "wss" if parsed.has_host() => Ok(()),
+        "http" | "https" | "ws" | "wss" => Err("missing host".to_string()),
+        scheme => Err(format!(
+            "unsupported scheme `{scheme}`, expected http, https, ws or wss"
+        )),
+    }
+}
+
+/// Why the configuration file could not be loaded
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Config file not found: {path}")]
+    NotFound { path: String },
+
+    #[error("Failed to read config file {path}: {source}")]
+    Read {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("Failed to substitute environment variables in {path}: {source}")]
+    EnvSubstitution {
+        path: String,
+        #[source]
+        source: envsubst::Error,
+    },
+
+    /// `field` is the dotted path of the offending field ("." when the document itself is
+    /// malformed)
+    #[error("Invalid config in {path} at `{field}`: {source}")]
+    Parse {
+        path: String,
+        field: String,
+        #[source]
+        source: serde_yaml::Error,
+    },
+
+    /// `field` is `rpc.url` or an `rpc.allowed_urls` entry. The URL itself is left out of the
+    /// message, as it often embeds an API key
+    #[error("Invalid config in {path} at `{field}`: {reason}")]
+    InvalidRpcUrl {
+        path: String,
+        field: String,
+        reason: String,
+    },
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
+    /// Maximum simultaneous SSE connections; further connections get 503. Unlimited when unset.
+    #[serde(default)]
+    pub max_connections: Option<usize>,
+    /// MCP transport(s) to serve. Defaults to SSE.
+    #[serde(default)]
+    pub transport: Transport,
+    /// Explain tool arguments with the wrong JSON shape field by field, with a corrected
+    /// example, instead of returning the raw deserialization error. Defaults to true.
+    #[serde(default = "ServerConfig::default_argument_hints")]
+    pub argument_hints: bool,
+    /// Bearer token required on the /trading endpoints. Unauthenticated when unset.
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Per-client-IP request rate on the /trading endpoints. Unlimited when unset.
+    #[serde(default)]
+    pub rate_limit: Option<RateLimitConfig>,
+    /// Origins browser clients may call the /trading endpoints from, or `["*"]` for any origin.
+    /// CORS is disabled when empty (the default).
+    #[serde(default)]
+    pub cors_allowed_origins: Vec<String>,
+    /// Seconds running tool calls may take to finish on shutdown before their sessions are
+    /// cancelled. Defaults to 30.
+    #[serde(default = "ServerConfig::default_shutdown_grace_secs")]
+    pub shutdown_grace_secs: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimitConfig {
+    /// Sustained requests per second allowed from one client IP
+    pub requests_per_second: u32,
+    /// Requests a client may send at once before the rate applies. Defaults to
+    /// `requests_per_second`.
+    #[serde(default)]
+    pub burst: Option<u32>,
+}
+
+impl ServerConfig {
+    pub(crate) fn default_argument_hints() -> bool {
+        true
+    }
+
+    fn default_shutdown_grace_secs() -> u64 {
+        30
+    }
+}
+
+/// MCP transports the server can expose
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Transport {
+    /// Legacy HTTP+SSE transport at `/trading/sse`
+    #[default]
+    Sse,
+    /// Streamable HTTP transport at `/trading/mcp`
+    Http,
+    /// Both HTTP transports side by side
+    Both,
+    /// A single session over stdin/stdout, for clients that launch the server as a subprocess
+    Stdio,
+}
+
+impl Transport {
+    pub fn serves_sse(self) -> bool {
+        matches!(self, Transport::Sse | Transport::Both)
+    }
+
+    pub fn serves_streamable_http(self) -> bool {
+        matches!(self, Transport::Http | Transport::Both)
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct RpcConfig {
     pub url: String,
+    /// Retries per fee tier when a V3 quote fails at the RPC level (reverts are never retried).
+    #[serde(default = "RpcConfig::default_v3_scan_retries")]
+    pub v3_scan_retries: u32,
+    /// Milliseconds before a read-only RPC call is abandoned with a timeout error.
+    #[serde(default = "RpcConfig::default_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Endpoints a request may select with `rpc_url` instead of `url`. Per-request overrides are
+    /// rejected when empty (the default).
+    #[serde(default)]
+    pub allowed_urls: Vec<AllowedRpcUrl>,
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive RPC failures (errors, rate limits, timeouts) that open the circuit.
+    #[serde(default = "CircuitBreakerConfig::default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// Seconds calls fail fast before a probe call tests the provider again.
+    #[serde(default = "CircuitBreakerConfig::default_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+impl CircuitBreakerConfig {
+    /// A breaker with these settings; clone it to share its state
+    pub fn breaker(&self) -> CircuitBreaker {
+        CircuitBreaker::new(
+            "RPC",
+            self.failure_threshold,
+            std::time::Duration::from_secs(self.cooldown_secs),
+        )
+    }
+
+    fn default_failure_threshold() -> u32 {
+        5
+    }
+
+    fn default_cooldown_secs() -> u64 {
+        30
+    }
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: Self::default_failure_threshold(),
+            cooldown_secs: Self::default_cooldown_secs(),
+        }
+    }
+}
+
+impl RpcConfig {
+    pub(crate) fn default_v3_scan_retries() -> u32 {
+        2
+    }
+
+    fn default_timeout_ms() -> u64 {
+        10_000
+    }
+}
+
+/// An RPC endpoint requests may be served from, with the network it belongs to
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AllowedRpcUrl {
+    pub url: String,
+    /// Network the endpoint serves, which selects the contract addresses used with it
+    pub network: Network,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct WalletConfig {
     pub private_key: String,
+    /// Allow tools to broadcast signed transactions. Defaults to false (simulation only).
+    #[serde(default)]
+    pub allow_execution: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DisplayConfig {
+    /// Maximum fraction digits for prices, rates and USD values. Full precision when unset.
+    #[serde(default)]
+    pub precision: Option<u32>,
+    /// Maximum fraction digits for formatted balances (the raw balance is always exact). Full
+    /// precision when unset.
+    #[serde(default)]
+    pub balance_decimals: Option<u8>,
+    /// How numeric response fields are serialized. Defaults to strings.
+    #[serde(default)]
+    pub numbers: NumberFormat,
+}
+
+/// JSON representation of numeric response fields
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NumberFormat {
+    /// Always strings, preserving full precision
+    #[default]
+    String,
+    /// JSON numbers for values an f64 holds exactly, strings for the rest
+    Number,
+}
+
+/// Source of the ETH/USD price that on-chain USD values are derived from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EthPriceSource {
+    /// Reserves of the Uniswap V2 USDC/WETH pair
+    #[default]
+    UniswapV2,
+    /// Spot price of the Uniswap V3 USDC/WETH 0.05% pool
+    UniswapV3,
+    /// The network's Chainlink ETH/USD feed
+    Chainlink,
+}
+
+/// Chainlink price feeds, used by get_token_price's "chainlink" source
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChainlinkConfig {
+    /// USD feeds by token symbol or address, e.g. `LINK: "0x2c1d072e956affc0d435cb7ac38ef18d24d9127c"`.
+    /// ETH and WETH use the network's ETH/USD feed without an entry.
+    #[serde(default)]
+    pub feeds: HashMap<String, String>,
+    /// Seconds after which a feed's latest round counts as stale. Defaults to 90000: one day,
+    /// the longest common heartbeat, plus an hour of margin.
+    #[serde(default = "ChainlinkConfig::default_max_age_secs")]
+    pub max_age_secs: u64,
+}
+
+impl ChainlinkConfig {
+    pub(crate) fn default_max_age_secs() -> u64 {
+        90_000
+    }
+}
+
+impl Default for ChainlinkConfig {
+    fn default() -> Self {
+        Self {
+            feeds: HashMap::new(),
+            max_age_secs: Self::default_max_age_secs(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PriceApiConfig {
+    /// Use CoinGecko for USD prices. Defaults to false (on-chain pricing only).
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "PriceApiConfig::default_base_url")]
+    pub base_url: String,
+    /// Request timeout in seconds.
+    #[serde(default = "PriceApiConfig::default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Consecutive failures before the API is skipped for `cooldown_secs`.
+    #[serde(default = "PriceApiConfig::default_failure_threshold")]
+    pub failure_threshold: u32,
+    #[serde(default = "PriceApiConfig::default_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+impl PriceApiConfig {
+    fn default_base_url() -> String {
+        "https://api.coingecko.com/api/v3".to_string()
+    }
+
+    fn default_timeout_secs() -> u64 {
+        5
+    }
+
+    fn default_failure_threshold() -> u32 {
+        3
+    }
+
+    fn default_cooldown_secs() -> u64 {
+        300
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TradingConfig {
+    /// Slippage tolerance in percent used when a swap request omits `slippage_tolerance`.
+    /// Defaults to "0.5".
+    #[serde(default = "TradingConfig::default_slippage")]
+    pub default_slippage: String,
+    /// Seconds from submission until a swap transaction expires. Defaults to 3600.
+    #[serde(default = "TradingConfig::default_deadline_secs")]
+    pub deadline_secs: u64,
+    /// Minimum USD liquidity of a Uniswap V2 pair for get_token_price to trust its price, counted
+    /// as twice its WETH reserve at the ETH/USD price. Unset by default (no minimum).
+    #[serde(default)]
+    pub min_liquidity_usd: Option<u64>,
+}
+
+impl TradingConfig {
+    fn default_slippage() -> String {
+        "0.5".to_string()
+    }
+
+    fn default_deadline_secs() -> u64 {
+        3600
+    }
+}
+
+impl Default for TradingConfig {
+    fn default() -> Self {
+        Self {
+            default_slippage: Self::default_slippage(),
+            deadline_secs: Self::default_deadline_secs(),
+            min_liquidity_usd: None,
+        }
+    }
+}
+
+impl Default for PriceApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base_url: Self::default_base_url(),
+            timeout_secs: Self::default_timeout_secs(),
+            failure_threshold: Self::default_failure_threshold(),
+            cooldown_secs: Self::default_cooldown_secs(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -58,17 +505,52 @@ mod tests {
 
     #[tokio::test]
     async fn test_load_config_from_yaml() {
-        let config = Config::from_yaml("config/test.yaml").await;
+        let config = Config::from_yaml("config/test.yaml").await.unwrap();
 
         // Verify server config
         assert_eq!(config.server.host, "0.0.0.0");
         assert_eq!(config.server.port, 8000);
+        assert_eq!(config.server.max_connections, None);
+        assert_eq!(config.server.transport, Transport::Sse);
+        assert!(config.server.argument_hints);
+        assert_eq!(config.server.api_key, None);
+        assert!(config.server.rate_limit.is_none());
 
         // Verify RPC config
         assert_eq!(config.rpc.url, "https://eth.llamarpc.com");
+        assert_eq!(config.rpc.v3_scan_retries, 2);
+        assert_eq!(config.rpc.timeout_ms, 10_000);
+        assert!(config.rpc.allowed_urls.is_empty());
+        assert_eq!(config.rpc.circuit_breaker.failure_threshold, 5);
+        assert_eq!(config.rpc.circuit_breaker.cooldown_secs, 30);
+        assert_eq!(config.gas_buffer_percent, 20);
+
+        // Network defaults to mainnet when not set
+        assert_eq!(config.network, Network::Mainnet);
 
         // Verify wallet config (should be empty in test.yaml)
         assert_eq!(config.wallet.private_key, "");
+        assert!(!config.wallet.allow_execution);
+
+        // Display precision defaults to full precision
+        assert_eq!(config.display.precision, None);
+        assert_eq!(config.display.balance_decimals, None);
+        assert_eq!(config.display.numbers, NumberFormat::String);
+
+        // No token list unless configured
+        assert_eq!(config.token_list_path, None);
+
+        // External price API is off by default
+        assert!(!config.price_api.enabled);
+        assert_eq!(config.price_api.failure_threshold, 3);
+
+        // Trading defaults apply when the section is absent
+        assert_eq!(config.trading.default_slippage, "0.5");
+        assert_eq!(config.trading.deadline_secs, 3600);
+        assert_eq!(config.trading.min_liquidity_usd, None);
+        assert_eq!(config.eth_price_source, EthPriceSource::UniswapV2);
+        assert!(config.chainlink.feeds.is_empty());
+        assert_eq!(config.chainlink.max_age_secs, 90_000);
     }
 
     #[tokio::test]
@@ -80,7 +562,7 @@ mod tests {
             std::env::set_var("SERVER_PORT", "9000");
         }
 
-        let config = Config::from_yaml("config/test.yaml").await;
+        let config = Config::from_yaml("config/test.yaml").await.unwrap();
 
         // Verify that config was loaded (env vars in YAML would be substituted)
         assert!(!config.server.host.is_empty());
@@ -96,7 +578,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_config_fields_are_accessible() {
-        let config = Config::from_yaml("config/test.yaml").await;
+        let config = Config::from_yaml("config/test.yaml").await.unwrap();
 
         // Verify all fields can be accessed
         let _host: &str = &config.server.host;
@@ -110,7 +592,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_config_debug_format() {
-        let config = Config::from_yaml("config/test.yaml").await;
+        let config = Config::from_yaml("config/test.yaml").await.unwrap();
 
         // Verify Debug trait works
         let debug_output = format!("{:?}", config);
@@ -119,4 +601,144 @@ mod tests {
         assert!(debug_output.contains("rpc"));
         assert!(debug_output.contains("wallet"));
     }
+
+    #[tokio::test]
+    async fn test_from_yaml_reports_missing_file() {
+        let err = Config::from_yaml("config/does-not-exist.yaml")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ConfigError::NotFound { .. }));
+        assert_eq!(
+            err.to_string(),
+            "Config file not found: config/does-not-exist.yaml"
+        );
+    }
+
+    #[test]
+    fn test_parse_reports_offending_field() {
+        let yaml = "server:\n  host: 0.0.0.0\n  port: not-a-port\nrpc:\n  url: http://localhost:8545\nwallet:\n  private_key: \"\"\n";
+
+        let err = Config::parse(yaml, "test.yaml").unwrap_err();
+
+        match &err {
+            ConfigError::Parse { field, .. } => assert_eq!(field, "server.port"),
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+        assert!(
+            err.to_string()
+                .starts_with("Invalid config in test.yaml at `server.port`:")
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_rpc_url() {
+        for (url, reason) in [
+            ("not a url", "relative URL without a base"),
+            ("ftp://localhost:8545", "unsupported scheme `ftp`"),
+        ] {
+            let yaml = format!(
+                "server:\n  host: 0.0.0.0\n  port: 8000\nrpc:\n  url: {url}\nwallet:\n  private_key: \"\"\n"
+            );
+
+            let err = Config::parse(&yaml, "test.yaml").unwrap_err();
+
+            assert!(matches!(err, ConfigError::InvalidRpcUrl { .. }));
+            assert!(
+                err.to_string()
+                    .starts_with("Invalid config in test.yaml at `rpc.url`:")
+            );
+            assert!(err.to_string().contains(reason), "{err}");
+        }
+
+        let yaml = "server:\n  host: 0.0.0.0\n  port: 8000\nrpc:\n  url: wss://localhost:8546\nwallet:\n  private_key: \"\"\n";
+        assert!(Config::parse(yaml, "test.yaml").is_ok());
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_allowed_rpc_url() {
+        let yaml = "server:\n  host: 0.0.0.0\n  port: 8000\nrpc:\n  url: http://localhost:8545\n  allowed_urls:\n    - url: https://mainnet.base.org\n      network: base\n    - url: mainnet.optimism.io\n      network: optimism\nwallet:\n  private_key: \"\"\n";
+
+        let err = Config::parse(yaml, "test.yaml").unwrap_err();
+
+        match &err {
+            ConfigError::InvalidRpcUrl { field, .. } => {
+                assert_eq!(field, "rpc.allowed_urls[1].url")
+            }
+            other => panic!("expected an invalid RPC URL error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_allowed_rpc_urls() {
+        let yaml = "server:\n  host: 0.0.0.0\n  port: 8000\nrpc:\n  url: http://localhost:8545\n  allowed_urls:\n    - url: https://mainnet.base.org\n      network: base\nwallet:\n  private_key: \"\"\n";
+
+        let config = Config::parse(yaml, "test.yaml").unwrap();
+
+        assert_eq!(
+            config.rpc.allowed_urls,
+            vec![AllowedRpcUrl {
+                url: "https://mainnet.base.org".to_string(),
+                network: Network::Base,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_decimals_override() {
+        let yaml = "decimals_override:\n  USDC: 6\n  \"0x0000000000000000000000000000000000000001\": 9\nserver:\n  host: 0.0.0.0\n  port: 8000\nrpc:\n  url: http://localhost:8545\nwallet:\n  private_key: \"\"\n";
+
+        let config = Config::parse(yaml, "test.yaml").unwrap();
+
+        assert_eq!(config.decimals_override.len(), 2);
+        assert_eq!(config.decimals_override["USDC"], 6);
+        assert_eq!(
+            config.decimals_override["0x0000000000000000000000000000000000000001"],
+            9
+        );
+    }
+
+    #[test]
+    fn test_parse_reports_missing_section() {
+        let err =
+            Config::parse("server:\n  host: 0.0.0.0\n  port: 8000\n", "test.yaml").unwrap_err();
+
+        assert!(matches!(err, ConfigError::Parse { .. }));
+        assert!(err.to_string().contains("missing field `rpc`"));
+    }
+
+    #[test]
+    fn test_transport_parses_from_yaml() {
+        let transport: Transport = serde_yaml::from_str("both").unwrap();
+        assert!(transport.serves_sse() && transport.serves_streamable_http());
+
+        let transport: Transport = serde_yaml::from_str("http").unwrap();
+        assert!(!transport.serves_sse() && transport.serves_streamable_http());
+
+        let transport: Transport = serde_yaml::from_str("stdio").unwrap();
+        assert!(!transport.serves_sse() && !transport.serves_streamable_http());
+
+        assert!(serde_yaml::from_str::<Transport>("websocket").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_restart_required_changes_lists_listener_settings() {
+        let previous = Config::from_yaml("config/test.yaml").await.unwrap();
+        assert!(previous.restart_required_changes(&previous).is_empty());
+
+        // RPC endpoint, token list and trading settings apply on reload
+        let mut config = previous.clone();
+        config.rpc.url = "https://rpc.example.com".to_string();
+        config.token_list_path = Some("tokens.json".to_string());
+        config.trading.deadline_secs += 60;
+        assert!(config.restart_required_changes(&previous).is_empty());
+
+        config.server.port += 1;
+        config.server.api_key = Some("new-key".to_string());
+        config.rpc.circuit_breaker.failure_threshold += 1;
+        assert_eq!(
+            config.restart_required_changes(&previous),
+            vec!["server.port", "rpc.circuit_breaker"]
+        );
+    }
 }
@@ -3,11 +3,13 @@ pub mod config;
 pub mod middleware;
 pub mod repository;
 pub mod service;
+pub mod shutdown;
+pub mod telemetry;
 
 pub use app::build_app;
 
 // Re-export commonly used types for tests
 pub use service::{
-    EthereumTradingService, GetBalanceRequest, GetBalanceResponse, GetTokenPriceRequest,
-    GetTokenPriceResponse, SwapTokensRequest, SwapTokensResponse,
+    EthereumTradingService, GetBalanceRequest, GetBalanceResponse, GetBestSwapRequest,
+    GetTokenPriceRequest, GetTokenPriceResponse, SwapTokensRequest, SwapTokensResponse,
 };
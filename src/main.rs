@@ -3,20 +3,58 @@ pub mod config;
 pub mod middleware;
 pub mod repository;
 pub mod service;
+pub mod shutdown;
+pub mod telemetry;
 
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use alloy::providers::DynProvider;
+use rmcp::ServiceExt;
 use tokio::signal;
 use tokio_util::sync::CancellationToken;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 
-use crate::app::build_app;
+use crate::app::{LiveConfig, build_app};
+use crate::config::{Config, Transport};
+use crate::repository::{CircuitBreaker, connect_provider};
+use crate::service::price_feed::CoinGeckoPriceFeed;
+use crate::service::{EthereumTradingService, ServiceError};
+use crate::shutdown::Drain;
+
+/// Read at startup and again on every SIGHUP
+const CONFIG_PATH: &str = "config/default.yaml";
 
 #[tokio::main]
 async fn main() {
+    let config = match Config::from_yaml(CONFIG_PATH).await {
+        Ok(config) => config,
+        Err(e) => {
+            // Logging is not set up until the transport is known, so report this directly
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    };
+    let transport = if std::env::args().any(|arg| arg == "--stdio") {
+        Transport::Stdio
+    } else {
+        config.server.transport
+    };
+
     let env_filter = tracing_subscriber::EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| "debug,alloy=info,rmcp=info".into());
 
+    // In stdio mode stdout carries the MCP protocol, so logs go to stderr
+    let writer = if transport == Transport::Stdio {
+        BoxMakeWriter::new(std::io::stderr)
+    } else {
+        BoxMakeWriter::new(std::io::stdout)
+    };
     let fmt_layer = tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .with_ansi(transport != Transport::Stdio)
         .with_file(true)
         .with_line_number(true);
 
@@ -27,12 +65,46 @@ async fn main() {
 
     tracing::debug!("debug logging enabled");
 
-    let config = config::Config::from_yaml("config/default.yaml").await;
+    // One provider for the whole process, so a WebSocket connection is shared by all sessions
+    let provider = connect_provider(&config.rpc.url).await;
+    let breaker = config.rpc.circuit_breaker.breaker();
+
+    // Preset contract addresses are only valid on their own chain
+    match EthereumTradingService::with_provider(&config, provider.clone(), breaker.clone())
+        .verify_network()
+        .await
+    {
+        Ok(()) => {}
+        Err(ServiceError::BlockchainError(e) | ServiceError::RateLimited(e)) => {
+            tracing::warn!("Could not verify the RPC chain id: {e}");
+        }
+        Err(e) => {
+            tracing::error!("{e}");
+            std::process::exit(1);
+        }
+    }
+
+    if transport == Transport::Stdio {
+        serve_stdio(&config, provider, breaker).await;
+        return;
+    }
 
     let cancellation_token = CancellationToken::new();
     let addr = config.server_uri();
 
-    let app = build_app(cancellation_token.clone(), config).expect("failed to build app");
+    let live = LiveConfig::new(config, provider);
+    let drain = Drain::new();
+
+    let app = build_app(
+        cancellation_token.clone(),
+        live.clone(),
+        breaker.clone(),
+        drain.clone(),
+    )
+    .expect("failed to build app");
+
+    #[cfg(unix)]
+    tokio::spawn(reload_on_sighup(live.clone(), breaker));
 
     let listener = tokio::net::TcpListener::bind(&addr)
         .await
@@ -40,13 +112,108 @@ async fn main() {
 
     tracing::info!("listening on {addr}");
 
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal(cancellation_token))
+    // Client addresses key the per-IP rate limit
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal(cancellation_token, drain, live))
+    .await
+    .expect("failed to start server")
+}
+
+/// Serves a single MCP session over stdin/stdout until the client disconnects
+async fn serve_stdio(config: &Config, provider: DynProvider, breaker: CircuitBreaker) {
+    tracing::info!("serving MCP over stdio");
+
+    let service = EthereumTradingService::with_provider(config, provider, breaker)
+        .with_price_feed(CoinGeckoPriceFeed::from_config(config))
+        .serve(rmcp::transport::stdio())
         .await
-        .expect("failed to start server")
+        .expect("failed to start stdio transport");
+
+    if let Err(e) = service.waiting().await {
+        tracing::error!("stdio session ended with an error: {e}");
+    }
 }
 
-async fn shutdown_signal(cancellation_token: CancellationToken) {
+/// Re-reads the config file on every SIGHUP; new MCP sessions use the reloaded settings
+#[cfg(unix)]
+async fn reload_on_sighup(live: LiveConfig, breaker: CircuitBreaker) {
+    let mut hangup = signal::unix::signal(signal::unix::SignalKind::hangup())
+        .expect("failed to install SIGHUP handler");
+
+    while hangup.recv().await.is_some() {
+        tracing::info!("SIGHUP received, reloading {CONFIG_PATH}");
+        reload_config(&live, &breaker).await;
+    }
+}
+
+/// Swaps in the config file's current contents, keeping the running config if it is invalid
+///
+/// A changed RPC URL gets a new provider, which must report the configured network's chain id
+/// before it replaces the old one. The listener is never touched, so settings it was built with
+/// only take effect after a restart.
+#[cfg(unix)]
+async fn reload_config(live: &LiveConfig, breaker: &CircuitBreaker) {
+    let config = match Config::from_yaml(CONFIG_PATH).await {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::error!("Config reload failed, keeping the current config: {e}");
+            return;
+        }
+    };
+    let previous = live.config();
+
+    let rpc_changed = config.rpc.url != previous.rpc.url;
+    let provider = if rpc_changed {
+        connect_provider(&config.rpc.url).await
+    } else {
+        live.provider()
+    };
+
+    // Verify the new endpoint on its own breaker, so a bad URL cannot trip the shared one
+    match EthereumTradingService::with_provider(
+        &config,
+        provider.clone(),
+        config.rpc.circuit_breaker.breaker(),
+    )
+    .verify_network()
+    .await
+    {
+        Ok(()) => {}
+        Err(ServiceError::BlockchainError(e) | ServiceError::RateLimited(e)) => {
+            tracing::warn!("Could not verify the RPC chain id after reload: {e}");
+        }
+        Err(e) => {
+            tracing::error!("Config reload rejected, keeping the current config: {e}");
+            return;
+        }
+    }
+
+    for setting in config.restart_required_changes(&previous) {
+        tracing::warn!("{setting} changed; the new value takes effect after a restart");
+    }
+
+    let no_api_key = |config: &Config| config.server.api_key.as_deref().is_none_or(str::is_empty);
+    if no_api_key(&config) && !no_api_key(&previous) {
+        tracing::warn!("server.api_key was removed; /trading endpoints are now unauthenticated");
+    }
+
+    live.replace(config, provider);
+    if rpc_changed {
+        // Failures of the previous endpoint say nothing about the new one
+        breaker.reset();
+        tracing::info!("Switched to the reloaded RPC endpoint");
+    }
+    tracing::info!("Config reloaded");
+}
+
+/// Waits for Ctrl+C or SIGTERM, drains running tool calls, then cancels the MCP sessions
+///
+/// The listener keeps accepting connections while draining, so open SSE sessions can still
+/// post to and hear from the server; new streams and tool calls are refused.
+async fn shutdown_signal(cancellation_token: CancellationToken, drain: Drain, live: LiveConfig) {
     let ctrl_c = async {
         signal::ctrl_c()
             .await
@@ -69,6 +236,17 @@ async fn shutdown_signal(cancellation_token: CancellationToken) {
         _ = terminate => {},
     }
 
-    tracing::info!("shutdown signal received, cancelling tasks...");
+    let grace = Duration::from_secs(live.config().server.shutdown_grace_secs);
+    tracing::info!(
+        "shutdown signal received, waiting up to {}s for {} running tool call(s)...",
+        grace.as_secs(),
+        drain.active()
+    );
+    let remaining = drain.drain(grace).await;
+    if remaining > 0 {
+        tracing::warn!("{remaining} tool call(s) still running after the grace period");
+    }
+
+    tracing::info!("cancelling tasks...");
     cancellation_token.cancel();
 }
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::header::{AUTHORIZATION, WWW_AUTHENTICATE};
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::app::LiveConfig;
+
+/// API key clients must present as `Authorization: Bearer <key>`
+#[derive(Debug, Clone)]
+pub struct ApiKey(Arc<str>);
+
+impl ApiKey {
+    pub fn new(key: &str) -> Self {
+        Self(Arc::from(key))
+    }
+
+    /// Whether the request headers carry this key as a bearer token
+    pub fn authorizes(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| constant_time_eq(token.trim().as_bytes(), self.0.as_bytes()))
+    }
+}
+
+/// Compares without returning early, so response timing does not reveal the matching prefix
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Rejects requests without the configured bearer token with 401
+///
+/// The key is read from the live config on every request, so a reload rotates it at once.
+/// Requests pass unchecked while no key is configured.
+pub async fn require_api_key(
+    State(live): State<LiveConfig>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let authorized = live
+        .api_key()
+        .is_none_or(|api_key| api_key.authorizes(request.headers()));
+    if authorized {
+        return next.run(request).await;
+    }
+
+    tracing::warn!("Rejecting unauthorized request to {}", request.uri().path());
+    (
+        StatusCode::UNAUTHORIZED,
+        [(WWW_AUTHENTICATE, "Bearer")],
+        "Missing or invalid API key",
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(authorization: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(authorization).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_api_key_accepts_matching_bearer_token() {
+        let key = ApiKey::new("secret-key");
+
+        assert!(key.authorizes(&headers("Bearer secret-key")));
+    }
+
+    #[test]
+    fn test_api_key_rejects_missing_or_wrong_token() {
+        let key = ApiKey::new("secret-key");
+
+        assert!(!key.authorizes(&HeaderMap::new()));
+        assert!(!key.authorizes(&headers("Bearer wrong-key")));
+        assert!(!key.authorizes(&headers("Bearer secret-key-2")));
+        assert!(!key.authorizes(&headers("secret-key")));
+        assert!(!key.authorizes(&headers("Basic secret-key")));
+    }
+}
@@ -0,0 +1,159 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll};
+
+use axum::body::{Body, Bytes, HttpBody};
+use axum::extract::{Request, State};
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http_body::{Frame, SizeHint};
+
+/// Counts open SSE connections and enforces an optional maximum
+#[derive(Debug, Clone)]
+pub struct ConnectionLimiter {
+    active: Arc<AtomicUsize>,
+    max: Option<usize>,
+}
+
+impl ConnectionLimiter {
+    pub fn new(max: Option<usize>) -> Self {
+        Self {
+            active: Arc::new(AtomicUsize::new(0)),
+            max,
+        }
+    }
+
+    /// Number of currently open connections
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    pub fn max(&self) -> Option<usize> {
+        self.max
+    }
+
+    /// Reserves a connection slot, or returns None when the limit is reached
+    pub fn try_acquire(&self) -> Option<ConnectionGuard> {
+        self.active
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |active| {
+                match self.max {
+                    Some(max) if active >= max => None,
+                    _ => Some(active + 1),
+                }
+            })
+            .ok()
+            .map(|_| ConnectionGuard {
+                active: self.active.clone(),
+            })
+    }
+}
+
+/// Releases its connection slot when dropped
+#[derive(Debug)]
+pub struct ConnectionGuard {
+    active: Arc<AtomicUsize>,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Response body that holds a connection slot until the stream ends
+struct GuardedBody {
+    inner: Body,
+    _guard: ConnectionGuard,
+}
+
+impl HttpBody for GuardedBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        Pin::new(&mut self.get_mut().inner).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Rejects new SSE streams with 503 once the limiter is full
+///
+/// Only `GET .../sse` and the streamable-HTTP `GET .../mcp` open a long-lived stream;
+/// message posts pass through uncounted.
+pub async fn limit_sse_connections(
+    State(limiter): State<ConnectionLimiter>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    if request.method() != Method::GET || !(path.ends_with("/sse") || path.ends_with("/mcp")) {
+        return next.run(request).await;
+    }
+
+    let Some(guard) = limiter.try_acquire() else {
+        tracing::warn!(
+            "Rejecting SSE connection: {} of {:?} connections in use",
+            limiter.active(),
+            limiter.max()
+        );
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Too many open connections, try again later",
+        )
+            .into_response();
+    };
+
+    let response = next.run(request).await;
+    let (parts, body) = response.into_parts();
+    Response::from_parts(
+        parts,
+        Body::new(GuardedBody {
+            inner: body,
+            _guard: guard,
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limiter_rejects_beyond_max() {
+        let limiter = ConnectionLimiter::new(Some(2));
+
+        let first = limiter.try_acquire();
+        let second = limiter.try_acquire();
+        assert!(first.is_some() && second.is_some());
+        assert!(limiter.try_acquire().is_none());
+        assert_eq!(limiter.active(), 2);
+
+        drop(first);
+        assert_eq!(limiter.active(), 1);
+        assert!(limiter.try_acquire().is_some());
+    }
+
+    #[test]
+    fn test_limiter_without_max_is_unbounded() {
+        let limiter = ConnectionLimiter::new(None);
+
+        let guards: Vec<_> = (0..100).map(|_| limiter.try_acquire()).collect();
+        assert!(guards.iter().all(Option::is_some));
+        assert_eq!(limiter.active(), 100);
+
+        drop(guards);
+        assert_eq!(limiter.active(), 0);
+    }
+}
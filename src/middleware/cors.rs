@@ -0,0 +1,73 @@
+use anyhow::{Context, bail};
+use axum::http::{HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+/// Response header carrying the streamable HTTP session, which browser clients must read back
+const MCP_SESSION_ID: HeaderName = HeaderName::from_static("mcp-session-id");
+
+/// CORS for browser-based MCP clients, allowing `allowed_origins`
+///
+/// `["*"]` allows any origin, for development; otherwise each entry is an exact origin such as
+/// `https://agent.example.com`. Returns `None` when the list is empty, leaving CORS disabled.
+pub fn cors_layer(allowed_origins: &[String]) -> anyhow::Result<Option<CorsLayer>> {
+    let allow_origin = match allowed_origins {
+        [] => return Ok(None),
+        [wildcard] if wildcard.trim() == "*" => AllowOrigin::from(Any),
+        origins => {
+            if origins.iter().any(|origin| origin.trim() == "*") {
+                bail!("server.cors_allowed_origins: \"*\" cannot be combined with other origins");
+            }
+            let origins = origins
+                .iter()
+                .map(|origin| {
+                    let origin = origin.trim().trim_end_matches('/');
+                    HeaderValue::from_str(origin).with_context(|| {
+                        format!("server.cors_allowed_origins: invalid origin {origin:?}")
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            AllowOrigin::list(origins)
+        }
+    };
+
+    Ok(Some(
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods([Method::GET, Method::POST, Method::DELETE])
+            .allow_headers(Any)
+            .expose_headers([MCP_SESSION_ID]),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn origins(origins: &[&str]) -> Vec<String> {
+        origins.iter().map(|origin| origin.to_string()).collect()
+    }
+
+    #[test]
+    fn test_cors_layer_is_disabled_without_origins() {
+        assert!(cors_layer(&[]).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_cors_layer_accepts_wildcard_and_origin_lists() {
+        assert!(cors_layer(&origins(&["*"])).unwrap().is_some());
+        assert!(
+            cors_layer(&origins(&[
+                "https://agent.example.com/",
+                "http://localhost:3000"
+            ]))
+            .unwrap()
+            .is_some()
+        );
+    }
+
+    #[test]
+    fn test_cors_layer_rejects_invalid_origins() {
+        assert!(cors_layer(&origins(&["*", "https://agent.example.com"])).is_err());
+        assert!(cors_layer(&origins(&["https://bad\u{7f}origin"])).is_err());
+    }
+}
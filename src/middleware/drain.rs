@@ -0,0 +1,30 @@
+use axum::extract::{Request, State};
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::shutdown::Drain;
+
+/// Rejects new SSE streams with 503 once shutdown has started draining
+///
+/// Message posts still pass, so sessions that are already open can receive the results of
+/// their running tool calls.
+pub async fn reject_streams_while_draining(
+    State(drain): State<Drain>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    let opens_stream =
+        request.method() == Method::GET && (path.ends_with("/sse") || path.ends_with("/mcp"));
+    if !opens_stream || !drain.is_draining() {
+        return next.run(request).await;
+    }
+
+    tracing::warn!("Rejecting SSE connection: server is shutting down");
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Server is shutting down, reconnect shortly",
+    )
+        .into_response()
+}
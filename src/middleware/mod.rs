@@ -1 +1,6 @@
+pub mod auth;
+pub mod connection_limit;
+pub mod cors;
+pub mod drain;
+pub mod rate_limit;
 pub mod trace;
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::StatusCode;
+use axum::http::header::RETRY_AFTER;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+/// Tracked clients beyond which buckets that have refilled completely are dropped
+const MAX_IDLE_CLIENTS: usize = 10_000;
+
+/// Token buckets per client IP: each request takes a token, tokens refill at a fixed rate
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    buckets: Arc<Mutex<HashMap<IpAddr, Bucket>>>,
+    per_second: f64,
+    burst: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    /// `burst` requests may arrive at once; afterwards `per_second` are allowed
+    pub fn new(per_second: u32, burst: u32) -> Self {
+        Self {
+            buckets: Arc::default(),
+            per_second: f64::from(per_second.max(1)),
+            burst: f64::from(burst.max(1)),
+        }
+    }
+
+    /// Takes a token for `client`, or returns how long until the next one is available
+    pub fn check(&self, client: IpAddr, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_IDLE_CLIENTS {
+            buckets.retain(|_, bucket| self.refilled(*bucket, now) < self.burst);
+        }
+
+        let bucket = buckets.entry(client).or_insert(Bucket {
+            tokens: self.burst,
+            updated: now,
+        });
+        bucket.tokens = self.refilled(*bucket, now);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / self.per_second,
+            ))
+        }
+    }
+
+    fn refilled(&self, bucket: Bucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        (bucket.tokens + elapsed * self.per_second).min(self.burst)
+    }
+}
+
+/// Answers 429 with `Retry-After` once a client IP exceeds the rate limit
+///
+/// Requires the app to be served with `ConnectInfo<SocketAddr>`; without it every request
+/// passes.
+pub async fn rate_limit_by_ip(
+    State(limiter): State<RateLimiter>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(ConnectInfo(addr)) = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .copied()
+    else {
+        return next.run(request).await;
+    };
+
+    match limiter.check(addr.ip(), Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            tracing::warn!("Rate limiting {} on {}", addr.ip(), request.uri().path());
+            let seconds = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(RETRY_AFTER, seconds.to_string())],
+                "Too many requests, slow down",
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT: IpAddr = IpAddr::V4(std::net::Ipv4Addr::LOCALHOST);
+    const OTHER: IpAddr = IpAddr::V4(std::net::Ipv4Addr::new(10, 0, 0, 1));
+
+    #[test]
+    fn test_rate_limiter_allows_burst_then_rejects() {
+        let limiter = RateLimiter::new(2, 3);
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.check(CLIENT, now).is_ok());
+        }
+        let retry_after = limiter.check(CLIENT, now).unwrap_err();
+        assert_eq!(retry_after, Duration::from_millis(500));
+
+        // Other clients have their own bucket
+        assert!(limiter.check(OTHER, now).is_ok());
+    }
+
+    #[test]
+    fn test_rate_limiter_refills_over_time() {
+        let limiter = RateLimiter::new(2, 2);
+        let now = Instant::now();
+
+        assert!(limiter.check(CLIENT, now).is_ok());
+        assert!(limiter.check(CLIENT, now).is_ok());
+        assert!(limiter.check(CLIENT, now).is_err());
+
+        // Half a second refills one token at 2 per second, never beyond the burst
+        let later = now + Duration::from_millis(500);
+        assert!(limiter.check(CLIENT, later).is_ok());
+        assert!(limiter.check(CLIENT, later).is_err());
+
+        let much_later = now + Duration::from_secs(60);
+        assert!(limiter.check(CLIENT, much_later).is_ok());
+        assert!(limiter.check(CLIENT, much_later).is_ok());
+        assert!(limiter.check(CLIENT, much_later).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+//! Local Anvil fork for the repository's chain tests
+//!
+//! The first test that asks for an endpoint spawns `anvil --fork-url <upstream>
+//! --fork-block-number FORK_BLOCK`, shared by every test in the binary and killed at exit. Tests
+//! then read mainnet state pinned at one block, so results are repeatable and public-RPC rate
+//! limits do not apply. Anvil caches fetched state under `~/.foundry/cache`, so later runs need
+//! the upstream only for state they have not read before.
+//!
+//! Environment:
+//! - `ANVIL_BIN`: the Anvil binary (defaults to `anvil` on `PATH`)
+//! - `ANVIL_FORK_URL`: the archive node the fork reads from (defaults to the public RPC)
+//! - `PUBLIC_RPC_TESTS=1`: skip the fork and query `RPC_URL` (or the public RPC) directly. Also
+//!   required to fall back to it when Anvil is not installed.
+
+use std::io::{BufRead, BufReader};
+use std::net::TcpListener;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Mutex, OnceLock, mpsc};
+use std::time::Duration;
+
+/// Block the fork is pinned at; assertions on chain state hold at this block
+pub const FORK_BLOCK: u64 = 21_000_000;
+
+/// Public endpoint used when neither `ANVIL_FORK_URL` nor `RPC_URL` is set
+const PUBLIC_RPC_URL: &str = "https://eth.llamarpc.com";
+
+/// How long Anvil may take to fetch the fork block and start listening
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+static FORK: OnceLock<Option<AnvilFork>> = OnceLock::new();
+
+/// An Anvil process forking mainnet at `FORK_BLOCK`
+pub struct AnvilFork {
+    child: Mutex<Child>,
+    url: String,
+}
+
+impl AnvilFork {
+    /// Spawns Anvil on a free local port and waits until it listens
+    ///
+    /// Returns `None` when the binary cannot be started, e.g. Foundry is not installed.
+    fn spawn() -> Option<Self> {
+        let bin = std::env::var("ANVIL_BIN").unwrap_or_else(|_| "anvil".to_string());
+        let upstream =
+            std::env::var("ANVIL_FORK_URL").unwrap_or_else(|_| PUBLIC_RPC_URL.to_string());
+        let port = free_port();
+
+        let mut child = match Command::new(&bin)
+            .args(["--fork-url", &upstream])
+            .args(["--fork-block-number", &FORK_BLOCK.to_string()])
+            .args(["--port", &port.to_string()])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(e) => {
+                eprintln!("Could not start {bin}: {e}");
+                return None;
+            }
+        };
+
+        // Anvil announces "Listening on 127.0.0.1:<port>" once the fork block is loaded. Its
+        // output is drained for as long as it runs, so logging never blocks on a full pipe.
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let (ready, listening) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut lines = BufReader::new(stdout).lines().map_while(Result::ok);
+            let _ = ready.send(lines.any(|line| line.contains("Listening on")));
+            lines.for_each(drop);
+        });
+        if listening.recv_timeout(STARTUP_TIMEOUT) != Ok(true) {
+            let _ = child.kill();
+            panic!("{bin} did not start forking {upstream} at block {FORK_BLOCK}");
+        }
+
+        Some(Self {
+            child: Mutex::new(child),
+            url: format!("http://127.0.0.1:{port}"),
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+/// Whether tests should query the public RPC instead of a local fork
+pub fn public_rpc_tests() -> bool {
+    std::env::var("PUBLIC_RPC_TESTS").is_ok_and(|value| value == "1" || value == "true")
+}
+
+/// The shared fork, started on first use; `None` when the public RPC is used instead
+pub fn shared_fork() -> Option<&'static AnvilFork> {
+    FORK.get_or_init(|| {
+        if public_rpc_tests() {
+            return None;
+        }
+        match AnvilFork::spawn() {
+            Some(fork) => Some(fork),
+            None => panic!(
+                "Chain tests run against a local Anvil fork: install Foundry (or set ANVIL_BIN), \
+                 or set PUBLIC_RPC_TESTS=1 to use the public RPC"
+            ),
+        }
+    })
+    .as_ref()
+}
+
+/// Endpoint for chain tests: the shared fork, or with `PUBLIC_RPC_TESTS=1` the `RPC_URL`
+/// environment variable or the public RPC
+pub fn test_rpc_url() -> String {
+    match shared_fork() {
+        Some(fork) => fork.url().to_string(),
+        None => std::env::var("RPC_URL").unwrap_or_else(|_| PUBLIC_RPC_URL.to_string()),
+    }
+}
+
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .expect("no free local port")
+}
+
+#[ctor::dtor]
+fn stop_shared_fork() {
+    if let Some(Some(fork)) = FORK.get()
+        && let Ok(mut child) = fork.child.lock()
+    {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::providers::{Provider, ProviderBuilder};
+
+    #[tokio::test]
+    #[serial_test::serial]
+    #[ignore]
+    async fn test_fork_serves_pinned_block() {
+        let Some(fork) = shared_fork() else {
+            return;
+        };
+        let provider = ProviderBuilder::new().connect_http(fork.url().parse().unwrap());
+
+        let block_number = provider.get_block_number().await.unwrap();
+        assert_eq!(block_number, FORK_BLOCK);
+        assert_eq!(provider.get_chain_id().await.unwrap(), 1);
+    }
+}
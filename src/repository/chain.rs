@@ -0,0 +1,284 @@
+use alloy::primitives::{Address, address};
+use serde::Deserialize;
+
+/// Networks with known Uniswap and token deployments.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Sepolia,
+    Arbitrum,
+    Base,
+    Optimism,
+}
+
+impl Network {
+    /// Returns the contract addresses deployed on this network.
+    pub fn chain_config(self) -> ChainConfig {
+        match self {
+            Network::Mainnet => ChainConfig::MAINNET,
+            Network::Sepolia => ChainConfig::SEPOLIA,
+            Network::Arbitrum => ChainConfig::ARBITRUM,
+            Network::Base => ChainConfig::BASE,
+            Network::Optimism => ChainConfig::OPTIMISM,
+        }
+    }
+
+    /// Returns the name used for this network in the configuration.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Sepolia => "sepolia",
+            Network::Arbitrum => "arbitrum",
+            Network::Base => "base",
+            Network::Optimism => "optimism",
+        }
+    }
+}
+
+/// Uniswap V2 and its forks, which share the V2 factory/router ABI.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum V2Dex {
+    #[default]
+    UniswapV2,
+    SushiSwap,
+}
+
+impl V2Dex {
+    /// Forks quoted through the same code paths as Uniswap V2.
+    pub const FORKS: [V2Dex; 1] = [V2Dex::SushiSwap];
+
+    /// Parses the name used in tool requests ("uniswap_v2" or "sushiswap").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "uniswap_v2" | "uniswap" => Some(V2Dex::UniswapV2),
+            "sushiswap" | "sushi" => Some(V2Dex::SushiSwap),
+            _ => None,
+        }
+    }
+
+    /// Returns the display name of this DEX.
+    pub fn label(self) -> &'static str {
+        match self {
+            V2Dex::UniswapV2 => "Uniswap V2",
+            V2Dex::SushiSwap => "SushiSwap",
+        }
+    }
+}
+
+/// Multicall3 shares one address across chains.
+const MULTICALL3: Address = address!("0xca11bde05977b3631167028862be2a173976ca11");
+
+/// Uniswap and token contract addresses for a single chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainConfig {
+    pub chain_id: u64,
+    /// Approximate number of blocks produced in 24 hours.
+    pub blocks_per_day: u64,
+    pub uniswap_v2_factory: Address,
+    pub uniswap_v2_router: Address,
+    /// SushiSwap V2 factory and router, where SushiSwap is deployed.
+    pub sushiswap_factory: Option<Address>,
+    pub sushiswap_router: Option<Address>,
+    pub uniswap_v3_quoter: Address,
+    pub uniswap_v3_factory: Address,
+    /// Original Uniswap V3 SwapRouter. `None` on chains that only have SwapRouter02,
+    /// whose `exactInputSingle` has a different ABI.
+    pub uniswap_v3_swap_router: Option<Address>,
+    pub weth: Address,
+    pub usdc: Address,
+    /// ENS registry, where ENS is deployed.
+    pub ens_registry: Option<Address>,
+    pub multicall3: Address,
+    /// Chainlink ETH/USD price feed.
+    pub chainlink_eth_usd: Option<Address>,
+}
+
+impl ChainConfig {
+    /// Ethereum mainnet.
+    pub const MAINNET: Self = Self {
+        chain_id: 1,
+        blocks_per_day: 7_200,
+        uniswap_v2_factory: address!("0x5c69bee701ef814a2b6a3edd4b1652cb9cc5aa6f"),
+        uniswap_v2_router: address!("0x7a250d5630b4cf539739df2c5dacb4c659f2488d"),
+        sushiswap_factory: Some(address!("0xc0aee478e3658e2610c5f7a4a2e1777ce9e4f2ac")),
+        sushiswap_router: Some(address!("0xd9e1ce17f2641f24ae83637ab66a2cca9c378b9f")),
+        uniswap_v3_quoter: address!("0x61ffe014ba17989e743c5f6cb21bf9697530b21e"),
+        uniswap_v3_factory: address!("0x1f98431c8ad98523631ae4a59f267346ea31f984"),
+        uniswap_v3_swap_router: Some(address!("0xe592427a0aece92de3edee1f18e0157c05861564")),
+        weth: address!("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
+        usdc: address!("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+        ens_registry: Some(address!("0x00000000000c2e074ec69a0dfb2997ba6c7d2e1e")),
+        multicall3: MULTICALL3,
+        chainlink_eth_usd: Some(address!("0x5f4ec3df9cbd43714fe2740f5e3616155c5b8419")),
+    };
+
+    /// Sepolia testnet.
+    pub const SEPOLIA: Self = Self {
+        chain_id: 11_155_111,
+        blocks_per_day: 7_200,
+        uniswap_v2_factory: address!("0xf62c03e08ada871a0beb309762e260a7a6a880e6"),
+        uniswap_v2_router: address!("0xee567fe1712faf6149d80da1e6934e354124cfe3"),
+        sushiswap_factory: None,
+        sushiswap_router: None,
+        uniswap_v3_quoter: address!("0xed1f6473345f45b75f8179591dd5ba1888cf2fb3"),
+        uniswap_v3_factory: address!("0x0227628f3f023bb0b980b67d528571c95c6dac1c"),
+        uniswap_v3_swap_router: None,
+        weth: address!("0xfff9976782d46cc05630d1f6ebab18b2324d6b14"),
+        usdc: address!("0x1c7d4b196cb0c7b01d743fbc6116a902379c7238"),
+        ens_registry: Some(address!("0x00000000000c2e074ec69a0dfb2997ba6c7d2e1e")),
+        multicall3: MULTICALL3,
+        chainlink_eth_usd: Some(address!("0x694aa1769357215de4fac081bf1f309adc325306")),
+    };
+
+    /// Arbitrum One.
+    pub const ARBITRUM: Self = Self {
+        chain_id: 42_161,
+        blocks_per_day: 345_600,
+        uniswap_v2_factory: address!("0xf1d7cc64fb4452f05c498126312ebe29f30fbcf9"),
+        uniswap_v2_router: address!("0x4752ba5dbc23f44d87826276bf6fd6b1c372ad24"),
+        sushiswap_factory: Some(address!("0xc35dadb65012ec5796536bd9864ed8773abc74c4")),
+        sushiswap_router: Some(address!("0x1b02da8cb0d097eb8d57a175b88c7d8b47997506")),
+        uniswap_v3_quoter: address!("0x61ffe014ba17989e743c5f6cb21bf9697530b21e"),
+        uniswap_v3_factory: address!("0x1f98431c8ad98523631ae4a59f267346ea31f984"),
+        uniswap_v3_swap_router: Some(address!("0xe592427a0aece92de3edee1f18e0157c05861564")),
+        weth: address!("0x82af49447d8a07e3bd95bd0d56f35241523fbab1"),
+        usdc: address!("0xaf88d065e77c8cc2239327c5edb3a432268e5831"),
+        ens_registry: None,
+        multicall3: MULTICALL3,
+        chainlink_eth_usd: Some(address!("0x639fe6ab55c921f74e7fac1ee960c0b6293ba612")),
+    };
+
+    /// Base.
+    pub const BASE: Self = Self {
+        chain_id: 8_453,
+        blocks_per_day: 43_200,
+        uniswap_v2_factory: address!("0x8909dc15e40173ff4699343b6eb8132c65e18ec6"),
+        uniswap_v2_router: address!("0x4752ba5dbc23f44d87826276bf6fd6b1c372ad24"),
+        sushiswap_factory: None,
+        sushiswap_router: None,
+        uniswap_v3_quoter: address!("0x3d4e44eb1374240ce5f1b871ab261cd16335b76a"),
+        uniswap_v3_factory: address!("0x33128a8fc17869897dce68ed026d694621f6fdfd"),
+        uniswap_v3_swap_router: None,
+        weth: address!("0x4200000000000000000000000000000000000006"),
+        usdc: address!("0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"),
+        ens_registry: None,
+        multicall3: MULTICALL3,
+        chainlink_eth_usd: Some(address!("0x71041dddad3595f9ced3dccfbe3d1f4b0a16bb70")),
+    };
+
+    /// OP Mainnet (Optimism).
+    pub const OPTIMISM: Self = Self {
+        chain_id: 10,
+        blocks_per_day: 43_200,
+        uniswap_v2_factory: address!("0x0c3c1c532f1e39edf36be9fe0be1410313e074bf"),
+        uniswap_v2_router: address!("0x4a7b5da61326a6379179b40d00f57e5bbdc962c2"),
+        sushiswap_factory: None,
+        sushiswap_router: None,
+        uniswap_v3_quoter: address!("0x61ffe014ba17989e743c5f6cb21bf9697530b21e"),
+        uniswap_v3_factory: address!("0x1f98431c8ad98523631ae4a59f267346ea31f984"),
+        uniswap_v3_swap_router: Some(address!("0xe592427a0aece92de3edee1f18e0157c05861564")),
+        weth: address!("0x4200000000000000000000000000000000000006"),
+        usdc: address!("0x0b2c639c533813f4aa9d7837caf62653d097ff85"),
+        ens_registry: None,
+        multicall3: MULTICALL3,
+        chainlink_eth_usd: Some(address!("0x13e3ee699d1909e989722e753853ae30b17e08c5")),
+    };
+
+    /// Returns the V2 factory and router of `dex`, or `None` where it is not deployed.
+    pub fn v2_deployment(&self, dex: V2Dex) -> Option<(Address, Address)> {
+        match dex {
+            V2Dex::UniswapV2 => Some((self.uniswap_v2_factory, self.uniswap_v2_router)),
+            V2Dex::SushiSwap => self.sushiswap_factory.zip(self.sushiswap_router),
+        }
+    }
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        Self::MAINNET
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_names_deserialize_to_their_presets() {
+        // 12s blocks on Ethereum, 2s on the OP Stack and ~0.25s on Arbitrum
+        let networks = [
+            (Network::Mainnet, 1, 7_200),
+            (Network::Sepolia, 11_155_111, 7_200),
+            (Network::Arbitrum, 42_161, 345_600),
+            (Network::Base, 8_453, 43_200),
+            (Network::Optimism, 10, 43_200),
+        ];
+
+        for (network, chain_id, blocks_per_day) in networks {
+            let parsed: Network = serde_json::from_value(network.as_str().into()).unwrap();
+            assert_eq!(parsed, network);
+            assert_eq!(network.chain_config().chain_id, chain_id);
+            assert_eq!(network.chain_config().blocks_per_day, blocks_per_day);
+        }
+    }
+
+    #[test]
+    fn test_presets_pin_token_addresses() {
+        let presets = [
+            (
+                ChainConfig::MAINNET,
+                address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+                address!("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+            ),
+            (
+                ChainConfig::SEPOLIA,
+                address!("0xfFf9976782d46CC05630D1f6eBAb18b2324d6B14"),
+                address!("0x1c7D4B196Cb0C7B01d743Fbc6116a902379C7238"),
+            ),
+            (
+                ChainConfig::ARBITRUM,
+                address!("0x82aF49447D8a07e3bd95BD0d56f35241523fBab1"),
+                address!("0xaf88d065e77c8cC2239327C5EDb3A432268e5831"),
+            ),
+            (
+                ChainConfig::BASE,
+                address!("0x4200000000000000000000000000000000000006"),
+                address!("0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"),
+            ),
+            (
+                ChainConfig::OPTIMISM,
+                address!("0x4200000000000000000000000000000000000006"),
+                address!("0x0b2C639c533813f4Aa9D7837CAf62653d097Ff85"),
+            ),
+        ];
+
+        for (chain, weth, usdc) in presets {
+            assert_eq!(chain.weth, weth, "WETH on chain {}", chain.chain_id);
+            assert_eq!(chain.usdc, usdc, "USDC on chain {}", chain.chain_id);
+        }
+    }
+
+    #[test]
+    fn test_v2_deployment_selects_fork_addresses() {
+        let mainnet = ChainConfig::MAINNET;
+
+        assert_eq!(
+            mainnet.v2_deployment(V2Dex::UniswapV2),
+            Some((mainnet.uniswap_v2_factory, mainnet.uniswap_v2_router))
+        );
+        assert_eq!(
+            mainnet.v2_deployment(V2Dex::SushiSwap),
+            Some((
+                address!("0xc0aee478e3658e2610c5f7a4a2e1777ce9e4f2ac"),
+                address!("0xd9e1ce17f2641f24ae83637ab66a2cca9c378b9f")
+            ))
+        );
+        assert_eq!(ChainConfig::SEPOLIA.v2_deployment(V2Dex::SushiSwap), None);
+
+        assert_eq!(V2Dex::from_name("SushiSwap"), Some(V2Dex::SushiSwap));
+        assert_eq!(V2Dex::from_name("uniswap_v2"), Some(V2Dex::UniswapV2));
+        assert_eq!(V2Dex::from_name("pancakeswap"), None);
+    }
+}
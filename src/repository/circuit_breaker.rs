@@ -0,0 +1,689 @@
+//! Circuit breaker for repository calls
+//!
+//! While the RPC provider is down, every tool call would otherwise wait for its own timeout.
+//! [`CircuitBreakerRepository`] wraps any [`EthereumRepository`] and, after
+//! `rpc.circuit_breaker.failure_threshold` consecutive provider failures, fails calls at once
+//! with [`RepositoryError::CircuitOpen`] for `cooldown_secs`. The next call after the cooldown
+//! is let through as a probe: its success closes the circuit, its failure opens it again.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ::alloy::primitives::{Address, B256, Signature, U256};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+
+use crate::telemetry::record_circuit_state;
+
+use super::{
+    EthereumRepository, FeeEstimates, NativeEth, PairReserves, PermitState, RepoResult,
+    RepositoryError, SwapEventLog, SwapExecution, TokenBalance, TokenInfo, TokenMetadata,
+    TransactionDetails, V3PoolState, WalletSnapshot, WalletTransaction,
+};
+
+/// Whether calls reach the provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls pass through
+    Closed,
+    /// Calls fail fast until the cooldown ends
+    Open,
+    /// The cooldown ended and one probe call is testing the provider
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+/// Failure count and state of a flaky dependency: the RPC provider shared by every repository
+/// built on it, or an external price API
+///
+/// Clones share state, so one breaker can guard all MCP sessions and be reported by `/health`.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    /// Dependency named in errors, logs and the `<name>_circuit_breaker_state` gauge
+    name: &'static str,
+    failure_threshold: u32,
+    cooldown: Duration,
+    state: Arc<Mutex<BreakerState>>,
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    /// When the half-open probe started; a probe older than the cooldown was abandoned
+    probe_started: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub fn new(name: &'static str, failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            name,
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            state: Arc::default(),
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        let state = self.state.lock().unwrap();
+        match (state.open_until, state.probe_started) {
+            (None, _) => CircuitState::Closed,
+            (Some(_), Some(_)) => CircuitState::HalfOpen,
+            (Some(open_until), None) if Instant::now() >= open_until => CircuitState::HalfOpen,
+            (Some(_), None) => CircuitState::Open,
+        }
+    }
+
+    /// Provider failures since the last successful call
+    pub fn consecutive_failures(&self) -> u32 {
+        self.state.lock().unwrap().consecutive_failures
+    }
+
+    /// Closes the circuit and forgets past failures, e.g. after switching to another provider
+    pub fn reset(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.open_until.is_some() {
+            record_circuit_state(self.name, CircuitState::Closed);
+        }
+        *state = BreakerState::default();
+    }
+
+    /// Admit a call, or fail it with `CircuitOpen` while the circuit is open or probing
+    pub(crate) fn admit(&self, method: &str, now: Instant) -> RepoResult<()> {
+        let mut state = self.state.lock().unwrap();
+        let Some(open_until) = state.open_until else {
+            return Ok(());
+        };
+
+        let probe_abandoned = state
+            .probe_started
+            .is_some_and(|started| now >= started + self.cooldown);
+        if now >= open_until && (state.probe_started.is_none() || probe_abandoned) {
+            state.probe_started = Some(now);
+            record_circuit_state(self.name, CircuitState::HalfOpen);
+            return Ok(());
+        }
+
+        let retry_in = open_until.saturating_duration_since(now);
+        Err(RepositoryError::CircuitOpen(format!(
+            "{method} skipped after {} consecutive {} failures; retrying in {}s",
+            state.consecutive_failures,
+            self.name,
+            retry_in.as_secs().max(1)
+        )))
+    }
+
+    /// Update the state with a repository call's outcome
+    ///
+    /// Only provider failures count; reverts and contract errors prove the node is answering.
+    fn record<T>(&self, result: &RepoResult<T>, now: Instant) {
+        let failed = matches!(
+            result,
+            Err(RepositoryError::RpcError(_)
+                | RepositoryError::NetworkError(_)
+                | RepositoryError::RateLimited(_)
+                | RepositoryError::Timeout(_))
+        );
+        self.record_outcome(failed, now);
+    }
+
+    /// Update the state with whether an admitted call failed
+    pub(crate) fn record_outcome(&self, failed: bool, now: Instant) {
+        let mut state = self.state.lock().unwrap();
+        if !failed {
+            if state.open_until.is_some() {
+                tracing::info!("{} recovered, closing the circuit breaker", self.name);
+                record_circuit_state(self.name, CircuitState::Closed);
+            }
+            *state = BreakerState::default();
+            return;
+        }
+
+        state.consecutive_failures += 1;
+        let probe_failed = state.probe_started.is_some();
+        if probe_failed || state.consecutive_failures >= self.failure_threshold {
+            if state.open_until.is_none() || probe_failed {
+                tracing::warn!(
+                    "Opening the {} circuit breaker for {}s after {} consecutive failures",
+                    self.name,
+                    self.cooldown.as_secs(),
+                    state.consecutive_failures
+                );
+            }
+            state.open_until = Some(now + self.cooldown);
+            state.probe_started = None;
+            record_circuit_state(self.name, CircuitState::Open);
+        }
+    }
+}
+
+/// Repository decorator that stops calling a failing provider for a while
+pub struct CircuitBreakerRepository {
+    inner: Box<dyn EthereumRepository>,
+    breaker: CircuitBreaker,
+}
+
+impl CircuitBreakerRepository {
+    pub fn new(inner: Box<dyn EthereumRepository>, breaker: CircuitBreaker) -> Self {
+        Self { inner, breaker }
+    }
+
+    async fn guarded<T>(
+        &self,
+        method: &'static str,
+        call: impl Future<Output = RepoResult<T>>,
+    ) -> RepoResult<T> {
+        guarded(&self.breaker, method, call).await
+    }
+}
+
+/// Run `call` unless the breaker is open, and record its outcome
+async fn guarded<T>(
+    breaker: &CircuitBreaker,
+    method: &'static str,
+    call: impl Future<Output = RepoResult<T>>,
+) -> RepoResult<T> {
+    breaker.admit(method, Instant::now())?;
+    let result = call.await;
+    breaker.record(&result, Instant::now());
+    result
+}
+
+#[async_trait]
+impl EthereumRepository for CircuitBreakerRepository {
+    fn wallet_address(&self) -> Option<Address> {
+        self.inner.wallet_address()
+    }
+
+    fn sign_hash(&self, hash: B256) -> RepoResult<Signature> {
+        self.inner.sign_hash(hash)
+    }
+
+    async fn get_eth_balance(&self, address: Address) -> RepoResult<U256> {
+        self.guarded("get_eth_balance", self.inner.get_eth_balance(address))
+            .await
+    }
+
+    async fn get_erc20_balance(&self, token: Address, owner: Address) -> RepoResult<TokenBalance> {
+        self.guarded(
+            "get_erc20_balance",
+            self.inner.get_erc20_balance(token, owner),
+        )
+        .await
+    }
+
+    async fn get_erc20_allowance(
+        &self,
+        token: Address,
+        owner: Address,
+        spender: Address,
+    ) -> RepoResult<U256> {
+        self.guarded(
+            "get_erc20_allowance",
+            self.inner.get_erc20_allowance(token, owner, spender),
+        )
+        .await
+    }
+
+    async fn get_permit_state(&self, token: Address, owner: Address) -> RepoResult<PermitState> {
+        self.guarded(
+            "get_permit_state",
+            self.inner.get_permit_state(token, owner),
+        )
+        .await
+    }
+
+    async fn get_token_metadata(&self, token: Address) -> RepoResult<TokenMetadata> {
+        self.guarded("get_token_metadata", self.inner.get_token_metadata(token))
+            .await
+    }
+
+    async fn get_token_info(&self, token: Address) -> RepoResult<TokenInfo> {
+        self.guarded("get_token_info", self.inner.get_token_info(token))
+            .await
+    }
+
+    async fn get_gas_price(&self) -> RepoResult<u128> {
+        self.guarded("get_gas_price", self.inner.get_gas_price())
+            .await
+    }
+
+    async fn get_fee_estimates(&self) -> RepoResult<FeeEstimates> {
+        self.guarded("get_fee_estimates", self.inner.get_fee_estimates())
+            .await
+    }
+
+    async fn get_wallet_snapshot(
+        &self,
+        owner: Address,
+        tokens: Vec<Address>,
+    ) -> RepoResult<WalletSnapshot> {
+        self.guarded(
+            "get_wallet_snapshot",
+            self.inner.get_wallet_snapshot(owner, tokens),
+        )
+        .await
+    }
+
+    async fn get_chain_id(&self) -> RepoResult<u64> {
+        self.guarded("get_chain_id", self.inner.get_chain_id())
+            .await
+    }
+
+    async fn get_block_number(&self) -> RepoResult<u64> {
+        self.guarded("get_block_number", self.inner.get_block_number())
+            .await
+    }
+
+    async fn get_block_timestamp(&self) -> RepoResult<u64> {
+        self.guarded("get_block_timestamp", self.inner.get_block_timestamp())
+            .await
+    }
+
+    async fn get_uniswap_pair_reserves(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        block: Option<u64>,
+    ) -> RepoResult<(U256, U256, Address, Address)> {
+        self.guarded(
+            "get_uniswap_pair_reserves",
+            self.inner
+                .get_uniswap_pair_reserves(token_a, token_b, block),
+        )
+        .await
+    }
+
+    async fn get_uniswap_pair_state(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        block: Option<u64>,
+    ) -> RepoResult<PairReserves> {
+        self.guarded(
+            "get_uniswap_pair_state",
+            self.inner.get_uniswap_pair_state(token_a, token_b, block),
+        )
+        .await
+    }
+
+    async fn get_eth_usd_price(&self, block: Option<u64>) -> RepoResult<Decimal> {
+        self.guarded("get_eth_usd_price", self.inner.get_eth_usd_price(block))
+            .await
+    }
+
+    async fn get_chainlink_price(
+        &self,
+        feed: Address,
+        block: Option<u64>,
+    ) -> RepoResult<(Decimal, u64)> {
+        self.guarded(
+            "get_chainlink_price",
+            self.inner.get_chainlink_price(feed, block),
+        )
+        .await
+    }
+
+    async fn resolve_ens(&self, name: &str) -> RepoResult<Address> {
+        self.guarded("resolve_ens", self.inner.resolve_ens(name))
+            .await
+    }
+
+    async fn get_v2_swap_events(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        from_block: u64,
+        to_block: u64,
+    ) -> RepoResult<Vec<SwapEventLog>> {
+        self.guarded(
+            "get_v2_swap_events",
+            self.inner
+                .get_v2_swap_events(token_a, token_b, from_block, to_block),
+        )
+        .await
+    }
+
+    async fn get_wallet_transactions(
+        &self,
+        address: Address,
+        from_block: u64,
+        to_block: u64,
+    ) -> RepoResult<Vec<WalletTransaction>> {
+        self.guarded(
+            "get_wallet_transactions",
+            self.inner
+                .get_wallet_transactions(address, from_block, to_block),
+        )
+        .await
+    }
+
+    async fn get_transaction(&self, hash: B256) -> RepoResult<Option<TransactionDetails>> {
+        self.guarded("get_transaction", self.inner.get_transaction(hash))
+            .await
+    }
+
+    async fn get_transaction_receipt(&self, hash: B256) -> RepoResult<Option<SwapExecution>> {
+        self.guarded(
+            "get_transaction_receipt",
+            self.inner.get_transaction_receipt(hash),
+        )
+        .await
+    }
+
+    async fn get_swap_amounts_out(
+        &self,
+        amount_in: U256,
+        path: Vec<Address>,
+    ) -> RepoResult<Vec<U256>> {
+        self.guarded(
+            "get_swap_amounts_out",
+            self.inner.get_swap_amounts_out(amount_in, path),
+        )
+        .await
+    }
+
+    async fn get_swap_amounts_in(
+        &self,
+        amount_out: U256,
+        path: Vec<Address>,
+    ) -> RepoResult<Vec<U256>> {
+        self.guarded(
+            "get_swap_amounts_in",
+            self.inner.get_swap_amounts_in(amount_out, path),
+        )
+        .await
+    }
+
+    async fn simulate_swap(
+        &self,
+        from: Address,
+        amount_in: U256,
+        amount_out_min: U256,
+        path: Vec<Address>,
+        deadline: U256,
+        native: NativeEth,
+    ) -> RepoResult<u64> {
+        self.guarded(
+            "simulate_swap",
+            self.inner
+                .simulate_swap(from, amount_in, amount_out_min, path, deadline, native),
+        )
+        .await
+    }
+
+    async fn detect_rebasing(&self, token: Address) -> RepoResult<Option<&'static str>> {
+        self.guarded("detect_rebasing", self.inner.detect_rebasing(token))
+            .await
+    }
+
+    async fn get_v3_quote(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        amount_in: U256,
+        fee: u32,
+    ) -> RepoResult<(U256, u64)> {
+        self.guarded(
+            "get_v3_quote",
+            self.inner.get_v3_quote(token_in, token_out, amount_in, fee),
+        )
+        .await
+    }
+
+    async fn get_v3_quote_exact_output(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        amount_out: U256,
+        fee: u32,
+    ) -> RepoResult<(U256, u64)> {
+        self.guarded(
+            "get_v3_quote_exact_output",
+            self.inner
+                .get_v3_quote_exact_output(token_in, token_out, amount_out, fee),
+        )
+        .await
+    }
+
+    async fn get_v3_pool_state(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        fee: u32,
+    ) -> RepoResult<V3PoolState> {
+        self.guarded(
+            "get_v3_pool_state",
+            self.inner.get_v3_pool_state(token_a, token_b, fee),
+        )
+        .await
+    }
+
+    async fn get_v3_quote_multihop(
+        &self,
+        path: Vec<(Address, u32)>,
+        token_out: Address,
+        amount_in: U256,
+    ) -> RepoResult<(U256, u64)> {
+        self.guarded(
+            "get_v3_quote_multihop",
+            self.inner.get_v3_quote_multihop(path, token_out, amount_in),
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn simulate_v3_swap(
+        &self,
+        from: Address,
+        token_in: Address,
+        token_out: Address,
+        amount_in: U256,
+        amount_out_min: U256,
+        fee: u32,
+        deadline: U256,
+        native: NativeEth,
+    ) -> RepoResult<u64> {
+        self.guarded(
+            "simulate_v3_swap",
+            self.inner.simulate_v3_swap(
+                from,
+                token_in,
+                token_out,
+                amount_in,
+                amount_out_min,
+                fee,
+                deadline,
+                native,
+            ),
+        )
+        .await
+    }
+
+    async fn execute_swap(
+        &self,
+        amount_in: U256,
+        amount_out_min: U256,
+        path: Vec<Address>,
+        deadline: U256,
+        native: NativeEth,
+    ) -> RepoResult<SwapExecution> {
+        self.guarded(
+            "execute_swap",
+            self.inner
+                .execute_swap(amount_in, amount_out_min, path, deadline, native),
+        )
+        .await
+    }
+
+    async fn approve_token(
+        &self,
+        token: Address,
+        spender: Address,
+        amount: U256,
+    ) -> RepoResult<SwapExecution> {
+        self.guarded(
+            "approve_token",
+            self.inner.approve_token(token, spender, amount),
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_v3_swap(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        amount_in: U256,
+        amount_out_min: U256,
+        fee: u32,
+        deadline: U256,
+        native: NativeEth,
+    ) -> RepoResult<SwapExecution> {
+        self.guarded(
+            "execute_v3_swap",
+            self.inner.execute_v3_swap(
+                token_in,
+                token_out,
+                amount_in,
+                amount_out_min,
+                fee,
+                deadline,
+                native,
+            ),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::AlloyEthereumRepository;
+
+    fn rpc_failure() -> RepoResult<u64> {
+        Err(RepositoryError::RpcError("connection refused".to_string()))
+    }
+
+    #[test]
+    fn test_breaker_opens_after_threshold() {
+        let breaker = CircuitBreaker::new("RPC", 2, Duration::from_secs(30));
+        let now = Instant::now();
+
+        breaker.record(&rpc_failure(), now);
+        assert!(breaker.admit("get_block_number", now).is_ok());
+        breaker.record(&rpc_failure(), now);
+
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(matches!(
+            breaker.admit("get_block_number", now),
+            Err(RepositoryError::CircuitOpen(_))
+        ));
+    }
+
+    #[test]
+    fn test_breaker_ignores_reverts_and_resets_on_success() {
+        let breaker = CircuitBreaker::new("RPC", 2, Duration::from_secs(30));
+        let now = Instant::now();
+
+        breaker.record(&rpc_failure(), now);
+        breaker.record::<u64>(
+            &Err(RepositoryError::Reverted {
+                reason: "no pool".to_string(),
+            }),
+            now,
+        );
+        breaker.record(&rpc_failure(), now);
+
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_breaker_reset_closes_open_circuit() {
+        let breaker = CircuitBreaker::new("RPC", 1, Duration::from_secs(30));
+        let now = Instant::now();
+
+        breaker.record(&rpc_failure(), now);
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        breaker.reset();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.consecutive_failures(), 0);
+        assert!(breaker.admit("get_block_number", now).is_ok());
+    }
+
+    #[test]
+    fn test_breaker_half_opens_for_one_probe() {
+        let breaker = CircuitBreaker::new("RPC", 1, Duration::from_secs(30));
+        let now = Instant::now();
+        breaker.record(&rpc_failure(), now);
+
+        // After the cooldown a single probe is admitted
+        let later = now + Duration::from_secs(30);
+        assert!(breaker.admit("get_block_number", later).is_ok());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.admit("get_block_number", later).is_err());
+
+        // A failed probe reopens the circuit, a successful one closes it
+        breaker.record(&rpc_failure(), later);
+        assert_eq!(breaker.state(), CircuitState::Open);
+        let recovered = later + Duration::from_secs(30);
+        assert!(breaker.admit("get_block_number", recovered).is_ok());
+        breaker.record(&Ok(1u64), recovered);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.admit("get_block_number", recovered).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_breaker_opens_on_contract_calls_to_unreachable_provider() {
+        // Nothing listens on port 1, so every eth_call is refused
+        let provider = crate::repository::connect_http_provider("http://127.0.0.1:1");
+        let breaker = CircuitBreaker::new("RPC", 2, Duration::from_secs(30));
+        let repository = CircuitBreakerRepository::new(
+            Box::new(AlloyEthereumRepository::new(Arc::new(provider))),
+            breaker.clone(),
+        );
+        let (token_a, token_b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+
+        for _ in 0..2 {
+            let err = repository
+                .get_uniswap_pair_reserves(token_a, token_b, None)
+                .await
+                .unwrap_err();
+            assert!(matches!(err, RepositoryError::NetworkError(_)), "{err}");
+        }
+
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(matches!(
+            repository.get_token_metadata(token_a).await,
+            Err(RepositoryError::CircuitOpen(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_guarded_short_circuits_while_open() {
+        let breaker = CircuitBreaker::new("RPC", 1, Duration::from_secs(30));
+        assert!(
+            guarded(&breaker, "get_block_number", async { rpc_failure() })
+                .await
+                .is_err()
+        );
+
+        let result: RepoResult<u64> = guarded(&breaker, "get_block_number", async {
+            panic!("the provider must not be called while the circuit is open")
+        })
+        .await;
+
+        assert!(matches!(result, Err(RepositoryError::CircuitOpen(_))));
+    }
+}
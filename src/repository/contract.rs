@@ -17,6 +17,26 @@ sol! {
         /// The balance in the token's smallest unit (considering decimals)
         function balanceOf(address account) external view returns (uint256);
 
+        /// Returns the remaining number of tokens that `spender` is allowed to spend on behalf of `owner`.
+        ///
+        /// # Arguments
+        /// * `owner` - The address of the token holder
+        /// * `spender` - The address allowed to spend the tokens (e.g., a Uniswap router)
+        ///
+        /// # Returns
+        /// The allowance in the token's smallest unit
+        function allowance(address owner, address spender) external view returns (uint256);
+
+        /// Allows `spender` to transfer up to `amount` tokens from the caller.
+        ///
+        /// # Arguments
+        /// * `spender` - The address allowed to spend the tokens (e.g., a Uniswap router)
+        /// * `amount` - The new allowance in the token's smallest unit (replaces the old one)
+        ///
+        /// # Returns
+        /// Whether the approval succeeded (some tokens, like USDT, return nothing)
+        function approve(address spender, uint256 amount) external returns (bool);
+
         /// Returns the number of decimals used by the token.
         ///
         /// # Returns
@@ -28,6 +48,31 @@ sol! {
         /// # Returns
         /// The token symbol as a string (e.g., "ETH", "USDT", "DAI")
         function symbol() external view returns (string memory);
+
+        /// Returns the token name.
+        ///
+        /// # Returns
+        /// The full token name (e.g., "Dai Stablecoin", "Tether USD")
+        function name() external view returns (string memory);
+
+        /// Returns the total amount of tokens in existence.
+        ///
+        /// # Returns
+        /// The total supply in the token's smallest unit
+        function totalSupply() external view returns (uint256);
+    }
+
+    /// EIP-2612 extension for approvals by signature.
+    ///
+    /// Only the views needed to build a permit are declared; the `permit` call itself is made
+    /// by whoever redeems the signature.
+    #[sol(rpc)]
+    interface IERC20Permit {
+        /// Returns the EIP-712 domain separator the token verifies permits against.
+        function DOMAIN_SEPARATOR() external view returns (bytes32);
+
+        /// Returns the next permit nonce of `owner`.
+        function nonces(address owner) external view returns (uint256);
     }
 
     /// Uniswap V2 Pair interface for liquidity pool interactions.
@@ -55,6 +100,99 @@ sol! {
         /// # Returns
         /// The contract address of token1
         function token1() external view returns (address);
+
+        /// Emitted on every swap through the pair.
+        ///
+        /// # Fields
+        /// * `sender` - The address that called the pair (usually a router)
+        /// * `amount0In` / `amount1In` - Amounts of token0/token1 sent into the pair
+        /// * `amount0Out` / `amount1Out` - Amounts of token0/token1 sent out of the pair
+        /// * `to` - Recipient of the output tokens
+        event Swap(
+            address indexed sender,
+            uint256 amount0In,
+            uint256 amount1In,
+            uint256 amount0Out,
+            uint256 amount1Out,
+            address indexed to
+        );
+    }
+
+    /// Multicall3 interface for batching view calls into a single `eth_call`.
+    ///
+    /// Deployed at the same address on all supported networks.
+    #[sol(rpc)]
+    interface IMulticall3 {
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+
+        struct Call3Result {
+            bool success;
+            bytes returnData;
+        }
+
+        /// Executes calls in order, tolerating failures of calls with `allowFailure` set.
+        ///
+        /// # Arguments
+        /// * `calls` - Target, failure policy and calldata of each call
+        ///
+        /// # Returns
+        /// Success flag and return data of each call
+        function aggregate3(Call3[] calldata calls) external payable returns (Call3Result[] memory returnData);
+
+        /// Returns the native ETH balance of an address.
+        function getEthBalance(address addr) external view returns (uint256 balance);
+
+        /// Returns the number of the block the call executes in.
+        function getBlockNumber() external view returns (uint256 blockNumber);
+    }
+
+    /// View functions that only rebasing tokens expose.
+    ///
+    /// A token whose balances change without transfers answers at least one of these; plain
+    /// ERC20s revert. Used by `detect_rebasing`, so only successful calls matter.
+    #[sol(rpc)]
+    interface IRebasingToken {
+        /// Lido stETH: shares backing an account's balance.
+        function sharesOf(address account) external view returns (uint256);
+
+        /// Aave aTokens: balance before the liquidity index is applied.
+        function scaledBalanceOf(address user) external view returns (uint256);
+
+        /// Ampleforth (AMPL): contract allowed to rebase the supply.
+        function monetaryPolicy() external view returns (address);
+
+        /// Olympus sOHM: internal gons backing a balance.
+        function gonsForBalance(uint256 amount) external view returns (uint256);
+    }
+
+    /// ENS registry interface for looking up the resolver of a name.
+    #[sol(rpc)]
+    interface IENSRegistry {
+        /// Returns the resolver contract for a name.
+        ///
+        /// # Arguments
+        /// * `node` - The namehash of the ENS name
+        ///
+        /// # Returns
+        /// The resolver address, or 0x0 if the name has no resolver
+        function resolver(bytes32 node) external view returns (address);
+    }
+
+    /// ENS public resolver interface for forward address resolution.
+    #[sol(rpc)]
+    interface IENSResolver {
+        /// Returns the Ethereum address a name points to.
+        ///
+        /// # Arguments
+        /// * `node` - The namehash of the ENS name
+        ///
+        /// # Returns
+        /// The resolved address, or 0x0 if no address is set
+        function addr(bytes32 node) external view returns (address);
     }
 
     /// Uniswap V2 Factory interface for pair discovery.
@@ -88,6 +226,16 @@ sol! {
         /// Array of amounts where the last element is the output amount
         function getAmountsOut(uint256 amountIn, address[] calldata path) external view returns (uint256[] memory amounts);
 
+        /// Given an output amount and token pair, returns the minimum input amount required for the swap.
+        ///
+        /// # Arguments
+        /// * `amountOut` - The desired output amount
+        /// * `path` - Array of token addresses representing the swap path
+        ///
+        /// # Returns
+        /// Array of amounts where the first element is the required input amount
+        function getAmountsIn(uint256 amountOut, address[] calldata path) external view returns (uint256[] memory amounts);
+
         /// Swaps an exact amount of input tokens for as many output tokens as possible.
         ///
         /// # Arguments
@@ -107,6 +255,49 @@ sol! {
             uint256 deadline
         ) external returns (uint256[] memory amounts);
 
+        /// Swaps as few input tokens as possible for an exact amount of output tokens.
+        function swapTokensForExactTokens(
+            uint256 amountOut,
+            uint256 amountInMax,
+            address[] calldata path,
+            address to,
+            uint256 deadline
+        ) external returns (uint256[] memory amounts);
+
+        /// Swaps an exact amount of ETH (`msg.value`) for as many output tokens as possible.
+        function swapExactETHForTokens(
+            uint256 amountOutMin,
+            address[] calldata path,
+            address to,
+            uint256 deadline
+        ) external payable returns (uint256[] memory amounts);
+
+        /// Swaps ETH (up to `msg.value`) for an exact amount of output tokens.
+        function swapETHForExactTokens(
+            uint256 amountOut,
+            address[] calldata path,
+            address to,
+            uint256 deadline
+        ) external payable returns (uint256[] memory amounts);
+
+        /// Swaps an exact amount of input tokens for as much ETH as possible.
+        function swapExactTokensForETH(
+            uint256 amountIn,
+            uint256 amountOutMin,
+            address[] calldata path,
+            address to,
+            uint256 deadline
+        ) external returns (uint256[] memory amounts);
+
+        /// Swaps as few input tokens as possible for an exact amount of ETH.
+        function swapTokensForExactETH(
+            uint256 amountOut,
+            uint256 amountInMax,
+            address[] calldata path,
+            address to,
+            uint256 deadline
+        ) external returns (uint256[] memory amounts);
+
         /// Returns the factory address.
         function factory() external view returns (address);
 
@@ -147,6 +338,35 @@ sol! {
                 uint256 gasEstimate
             );
 
+        /// QuoteExactOutputSingle parameters struct
+        struct QuoteExactOutputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint256 amount;
+            uint24 fee;
+            uint160 sqrtPriceLimitX96;
+        }
+
+        /// Returns the amount in required for a single-hop exact output swap without executing
+        /// the swap.
+        ///
+        /// # Arguments
+        /// * `params` - The parameters for the quote, with `amount` being the desired output
+        ///
+        /// # Returns
+        /// * `amountIn` - The required input amount
+        /// * `sqrtPriceX96After` - The sqrt price after the swap
+        /// * `initializedTicksCrossed` - The number of ticks crossed
+        /// * `gasEstimate` - The estimated gas usage
+        function quoteExactOutputSingle(QuoteExactOutputSingleParams calldata params)
+            external
+            returns (
+                uint256 amountIn,
+                uint160 sqrtPriceX96After,
+                uint32 initializedTicksCrossed,
+                uint256 gasEstimate
+            );
+
         /// QuoteExactInput parameters for multi-hop swaps
         struct QuoteExactInputParams {
             bytes path;
@@ -222,5 +442,80 @@ sol! {
             external
             payable
             returns (uint256 amountOut);
+
+        /// Unwraps the router's entire WETH9 balance and sends it to `recipient` as ETH.
+        ///
+        /// # Arguments
+        /// * `amountMinimum` - The minimum amount of WETH9 to unwrap (reverts below it)
+        /// * `recipient` - The address receiving ETH
+        function unwrapWETH9(uint256 amountMinimum, address recipient) external payable;
+
+        /// Calls several router functions in a single transaction.
+        ///
+        /// # Arguments
+        /// * `data` - ABI-encoded router calls, executed in order
+        ///
+        /// # Returns
+        /// The result of each call
+        function multicall(bytes[] calldata data) external payable returns (bytes[] memory results);
+    }
+
+    /// Uniswap V3 Factory interface for pool discovery.
+    #[sol(rpc)]
+    interface IUniswapV3Factory {
+        /// Returns the pool address for two tokens and a fee tier, or zero address if no pool exists.
+        ///
+        /// # Arguments
+        /// * `tokenA` - The address of the first token
+        /// * `tokenB` - The address of the second token
+        /// * `fee` - The fee tier in hundredths of a bip (100, 500, 3000, 10000)
+        ///
+        /// # Returns
+        /// The address of the pool contract, or 0x0 if the pool doesn't exist
+        function getPool(address tokenA, address tokenB, uint24 fee) external view returns (address pool);
+    }
+
+    /// Uniswap V3 Pool interface for reading the pool's price and liquidity.
+    #[sol(rpc)]
+    interface IUniswapV3Pool {
+        /// Returns the pool's current price and tick, plus oracle and protocol-fee state.
+        ///
+        /// # Returns
+        /// * `sqrtPriceX96` - sqrt(token1/token0) as a Q64.96 fixed-point number
+        /// * `tick` - The current tick
+        /// * remaining fields - Oracle observation and protocol-fee bookkeeping
+        function slot0() external view returns (
+            uint160 sqrtPriceX96,
+            int24 tick,
+            uint16 observationIndex,
+            uint16 observationCardinality,
+            uint16 observationCardinalityNext,
+            uint8 feeProtocol,
+            bool unlocked
+        );
+
+        /// Returns the liquidity currently in range.
+        function liquidity() external view returns (uint128);
+    }
+
+    /// Chainlink aggregator (AggregatorV3Interface) for reading a price feed.
+    #[sol(rpc)]
+    interface IChainlinkAggregator {
+        /// Returns the number of decimals the answer is scaled by.
+        function decimals() external view returns (uint8);
+
+        /// Returns the latest round of the feed.
+        ///
+        /// # Returns
+        /// * `answer` - The price, scaled by `decimals()`
+        /// * `updatedAt` - Unix timestamp of the round's last update
+        /// * remaining fields - Round bookkeeping
+        function latestRoundData() external view returns (
+            uint80 roundId,
+            int256 answer,
+            uint256 startedAt,
+            uint256 updatedAt,
+            uint80 answeredInRound
+        );
     }
 }
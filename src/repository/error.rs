@@ -14,6 +14,18 @@ pub enum RepositoryError {
     #[error("Parse error: {0}")]
     ParseError(String),
 
+    #[error("Rate limited by RPC provider: {0}")]
+    RateLimited(String),
+
+    #[error("RPC call timed out: {0}")]
+    Timeout(String),
+
+    #[error("RPC circuit breaker open: {0}")]
+    CircuitOpen(String),
+
+    #[error("Execution reverted: {reason}")]
+    Reverted { reason: String },
+
     #[error("{0}")]
     Other(String),
 }
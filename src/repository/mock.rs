@@ -0,0 +1,557 @@
+//! In-memory repository for service tests
+//!
+//! [`MockEthereumRepository`] answers from state programmed through its `with_*` builders, so
+//! the service logic can be tested without an RPC endpoint. Uniswap V2 quotes are computed
+//! from the programmed pair reserves with the pair's 0.3% fee, as the router would. A
+//! missing pair or V3 pool fails the way the chain reports it, and any other call that was
+//! not programmed fails with [`RepositoryError::Other`].
+
+use std::collections::HashMap;
+
+use ::alloy::primitives::{Address, B256, Signature, U256};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+
+use super::{
+    EthereumRepository, FeeEstimates, NativeEth, PairReserves, PermitState, RepoResult,
+    RepositoryError, SwapEventLog, SwapExecution, TokenBalance, TokenInfo, TokenMetadata,
+    TransactionDetails, V3PoolState, WalletSnapshot, WalletTransaction,
+};
+
+/// Gas the mock reports for simulated V2 swaps
+pub const MOCK_SWAP_GAS: u64 = 120_000;
+
+/// Repository with programmable chain state
+#[derive(Debug, Clone, Default)]
+pub struct MockEthereumRepository {
+    wallet: Option<Address>,
+    tokens: HashMap<Address, TokenMetadata>,
+    eth_balances: HashMap<Address, U256>,
+    token_balances: HashMap<(Address, Address), U256>,
+    allowances: HashMap<(Address, Address, Address), U256>,
+    /// Reserves keyed by the pair's tokens in either order
+    pairs: HashMap<(Address, Address), (U256, U256)>,
+    /// (amount_out, gas) keyed by token in, token out and fee tier
+    v3_quotes: HashMap<(Address, Address, u32), (U256, u64)>,
+    /// (amount_in, gas) keyed by token in, token out and fee tier
+    v3_exact_output_quotes: HashMap<(Address, Address, u32), (U256, u64)>,
+    gas_price: Option<u128>,
+    eth_usd_price: Option<Decimal>,
+    /// (answer, updatedAt) keyed by Chainlink feed
+    chainlink_prices: HashMap<Address, (Decimal, u64)>,
+    /// Pool states keyed by the pool's tokens in either order and its fee tier
+    v3_pools: HashMap<(Address, Address, u32), V3PoolState>,
+    chain_id: Option<u64>,
+    block_number: Option<u64>,
+    block_timestamp: Option<u64>,
+    /// Returned by every swap and approval that is sent
+    execution: Option<SwapExecution>,
+}
+
+impl MockEthereumRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report `address` as the configured wallet
+    pub fn with_wallet(mut self, address: Address) -> Self {
+        self.wallet = Some(address);
+        self
+    }
+
+    /// Register a token's symbol and decimals
+    pub fn with_token(mut self, token: Address, symbol: &str, decimals: u8) -> Self {
+        self.tokens.insert(
+            token,
+            TokenMetadata {
+                decimals,
+                symbol: symbol.to_string(),
+            },
+        );
+        self
+    }
+
+    pub fn with_eth_balance(mut self, owner: Address, balance: U256) -> Self {
+        self.eth_balances.insert(owner, balance);
+        self
+    }
+
+    /// Set `owner`'s balance of a token registered with [`with_token`](Self::with_token)
+    pub fn with_token_balance(mut self, token: Address, owner: Address, balance: U256) -> Self {
+        self.token_balances.insert((token, owner), balance);
+        self
+    }
+
+    pub fn with_allowance(
+        mut self,
+        token: Address,
+        owner: Address,
+        spender: Address,
+        amount: U256,
+    ) -> Self {
+        self.allowances.insert((token, owner, spender), amount);
+        self
+    }
+
+    /// Create a V2 pair holding `reserve_a` of `token_a` and `reserve_b` of `token_b`
+    pub fn with_pair(
+        mut self,
+        token_a: Address,
+        token_b: Address,
+        reserve_a: U256,
+        reserve_b: U256,
+    ) -> Self {
+        self.pairs
+            .insert((token_a, token_b), (reserve_a, reserve_b));
+        self.pairs
+            .insert((token_b, token_a), (reserve_b, reserve_a));
+        self
+    }
+
+    /// Quote `amount_out` for any input on the V3 pool with the given fee tier
+    pub fn with_v3_quote(
+        mut self,
+        token_in: Address,
+        token_out: Address,
+        fee: u32,
+        amount_out: U256,
+        gas: u64,
+    ) -> Self {
+        self.v3_quotes
+            .insert((token_in, token_out, fee), (amount_out, gas));
+        self
+    }
+
+    /// Require `amount_in` for any output on the V3 pool with the given fee tier
+    pub fn with_v3_quote_exact_output(
+        mut self,
+        token_in: Address,
+        token_out: Address,
+        fee: u32,
+        amount_in: U256,
+        gas: u64,
+    ) -> Self {
+        self.v3_exact_output_quotes
+            .insert((token_in, token_out, fee), (amount_in, gas));
+        self
+    }
+
+    /// Use `gas_price` as both the legacy gas price and the EIP-1559 max fee
+    pub fn with_gas_price(mut self, gas_price: u128) -> Self {
+        self.gas_price = Some(gas_price);
+        self
+    }
+
+    pub fn with_eth_usd_price(mut self, price: Decimal) -> Self {
+        self.eth_usd_price = Some(price);
+        self
+    }
+
+    /// Answer `price`, last updated at `updated_at`, from a Chainlink feed
+    pub fn with_chainlink_price(mut self, feed: Address, price: Decimal, updated_at: u64) -> Self {
+        self.chainlink_prices.insert(feed, (price, updated_at));
+        self
+    }
+
+    /// Create a V3 pool in the given state
+    pub fn with_v3_pool(mut self, state: V3PoolState) -> Self {
+        self.v3_pools
+            .insert((state.token0, state.token1, state.fee), state.clone());
+        self.v3_pools
+            .insert((state.token1, state.token0, state.fee), state);
+        self
+    }
+
+    /// Report `execution` for every swap and approval sent
+    pub fn with_execution(mut self, execution: SwapExecution) -> Self {
+        self.execution = Some(execution);
+        self
+    }
+
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    pub fn with_block(mut self, number: u64, timestamp: u64) -> Self {
+        self.block_number = Some(number);
+        self.block_timestamp = Some(timestamp);
+        self
+    }
+
+    fn pair(&self, token_in: Address, token_out: Address) -> RepoResult<(U256, U256)> {
+        self.pairs
+            .get(&(token_in, token_out))
+            .copied()
+            .ok_or_else(|| {
+                RepositoryError::ContractError(format!(
+                    "No Uniswap V2 pair found for tokens {} and {}",
+                    token_in, token_out
+                ))
+            })
+    }
+
+    fn metadata(&self, token: Address) -> RepoResult<TokenMetadata> {
+        self.tokens.get(&token).cloned().ok_or_else(|| {
+            RepositoryError::ContractError(format!(
+                "Failed to get decimals: {token} is not a token"
+            ))
+        })
+    }
+}
+
+fn not_programmed<T>(method: &str) -> RepoResult<T> {
+    Err(RepositoryError::Other(format!(
+        "{method} is not programmed on the mock repository"
+    )))
+}
+
+/// UniswapV2Library.getAmountOut: the output for `amount_in` after the 0.3% fee
+fn amount_out(amount_in: U256, reserve_in: U256, reserve_out: U256) -> U256 {
+    let amount_in_with_fee = amount_in * U256::from(997);
+    let denominator = reserve_in * U256::from(1000) + amount_in_with_fee;
+    amount_in_with_fee * reserve_out / denominator
+}
+
+/// UniswapV2Library.getAmountIn: the input needed for `amount_out` after the 0.3% fee
+fn amount_in(amount_out: U256, reserve_in: U256, reserve_out: U256) -> RepoResult<U256> {
+    if amount_out >= reserve_out {
+        return Err(RepositoryError::Reverted {
+            reason: "UniswapV2Library: INSUFFICIENT_LIQUIDITY".to_string(),
+        });
+    }
+    let numerator = reserve_in * amount_out * U256::from(1000);
+    let denominator = (reserve_out - amount_out) * U256::from(997);
+    Ok(numerator / denominator + U256::from(1))
+}
+
+#[async_trait]
+impl EthereumRepository for MockEthereumRepository {
+    fn wallet_address(&self) -> Option<Address> {
+        self.wallet
+    }
+
+    fn sign_hash(&self, _hash: B256) -> RepoResult<Signature> {
+        not_programmed("sign_hash")
+    }
+
+    async fn get_eth_balance(&self, address: Address) -> RepoResult<U256> {
+        Ok(self.eth_balances.get(&address).copied().unwrap_or_default())
+    }
+
+    async fn get_erc20_balance(&self, token: Address, owner: Address) -> RepoResult<TokenBalance> {
+        let metadata = self.metadata(token)?;
+        Ok(TokenBalance {
+            balance: self
+                .token_balances
+                .get(&(token, owner))
+                .copied()
+                .unwrap_or_default(),
+            decimals: metadata.decimals,
+            symbol: metadata.symbol,
+        })
+    }
+
+    async fn get_erc20_allowance(
+        &self,
+        token: Address,
+        owner: Address,
+        spender: Address,
+    ) -> RepoResult<U256> {
+        self.metadata(token)?;
+        Ok(self
+            .allowances
+            .get(&(token, owner, spender))
+            .copied()
+            .unwrap_or_default())
+    }
+
+    async fn get_permit_state(&self, _token: Address, _owner: Address) -> RepoResult<PermitState> {
+        not_programmed("get_permit_state")
+    }
+
+    async fn get_token_metadata(&self, token: Address) -> RepoResult<TokenMetadata> {
+        self.metadata(token)
+    }
+
+    async fn get_token_info(&self, _token: Address) -> RepoResult<TokenInfo> {
+        not_programmed("get_token_info")
+    }
+
+    async fn get_gas_price(&self) -> RepoResult<u128> {
+        match self.gas_price {
+            Some(gas_price) => Ok(gas_price),
+            None => not_programmed("get_gas_price"),
+        }
+    }
+
+    async fn get_fee_estimates(&self) -> RepoResult<FeeEstimates> {
+        match self.gas_price {
+            Some(gas_price) => Ok(FeeEstimates {
+                base_fee: gas_price,
+                max_priority_fee: 0,
+                max_fee_per_gas: gas_price,
+            }),
+            None => not_programmed("get_fee_estimates"),
+        }
+    }
+
+    async fn get_wallet_snapshot(
+        &self,
+        _owner: Address,
+        _tokens: Vec<Address>,
+    ) -> RepoResult<WalletSnapshot> {
+        not_programmed("get_wallet_snapshot")
+    }
+
+    async fn get_chain_id(&self) -> RepoResult<u64> {
+        match self.chain_id {
+            Some(chain_id) => Ok(chain_id),
+            None => not_programmed("get_chain_id"),
+        }
+    }
+
+    async fn get_block_number(&self) -> RepoResult<u64> {
+        match self.block_number {
+            Some(number) => Ok(number),
+            None => not_programmed("get_block_number"),
+        }
+    }
+
+    async fn get_block_timestamp(&self) -> RepoResult<u64> {
+        match self.block_timestamp {
+            Some(timestamp) => Ok(timestamp),
+            None => not_programmed("get_block_timestamp"),
+        }
+    }
+
+    async fn get_uniswap_pair_reserves(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        _block: Option<u64>,
+    ) -> RepoResult<(U256, U256, Address, Address)> {
+        let (reserve_a, reserve_b) = self.pair(token_a, token_b)?;
+        Ok((reserve_a, reserve_b, token_a, token_b))
+    }
+
+    async fn get_uniswap_pair_state(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        _block: Option<u64>,
+    ) -> RepoResult<PairReserves> {
+        let (reserve_a, reserve_b) = self.pair(token_a, token_b)?;
+        Ok(PairReserves {
+            reserve_a,
+            reserve_b,
+            updated_at: self.block_timestamp.unwrap_or_default(),
+        })
+    }
+
+    async fn get_eth_usd_price(&self, _block: Option<u64>) -> RepoResult<Decimal> {
+        match self.eth_usd_price {
+            Some(price) => Ok(price),
+            None => not_programmed("get_eth_usd_price"),
+        }
+    }
+
+    async fn get_chainlink_price(
+        &self,
+        feed: Address,
+        _block: Option<u64>,
+    ) -> RepoResult<(Decimal, u64)> {
+        match self.chainlink_prices.get(&feed) {
+            Some(round) => Ok(*round),
+            None => not_programmed("get_chainlink_price"),
+        }
+    }
+
+    async fn resolve_ens(&self, _name: &str) -> RepoResult<Address> {
+        not_programmed("resolve_ens")
+    }
+
+    async fn get_v2_swap_events(
+        &self,
+        _token_a: Address,
+        _token_b: Address,
+        _from_block: u64,
+        _to_block: u64,
+    ) -> RepoResult<Vec<SwapEventLog>> {
+        not_programmed("get_v2_swap_events")
+    }
+
+    async fn get_wallet_transactions(
+        &self,
+        _address: Address,
+        _from_block: u64,
+        _to_block: u64,
+    ) -> RepoResult<Vec<WalletTransaction>> {
+        not_programmed("get_wallet_transactions")
+    }
+
+    async fn get_transaction(&self, _hash: B256) -> RepoResult<Option<TransactionDetails>> {
+        not_programmed("get_transaction")
+    }
+
+    async fn get_transaction_receipt(&self, _hash: B256) -> RepoResult<Option<SwapExecution>> {
+        not_programmed("get_transaction_receipt")
+    }
+
+    async fn get_swap_amounts_out(
+        &self,
+        amount_in: U256,
+        path: Vec<Address>,
+    ) -> RepoResult<Vec<U256>> {
+        let mut amounts = vec![amount_in];
+        for hop in path.windows(2) {
+            let (reserve_in, reserve_out) = self.pair(hop[0], hop[1])?;
+            let last = *amounts.last().unwrap();
+            amounts.push(amount_out(last, reserve_in, reserve_out));
+        }
+        Ok(amounts)
+    }
+
+    async fn get_swap_amounts_in(
+        &self,
+        amount_out: U256,
+        path: Vec<Address>,
+    ) -> RepoResult<Vec<U256>> {
+        let mut amounts = vec![amount_out];
+        for hop in path.windows(2).rev() {
+            let (reserve_in, reserve_out) = self.pair(hop[0], hop[1])?;
+            amounts.insert(0, amount_in(amounts[0], reserve_in, reserve_out)?);
+        }
+        Ok(amounts)
+    }
+
+    async fn simulate_swap(
+        &self,
+        _from: Address,
+        amount_in: U256,
+        amount_out_min: U256,
+        path: Vec<Address>,
+        _deadline: U256,
+        _native: NativeEth,
+    ) -> RepoResult<u64> {
+        let amounts = self.get_swap_amounts_out(amount_in, path).await?;
+        if amounts.last().copied().unwrap_or_default() < amount_out_min {
+            return Err(RepositoryError::Reverted {
+                reason: "UniswapV2Router: INSUFFICIENT_OUTPUT_AMOUNT".to_string(),
+            });
+        }
+        Ok(MOCK_SWAP_GAS)
+    }
+
+    async fn detect_rebasing(&self, _token: Address) -> RepoResult<Option<&'static str>> {
+        Ok(None)
+    }
+
+    async fn get_v3_quote(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        _amount_in: U256,
+        fee: u32,
+    ) -> RepoResult<(U256, u64)> {
+        self.v3_quotes
+            .get(&(token_in, token_out, fee))
+            .copied()
+            .ok_or_else(|| RepositoryError::Reverted {
+                reason: format!("no V3 pool for fee tier {fee}"),
+            })
+    }
+
+    async fn get_v3_quote_exact_output(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        _amount_out: U256,
+        fee: u32,
+    ) -> RepoResult<(U256, u64)> {
+        self.v3_exact_output_quotes
+            .get(&(token_in, token_out, fee))
+            .copied()
+            .ok_or_else(|| RepositoryError::Reverted {
+                reason: format!("no V3 pool for fee tier {fee}"),
+            })
+    }
+
+    async fn get_v3_pool_state(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        fee: u32,
+    ) -> RepoResult<V3PoolState> {
+        self.v3_pools
+            .get(&(token_a, token_b, fee))
+            .cloned()
+            .ok_or_else(|| {
+                RepositoryError::ContractError(format!(
+                    "No Uniswap V3 pool found for tokens {token_a} and {token_b} with fee {fee}"
+                ))
+            })
+    }
+
+    async fn get_v3_quote_multihop(
+        &self,
+        _path: Vec<(Address, u32)>,
+        _token_out: Address,
+        _amount_in: U256,
+    ) -> RepoResult<(U256, u64)> {
+        not_programmed("get_v3_quote_multihop")
+    }
+
+    async fn simulate_v3_swap(
+        &self,
+        _from: Address,
+        _token_in: Address,
+        _token_out: Address,
+        _amount_in: U256,
+        _amount_out_min: U256,
+        _fee: u32,
+        _deadline: U256,
+        _native: NativeEth,
+    ) -> RepoResult<u64> {
+        not_programmed("simulate_v3_swap")
+    }
+
+    async fn execute_swap(
+        &self,
+        _amount_in: U256,
+        _amount_out_min: U256,
+        _path: Vec<Address>,
+        _deadline: U256,
+        _native: NativeEth,
+    ) -> RepoResult<SwapExecution> {
+        self.execution
+            .clone()
+            .map_or_else(|| not_programmed("execute_swap"), Ok)
+    }
+
+    async fn approve_token(
+        &self,
+        _token: Address,
+        _spender: Address,
+        _amount: U256,
+    ) -> RepoResult<SwapExecution> {
+        self.execution
+            .clone()
+            .map_or_else(|| not_programmed("approve_token"), Ok)
+    }
+
+    async fn execute_v3_swap(
+        &self,
+        _token_in: Address,
+        _token_out: Address,
+        _amount_in: U256,
+        _amount_out_min: U256,
+        _fee: u32,
+        _deadline: U256,
+        _native: NativeEth,
+    ) -> RepoResult<SwapExecution> {
+        self.execution
+            .clone()
+            .map_or_else(|| not_programmed("execute_v3_swap"), Ok)
+    }
+}
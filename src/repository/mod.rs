@@ -1,12 +1,26 @@
 pub mod alloy;
+#[cfg(test)]
+mod anvil;
+pub mod chain;
+pub mod circuit_breaker;
 pub mod contract;
 pub mod error;
+#[cfg(test)]
+pub mod mock;
+pub mod timeout;
 
-use ::alloy::primitives::{Address, U256};
-pub use alloy::{AlloyEthereumRepository, TokenBalance, TokenMetadata};
+use ::alloy::primitives::{Address, B256, Signature, U256};
+pub use alloy::{
+    AlloyEthereumRepository, FeeEstimates, NativeEth, PairReserves, PermitState, SwapEventLog,
+    SwapExecution, SwapIntent, TokenBalance, TokenInfo, TokenMetadata, TransactionDetails,
+    V3PoolState, WalletSnapshot, WalletTransaction, connect_http_provider, connect_provider,
+};
 use async_trait::async_trait;
+pub use chain::{ChainConfig, Network, V2Dex};
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerRepository, CircuitState};
 pub use error::RepositoryError;
 use rust_decimal::Decimal;
+pub use timeout::TimeoutRepository;
 
 pub(crate) type RepoResult<T> = std::result::Result<T, RepositoryError>;
 
@@ -17,6 +31,33 @@ pub(crate) type RepoResult<T> = std::result::Result<T, RepositoryError>;
 /// Implementations should handle RPC communication and error conversion.
 #[async_trait]
 pub trait EthereumRepository: Send + Sync {
+    /// Returns the address of the configured signing wallet.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Address)` - The wallet address when a private key is configured
+    /// * `None` - When the repository runs in read-only mode
+    fn wallet_address(&self) -> Option<Address>;
+
+    /// Signs a 32-byte hash with the configured wallet's key, without any prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `hash` - The digest to sign, e.g. an EIP-712 permit digest
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Signature)` - The secp256k1 signature with its y-parity
+    /// * `Err(RepositoryError)` - If no wallet is configured
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let signature = repository.sign_hash(digest)?;
+    /// println!("r={} s={}", signature.r(), signature.s());
+    /// ```
+    fn sign_hash(&self, hash: B256) -> RepoResult<Signature>;
+
     /// Retrieves the native ETH balance for a given address.
     ///
     /// # Arguments
@@ -56,6 +97,53 @@ pub trait EthereumRepository: Send + Sync {
     /// ```
     async fn get_erc20_balance(&self, token: Address, owner: Address) -> RepoResult<TokenBalance>;
 
+    /// Retrieves the amount of tokens a spender is allowed to transfer on behalf of an owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The ERC20 token contract address
+    /// * `owner` - The address of the token holder
+    /// * `spender` - The address approved to spend the tokens (e.g., the Uniswap router)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(U256)` - The allowance in the token's smallest unit
+    /// * `Err(RepositoryError)` - If the contract call fails or the address is not a valid ERC20 contract
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let allowance = repository.get_erc20_allowance(usdt_address, wallet, router).await?;
+    /// println!("Router may spend {} units", allowance);
+    /// ```
+    async fn get_erc20_allowance(
+        &self,
+        token: Address,
+        owner: Address,
+        spender: Address,
+    ) -> RepoResult<U256>;
+
+    /// Reads what an EIP-2612 permit on a token is signed against.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The ERC20 token contract address
+    /// * `owner` - The address that would sign the permit
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PermitState)` - The token's `DOMAIN_SEPARATOR()` and the owner's `nonces(owner)`
+    /// * `Err(RepositoryError)` - A `ContractError` if the token does not expose these views, or
+    ///   an RPC error
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let state = repository.get_permit_state(usdc, owner).await?;
+    /// println!("Next nonce: {}", state.nonce);
+    /// ```
+    async fn get_permit_state(&self, token: Address, owner: Address) -> RepoResult<PermitState>;
+
     /// Retrieves metadata for an ERC20 token contract.
     ///
     /// # Arguments
@@ -75,6 +163,25 @@ pub trait EthereumRepository: Send + Sync {
     /// ```
     async fn get_token_metadata(&self, token: Address) -> RepoResult<TokenMetadata>;
 
+    /// Retrieves the name, symbol, decimals and total supply of an ERC20 token contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The ERC20 token contract address
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TokenInfo)` - Token metadata with the name and current total supply (raw)
+    /// * `Err(RepositoryError)` - If any call fails, e.g. for tokens without a string `name()`
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let info = repository.get_token_info(dai_address).await?;
+    /// println!("{} ({}) supply: {}", info.name, info.symbol, info.total_supply);
+    /// ```
+    async fn get_token_info(&self, token: Address) -> RepoResult<TokenInfo>;
+
     /// Retrieves the current gas price from the network.
     ///
     /// # Returns
@@ -90,12 +197,97 @@ pub trait EthereumRepository: Send + Sync {
     /// ```
     async fn get_gas_price(&self) -> RepoResult<u128>;
 
+    /// Retrieves EIP-1559 fee estimates for the next block.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FeeEstimates)` - Next block base fee, suggested priority fee and max fee per gas (wei)
+    /// * `Err(RepositoryError)` - If the RPC call fails or the chain does not support EIP-1559
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let fees = repository.get_fee_estimates().await?;
+    /// println!("Base fee: {} gwei", fees.base_fee / 1_000_000_000);
+    /// ```
+    async fn get_fee_estimates(&self) -> RepoResult<FeeEstimates>;
+
+    /// Reads the native ETH balance and several ERC20 balances of a wallet in one Multicall3 round trip.
+    ///
+    /// All values come from the same block.
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - The wallet address
+    /// * `tokens` - ERC20 contract addresses to read
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(WalletSnapshot)` - Block number, ETH balance and one result per token (a token that
+    ///   is not an ERC20 contract gets an error entry)
+    /// * `Err(RepositoryError)` - If the multicall itself fails
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let snapshot = repository.get_wallet_snapshot(owner, vec![usdc, dai]).await?;
+    /// println!("ETH at block {}: {}", snapshot.block_number, snapshot.eth_balance);
+    /// ```
+    async fn get_wallet_snapshot(
+        &self,
+        owner: Address,
+        tokens: Vec<Address>,
+    ) -> RepoResult<WalletSnapshot>;
+
+    /// Retrieves the chain id reported by the RPC endpoint.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - The chain id (e.g., 1 for Ethereum mainnet)
+    /// * `Err(RepositoryError)` - If the RPC call fails
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let chain_id = repository.get_chain_id().await?;
+    /// ```
+    async fn get_chain_id(&self) -> RepoResult<u64>;
+
+    /// Retrieves the latest block number.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - The number of the most recent block
+    /// * `Err(RepositoryError)` - If the RPC call fails
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let latest = repository.get_block_number().await?;
+    /// ```
+    async fn get_block_number(&self) -> RepoResult<u64>;
+
+    /// Retrieves the timestamp of the latest block.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - Unix timestamp (seconds) of the most recent block
+    /// * `Err(RepositoryError)` - If the RPC call fails
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let timestamp = repository.get_block_timestamp().await?;
+    /// ```
+    async fn get_block_timestamp(&self) -> RepoResult<u64>;
+
     /// Retrieves the reserves from a Uniswap V2 pair contract.
     ///
     /// # Arguments
     ///
     /// * `token_a` - The address of the first token
     /// * `token_b` - The address of the second token
+    /// * `block` - Block number to read the reserves at, or `None` for the latest block
     ///
     /// # Returns
     ///
@@ -110,31 +302,216 @@ pub trait EthereumRepository: Send + Sync {
     ///
     /// ```ignore
     /// let (reserve_a, reserve_b, token0, token1) =
-    ///     repository.get_uniswap_pair_reserves(usdt_address, weth_address).await?;
+    ///     repository.get_uniswap_pair_reserves(usdt_address, weth_address, None).await?;
     /// let price = reserve_b as f64 / reserve_a as f64;
     /// ```
     async fn get_uniswap_pair_reserves(
         &self,
         token_a: Address,
         token_b: Address,
+        block: Option<u64>,
     ) -> RepoResult<(U256, U256, Address, Address)>;
 
-    /// Retrieves the current ETH price in USD from Uniswap V2 USDC/WETH pair.
+    /// Retrieves the reserves of a Uniswap V2 pair with the time they last changed.
+    ///
+    /// Reserves only change when the pair trades or its liquidity changes, so an old
+    /// `updated_at` means the price they imply may be stale.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_a` - The address of the first token
+    /// * `token_b` - The address of the second token
+    /// * `block` - Block number to read the reserves at, or `None` for the latest block
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PairReserves)` - Reserves of token A and token B and the pair's `blockTimestampLast`
+    /// * `Err(RepositoryError)` - If the pair doesn't exist or contract call fails
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let reserves = repository.get_uniswap_pair_state(usdc_address, weth_address, None).await?;
+    /// println!("Last updated at {}", reserves.updated_at);
+    /// ```
+    async fn get_uniswap_pair_state(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        block: Option<u64>,
+    ) -> RepoResult<PairReserves>;
+
+    /// Retrieves the ETH price in USD from Uniswap V2 USDC/WETH pair.
     ///
     /// Uses Decimal for precise financial calculations.
     ///
+    /// # Arguments
+    ///
+    /// * `block` - Block number to price at, or `None` for the latest block
+    ///
     /// # Returns
     ///
-    /// * `Ok(Decimal)` - The current ETH price in USD with full precision
+    /// * `Ok(Decimal)` - The ETH price in USD with full precision
     /// * `Err(RepositoryError)` - If the pair doesn't exist or contract call fails
     ///
     /// # Examples
     ///
     /// ```ignore
-    /// let eth_price = repository.get_eth_usd_price().await?;
+    /// let eth_price = repository.get_eth_usd_price(None).await?;
     /// println!("ETH price: ${}", eth_price);
     /// ```
-    async fn get_eth_usd_price(&self) -> RepoResult<Decimal>;
+    async fn get_eth_usd_price(&self, block: Option<u64>) -> RepoResult<Decimal>;
+
+    /// Reads the latest answer of a Chainlink price feed.
+    ///
+    /// # Arguments
+    ///
+    /// * `feed` - The feed's aggregator (proxy) address
+    /// * `block` - Block number to read at, or `None` for the latest block
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Decimal, u64))` - Tuple containing:
+    ///   - The answer, scaled by the feed's decimals
+    ///   - The Unix timestamp of the round's last update
+    /// * `Err(RepositoryError)` - If a contract call fails or the answer is not positive
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let (eth_price, updated_at) = repository.get_chainlink_price(feed, None).await?;
+    /// println!("ETH price: ${} as of {}", eth_price, updated_at);
+    /// ```
+    async fn get_chainlink_price(
+        &self,
+        feed: Address,
+        block: Option<u64>,
+    ) -> RepoResult<(Decimal, u64)>;
+
+    /// Resolves an ENS name (e.g. "vitalik.eth") to an address.
+    ///
+    /// Successful lookups are cached for a few minutes, so repeated queries by name do not
+    /// hit the registry each time.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The ENS name to resolve
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Address)` - The address the name points to
+    /// * `Err(RepositoryError)` - If the name is unregistered, has no address, or ENS is unavailable on the chain
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let address = repository.resolve_ens("vitalik.eth").await?;
+    /// ```
+    async fn resolve_ens(&self, name: &str) -> RepoResult<Address>;
+
+    /// Reads the Uniswap V2 `Swap` events of a pair over a block range.
+    ///
+    /// Logs are fetched in block windows; when the RPC rejects a window (range or
+    /// result-count caps), the window is halved and retried.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_a` - The address of the first token
+    /// * `token_b` - The address of the second token
+    /// * `from_block` - First block to scan (inclusive)
+    /// * `to_block` - Last block to scan (inclusive)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<SwapEventLog>)` - Swap events with amounts oriented to `token_a`/`token_b`
+    /// * `Err(RepositoryError)` - If the pair doesn't exist or the logs cannot be fetched
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let latest = repository.get_block_number().await?;
+    /// let swaps = repository.get_v2_swap_events(weth, usdc, latest - 7200, latest).await?;
+    /// println!("{} swaps in the last day", swaps.len());
+    /// ```
+    async fn get_v2_swap_events(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        from_block: u64,
+        to_block: u64,
+    ) -> RepoResult<Vec<SwapEventLog>>;
+
+    /// Retrieves the transactions sent from or to an address within a block range.
+    ///
+    /// Every block body in the range is fetched, so callers should keep the range small.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The wallet address to match against each transaction's sender and recipient
+    /// * `from_block` - First block to scan (inclusive)
+    /// * `to_block` - Last block to scan (inclusive)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<WalletTransaction>)` - Matching transactions in block order, with Uniswap
+    ///   router calls decoded into swap intents
+    /// * `Err(RepositoryError)` - If a block cannot be fetched
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let latest = repository.get_block_number().await?;
+    /// let txs = repository.get_wallet_transactions(wallet, latest - 20, latest).await?;
+    /// ```
+    async fn get_wallet_transactions(
+        &self,
+        address: Address,
+        from_block: u64,
+        to_block: u64,
+    ) -> RepoResult<Vec<WalletTransaction>>;
+
+    /// Retrieves a transaction by hash, whether mined or still pending.
+    ///
+    /// # Arguments
+    ///
+    /// * `hash` - The transaction hash
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(TransactionDetails))` - Sender, recipient, value, gas limit, calldata and the
+    ///   inclusion block once mined
+    /// * `Ok(None)` - If the node does not know the transaction
+    /// * `Err(RepositoryError)` - If the RPC call fails
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// if let Some(tx) = repository.get_transaction(hash).await? {
+    ///     println!("{} -> {:?}", tx.from, tx.to);
+    /// }
+    /// ```
+    async fn get_transaction(&self, hash: B256) -> RepoResult<Option<TransactionDetails>>;
+
+    /// Retrieves the outcome of a transaction from its receipt.
+    ///
+    /// # Arguments
+    ///
+    /// * `hash` - The transaction hash
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(SwapExecution))` - Status, block, gas used and effective gas price of a mined
+    ///   transaction
+    /// * `Ok(None)` - If the transaction is pending or unknown to the node
+    /// * `Err(RepositoryError)` - If the RPC call fails
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let execution = repository.execute_swap(amount, min_out, path, deadline, NativeEth::None).await?;
+    /// let receipt = repository.get_transaction_receipt(execution.transaction_hash).await?;
+    /// ```
+    async fn get_transaction_receipt(&self, hash: B256) -> RepoResult<Option<SwapExecution>>;
 
     /// Retrieves the expected output amounts for a token swap from Uniswap V2 Router.
     ///
@@ -160,6 +537,30 @@ pub trait EthereumRepository: Send + Sync {
         path: Vec<Address>,
     ) -> RepoResult<Vec<U256>>;
 
+    /// Retrieves the required input amounts for an exact-output swap from Uniswap V2 Router.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount_out` - The desired output amount
+    /// * `path` - Array of token addresses representing the swap path
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<U256>)` - Array of amounts where the first element is the required input
+    /// * `Err(RepositoryError)` - If the router call fails, the path is invalid, or liquidity is insufficient
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let amounts = repository.get_swap_amounts_in(amount_out, vec![token_a, token_b]).await?;
+    /// let input = amounts.first().unwrap();
+    /// ```
+    async fn get_swap_amounts_in(
+        &self,
+        amount_out: U256,
+        path: Vec<Address>,
+    ) -> RepoResult<Vec<U256>>;
+
     /// Simulates a swap transaction using eth_call to estimate gas and validate the swap.
     ///
     /// # Arguments
@@ -169,6 +570,8 @@ pub trait EthereumRepository: Send + Sync {
     /// * `amount_out_min` - The minimum output amount (for slippage protection)
     /// * `path` - Array of token addresses representing the swap path
     /// * `deadline` - Unix timestamp deadline for the swap
+    /// * `native` - Take the input as native ETH (`Wrap`) or deliver the output as native ETH
+    ///   (`Unwrap`); the pools still trade WETH
     ///
     /// # Returns
     ///
@@ -178,7 +581,7 @@ pub trait EthereumRepository: Send + Sync {
     /// # Examples
     ///
     /// ```ignore
-    /// let gas = repository.simulate_swap(wallet, amount_in, min_out, path, deadline).await?;
+    /// let gas = repository.simulate_swap(wallet, amount_in, min_out, path, deadline, NativeEth::None).await?;
     /// println!("Estimated gas: {}", gas);
     /// ```
     async fn simulate_swap(
@@ -188,8 +591,34 @@ pub trait EthereumRepository: Send + Sync {
         amount_out_min: U256,
         path: Vec<Address>,
         deadline: U256,
+        native: NativeEth,
     ) -> RepoResult<u64>;
 
+    /// Checks whether a token rebases, i.e. changes holder balances without transfers.
+    ///
+    /// Probes the token for view functions that only rebasing designs expose (stETH shares,
+    /// Aave scaled balances, Ampleforth monetary policy, Olympus gons) in a single multicall.
+    /// The probe is a heuristic: a rebasing token with none of these functions is not detected.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The ERC20 token contract address
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(&str))` - The rebasing mechanism that was detected
+    /// * `Ok(None)` - If no rebasing function answered
+    /// * `Err(RepositoryError)` - If the multicall fails
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// if let Some(mechanism) = repository.detect_rebasing(steth).await? {
+    ///     println!("Rebasing token: {}", mechanism);
+    /// }
+    /// ```
+    async fn detect_rebasing(&self, token: Address) -> RepoResult<Option<&'static str>>;
+
     /// Gets a quote for a Uniswap V3 swap using QuoterV2.
     ///
     /// # Arguments
@@ -220,6 +649,93 @@ pub trait EthereumRepository: Send + Sync {
         fee: u32,
     ) -> RepoResult<(U256, u64)>;
 
+    /// Gets a quote for buying an exact output amount on a Uniswap V3 pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_in` - The address of the input token
+    /// * `token_out` - The address of the output token
+    /// * `amount_out` - The desired output amount
+    /// * `fee` - The pool fee tier (100, 500, 3000 or 10000)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((U256, u64))` - Tuple containing:
+    ///   - The required input amount
+    ///   - The estimated gas for the swap
+    /// * `Err(RepositoryError)` - If the quote fails, e.g. the pool cannot fill `amount_out`
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let (amount_in, gas) = repository
+    ///     .get_v3_quote_exact_output(token_a, token_b, amount_out, 500)
+    ///     .await?;
+    /// println!("Required input: {}, Gas: {}", amount_in, gas);
+    /// ```
+    async fn get_v3_quote_exact_output(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        amount_out: U256,
+        fee: u32,
+    ) -> RepoResult<(U256, u64)>;
+
+    /// Reads the current state of a Uniswap V3 pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_a` - The address of the first token
+    /// * `token_b` - The address of the second token
+    /// * `fee` - The pool fee tier (500 for 0.05%, 3000 for 0.3%, 10000 for 1%)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(V3PoolState)` - Pool address, token order, sqrtPriceX96, tick and in-range liquidity
+    /// * `Err(RepositoryError)` - If the pool doesn't exist or a contract call fails
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let state = repository.get_v3_pool_state(usdc, weth, 500).await?;
+    /// println!("Pool {} at tick {}", state.pool, state.tick);
+    /// ```
+    async fn get_v3_pool_state(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        fee: u32,
+    ) -> RepoResult<V3PoolState>;
+
+    /// Gets a quote for a multi-hop Uniswap V3 swap using QuoterV2's `quoteExactInput`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Each hop as (input token, pool fee tier), in swap order
+    /// * `token_out` - The final output token address
+    /// * `amount_in` - The input amount to swap
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((U256, u64))` - Tuple containing:
+    ///   - The expected output amount
+    ///   - The estimated gas for the whole route
+    /// * `Err(RepositoryError)` - If the path is empty or any hop has no pool
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // USDC -> WETH (0.05%) -> UNI (0.3%)
+    /// let path = vec![(usdc, 500), (weth, 3000)];
+    /// let (amount_out, gas) = repository.get_v3_quote_multihop(path, uni, amount).await?;
+    /// ```
+    async fn get_v3_quote_multihop(
+        &self,
+        path: Vec<(Address, u32)>,
+        token_out: Address,
+        amount_in: U256,
+    ) -> RepoResult<(U256, u64)>;
+
     /// Simulates a Uniswap V3 swap transaction using eth_call to estimate gas and validate the swap.
     ///
     /// # Arguments
@@ -231,6 +747,8 @@ pub trait EthereumRepository: Send + Sync {
     /// * `amount_out_min` - The minimum output amount (for slippage protection)
     /// * `fee` - The pool fee tier (500 for 0.05%, 3000 for 0.3%, 10000 for 1%)
     /// * `deadline` - Unix timestamp deadline for the swap
+    /// * `native` - Take the input as native ETH (`Wrap`) or deliver the output as native ETH
+    ///   (`Unwrap`); the pools still trade WETH
     ///
     /// # Returns
     ///
@@ -240,9 +758,10 @@ pub trait EthereumRepository: Send + Sync {
     /// # Examples
     ///
     /// ```ignore
-    /// let gas = repository.simulate_v3_swap(wallet, token_in, token_out, amount_in, min_out, 3000, deadline).await?;
+    /// let gas = repository.simulate_v3_swap(wallet, token_in, token_out, amount_in, min_out, 3000, deadline, NativeEth::None).await?;
     /// println!("Estimated gas: {}", gas);
     /// ```
+    #[allow(clippy::too_many_arguments)]
     async fn simulate_v3_swap(
         &self,
         from: Address,
@@ -252,5 +771,111 @@ pub trait EthereumRepository: Send + Sync {
         amount_out_min: U256,
         fee: u32,
         deadline: U256,
+        native: NativeEth,
     ) -> RepoResult<u64>;
+
+    /// Broadcasts a Uniswap V2 swapExactTokensForTokens transaction signed by the configured wallet.
+    ///
+    /// The output tokens are sent to the wallet address. With `NativeEth::Wrap` the router's
+    /// swapExactETHForTokens is used and the input is sent as value; with `NativeEth::Unwrap`
+    /// swapExactTokensForETH is used so the wallet receives native ETH. The call waits for the
+    /// transaction receipt.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount_in` - The input amount to swap
+    /// * `amount_out_min` - The minimum output amount (for slippage protection)
+    /// * `path` - Array of token addresses representing the swap path
+    /// * `deadline` - Unix timestamp deadline for the swap
+    /// * `native` - Take the input as native ETH (`Wrap`) or deliver the output as native ETH
+    ///   (`Unwrap`); the pools still trade WETH
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SwapExecution)` - The transaction hash, status, and gas used from the receipt
+    /// * `Err(RepositoryError)` - If no wallet is configured or the transaction could not be sent
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let execution = repository.execute_swap(amount_in, min_out, path, deadline, NativeEth::None).await?;
+    /// println!("Sent {}", execution.transaction_hash);
+    /// ```
+    async fn execute_swap(
+        &self,
+        amount_in: U256,
+        amount_out_min: U256,
+        path: Vec<Address>,
+        deadline: U256,
+        native: NativeEth,
+    ) -> RepoResult<SwapExecution>;
+
+    /// Broadcasts an ERC20 `approve(spender, amount)` transaction signed by the configured wallet.
+    ///
+    /// The call waits for the transaction receipt.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The ERC20 token contract address
+    /// * `spender` - The address allowed to spend the tokens (e.g., a Uniswap router)
+    /// * `amount` - The new allowance; `U256::MAX` grants an unlimited approval
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SwapExecution)` - The transaction hash, status, and gas used from the receipt
+    /// * `Err(RepositoryError)` - If no wallet is configured or the transaction could not be sent
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let execution = repository.approve_token(usdc, router, U256::MAX).await?;
+    /// println!("Sent {}", execution.transaction_hash);
+    /// ```
+    async fn approve_token(
+        &self,
+        token: Address,
+        spender: Address,
+        amount: U256,
+    ) -> RepoResult<SwapExecution>;
+
+    /// Broadcasts a Uniswap V3 exactInputSingle transaction signed by the configured wallet.
+    ///
+    /// The output tokens are sent to the wallet address. With `NativeEth::Wrap` the input is sent
+    /// as value and wrapped by the router; with `NativeEth::Unwrap` the swap and an unwrapWETH9
+    /// call are batched in a router multicall so the wallet receives native ETH. The call waits
+    /// for the transaction receipt.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_in` - The input token address
+    /// * `token_out` - The output token address
+    /// * `amount_in` - The input amount to swap
+    /// * `amount_out_min` - The minimum output amount (for slippage protection)
+    /// * `fee` - The pool fee tier (500 for 0.05%, 3000 for 0.3%, 10000 for 1%)
+    /// * `deadline` - Unix timestamp deadline for the swap
+    /// * `native` - Take the input as native ETH (`Wrap`) or deliver the output as native ETH
+    ///   (`Unwrap`); the pools still trade WETH
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SwapExecution)` - The transaction hash, status, and gas used from the receipt
+    /// * `Err(RepositoryError)` - If no wallet is configured or the transaction could not be sent
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let execution = repository.execute_v3_swap(token_a, token_b, amount, min_out, 3000, deadline, NativeEth::None).await?;
+    /// println!("Sent {}", execution.transaction_hash);
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_v3_swap(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        amount_in: U256,
+        amount_out_min: U256,
+        fee: u32,
+        deadline: U256,
+        native: NativeEth,
+    ) -> RepoResult<SwapExecution>;
 }
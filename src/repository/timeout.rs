@@ -0,0 +1,470 @@
+//! Per-call timeout for repository calls
+//!
+//! A stalled RPC node would otherwise hold a tool call open indefinitely. [`TimeoutRepository`]
+//! wraps any [`EthereumRepository`] and fails calls that exceed `rpc.timeout_ms` with
+//! [`RepositoryError::Timeout`]. Being the one place every call passes through, it also records
+//! each call's latency for the `/metrics` endpoint.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use ::alloy::primitives::{Address, B256, Signature, U256};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+
+use crate::telemetry::record_rpc_call;
+
+use super::{
+    EthereumRepository, FeeEstimates, NativeEth, PairReserves, PermitState, RepoResult,
+    RepositoryError, SwapEventLog, SwapExecution, TokenBalance, TokenInfo, TokenMetadata,
+    TransactionDetails, V3PoolState, WalletSnapshot, WalletTransaction,
+};
+
+/// Repository decorator that bounds the duration of every read call
+///
+/// Calls that broadcast a transaction (`execute_swap`, `execute_v3_swap`, `approve_token`)
+/// are passed through untimed: giving up after the transaction was sent would report a
+/// failure for a swap or approval that may still be mined.
+pub struct TimeoutRepository {
+    inner: Box<dyn EthereumRepository>,
+    timeout: Duration,
+}
+
+impl TimeoutRepository {
+    /// Wrap `inner` so each call fails after `timeout`
+    pub fn new(inner: Box<dyn EthereumRepository>, timeout: Duration) -> Self {
+        Self { inner, timeout }
+    }
+
+    async fn timed<T>(
+        &self,
+        method: &'static str,
+        call: impl Future<Output = RepoResult<T>>,
+    ) -> RepoResult<T> {
+        observed(method, with_timeout(self.timeout, method, call)).await
+    }
+}
+
+/// Run `call` and record its latency under `method`
+async fn observed<T>(
+    method: &'static str,
+    call: impl Future<Output = RepoResult<T>>,
+) -> RepoResult<T> {
+    let started = Instant::now();
+    let result = call.await;
+    record_rpc_call(method, started.elapsed(), &result);
+    result
+}
+
+/// Run `call`, failing with [`RepositoryError::Timeout`] if it takes longer than `timeout`
+async fn with_timeout<T>(
+    timeout: Duration,
+    method: &'static str,
+    call: impl Future<Output = RepoResult<T>>,
+) -> RepoResult<T> {
+    tokio::time::timeout(timeout, call)
+        .await
+        .unwrap_or_else(|_| {
+            Err(RepositoryError::Timeout(format!(
+                "{method} did not complete within {}ms",
+                timeout.as_millis()
+            )))
+        })
+}
+
+#[async_trait]
+impl EthereumRepository for TimeoutRepository {
+    fn wallet_address(&self) -> Option<Address> {
+        self.inner.wallet_address()
+    }
+
+    fn sign_hash(&self, hash: B256) -> RepoResult<Signature> {
+        self.inner.sign_hash(hash)
+    }
+
+    async fn get_eth_balance(&self, address: Address) -> RepoResult<U256> {
+        self.timed("get_eth_balance", self.inner.get_eth_balance(address))
+            .await
+    }
+
+    async fn get_erc20_balance(&self, token: Address, owner: Address) -> RepoResult<TokenBalance> {
+        self.timed(
+            "get_erc20_balance",
+            self.inner.get_erc20_balance(token, owner),
+        )
+        .await
+    }
+
+    async fn get_erc20_allowance(
+        &self,
+        token: Address,
+        owner: Address,
+        spender: Address,
+    ) -> RepoResult<U256> {
+        self.timed(
+            "get_erc20_allowance",
+            self.inner.get_erc20_allowance(token, owner, spender),
+        )
+        .await
+    }
+
+    async fn get_permit_state(&self, token: Address, owner: Address) -> RepoResult<PermitState> {
+        self.timed(
+            "get_permit_state",
+            self.inner.get_permit_state(token, owner),
+        )
+        .await
+    }
+
+    async fn get_token_metadata(&self, token: Address) -> RepoResult<TokenMetadata> {
+        self.timed("get_token_metadata", self.inner.get_token_metadata(token))
+            .await
+    }
+
+    async fn get_token_info(&self, token: Address) -> RepoResult<TokenInfo> {
+        self.timed("get_token_info", self.inner.get_token_info(token))
+            .await
+    }
+
+    async fn get_gas_price(&self) -> RepoResult<u128> {
+        self.timed("get_gas_price", self.inner.get_gas_price())
+            .await
+    }
+
+    async fn get_fee_estimates(&self) -> RepoResult<FeeEstimates> {
+        self.timed("get_fee_estimates", self.inner.get_fee_estimates())
+            .await
+    }
+
+    async fn get_wallet_snapshot(
+        &self,
+        owner: Address,
+        tokens: Vec<Address>,
+    ) -> RepoResult<WalletSnapshot> {
+        self.timed(
+            "get_wallet_snapshot",
+            self.inner.get_wallet_snapshot(owner, tokens),
+        )
+        .await
+    }
+
+    async fn get_chain_id(&self) -> RepoResult<u64> {
+        self.timed("get_chain_id", self.inner.get_chain_id()).await
+    }
+
+    async fn get_block_number(&self) -> RepoResult<u64> {
+        self.timed("get_block_number", self.inner.get_block_number())
+            .await
+    }
+
+    async fn get_block_timestamp(&self) -> RepoResult<u64> {
+        self.timed("get_block_timestamp", self.inner.get_block_timestamp())
+            .await
+    }
+
+    async fn get_uniswap_pair_reserves(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        block: Option<u64>,
+    ) -> RepoResult<(U256, U256, Address, Address)> {
+        self.timed(
+            "get_uniswap_pair_reserves",
+            self.inner
+                .get_uniswap_pair_reserves(token_a, token_b, block),
+        )
+        .await
+    }
+
+    async fn get_uniswap_pair_state(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        block: Option<u64>,
+    ) -> RepoResult<PairReserves> {
+        self.timed(
+            "get_uniswap_pair_state",
+            self.inner.get_uniswap_pair_state(token_a, token_b, block),
+        )
+        .await
+    }
+
+    async fn get_eth_usd_price(&self, block: Option<u64>) -> RepoResult<Decimal> {
+        self.timed("get_eth_usd_price", self.inner.get_eth_usd_price(block))
+            .await
+    }
+
+    async fn get_chainlink_price(
+        &self,
+        feed: Address,
+        block: Option<u64>,
+    ) -> RepoResult<(Decimal, u64)> {
+        self.timed(
+            "get_chainlink_price",
+            self.inner.get_chainlink_price(feed, block),
+        )
+        .await
+    }
+
+    async fn resolve_ens(&self, name: &str) -> RepoResult<Address> {
+        self.timed("resolve_ens", self.inner.resolve_ens(name))
+            .await
+    }
+
+    async fn get_v2_swap_events(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        from_block: u64,
+        to_block: u64,
+    ) -> RepoResult<Vec<SwapEventLog>> {
+        self.timed(
+            "get_v2_swap_events",
+            self.inner
+                .get_v2_swap_events(token_a, token_b, from_block, to_block),
+        )
+        .await
+    }
+
+    async fn get_wallet_transactions(
+        &self,
+        address: Address,
+        from_block: u64,
+        to_block: u64,
+    ) -> RepoResult<Vec<WalletTransaction>> {
+        self.timed(
+            "get_wallet_transactions",
+            self.inner
+                .get_wallet_transactions(address, from_block, to_block),
+        )
+        .await
+    }
+
+    async fn get_transaction(&self, hash: B256) -> RepoResult<Option<TransactionDetails>> {
+        self.timed("get_transaction", self.inner.get_transaction(hash))
+            .await
+    }
+
+    async fn get_transaction_receipt(&self, hash: B256) -> RepoResult<Option<SwapExecution>> {
+        self.timed(
+            "get_transaction_receipt",
+            self.inner.get_transaction_receipt(hash),
+        )
+        .await
+    }
+
+    async fn get_swap_amounts_out(
+        &self,
+        amount_in: U256,
+        path: Vec<Address>,
+    ) -> RepoResult<Vec<U256>> {
+        self.timed(
+            "get_swap_amounts_out",
+            self.inner.get_swap_amounts_out(amount_in, path),
+        )
+        .await
+    }
+
+    async fn get_swap_amounts_in(
+        &self,
+        amount_out: U256,
+        path: Vec<Address>,
+    ) -> RepoResult<Vec<U256>> {
+        self.timed(
+            "get_swap_amounts_in",
+            self.inner.get_swap_amounts_in(amount_out, path),
+        )
+        .await
+    }
+
+    async fn simulate_swap(
+        &self,
+        from: Address,
+        amount_in: U256,
+        amount_out_min: U256,
+        path: Vec<Address>,
+        deadline: U256,
+        native: NativeEth,
+    ) -> RepoResult<u64> {
+        self.timed(
+            "simulate_swap",
+            self.inner
+                .simulate_swap(from, amount_in, amount_out_min, path, deadline, native),
+        )
+        .await
+    }
+
+    async fn detect_rebasing(&self, token: Address) -> RepoResult<Option<&'static str>> {
+        self.timed("detect_rebasing", self.inner.detect_rebasing(token))
+            .await
+    }
+
+    async fn get_v3_quote(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        amount_in: U256,
+        fee: u32,
+    ) -> RepoResult<(U256, u64)> {
+        self.timed(
+            "get_v3_quote",
+            self.inner.get_v3_quote(token_in, token_out, amount_in, fee),
+        )
+        .await
+    }
+
+    async fn get_v3_quote_exact_output(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        amount_out: U256,
+        fee: u32,
+    ) -> RepoResult<(U256, u64)> {
+        self.timed(
+            "get_v3_quote_exact_output",
+            self.inner
+                .get_v3_quote_exact_output(token_in, token_out, amount_out, fee),
+        )
+        .await
+    }
+
+    async fn get_v3_pool_state(
+        &self,
+        token_a: Address,
+        token_b: Address,
+        fee: u32,
+    ) -> RepoResult<V3PoolState> {
+        self.timed(
+            "get_v3_pool_state",
+            self.inner.get_v3_pool_state(token_a, token_b, fee),
+        )
+        .await
+    }
+
+    async fn get_v3_quote_multihop(
+        &self,
+        path: Vec<(Address, u32)>,
+        token_out: Address,
+        amount_in: U256,
+    ) -> RepoResult<(U256, u64)> {
+        self.timed(
+            "get_v3_quote_multihop",
+            self.inner.get_v3_quote_multihop(path, token_out, amount_in),
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn simulate_v3_swap(
+        &self,
+        from: Address,
+        token_in: Address,
+        token_out: Address,
+        amount_in: U256,
+        amount_out_min: U256,
+        fee: u32,
+        deadline: U256,
+        native: NativeEth,
+    ) -> RepoResult<u64> {
+        self.timed(
+            "simulate_v3_swap",
+            self.inner.simulate_v3_swap(
+                from,
+                token_in,
+                token_out,
+                amount_in,
+                amount_out_min,
+                fee,
+                deadline,
+                native,
+            ),
+        )
+        .await
+    }
+
+    async fn execute_swap(
+        &self,
+        amount_in: U256,
+        amount_out_min: U256,
+        path: Vec<Address>,
+        deadline: U256,
+        native: NativeEth,
+    ) -> RepoResult<SwapExecution> {
+        observed(
+            "execute_swap",
+            self.inner
+                .execute_swap(amount_in, amount_out_min, path, deadline, native),
+        )
+        .await
+    }
+
+    async fn approve_token(
+        &self,
+        token: Address,
+        spender: Address,
+        amount: U256,
+    ) -> RepoResult<SwapExecution> {
+        observed(
+            "approve_token",
+            self.inner.approve_token(token, spender, amount),
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_v3_swap(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        amount_in: U256,
+        amount_out_min: U256,
+        fee: u32,
+        deadline: U256,
+        native: NativeEth,
+    ) -> RepoResult<SwapExecution> {
+        observed(
+            "execute_v3_swap",
+            self.inner.execute_v3_swap(
+                token_in,
+                token_out,
+                amount_in,
+                amount_out_min,
+                fee,
+                deadline,
+                native,
+            ),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_with_timeout_fails_slow_calls() {
+        let slow = async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(U256::ZERO)
+        };
+
+        let result = with_timeout(Duration::from_millis(10), "get_eth_balance", slow).await;
+
+        match result {
+            Err(RepositoryError::Timeout(msg)) => {
+                assert_eq!(msg, "get_eth_balance did not complete within 10ms")
+            }
+            other => panic!("expected a timeout, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_with_timeout_passes_fast_results_through() {
+        let fast = async { Err::<U256, _>(RepositoryError::RateLimited("429".to_string())) };
+
+        let result = with_timeout(Duration::from_secs(1), "get_eth_balance", fast).await;
+
+        assert!(matches!(result, Err(RepositoryError::RateLimited(_))));
+    }
+}
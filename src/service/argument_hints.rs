@@ -0,0 +1,333 @@
+//! Helpful errors for tool arguments with the wrong JSON shape
+//!
+//! rmcp rejects arguments that fail to deserialize with the bare serde message, which rarely
+//! names the field (e.g. "invalid type: integer `2`, expected a string"). With
+//! `server.argument_hints` enabled, the arguments are checked against the tool's input schema
+//! instead, so the error names each offending field, suggests a corrected value and shows the
+//! expected shape. This is about the wire format only; semantic validation stays in the tools.
+
+use std::cmp::Reverse;
+
+use rmcp::model::JsonObject;
+use serde_json::{Map, Value};
+
+/// Prefix of the error rmcp returns when tool arguments do not deserialize
+pub const DESERIALIZE_ERROR_PREFIX: &str = "failed to deserialize parameters";
+
+/// Describe why `arguments` do not fit the input `schema` of `tool`
+///
+/// Falls back to the raw serde message when no field-level problem is found.
+pub fn describe_argument_error(
+    tool: &str,
+    schema: &JsonObject,
+    arguments: &JsonObject,
+    raw: &str,
+) -> String {
+    let variant = select_variant(schema, arguments);
+    let empty = Map::new();
+    let properties = variant
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    let required = required_fields(variant);
+
+    let mut problems = vec![];
+    for (name, value) in arguments {
+        let Some(property) = properties.get(name).and_then(Value::as_object) else {
+            continue;
+        };
+        if !fits(value, property) {
+            let expected = describe_type(property);
+            let mut problem = format!("`{name}` must be {expected}, got {}", describe_value(value));
+            if let Some(suggestion) = suggest(value, property) {
+                problem.push_str(&format!(" (try \"{name}\": {suggestion})"));
+            }
+            problems.push(problem);
+        }
+    }
+    for name in &required {
+        if !arguments.contains_key(*name) {
+            let expected = properties
+                .get(*name)
+                .and_then(Value::as_object)
+                .map(describe_type)
+                .unwrap_or_else(|| "a value".to_string());
+            problems.push(format!("`{name}` is missing (expected {expected})"));
+        }
+    }
+
+    let example = Value::Object(example_arguments(properties, &required, arguments));
+    if problems.is_empty() {
+        format!("Invalid arguments for `{tool}`: {raw}. Expected shape: {example}")
+    } else {
+        format!(
+            "Invalid arguments for `{tool}`: {}. Expected shape: {example}",
+            problems.join("; ")
+        )
+    }
+}
+
+/// The object schema to check against; for untagged enums (`anyOf`), the variant whose
+/// required fields the arguments cover best
+fn select_variant<'a>(schema: &'a JsonObject, arguments: &JsonObject) -> &'a JsonObject {
+    let Some(variants) = schema
+        .get("anyOf")
+        .or_else(|| schema.get("oneOf"))
+        .and_then(Value::as_array)
+    else {
+        return schema;
+    };
+
+    // Prefer covered required fields, then fewer missing ones; `max_by_key` keeps the last of
+    // equal variants, so iterate in reverse to let the first one win ties
+    variants
+        .iter()
+        .rev()
+        .filter_map(Value::as_object)
+        .max_by_key(|variant| {
+            let required = required_fields(variant);
+            let covered = required
+                .iter()
+                .filter(|name| arguments.contains_key(**name))
+                .count();
+            (covered, Reverse(required.len() - covered))
+        })
+        .unwrap_or(schema)
+}
+
+fn required_fields(schema: &JsonObject) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn schema_type(property: &JsonObject) -> Option<&str> {
+    property.get("type").and_then(Value::as_str)
+}
+
+/// Whether a value has the JSON type a property schema asks for
+fn fits(value: &Value, property: &JsonObject) -> bool {
+    let nullable = property.get("nullable").and_then(Value::as_bool) == Some(true);
+    match (schema_type(property), value) {
+        (_, Value::Null) => nullable,
+        (Some("string"), value) => value.is_string(),
+        (Some("integer"), value) => value.is_i64() || value.is_u64(),
+        (Some("number"), value) => value.is_number(),
+        (Some("boolean"), value) => value.is_boolean(),
+        (Some("object"), value) => value.is_object(),
+        (Some("array"), Value::Array(items)) => {
+            match property.get("items").and_then(Value::as_object) {
+                Some(item) => items.iter().all(|value| fits(value, item)),
+                None => true,
+            }
+        }
+        (Some("array"), _) => false,
+        _ => true,
+    }
+}
+
+/// The value converted to the type the property expects, when the intent is unambiguous
+fn suggest(value: &Value, property: &JsonObject) -> Option<Value> {
+    match (schema_type(property), value) {
+        (Some("string"), Value::Number(_) | Value::Bool(_)) => Some(Value::from(value.to_string())),
+        (Some("integer"), Value::String(s)) => s
+            .trim()
+            .parse::<u64>()
+            .map(Value::from)
+            .or_else(|_| s.trim().parse::<i64>().map(Value::from))
+            .ok(),
+        (Some("number"), Value::String(s)) => s.trim().parse::<f64>().ok().map(Value::from),
+        (Some("boolean"), Value::String(s)) => s.trim().parse::<bool>().ok().map(Value::from),
+        (Some("array"), value) if !value.is_array() => {
+            let item = property.get("items").and_then(Value::as_object)?;
+            let value = if fits(value, item) {
+                value.clone()
+            } else {
+                suggest(value, item)?
+            };
+            Some(Value::Array(vec![value]))
+        }
+        _ => None,
+    }
+}
+
+fn describe_type(property: &JsonObject) -> String {
+    match schema_type(property) {
+        Some("string") => "a string".to_string(),
+        Some("integer") => "an integer".to_string(),
+        Some("number") => "a number".to_string(),
+        Some("boolean") => "a boolean".to_string(),
+        Some("object") => "an object".to_string(),
+        Some("array") => match property
+            .get("items")
+            .and_then(Value::as_object)
+            .and_then(schema_type)
+        {
+            Some(item) => format!("an array of {item}s"),
+            None => "an array".to_string(),
+        },
+        _ => "a value of another type".to_string(),
+    }
+}
+
+fn describe_value(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => format!("boolean {b}"),
+        Value::Number(n) => format!("number {n}"),
+        Value::String(s) => format!("string \"{s}\""),
+        Value::Array(_) => "an array".to_string(),
+        Value::Object(_) => "an object".to_string(),
+    }
+}
+
+/// Arguments in the expected shape: required fields and the fields that were sent, using the
+/// sent value where it fits, its corrected form where one is obvious, or a placeholder
+fn example_arguments(
+    properties: &JsonObject,
+    required: &[&str],
+    arguments: &JsonObject,
+) -> JsonObject {
+    properties
+        .iter()
+        .filter(|(name, _)| required.contains(&name.as_str()) || arguments.contains_key(*name))
+        .filter_map(|(name, property)| {
+            let property = property.as_object()?;
+            let value = match arguments.get(name) {
+                Some(value) if fits(value, property) => value.clone(),
+                Some(value) => suggest(value, property).unwrap_or_else(|| placeholder(property)),
+                None => placeholder(property),
+            };
+            Some((name.clone(), value))
+        })
+        .collect()
+}
+
+/// Example value for a property: the first quoted "e.g." example in its description for
+/// strings, otherwise a neutral value of the right type
+fn placeholder(property: &JsonObject) -> Value {
+    match schema_type(property) {
+        Some("string") => Value::from(description_example(property).unwrap_or("<string>")),
+        Some("integer") | Some("number") => Value::from(0),
+        Some("boolean") => Value::from(false),
+        Some("array") => Value::Array(vec![]),
+        Some("object") => Value::Object(Map::new()),
+        _ => Value::Null,
+    }
+}
+
+fn description_example(property: &JsonObject) -> Option<&str> {
+    let description = property.get("description").and_then(Value::as_str)?;
+    let after = &description[description.find("e.g.")?..];
+    let start = after.find('"')? + 1;
+    let len = after[start..].find('"')?;
+    Some(&after[start..start + len])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn object(value: Value) -> JsonObject {
+        match value {
+            Value::Object(object) => object,
+            _ => unreachable!(),
+        }
+    }
+
+    fn swap_schema() -> JsonObject {
+        object(json!({
+            "type": "object",
+            "properties": {
+                "amount": {
+                    "description": "Amount to swap (e.g., \"1\" for 1 ETH)",
+                    "type": "string"
+                },
+                "from_token": {"type": "string"},
+                "uniswap_version": {"nullable": true, "type": "string"},
+                "summarize": {"nullable": true, "type": "boolean"}
+            },
+            "required": ["amount", "from_token"]
+        }))
+    }
+
+    #[test]
+    fn test_describe_argument_error_names_field_and_suggests_fix() {
+        let arguments = object(json!({"amount": 1.5, "from_token": "WETH", "uniswap_version": 3}));
+
+        let message = describe_argument_error("swap_tokens", &swap_schema(), &arguments, "raw");
+
+        assert!(message.contains("`amount` must be a string, got number 1.5"));
+        assert!(message.contains("(try \"amount\": \"1.5\")"));
+        assert!(message.contains("`uniswap_version` must be a string, got number 3"));
+        assert!(message.contains(
+            r#"Expected shape: {"amount":"1.5","from_token":"WETH","uniswap_version":"3"}"#
+        ));
+        assert!(!message.contains("raw"));
+    }
+
+    #[test]
+    fn test_describe_argument_error_reports_missing_fields_with_example() {
+        let arguments = object(json!({"from_token": "WETH", "summarize": "true"}));
+
+        let message = describe_argument_error("swap_tokens", &swap_schema(), &arguments, "raw");
+
+        assert!(message.contains("`amount` is missing (expected a string)"));
+        assert!(message.contains("(try \"summarize\": true)"));
+        assert!(message.contains(r#"{"amount":"1","from_token":"WETH","summarize":true}"#));
+    }
+
+    #[test]
+    fn test_describe_argument_error_picks_matching_any_of_variant() {
+        let schema = object(json!({
+            "anyOf": [
+                {
+                    "type": "object",
+                    "properties": {"symbol": {"type": "string"}},
+                    "required": ["symbol"]
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "contract_address": {"type": "string"},
+                        "block_number": {"nullable": true, "type": "integer"}
+                    },
+                    "required": ["contract_address"]
+                }
+            ]
+        }));
+        let arguments = object(json!({"contract_address": "0xabc", "block_number": "18000000"}));
+
+        let message = describe_argument_error("get_token_price", &schema, &arguments, "raw");
+
+        assert!(message.contains("`block_number` must be an integer, got string \"18000000\""));
+        assert!(message.contains("(try \"block_number\": 18000000)"));
+    }
+
+    #[test]
+    fn test_describe_argument_error_wraps_single_value_into_array() {
+        let schema = object(json!({
+            "type": "object",
+            "properties": {"tokens": {"type": "array", "items": {"type": "string"}}}
+        }));
+        let arguments = object(json!({"tokens": "USDC"}));
+
+        let message = describe_argument_error("get_balances", &schema, &arguments, "raw");
+
+        assert!(message.contains("`tokens` must be an array of strings, got string \"USDC\""));
+        assert!(message.contains(r#"(try "tokens": ["USDC"])"#));
+    }
+
+    #[test]
+    fn test_describe_argument_error_falls_back_to_raw_message() {
+        let arguments = object(json!({"amount": "1", "from_token": "WETH"}));
+
+        let message =
+            describe_argument_error("swap_tokens", &swap_schema(), &arguments, "unknown variant");
+
+        assert!(message.starts_with("Invalid arguments for `swap_tokens`: unknown variant."));
+    }
+}
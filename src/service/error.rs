@@ -16,6 +16,10 @@ pub enum ServiceError {
     #[error("Token not found or not supported: {0}")]
     TokenNotFound(String),
 
+    /// The transaction hash is not a 32-byte hex string.
+    #[error("Invalid transaction hash: {0}")]
+    InvalidTransactionHash(String),
+
     /// The requested amount is invalid (e.g., negative, zero, or malformed).
     #[error("Invalid amount: {0}")]
     InvalidAmount(String),
@@ -44,10 +48,22 @@ pub enum ServiceError {
     #[error("Insufficient liquidity in pool: {0}")]
     InsufficientLiquidity(String),
 
+    /// The pool reserves behind a price have not changed for longer than the caller accepts.
+    #[error("Stale price: {0}")]
+    StalePrice(String),
+
     /// Swap simulation failed.
     #[error("Swap simulation failed: {0}")]
     SwapSimulationFailed(String),
 
+    /// The requested RPC endpoint is not in `rpc.allowed_urls`.
+    #[error("RPC URL not allowed: {0}")]
+    RpcUrlNotAllowed(String),
+
+    /// Sending transactions is disabled or no wallet is configured.
+    #[error("Execution not allowed: {0}")]
+    ExecutionNotAllowed(String),
+
     // External API errors
     /// An error occurred while querying an external API (e.g., CoinGecko).
     #[error("External API error: {0}")]
@@ -58,6 +74,14 @@ pub enum ServiceError {
     #[error("Blockchain connection error: {0}")]
     BlockchainError(String),
 
+    /// The RPC provider rate-limited the request; retry after a short delay.
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+
+    /// The RPC provider did not respond within `rpc.timeout_ms`.
+    #[error("RPC timeout: {0}")]
+    Timeout(String),
+
     /// An unexpected internal error occurred.
     #[error("Internal error: {0}")]
     InternalError(String),
@@ -71,6 +95,14 @@ impl From<RepositoryError> for ServiceError {
             | RepositoryError::ContractError(msg) => {
                 ServiceError::BlockchainError(format!("Failed to interact with blockchain: {msg}"))
             }
+            RepositoryError::RateLimited(msg) => ServiceError::RateLimited(msg),
+            RepositoryError::Timeout(msg) => ServiceError::Timeout(msg),
+            RepositoryError::CircuitOpen(msg) => {
+                ServiceError::BlockchainError(format!("RPC provider unavailable: {msg}"))
+            }
+            RepositoryError::Reverted { reason } => {
+                ServiceError::SwapSimulationFailed(format!("execution reverted: {reason}"))
+            }
             RepositoryError::ParseError(msg) => ServiceError::InvalidWalletAddress(msg),
             RepositoryError::Other(msg) => ServiceError::InternalError(msg),
         }
@@ -1,4 +1,7 @@
+pub mod argument_hints;
 pub mod error;
+pub mod number_format;
+pub mod price_feed;
 pub mod token_registry;
 pub mod trading;
 pub mod types;
@@ -0,0 +1,151 @@
+//! Optional JSON-number rendering of tool responses
+//!
+//! Response types carry numeric values as strings so no precision is lost. Deployments that
+//! prefer JSON numbers enable `display.numbers: number`, and tool results are rewritten here:
+//! a numeric string becomes a number only when an f64 represents it exactly.
+
+use serde_json::{Number, Value};
+
+/// Most significant digits an f64 always round-trips
+const MAX_SAFE_DIGITS: usize = 15;
+
+/// Replace numeric strings in a tool response with JSON numbers where precision allows
+///
+/// Only plain decimals ("2000", "-0.35") with at most 15 significant digits are converted.
+/// Longer values, such as raw wei amounts, and anything else (addresses, hashes, "N/A")
+/// stay strings.
+pub fn numeric_strings_to_numbers(value: &mut Value) {
+    match value {
+        Value::String(s) => {
+            if let Some(number) = safe_number(s) {
+                *value = Value::Number(number);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(numeric_strings_to_numbers),
+        Value::Object(fields) => fields.values_mut().for_each(numeric_strings_to_numbers),
+        _ => {}
+    }
+}
+
+/// Widen every `"type": "string"` in an output schema to also accept numbers
+pub fn allow_numbers_in_schema(schema: &mut Value) {
+    match schema {
+        Value::Object(fields) => {
+            match fields.get_mut("type") {
+                Some(Value::String(ty)) if ty == "string" => {
+                    fields.insert("type".to_string(), serde_json::json!(["string", "number"]));
+                }
+                Some(Value::Array(types))
+                    if types.iter().any(|t| t == "string")
+                        && !types.iter().any(|t| t == "number") =>
+                {
+                    types.push(Value::from("number"));
+                }
+                _ => {}
+            }
+            fields.values_mut().for_each(allow_numbers_in_schema);
+        }
+        Value::Array(items) => items.iter_mut().for_each(allow_numbers_in_schema),
+        _ => {}
+    }
+}
+
+/// Parse a plain decimal string into a JSON number if it has few enough digits to be exact
+fn safe_number(s: &str) -> Option<Number> {
+    let unsigned = s.strip_prefix('-').unwrap_or(s);
+    let (int, frac) = match unsigned.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (unsigned, None),
+    };
+
+    let is_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    if !is_digits(int) || (int.len() > 1 && int.starts_with('0')) {
+        return None;
+    }
+    if frac.is_some_and(|frac| !is_digits(frac)) {
+        return None;
+    }
+
+    let digits = format!("{int}{}", frac.unwrap_or_default());
+    if digits.trim_start_matches('0').len() > MAX_SAFE_DIGITS {
+        return None;
+    }
+
+    match frac {
+        None => s.parse::<i64>().ok().map(Number::from),
+        Some(_) => s.parse::<f64>().ok().and_then(Number::from_f64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_numeric_strings_to_numbers_converts_safe_values() {
+        let mut value = json!({
+            "price_usd": "3024.58",
+            "price_eth": "0.0003305",
+            "estimated_gas": "150000",
+            "change": "-1.25",
+            "nested": [{"amount": "2000"}],
+        });
+
+        numeric_strings_to_numbers(&mut value);
+
+        assert_eq!(
+            value,
+            json!({
+                "price_usd": 3024.58,
+                "price_eth": 0.0003305,
+                "estimated_gas": 150000,
+                "change": -1.25,
+                "nested": [{"amount": 2000}],
+            })
+        );
+    }
+
+    #[test]
+    fn test_numeric_strings_to_numbers_keeps_unsafe_values() {
+        let mut value = json!({
+            "raw": "330500000000000000",
+            "long_fraction": "0.1234567890123456",
+            "address": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "impact": "N/A (V3)",
+            "leading_zero": "007",
+            "exponent": "1e5",
+            "symbol": "USDC",
+        });
+        let expected = value.clone();
+
+        numeric_strings_to_numbers(&mut value);
+
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn test_allow_numbers_in_schema_widens_string_types() {
+        let mut schema = json!({
+            "type": "object",
+            "properties": {
+                "price_usd": {"type": "string"},
+                "summary": {"type": ["string", "null"]},
+                "block_number": {"type": "integer"},
+            },
+        });
+
+        allow_numbers_in_schema(&mut schema);
+
+        assert_eq!(schema["type"], "object");
+        assert_eq!(
+            schema["properties"]["price_usd"]["type"],
+            json!(["string", "number"])
+        );
+        assert_eq!(
+            schema["properties"]["summary"]["type"],
+            json!(["string", "null", "number"])
+        );
+        assert_eq!(schema["properties"]["block_number"]["type"], "integer");
+    }
+}
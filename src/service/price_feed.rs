@@ -0,0 +1,164 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use alloy::primitives::Address;
+use rust_decimal::Decimal;
+
+use crate::config::{Config, PriceApiConfig};
+use crate::repository::{ChainConfig, CircuitBreaker};
+use crate::service::{ServiceError, ServiceResult};
+
+/// USD prices from the CoinGecko API, guarded by a circuit breaker
+#[derive(Debug)]
+pub struct CoinGeckoPriceFeed {
+    client: reqwest::Client,
+    base_url: String,
+    platform: Option<&'static str>,
+    breaker: CircuitBreaker,
+}
+
+impl CoinGeckoPriceFeed {
+    pub fn new(config: &PriceApiConfig, chain: &ChainConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .expect("Failed to build HTTP client");
+
+        Self {
+            client,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            platform: Self::platform(chain.chain_id),
+            breaker: CircuitBreaker::new(
+                "CoinGecko",
+                config.failure_threshold,
+                Duration::from_secs(config.cooldown_secs),
+            ),
+        }
+    }
+
+    /// The feed `config.price_api` enables, if any, ready to be shared by every session
+    pub fn from_config(config: &Config) -> Option<Arc<Self>> {
+        config
+            .price_api
+            .enabled
+            .then(|| Arc::new(Self::new(&config.price_api, &config.network.chain_config())))
+    }
+
+    /// CoinGecko asset platform id for a chain (testnets are not listed)
+    fn platform(chain_id: u64) -> Option<&'static str> {
+        match chain_id {
+            1 => Some("ethereum"),
+            42161 => Some("arbitrum-one"),
+            8453 => Some("base"),
+            10 => Some("optimistic-ethereum"),
+            _ => None,
+        }
+    }
+
+    /// USD price of a token contract
+    ///
+    /// Fails fast with `ExternalApiError` while the circuit breaker is open.
+    pub async fn token_usd_price(&self, token: Address) -> ServiceResult<Decimal> {
+        let platform = self.platform.ok_or_else(|| {
+            ServiceError::ExternalApiError("CoinGecko does not list this network".to_string())
+        })?;
+        self.breaker
+            .admit("CoinGecko price request", Instant::now())
+            .map_err(|e| ServiceError::ExternalApiError(e.to_string()))?;
+
+        let url = format!(
+            "{}/simple/token_price/{platform}?contract_addresses={token}&vs_currencies=usd",
+            self.base_url
+        );
+        let result = self
+            .fetch_usd_price(&url, &token.to_string().to_lowercase())
+            .await;
+        self.breaker.record_outcome(result.is_err(), Instant::now());
+        result
+    }
+
+    async fn fetch_usd_price(&self, url: &str, key: &str) -> ServiceResult<Decimal> {
+        let body: serde_json::Value = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| ServiceError::ExternalApiError(format!("CoinGecko request failed: {e}")))?
+            .json()
+            .await
+            .map_err(|e| {
+                ServiceError::ExternalApiError(format!("Invalid CoinGecko response: {e}"))
+            })?;
+
+        parse_usd_price(&body, key)
+    }
+}
+
+/// Extract `{ "<key>": { "usd": <price> } }` from a CoinGecko response
+fn parse_usd_price(body: &serde_json::Value, key: &str) -> ServiceResult<Decimal> {
+    let price = body
+        .get(key)
+        .and_then(|entry| entry.get("usd"))
+        .ok_or_else(|| ServiceError::ExternalApiError(format!("No CoinGecko USD price for {key}")))?
+        .to_string();
+
+    Decimal::from_str(&price)
+        .or_else(|_| Decimal::from_scientific(&price))
+        .map_err(|e| {
+            ServiceError::ExternalApiError(format!("Invalid CoinGecko price {price}: {e}"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_price_feed_skips_api_while_circuit_is_open() {
+        // Nothing listens on port 1, so every request fails at once
+        let config = PriceApiConfig {
+            enabled: true,
+            base_url: "http://127.0.0.1:1".to_string(),
+            failure_threshold: 1,
+            ..PriceApiConfig::default()
+        };
+        let feed = CoinGeckoPriceFeed::new(&config, &ChainConfig::MAINNET);
+        let token = ChainConfig::MAINNET.usdc;
+
+        let err = feed.token_usd_price(token).await.unwrap_err();
+        assert!(
+            err.to_string().contains("CoinGecko request failed"),
+            "{err}"
+        );
+
+        let err = feed.token_usd_price(token).await.unwrap_err();
+        assert!(
+            err.to_string().contains("consecutive CoinGecko failures"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_parse_usd_price() {
+        let key = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+        let body = serde_json::json!({ key: { "usd": 0.9998 } });
+        assert_eq!(
+            parse_usd_price(&body, key).unwrap(),
+            Decimal::from_str("0.9998").unwrap()
+        );
+
+        let body = serde_json::json!({ key: { "usd": 1.2e-8 } });
+        assert_eq!(
+            parse_usd_price(&body, key).unwrap(),
+            Decimal::from_str("0.000000012").unwrap()
+        );
+
+        let body = serde_json::json!({});
+        assert!(matches!(
+            parse_usd_price(&body, key),
+            Err(ServiceError::ExternalApiError(_))
+        ));
+    }
+}
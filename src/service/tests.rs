@@ -1,11 +1,27 @@
+use alloy::primitives::{Address, B256, U256, address};
 use rmcp::handler::server::wrapper::Parameters;
+use rust_decimal::Decimal;
 use tokio::time::{Duration, sleep};
 
-use crate::config::Config;
+use crate::config::{Config, EthPriceSource};
+use crate::repository::mock::MockEthereumRepository;
+use crate::repository::{Network, SwapExecution, V3PoolState};
+use crate::service::price_feed::CoinGeckoPriceFeed;
 use crate::service::trading::EthereumTradingService;
 use crate::service::types::{
-    GetBalanceRequest, GetBalanceResult, GetTokenPriceRequest, GetTokenPriceResult,
+    ApproveTokenRequest, ApproveTokenResult, BuildPermitRequest, BuildPermitResult,
+    ConvertUnitsRequest, ConvertUnitsResult, EstimateRoundTripRequest, EstimateRoundTripResult,
+    EstimateSwapGasRequest, EstimateSwapGasResult, ExecuteSwapResult, GetBalanceRequest,
+    GetBalanceResult, GetBalancesRequest, GetBalancesResult, GetBestSwapRequest, GetBestSwapResult,
+    GetChainStatusResult, GetEthPriceResult, GetEthStablecoinPricesResult, GetMyApprovalRequest,
+    GetMyApprovalResult, GetPoolInfoRequest, GetPoolInfoResult, GetRecentTransactionsRequest,
+    GetRecentTransactionsResult, GetSwapStatusRequest, GetSwapStatusResult, GetTokenInfoRequest,
+    GetTokenInfoResult, GetTokenPriceRequest, GetTokenPriceResult, GetTokenPricesRequest,
+    GetTokenPricesResult, GetTransactionRequest, GetTransactionResult, ImpliedSlippageRequest,
+    ImpliedSlippageResult, ListSupportedTokensRequest, QuotePathRequest, QuotePathResult,
+    SimulatePendingSwapRequest, SimulatePendingSwapResult, SwapTokensRequest, SwapTokensResult,
 };
+use crate::service::{ServiceError, TokenRegistry};
 
 // Vitalik Buterin's address
 const WALLET_ADDRESS: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
@@ -13,9 +29,34 @@ const WALLET_ADDRESS: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
 // ERC20 Token Contract Addresses (Ethereum Mainnet)
 const USDT_CONTRACT_ADDRESS: &str = "0xdac17f958d2ee523a2206206994597c13d831ec7";
 
+// Mainnet registry addresses the mock repository is programmed with
+const WETH: Address = address!("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2");
+const USDC: Address = address!("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+const WBTC: Address = address!("0x2260fac5e5542a773aa44fbcfedf7c193bc2c599");
+const DAI: Address = address!("0x6b175474e89094c44da98b954eedeac495271d0f");
+
 /// Helper function to load test configuration
 async fn get_test_config() -> Config {
-    Config::from_yaml("config/test.yaml").await
+    Config::from_yaml("config/test.yaml")
+        .await
+        .expect("Failed to load test config")
+}
+
+/// Service on a mock repository that knows WETH, USDC and DAI and prices gas at 20 gwei
+fn mock_service(
+    program: impl FnOnce(MockEthereumRepository) -> MockEthereumRepository,
+) -> EthereumTradingService {
+    let repository = MockEthereumRepository::new()
+        .with_token(WETH, "WETH", 18)
+        .with_token(USDC, "USDC", 6)
+        .with_token(DAI, "DAI", 18)
+        .with_gas_price(20_000_000_000);
+    let registry = TokenRegistry::for_chain(&Network::Mainnet.chain_config());
+    EthereumTradingService::with_repository(Box::new(program(repository)), registry)
+}
+
+fn units(amount: u64, decimals: u8) -> U256 {
+    U256::from(amount) * U256::from(10).pow(U256::from(decimals))
 }
 
 /// Add delay between tests to avoid rate limiting
@@ -33,6 +74,8 @@ async fn test_get_balance_with_eth_should_work() {
     let params = Parameters(GetBalanceRequest {
         wallet_address: WALLET_ADDRESS.to_string(),
         token_contract_address: None,
+        summarize: None,
+        rpc_url: None,
     });
 
     let result = service.get_balance(params).await.0;
@@ -65,6 +108,8 @@ async fn test_get_balance_with_erc20_token_should_work() {
     let params = Parameters(GetBalanceRequest {
         wallet_address: WALLET_ADDRESS.to_string(),
         token_contract_address: Some(USDT_CONTRACT_ADDRESS.to_string()),
+        summarize: None,
+        rpc_url: None,
     });
 
     let result = service.get_balance(params).await.0;
@@ -97,6 +142,8 @@ async fn test_get_balance_with_invalid_address_should_return_error() {
     let params = Parameters(GetBalanceRequest {
         wallet_address: "invalid_address".to_string(),
         token_contract_address: None,
+        summarize: None,
+        rpc_url: None,
     });
 
     let result = service.get_balance(params).await.0;
@@ -120,28 +167,65 @@ async fn test_get_balance_with_invalid_address_should_return_error() {
 #[tokio::test]
 #[serial_test::serial]
 #[ignore]
-async fn test_get_token_price_usdc_should_work() {
+async fn test_get_balances_should_work() {
     avoid_rate_limit().await;
     let config = get_test_config().await;
     let service = EthereumTradingService::new(&config);
-    let params = Parameters(GetTokenPriceRequest::Symbol {
-        symbol: "USDC".to_string(),
+    let params = Parameters(GetBalancesRequest {
+        wallet_address: WALLET_ADDRESS.to_string(),
+        tokens: vec![
+            "USDC".to_string(),
+            USDT_CONTRACT_ADDRESS.to_string(),
+            "eth".to_string(),
+            "NOT_A_TOKEN".to_string(),
+        ],
     });
 
+    let result = service.get_balances(params).await.0;
+    match result {
+        GetBalancesResult::Success(resp) => {
+            println!("✅ Balances Response:");
+            for entry in &resp.balances {
+                println!("   {}: {:?} {:?}", entry.token, entry.balance, entry.error);
+            }
+
+            // Native ETH first, "eth" is not repeated, unknown tokens carry an error
+            assert_eq!(resp.balances.len(), 4);
+            assert_eq!(resp.balances[0].token, "ETH");
+            assert_eq!(resp.balances[0].balance.as_ref().unwrap().decimals, 18);
+            assert_eq!(resp.balances[1].balance.as_ref().unwrap().symbol, "USDC");
+            assert_eq!(resp.balances[2].balance.as_ref().unwrap().symbol, "USDT");
+            assert!(resp.balances[3].error.is_some());
+        }
+        GetBalancesResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_token_price_usdc_should_work() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+    let params = Parameters(GetTokenPriceRequest::symbol("USDC"));
+
     let result = service.get_token_price(params).await.0;
     match result {
         GetTokenPriceResult::Success(resp) => {
             println!("✅ USDC Price Response:");
             println!("   Symbol: {}", resp.symbol);
             println!("   Address: {}", resp.address);
-            println!("   Price in USD: ${}", resp.price_usd);
+            println!("   Price in USD: ${}", resp.price_usd.as_deref().unwrap());
             println!("   Price in ETH: {} ETH", resp.price_eth);
             println!("   Timestamp: {}", resp.timestamp);
             println!();
             println!("💡 Usage Examples:");
             println!(
                 "   - 'What's the current price of USDC in USD?' → ${}",
-                resp.price_usd
+                resp.price_usd.as_deref().unwrap()
             );
             println!(
                 "   - 'What's the current price of USDC in ETH?' → {} ETH",
@@ -150,8 +234,9 @@ async fn test_get_token_price_usdc_should_work() {
             println!("   - To convert: 1 USDC = {} ETH", resp.price_eth);
 
             assert_eq!(resp.symbol, "USDC");
+            assert!(resp.usd_price_available);
             // USDC should be close to $1
-            let price_usd: f64 = resp.price_usd.parse().unwrap_or(0.0);
+            let price_usd: f64 = resp.price_usd.as_deref().unwrap().parse().unwrap_or(0.0);
             assert!(
                 price_usd > 0.9 && price_usd < 1.1,
                 "USDC price should be close to $1"
@@ -170,9 +255,7 @@ async fn test_get_token_price_eth_should_work() {
     avoid_rate_limit().await;
     let config = get_test_config().await;
     let service = EthereumTradingService::new(&config);
-    let params = Parameters(GetTokenPriceRequest::Symbol {
-        symbol: "ETH".to_string(),
-    });
+    let params = Parameters(GetTokenPriceRequest::symbol("ETH"));
 
     let result = service.get_token_price(params).await.0;
     match result {
@@ -180,7 +263,7 @@ async fn test_get_token_price_eth_should_work() {
             println!("✅ ETH Price Response:");
             println!("   Symbol: {}", resp.symbol);
             println!("   Address: {}", resp.address);
-            println!("   Price in USD: ${}", resp.price_usd);
+            println!("   Price in USD: ${}", resp.price_usd.as_deref().unwrap());
             println!("   Price in ETH: {} ETH", resp.price_eth);
             println!("   Timestamp: {}", resp.timestamp);
             println!();
@@ -189,7 +272,7 @@ async fn test_get_token_price_eth_should_work() {
             assert_eq!(resp.symbol, "ETH");
             assert_eq!(resp.price_eth, "1.0");
             // ETH price should be reasonable (between $500 and $10000)
-            let price_usd: f64 = resp.price_usd.parse().unwrap_or(0.0);
+            let price_usd: f64 = resp.price_usd.as_deref().unwrap().parse().unwrap_or(0.0);
             assert!(
                 price_usd > 500.0 && price_usd < 10000.0,
                 "ETH price should be reasonable"
@@ -200,3 +283,2265 @@ async fn test_get_token_price_eth_should_work() {
         }
     }
 }
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_token_price_with_explain_should_include_explanation() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+    let params = Parameters(GetTokenPriceRequest::symbol("USDC").with_explain(true));
+
+    let result = service.get_token_price(params).await.0;
+    match result {
+        GetTokenPriceResult::Success(resp) => {
+            println!("✅ USDC Price Explanation:");
+            println!("   {:?}", resp.explanation);
+
+            let explanation = resp.explanation.expect("explanation should be present");
+            assert!(explanation.contains("USDC/WETH"));
+        }
+        GetTokenPriceResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_get_contract_addresses_reports_mainnet_defaults() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let resp = service.get_contract_addresses().await.0;
+    assert_eq!(resp.network, "mainnet");
+    assert_eq!(resp.chain_id, 1);
+    assert_eq!(
+        resp.weth.to_lowercase(),
+        "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"
+    );
+    assert_eq!(
+        resp.uniswap_v2_router.to_lowercase(),
+        "0x7a250d5630b4cf539739df2c5dacb4c659f2488d"
+    );
+    assert!(resp.uniswap_v3_swap_router.is_some());
+    assert!(resp.ens_registry.is_some());
+}
+
+#[tokio::test]
+async fn test_rpc_url_outside_allowlist_is_rejected() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let result = service
+        .get_balance(Parameters(GetBalanceRequest {
+            wallet_address: WALLET_ADDRESS.to_string(),
+            token_contract_address: None,
+            summarize: None,
+            rpc_url: Some("https://mainnet.base.org".to_string()),
+        }))
+        .await;
+    assert!(matches!(
+        result.0,
+        GetBalanceResult::Error {
+            error: ServiceError::RpcUrlNotAllowed(_)
+        }
+    ));
+
+    let mut req = GetTokenPriceRequest::symbol("USDC");
+    req.options_mut().rpc_url = Some("https://mainnet.base.org".to_string());
+    let result = service.get_token_price(Parameters(req)).await;
+    assert!(matches!(
+        result.0,
+        GetTokenPriceResult::Error {
+            error: ServiceError::RpcUrlNotAllowed(_)
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_get_token_price_in_quote_token() {
+    // 1 DAI = 1/3000 ETH and 1 USDC = 1/1500 ETH, so 1 DAI = 0.5 USDC
+    let service = mock_service(|repo| {
+        repo.with_pair(DAI, WETH, units(3_000_000, 18), units(1_000, 18))
+            .with_pair(USDC, WETH, units(1_500_000, 6), units(1_000, 18))
+    });
+
+    let params = Parameters(GetTokenPriceRequest::symbol("DAI").with_quote("usdc"));
+    let response = match service.get_token_price(params).await.0 {
+        GetTokenPriceResult::Success(response) => response,
+        GetTokenPriceResult::Error { error } => panic!("Failed to price DAI in USDC: {error}"),
+    };
+
+    assert_eq!(response.quote_symbol.as_deref(), Some("USDC"));
+    assert_eq!(response.price_in_quote.as_deref(), Some("0.5"));
+}
+
+#[tokio::test]
+async fn test_get_token_price_rejects_quote_without_weth_pair() {
+    let service = mock_service(|repo| {
+        repo.with_pair(DAI, WETH, units(3_000_000, 18), units(1_000, 18))
+            .with_pair(USDC, WETH, units(1_500_000, 6), units(1_000, 18))
+            .with_token(WBTC, "WBTC", 8)
+    });
+
+    let params = Parameters(GetTokenPriceRequest::symbol("DAI").with_quote("WBTC"));
+    match service.get_token_price(params).await.0 {
+        GetTokenPriceResult::Error {
+            error: ServiceError::LiquidityPoolNotFound { token0, token1 },
+        } => {
+            assert_eq!(token0, "WBTC");
+            assert_eq!(token1, "WETH");
+        }
+        other => panic!("Expected LiquidityPoolNotFound, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_get_token_price_rejects_pair_below_min_liquidity() {
+    // 0.01 WETH at $3000 is $60 of liquidity, below a $10,000 minimum
+    let mut config = get_test_config().await;
+    config.trading.min_liquidity_usd = Some(10_000);
+    let service = mock_service(|repo| {
+        repo.with_pair(DAI, WETH, units(30, 18), units(1, 16))
+            .with_eth_usd_price(Decimal::from(3000))
+    })
+    .with_config(&config);
+
+    let params = Parameters(GetTokenPriceRequest::symbol("DAI"));
+    match service.get_token_price(params).await.0 {
+        GetTokenPriceResult::Error {
+            error: ServiceError::InsufficientLiquidity(message),
+        } => assert!(message.contains("$60"), "{message}"),
+        other => panic!("Expected InsufficientLiquidity, got {other:?}"),
+    }
+
+    // The batch path applies the same minimum per token
+    let params = Parameters(GetTokenPricesRequest {
+        tokens: vec!["DAI".to_string()],
+    });
+    match service.get_token_prices(params).await.0 {
+        GetTokenPricesResult::Success(resp) => {
+            assert!(!resp.prices[0].priced);
+            let error = resp.prices[0].error.as_deref().unwrap();
+            assert!(error.contains("$60"), "{error}");
+        }
+        GetTokenPricesResult::Error { error } => panic!("Expected success, got {error}"),
+    }
+
+    // Without ETH/USD the pair's depth cannot be checked, so it is not priced in ETH either
+    let service = mock_service(|repo| repo.with_pair(DAI, WETH, units(30, 18), units(1, 16)))
+        .with_config(&config);
+    let params = Parameters(GetTokenPriceRequest::symbol("DAI"));
+    match service.get_token_price(params).await.0 {
+        GetTokenPriceResult::Error {
+            error: ServiceError::InsufficientLiquidity(message),
+        } => assert!(message.contains("ETH/USD is unavailable"), "{message}"),
+        other => panic!("Expected InsufficientLiquidity, got {other:?}"),
+    }
+
+    // Without a minimum the same pair is priced
+    let service = mock_service(|repo| {
+        repo.with_pair(DAI, WETH, units(30, 18), units(1, 16))
+            .with_eth_usd_price(Decimal::from(3000))
+    });
+    let params = Parameters(GetTokenPriceRequest::symbol("DAI"));
+    assert!(matches!(
+        service.get_token_price(params).await.0,
+        GetTokenPriceResult::Success(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_sessions_share_the_coingecko_circuit_breaker() {
+    // Nothing listens on port 1, so the first CoinGecko request fails and opens the breaker
+    let mut config = get_test_config().await;
+    config.price_api.enabled = true;
+    config.price_api.base_url = "http://127.0.0.1:1".to_string();
+    config.price_api.failure_threshold = 1;
+    let price_feed = CoinGeckoPriceFeed::from_config(&config);
+    let session = || {
+        mock_service(|repo| {
+            repo.with_pair(DAI, WETH, units(3_000_000, 18), units(1_000, 18))
+                .with_eth_usd_price(Decimal::from(3000))
+        })
+        .with_config(&config)
+        .with_price_feed(price_feed.clone())
+    };
+    let fallback_reason = |result| match result {
+        GetTokenPriceResult::Success(response) => {
+            assert_eq!(response.price_source, "onchain");
+            response.fallback_reason.unwrap()
+        }
+        other => panic!("Expected an on-chain fallback, got {other:?}"),
+    };
+
+    let params = Parameters(GetTokenPriceRequest::symbol("DAI"));
+    let reason = fallback_reason(session().get_token_price(params).await.0);
+    assert!(reason.contains("CoinGecko request failed"), "{reason}");
+
+    // A new session skips the API instead of starting with a closed breaker
+    let params = Parameters(GetTokenPriceRequest::symbol("DAI"));
+    let reason = fallback_reason(session().get_token_price(params).await.0);
+    assert!(
+        reason.contains("consecutive CoinGecko failures"),
+        "{reason}"
+    );
+}
+
+#[tokio::test]
+async fn test_explanation_names_coingecko_as_usd_source() {
+    // Local stand-in for the CoinGecko simple price endpoint
+    let body = serde_json::json!({ DAI.to_string().to_lowercase(): { "usd": 1.01 } });
+    let api = axum::Router::new().route(
+        "/simple/token_price/ethereum",
+        axum::routing::get(move || async move { axum::Json(body) }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, api).await });
+
+    let mut config = get_test_config().await;
+    config.price_api.enabled = true;
+    config.price_api.base_url = base_url;
+    let service = mock_service(|repo| {
+        repo.with_pair(DAI, WETH, units(3_000_000, 18), units(1_000, 18))
+            .with_eth_usd_price(Decimal::from(3000))
+    })
+    .with_config(&config)
+    .with_price_feed(CoinGeckoPriceFeed::from_config(&config));
+
+    let mut request = GetTokenPriceRequest::symbol("DAI");
+    if let GetTokenPriceRequest::Symbol { options, .. } = &mut request {
+        options.explain = Some(true);
+    }
+    match service.get_token_price(Parameters(request)).await.0 {
+        GetTokenPriceResult::Success(response) => {
+            assert_eq!(response.price_source, "coingecko");
+            let explanation = response.explanation.unwrap();
+            assert!(
+                explanation.contains("CoinGecko's USD price"),
+                "{explanation}"
+            );
+            assert!(!explanation.contains("ETH/USD price from"), "{explanation}");
+        }
+        other => panic!("Expected a CoinGecko price, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_eth_price_source_chainlink_prices_usd_values() {
+    let mut config = get_test_config().await;
+    config.eth_price_source = EthPriceSource::Chainlink;
+    let feed = Network::Mainnet.chain_config().chainlink_eth_usd.unwrap();
+    let now = chrono::Utc::now().timestamp() as u64;
+    let service = mock_service(|repo| {
+        repo.with_pair(DAI, WETH, units(2_000_000, 18), units(1_000, 18))
+            .with_pair(USDC, WETH, units(2_000_000, 6), units(1_000, 18))
+            .with_chainlink_price(feed, Decimal::from(3200), now)
+    })
+    .with_config(&config);
+
+    // ETH/USD comes from the feed, not the 2000 USDC/WETH pair
+    let params = Parameters(GetTokenPriceRequest::symbol("ETH"));
+    let response = match service.get_token_price(params).await.0 {
+        GetTokenPriceResult::Success(response) => response,
+        GetTokenPriceResult::Error { error } => panic!("Failed to price ETH: {error}"),
+    };
+    assert_eq!(response.price_usd.as_deref(), Some("3200"));
+    assert_eq!(response.reserves_updated_at, None);
+
+    let params = Parameters(GetTokenPriceRequest::symbol("DAI"));
+    let response = match service.get_token_price(params).await.0 {
+        GetTokenPriceResult::Success(response) => response,
+        GetTokenPriceResult::Error { error } => panic!("Failed to price DAI: {error}"),
+    };
+    // 0.0005 ETH at the feed's 3200
+    assert_eq!(response.price_usd.as_deref(), Some("1.6"));
+}
+
+#[tokio::test]
+async fn test_get_token_price_chainlink_source() {
+    let mut config = get_test_config().await;
+    let dai_feed = Address::repeat_byte(0xda);
+    config
+        .chainlink
+        .feeds
+        .insert("DAI".to_string(), dai_feed.to_string());
+    let eth_feed = Network::Mainnet.chain_config().chainlink_eth_usd.unwrap();
+    let now = chrono::Utc::now().timestamp() as u64;
+    let service = mock_service(|repo| {
+        repo.with_chainlink_price(eth_feed, Decimal::from(2500), now)
+            .with_chainlink_price(dai_feed, Decimal::new(9995, 4), now - 60)
+    })
+    .with_config(&config);
+
+    let params = Parameters(GetTokenPriceRequest::symbol("DAI").with_source("chainlink"));
+    let response = match service.get_token_price(params).await.0 {
+        GetTokenPriceResult::Success(response) => response,
+        GetTokenPriceResult::Error { error } => panic!("Failed to price DAI: {error}"),
+    };
+    assert_eq!(response.onchain_source, "chainlink");
+    assert_eq!(response.price_usd.as_deref(), Some("0.9995"));
+    assert_eq!(response.price_eth, "0.0003998");
+    assert_eq!(response.feed_updated_at, Some(now - 60));
+
+    // WETH is priced by the ETH/USD feed without a configured entry
+    let params = Parameters(GetTokenPriceRequest::symbol("WETH").with_source("chainlink"));
+    let response = match service.get_token_price(params).await.0 {
+        GetTokenPriceResult::Success(response) => response,
+        GetTokenPriceResult::Error { error } => panic!("Failed to price WETH: {error}"),
+    };
+    assert_eq!(response.price_usd.as_deref(), Some("2500"));
+
+    let params = Parameters(GetTokenPriceRequest::symbol("USDC").with_source("chainlink"));
+    assert!(matches!(
+        service.get_token_price(params).await.0,
+        GetTokenPriceResult::Error {
+            error: ServiceError::TokenNotFound(_)
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_get_token_price_chainlink_rejects_stale_round() {
+    let mut config = get_test_config().await;
+    let dai_feed = Address::repeat_byte(0xda);
+    config
+        .chainlink
+        .feeds
+        .insert(DAI.to_string(), dai_feed.to_string());
+    config.chainlink.max_age_secs = 3600;
+    let eth_feed = Network::Mainnet.chain_config().chainlink_eth_usd.unwrap();
+    let now = chrono::Utc::now().timestamp() as u64;
+    let service = mock_service(|repo| {
+        repo.with_chainlink_price(eth_feed, Decimal::from(2500), now)
+            .with_chainlink_price(dai_feed, Decimal::ONE, now - 7200)
+    })
+    .with_config(&config);
+
+    let params = Parameters(GetTokenPriceRequest::symbol("DAI").with_source("chainlink"));
+    assert!(matches!(
+        service.get_token_price(params).await.0,
+        GetTokenPriceResult::Error {
+            error: ServiceError::StalePrice(_)
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_eth_price_source_uniswap_v3_reads_pool_price() {
+    let mut config = get_test_config().await;
+    config.eth_price_source = EthPriceSource::UniswapV3;
+    // sqrt(1e18 / 2500e6) = 20000: 1 WETH = 2500 USDC
+    let pool = V3PoolState {
+        pool: Address::repeat_byte(0x11),
+        token0: USDC,
+        token1: WETH,
+        fee: 500,
+        sqrt_price_x96: U256::from(20_000) << 96,
+        tick: 0,
+        liquidity: 1,
+    };
+    let service = mock_service(|repo| {
+        repo.with_v3_pool(pool)
+            .with_block(19_000_000, 1_700_000_000)
+    })
+    .with_config(&config);
+
+    let params = Parameters(GetTokenPriceRequest::symbol("ETH"));
+    let response = match service.get_token_price(params).await.0 {
+        GetTokenPriceResult::Success(response) => response,
+        GetTokenPriceResult::Error { error } => panic!("Failed to price ETH: {error}"),
+    };
+    assert_eq!(response.price_usd.as_deref(), Some("2500"));
+
+    // The pool only has a current price
+    let params = Parameters(GetTokenPriceRequest::symbol("ETH").at_block(18_000_000));
+    assert!(matches!(
+        service.get_token_price(params).await.0,
+        GetTokenPriceResult::Error {
+            error: ServiceError::InvalidAmount(_)
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_list_supported_tokens_searches_registry() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let all = service
+        .list_supported_tokens(Parameters(ListSupportedTokensRequest { query: None }))
+        .await
+        .0;
+    assert_eq!(all.network, "mainnet");
+    assert!(all.tokens.iter().any(|t| t.symbol == "UNI"));
+    assert!(all.tokens.windows(2).all(|w| w[0].symbol <= w[1].symbol));
+
+    let found = service
+        .list_supported_tokens(Parameters(ListSupportedTokensRequest {
+            query: Some("Usd".to_string()),
+        }))
+        .await
+        .0;
+    let symbols: Vec<&str> = found.tokens.iter().map(|t| t.symbol.as_str()).collect();
+    assert_eq!(symbols, vec!["USDC", "USDT", "BUSD"]);
+    assert_eq!(
+        found.tokens[0].contract_address.to_lowercase(),
+        "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+    );
+}
+
+#[tokio::test]
+async fn test_approve_token_is_rejected_when_execution_disabled() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let req = ApproveTokenRequest {
+        token: "USDC".to_string(),
+        amount: "max".to_string(),
+        spender: None,
+    };
+
+    let result = service.approve_token(Parameters(req)).await;
+    assert!(
+        matches!(
+            result.0,
+            ApproveTokenResult::Error {
+                error: ServiceError::ExecutionNotAllowed(_)
+            }
+        ),
+        "Expected approval to be refused in read-only mode"
+    );
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_token_price_with_summary_should_include_summary() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+    let params = Parameters(GetTokenPriceRequest::symbol("USDC").with_summary(true));
+
+    let result = service.get_token_price(params).await.0;
+    match result {
+        GetTokenPriceResult::Success(resp) => {
+            let summary = resp.summary.expect("summary should be present");
+            println!("✅ {summary}");
+            assert!(summary.starts_with("USDC is $"));
+            assert!(summary.contains(resp.price_usd.as_deref().unwrap()));
+        }
+        GetTokenPriceResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_token_price_at_block_should_work() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+    let params = Parameters(GetTokenPriceRequest::symbol("ETH").at_block(18_000_000));
+
+    let result = service.get_token_price(params).await.0;
+    match result {
+        GetTokenPriceResult::Success(resp) => {
+            println!(
+                "✅ ETH Price at block 18000000: ${}",
+                resp.price_usd.as_deref().unwrap()
+            );
+
+            assert_eq!(resp.block_number, Some(18_000_000));
+            assert_eq!(resp.price_source, "onchain");
+            let price: f64 = resp
+                .price_usd
+                .as_deref()
+                .unwrap()
+                .parse()
+                .expect("price should be numeric");
+            assert!(
+                price > 1_500.0 && price < 1_800.0,
+                "Unexpected price {price}"
+            );
+        }
+        GetTokenPriceResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_token_price_at_future_block_should_return_error() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+    let params = Parameters(GetTokenPriceRequest::symbol("ETH").at_block(u64::MAX));
+
+    let result = service.get_token_price(params).await.0;
+    assert!(
+        matches!(result, GetTokenPriceResult::Error { .. }),
+        "Expected error for a future block"
+    );
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_balance_with_ens_name_should_work() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(GetBalanceRequest {
+        wallet_address: "vitalik.eth".to_string(),
+        token_contract_address: None,
+        summarize: None,
+        rpc_url: None,
+    });
+
+    let result = service.get_balance(params).await.0;
+    match result {
+        GetBalanceResult::Success(resp) => {
+            println!("✅ vitalik.eth ETH Balance: {}", resp.formatted_balance);
+            assert_eq!(resp.symbol, "ETH");
+        }
+        GetBalanceResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_verify_network_should_match_mainnet_rpc() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let result = service.verify_network().await;
+    assert!(result.is_ok(), "Network check failed: {:?}", result.err());
+}
+
+#[tokio::test]
+async fn test_simulate_pending_swap_rejects_unknown_direction() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(SimulatePendingSwapRequest {
+        from_token: "USDC".to_string(),
+        to_token: "WETH".to_string(),
+        amount: "1000".to_string(),
+        pending_amount: "500000".to_string(),
+        pending_direction: Some("sideways".to_string()),
+    });
+
+    let result = service.simulate_pending_swap(params).await.0;
+    assert!(
+        matches!(
+            result,
+            SimulatePendingSwapResult::Error {
+                error: ServiceError::InvalidAmount(_)
+            }
+        ),
+        "Expected InvalidAmount for an unknown direction"
+    );
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_simulate_pending_swap_same_direction_should_lower_output() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(SimulatePendingSwapRequest {
+        from_token: "USDC".to_string(),
+        to_token: "WETH".to_string(),
+        amount: "1000".to_string(),
+        pending_amount: "500000".to_string(),
+        pending_direction: None,
+    });
+
+    let result = service.simulate_pending_swap(params).await.0;
+    match result {
+        SimulatePendingSwapResult::Success(resp) => {
+            println!(
+                "✅ 1000 USDC -> {} WETH, {} WETH after pending swap ({}%)",
+                resp.expected_output, resp.output_after_pending, resp.output_change_percentage
+            );
+            assert!(resp.output_change_percentage.starts_with('-'));
+        }
+        SimulatePendingSwapResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_get_recent_transactions_rejects_zero_blocks() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(GetRecentTransactionsRequest {
+        wallet_address: WALLET_ADDRESS.to_string(),
+        blocks: Some(0),
+    });
+
+    let result = service.get_recent_transactions(params).await.0;
+    assert!(
+        matches!(
+            result,
+            GetRecentTransactionsResult::Error {
+                error: ServiceError::InvalidAmount(_)
+            }
+        ),
+        "Expected InvalidAmount for an empty block range"
+    );
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_recent_transactions_should_cap_block_range() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(GetRecentTransactionsRequest {
+        wallet_address: WALLET_ADDRESS.to_string(),
+        blocks: Some(500),
+    });
+
+    let result = service.get_recent_transactions(params).await.0;
+    match result {
+        GetRecentTransactionsResult::Success(resp) => {
+            println!(
+                "✅ {} transactions in blocks {}..={}",
+                resp.transactions.len(),
+                resp.from_block,
+                resp.to_block
+            );
+            assert_eq!(resp.to_block - resp.from_block + 1, 100);
+            assert!(resp.warning.is_some());
+        }
+        GetRecentTransactionsResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_token_price_eth_and_weth_should_match() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    // Pin both lookups to one block so a new block between them cannot move the price
+    let mut prices = Vec::new();
+    for symbol in ["ETH", "WETH"] {
+        let params = Parameters(GetTokenPriceRequest::symbol(symbol).at_block(18_000_000));
+        match service.get_token_price(params).await.0 {
+            GetTokenPriceResult::Success(resp) => {
+                println!(
+                    "✅ {symbol}: ${} ({} ETH)",
+                    resp.price_usd.as_deref().unwrap(),
+                    resp.price_eth
+                );
+                prices.push((resp.address, resp.price_eth, resp.price_usd));
+            }
+            GetTokenPriceResult::Error { error } => {
+                panic!("Expected success for {symbol} but got error: {}", error);
+            }
+        }
+    }
+
+    assert_eq!(prices[0], prices[1]);
+    assert_eq!(prices[0].1, "1.0");
+}
+
+#[tokio::test]
+async fn test_swap_tokens_rejects_out_of_range_slippage() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    for slippage in ["-0.5", "75"] {
+        let params = Parameters(SwapTokensRequest {
+            from_token: "WETH".to_string(),
+            to_token: "USDC".to_string(),
+            amount: "1".to_string(),
+            slippage_tolerance: Some(slippage.to_string()),
+            ..Default::default()
+        });
+
+        let result = service.swap_tokens(params).await.0;
+        assert!(
+            matches!(
+                result,
+                SwapTokensResult::Error {
+                    error: ServiceError::InvalidAmount(_)
+                }
+            ),
+            "Expected InvalidAmount for slippage {slippage}"
+        );
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_swap_tokens_whale_trade_should_trip_price_impact_guard() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    // 50k WETH is far larger than the V2 USDC/WETH pool can absorb cheaply
+    let params = Parameters(SwapTokensRequest {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "50000".to_string(),
+        slippage_tolerance: Some("0.5".to_string()),
+        uniswap_version: Some("v2".to_string()),
+        max_price_impact: Some("5".to_string()),
+        ..Default::default()
+    });
+
+    let result = service.swap_tokens(params).await.0;
+    match result {
+        SwapTokensResult::Error {
+            error: ServiceError::PriceImpactTooHigh { impact, max },
+        } => {
+            println!("✅ Rejected whale trade: {impact}% impact (max {max}%)");
+            assert_eq!(max, "5");
+        }
+        other => panic!("Expected PriceImpactTooHigh but got: {:?}", other),
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_estimate_round_trip_should_cost_about_lp_fees() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(EstimateRoundTripRequest {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "10".to_string(),
+    });
+
+    let result = service.estimate_round_trip(params).await.0;
+    match result {
+        EstimateRoundTripResult::Success(resp) => {
+            println!(
+                "✅ 10 WETH -> {} USDC -> {} WETH ({}% cost, {}% impact)",
+                resp.amount_bought,
+                resp.amount_returned,
+                resp.total_cost_percentage,
+                resp.price_impact_percentage
+            );
+            let total: f64 = resp.total_cost_percentage.parse().unwrap();
+            // Roughly the two 0.3% LP fees; the sell recovers almost all of the buy's impact
+            assert!(
+                total > 0.5 && total < 1.0,
+                "Unexpected round-trip cost {total}%"
+            );
+        }
+        EstimateRoundTripResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_estimate_swap_gas_should_work() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(EstimateSwapGasRequest {
+        from_token: "USDC".to_string(),
+        to_token: "WETH".to_string(),
+        amount: "100".to_string(),
+        from_address: Some(WALLET_ADDRESS.to_string()),
+    });
+
+    let result = service.estimate_swap_gas(params).await.0;
+    match result {
+        EstimateSwapGasResult::Success(resp) => {
+            println!(
+                "✅ Swap gas: {} units ({} ETH)",
+                resp.estimated_gas, resp.estimated_gas_eth
+            );
+            let gas: u64 = resp.estimated_gas.parse().unwrap();
+            let raw: u64 = resp.estimated_gas_raw.parse().unwrap();
+            assert!(gas > 0);
+            assert!(gas >= raw, "buffered gas {gas} is below the node's {raw}");
+        }
+        EstimateSwapGasResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_token_info_usdt_should_work() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(GetTokenInfoRequest {
+        token: USDT_CONTRACT_ADDRESS.to_string(),
+    });
+
+    let result = service.get_token_info(params).await.0;
+    match result {
+        GetTokenInfoResult::Success(resp) => {
+            println!(
+                "✅ {} ({}), {} decimals, supply {}",
+                resp.name, resp.symbol, resp.decimals, resp.total_supply
+            );
+            assert_eq!(resp.name, "Tether USD");
+            assert_eq!(resp.symbol, "USDT");
+            assert_eq!(resp.decimals, 6);
+            assert_ne!(resp.total_supply_raw, "0");
+        }
+        GetTokenInfoResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_get_my_approval_requires_wallet() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(GetMyApprovalRequest {
+        token: "USDC".to_string(),
+        spender: None,
+    });
+
+    let result = service.get_my_approval(params).await.0;
+    assert!(
+        matches!(
+            result,
+            GetMyApprovalResult::Error {
+                error: ServiceError::ExecutionNotAllowed(_)
+            }
+        ),
+        "Expected ExecutionNotAllowed without a configured wallet"
+    );
+}
+
+#[tokio::test]
+async fn test_get_wallet_address_reports_signer_or_read_only_mode() {
+    let mut config = get_test_config().await;
+    let resp = EthereumTradingService::new(&config)
+        .get_wallet_address()
+        .await
+        .0;
+    assert!(resp.read_only);
+    assert!(resp.wallet_address.is_none());
+    assert!(!resp.execution_allowed);
+    assert!(resp.message.starts_with("Read-only mode"));
+
+    // Well-known Anvil test key
+    config.wallet.private_key =
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string();
+    config.wallet.allow_execution = false;
+    let resp = EthereumTradingService::new(&config)
+        .get_wallet_address()
+        .await
+        .0;
+    assert!(!resp.read_only);
+    assert_eq!(
+        resp.wallet_address.as_deref(),
+        Some("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266")
+    );
+    assert!(!resp.execution_allowed);
+    assert!(resp.message.contains("allow_execution is false"));
+}
+
+#[tokio::test]
+async fn test_execute_swap_unwrap_weth_requires_weth_output() {
+    let mut config = get_test_config().await;
+    // Well-known Anvil test key; validation fails before anything is signed or sent
+    config.wallet.private_key =
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string();
+    config.wallet.allow_execution = true;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".to_string(),
+        to_token: USDT_CONTRACT_ADDRESS.to_string(),
+        amount: "1".to_string(),
+        slippage_tolerance: Some("0.5".to_string()),
+        unwrap_weth: Some(true),
+        ..Default::default()
+    });
+
+    let result = service.execute_swap(params).await.0;
+    assert!(
+        matches!(
+            result,
+            ExecuteSwapResult::Error {
+                error: ServiceError::InvalidAmount(_)
+            }
+        ),
+        "Expected InvalidAmount when unwrapping a non-WETH output"
+    );
+}
+
+#[tokio::test]
+async fn test_execute_swap_reports_hash_when_receipt_is_unavailable() {
+    let mut config = get_test_config().await;
+    config.wallet.allow_execution = true;
+    let hash = B256::repeat_byte(0xab);
+    let service = mock_service(|repo| {
+        repo.with_wallet(Address::repeat_byte(0x01))
+            .with_pair(WETH, USDC, units(1_000, 18), units(3_000_000, 6))
+            .with_execution(SwapExecution::pending(
+                hash,
+                "Failed to get receipt: request timed out".to_string(),
+            ))
+    })
+    .with_config(&config);
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "1".to_string(),
+        ..Default::default()
+    });
+
+    // The swap was broadcast, so its hash is reported for get_swap_status instead of an error
+    match service.execute_swap(params).await.0 {
+        ExecuteSwapResult::Success(response) => {
+            assert_eq!(response.transaction_hash, hash.to_string());
+            assert_eq!(response.status, "pending");
+            assert!(response.receipt_error.is_some());
+            assert_eq!(response.block_number, None);
+        }
+        other => panic!("Expected a pending execution, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_execute_swap_dry_run_skips_execution_checks() {
+    // Read-only config: a real execution would be refused with ExecutionNotAllowed
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    // The dry run takes the simulation path, whose validation fails before any RPC call
+    let params = Parameters(SwapTokensRequest {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0".to_string(),
+        dry_run: Some(true),
+        ..Default::default()
+    });
+
+    let result = service.execute_swap(params).await.0;
+    assert!(
+        matches!(
+            result,
+            ExecuteSwapResult::Error {
+                error: ServiceError::InvalidAmount(_)
+            }
+        ),
+        "Expected the dry run to reach the simulation's amount validation"
+    );
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "100".to_string(),
+        swap_mode: Some("exact_output".to_string()),
+        dry_run: Some(true),
+        ..Default::default()
+    });
+    assert!(
+        matches!(
+            service.execute_swap(params).await.0,
+            ExecuteSwapResult::Error {
+                error: ServiceError::InvalidAmount(_)
+            }
+        ),
+        "Expected dry runs to reject exact_output like execute_swap"
+    );
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_execute_swap_dry_run_returns_quote_with_wallet() {
+    avoid_rate_limit().await;
+    let mut config = get_test_config().await;
+    // Well-known Anvil test key with execution enabled; the dry run must not send anything
+    config.wallet.private_key =
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string();
+    config.wallet.allow_execution = true;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "1".to_string(),
+        slippage_tolerance: Some("0.5".to_string()),
+        dry_run: Some(true),
+        ..Default::default()
+    });
+
+    match service.execute_swap(params).await.0 {
+        ExecuteSwapResult::Simulated(resp) => {
+            assert!(!resp.estimated_output.is_empty());
+            assert_eq!(resp.uniswap_version, "v2");
+        }
+        other => panic!("Expected a simulated quote, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_token_price_best_source_should_compare_v2_and_v3() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+    let params = Parameters(GetTokenPriceRequest::symbol("UNI").with_source("best"));
+
+    let result = service.get_token_price(params).await.0;
+    match result {
+        GetTokenPriceResult::Success(resp) => {
+            println!("✅ UNI Price Source Comparison:");
+            println!("   {:?}", resp.comparison);
+
+            let comparison = resp.comparison.expect("comparison should be present");
+            assert_eq!(comparison.sources.len(), 2);
+            assert_eq!(resp.onchain_source, comparison.more_liquid);
+            assert!(comparison.spread_percent.is_some());
+        }
+        GetTokenPriceResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_token_price_reports_reserves_update_time() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let request = GetTokenPriceRequest::symbol("UNI").with_max_staleness(7 * 24 * 3600);
+    match service.get_token_price(Parameters(request)).await.0 {
+        GetTokenPriceResult::Success(resp) => {
+            let updated_at = resp
+                .reserves_updated_at
+                .expect("V2 prices carry reserves_updated_at");
+            println!("✅ UNI reserves updated at {updated_at}");
+            assert!(updated_at as i64 <= resp.timestamp + 60);
+        }
+        GetTokenPriceResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+
+    // A zero limit only passes when the USDC/WETH pair traded within the current second
+    avoid_rate_limit().await;
+    let request = GetTokenPriceRequest::symbol("ETH").with_max_staleness(0);
+    if let GetTokenPriceResult::Error { error } =
+        service.get_token_price(Parameters(request)).await.0
+    {
+        assert!(
+            matches!(error, ServiceError::StalePrice(_)),
+            "unexpected error: {error}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_get_token_price_rejects_invalid_source() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    for request in [
+        GetTokenPriceRequest::symbol("USDC").with_source("v4"),
+        GetTokenPriceRequest::symbol("USDC")
+            .with_source("v3")
+            .at_block(18_000_000),
+        // Staleness is only checked on current V2 reserves
+        GetTokenPriceRequest::symbol("USDC")
+            .with_source("best")
+            .with_max_staleness(3600),
+        GetTokenPriceRequest::symbol("USDC")
+            .at_block(18_000_000)
+            .with_max_staleness(3600),
+    ] {
+        let result = service.get_token_price(Parameters(request)).await.0;
+        assert!(
+            matches!(
+                result,
+                GetTokenPriceResult::Error {
+                    error: ServiceError::InvalidAmount(_)
+                }
+            ),
+            "Expected InvalidAmount before any RPC call"
+        );
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_eth_stablecoin_prices_should_work() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let result = service.get_eth_stablecoin_prices().await.0;
+    match result {
+        GetEthStablecoinPricesResult::Success(resp) => {
+            println!("✅ ETH Stablecoin Prices:");
+            for price in &resp.prices {
+                println!(
+                    "   {}: {:?} {:?}",
+                    price.symbol, price.eth_price, price.error
+                );
+            }
+            println!("   Spread: {}%", resp.spread_percent);
+
+            assert_eq!(resp.prices.len(), 4);
+            assert!(resp.prices.iter().any(|p| p.eth_price.is_some()));
+        }
+        GetEthStablecoinPricesResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_swap_tokens_rejects_non_positive_amounts() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    for amount in ["0", "-1", ""] {
+        let params = Parameters(SwapTokensRequest {
+            from_token: "WETH".to_string(),
+            to_token: "USDC".to_string(),
+            amount: amount.to_string(),
+            slippage_tolerance: Some("0.5".to_string()),
+            ..Default::default()
+        });
+
+        // Rejected before any RPC call, so this passes without network access
+        let result = service.swap_tokens(params).await.0;
+        assert!(
+            matches!(
+                result,
+                SwapTokensResult::Error {
+                    error: ServiceError::InvalidAmount(_)
+                }
+            ),
+            "Expected InvalidAmount for amount {amount:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_calculate_implied_slippage_rejects_zero_minimum() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(ImpliedSlippageRequest {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "1".to_string(),
+        minimum_output: "0".to_string(),
+        uniswap_version: None,
+    });
+
+    let result = service.calculate_implied_slippage(params).await.0;
+    assert!(
+        matches!(
+            result,
+            ImpliedSlippageResult::Error {
+                error: ServiceError::InvalidAmount(_)
+            }
+        ),
+        "Expected InvalidAmount for a zero minimum output"
+    );
+}
+
+#[tokio::test]
+async fn test_swap_tokens_rejects_unknown_dex_and_dex_with_v3() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    for (dex, version) in [("pancakeswap", "v2"), ("sushiswap", "v3")] {
+        let params = Parameters(SwapTokensRequest {
+            from_token: "WETH".to_string(),
+            to_token: "USDC".to_string(),
+            amount: "1".to_string(),
+            slippage_tolerance: Some("0.5".to_string()),
+            uniswap_version: Some(version.to_string()),
+            dex: Some(dex.to_string()),
+            ..Default::default()
+        });
+
+        let result = service.swap_tokens(params).await.0;
+        assert!(
+            matches!(
+                result,
+                SwapTokensResult::Error {
+                    error: ServiceError::InvalidAmount(_)
+                }
+            ),
+            "Expected InvalidAmount for dex {dex} on {version}"
+        );
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_swap_tokens_on_sushiswap() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "1".to_string(),
+        slippage_tolerance: Some("0.5".to_string()),
+        dex: Some("sushiswap".to_string()),
+        explain: Some(true),
+        ..Default::default()
+    });
+
+    match service.swap_tokens(params).await.0 {
+        SwapTokensResult::Success(response) => {
+            println!("✅ SushiSwap quote: {} USDC", response.estimated_output);
+            assert!(
+                response
+                    .explanation
+                    .unwrap()
+                    .starts_with("Quoted on SushiSwap")
+            );
+        }
+        SwapTokensResult::Error { error } => panic!("SushiSwap quote failed: {error:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_get_best_swap_rejects_invalid_amount() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(GetBestSwapRequest {
+        from_token: "USDC".to_string(),
+        to_token: "WETH".to_string(),
+        amount: "0".to_string(),
+        slippage_tolerance: "0.5".to_string(),
+        from_address: None,
+    });
+
+    let result = service.get_best_swap(params).await.0;
+    assert!(matches!(
+        result,
+        GetBestSwapResult::Error {
+            error: ServiceError::InvalidAmount(_)
+        }
+    ));
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_best_swap_should_pick_larger_output() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(GetBestSwapRequest {
+        from_token: "USDC".to_string(),
+        to_token: "WETH".to_string(),
+        amount: "1000".to_string(),
+        slippage_tolerance: "0.5".to_string(),
+        from_address: None,
+    });
+
+    match service.get_best_swap(params).await.0 {
+        GetBestSwapResult::Success(response) => {
+            println!(
+                "✅ Best swap on {}: {} WETH (advantage {:?}%)",
+                response.chosen_version, response.estimated_output, response.advantage_percent
+            );
+            let chosen = match response.chosen_version.as_str() {
+                "v2" => response.v2_quote.as_ref(),
+                _ => response.v3_quote.as_ref(),
+            };
+            assert_eq!(chosen.unwrap().estimated_output, response.estimated_output);
+        }
+        GetBestSwapResult::Error { error } => panic!("get_best_swap failed: {error:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_get_swap_status_rejects_malformed_hash() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(GetSwapStatusRequest {
+        transaction_hash: "0x1234".to_string(),
+    });
+
+    let result = service.get_swap_status(params).await.0;
+    assert!(matches!(
+        result,
+        GetSwapStatusResult::Error {
+            error: ServiceError::InvalidTransactionHash(_)
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_get_transaction_rejects_malformed_hash() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    for hash in ["0x1234", "not a hash", ""] {
+        let params = Parameters(GetTransactionRequest {
+            transaction_hash: hash.to_string(),
+        });
+        assert!(matches!(
+            service.get_transaction(params).await.0,
+            GetTransactionResult::Error {
+                error: ServiceError::InvalidTransactionHash(_)
+            }
+        ));
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_transaction_should_work() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    // The first ETH transfer on mainnet, 31337 wei in block 46147
+    let params = Parameters(GetTransactionRequest {
+        transaction_hash: "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060"
+            .to_string(),
+    });
+    match service.get_transaction(params).await.0 {
+        GetTransactionResult::Success(resp) => {
+            println!("✅ Transaction {}: {}", resp.transaction_hash, resp.status);
+            assert_eq!(resp.status, "success");
+            assert_eq!(resp.block_number, Some(46147));
+            assert_eq!(resp.value_eth.as_deref(), Some("0.000000000000031337"));
+            assert!(resp.input_selector.is_none());
+            assert!(resp.swap.is_none());
+        }
+        GetTransactionResult::Error { error } => panic!("get_transaction failed: {error:?}"),
+    }
+
+    avoid_rate_limit().await;
+    let params = Parameters(GetTransactionRequest {
+        transaction_hash: format!("0x{}", "ab".repeat(32)),
+    });
+    match service.get_transaction(params).await.0 {
+        GetTransactionResult::Success(resp) => {
+            assert_eq!(resp.status, "not_found");
+            assert!(resp.from.is_none());
+        }
+        GetTransactionResult::Error { error } => panic!("get_transaction failed: {error:?}"),
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_swap_status_unknown_hash_should_be_pending() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(GetSwapStatusRequest {
+        transaction_hash: format!("0x{}", "ab".repeat(32)),
+    });
+
+    match service.get_swap_status(params).await.0 {
+        GetSwapStatusResult::Success(response) => {
+            println!("✅ Unknown transaction status: {}", response.status);
+            assert_eq!(response.status, "pending");
+            assert!(response.block_number.is_none());
+        }
+        GetSwapStatusResult::Error { error } => panic!("get_swap_status failed: {error:?}"),
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_swap_tokens_should_report_path_and_amounts() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    // UNI -> DAI has no deep direct pair, so the quote may route through WETH
+    let params = Parameters(SwapTokensRequest {
+        from_token: "UNI".to_string(),
+        to_token: "DAI".to_string(),
+        amount: "10".to_string(),
+        slippage_tolerance: Some("0.5".to_string()),
+        ..Default::default()
+    });
+
+    match service.swap_tokens(params).await.0 {
+        SwapTokensResult::Success(response) => {
+            println!(
+                "✅ Path {:?}, amounts {:?}",
+                response.path, response.amounts
+            );
+            assert_eq!(response.uniswap_version, "v2");
+            assert_eq!(response.path.len(), response.amounts.len());
+            assert_eq!(
+                response.amounts.last().unwrap(),
+                &response.estimated_output_raw
+            );
+        }
+        SwapTokensResult::Error { error } => panic!("Swap quote failed: {error:?}"),
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_eth_price_should_work() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let result = service.get_eth_price().await.0;
+    match result {
+        GetEthPriceResult::Success(resp) => {
+            println!("✅ ETH Price: ${}", resp.price_usd);
+            println!(
+                "   Reserves: {} USDC / {} WETH",
+                resp.reserve_usdc, resp.reserve_weth
+            );
+
+            let price: f64 = resp.price_usd.parse().unwrap();
+            assert!(price > 0.0);
+            assert!(resp.timestamp > 0);
+        }
+        GetEthPriceResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_chain_status_should_work() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    match service.get_chain_status().await.0 {
+        GetChainStatusResult::Success(resp) => {
+            println!(
+                "✅ Block {} at {} ({}s old), base fee {} gwei",
+                resp.block_number, resp.block_timestamp, resp.block_age_secs, resp.base_fee_gwei
+            );
+            assert_eq!(resp.network, "mainnet");
+            assert_eq!(resp.chain_id, 1);
+            assert!(resp.chain_id_matches);
+            assert!(resp.block_number > 18_000_000);
+            assert!(resp.block_age_secs < 3600);
+        }
+        GetChainStatusResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_swap_tokens_rejects_native_eth_on_both_sides() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "ETH".to_string(),
+        to_token: "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE".to_string(),
+        amount: "1".to_string(),
+        slippage_tolerance: Some("0.5".to_string()),
+        ..Default::default()
+    });
+
+    // Rejected before any RPC call, so this passes without network access
+    let result = service.swap_tokens(params).await.0;
+    assert!(matches!(
+        result,
+        SwapTokensResult::Error {
+            error: ServiceError::InvalidAmount(_)
+        }
+    ));
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_swap_tokens_with_native_eth_reports_wrapping() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "ETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        slippage_tolerance: Some("0.5".to_string()),
+        ..Default::default()
+    });
+
+    match service.swap_tokens(params).await.0 {
+        SwapTokensResult::Success(resp) => {
+            assert!(resp.wraps_eth);
+            assert!(!resp.unwraps_eth);
+        }
+        SwapTokensResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "USDC".to_string(),
+        to_token: "ETH".to_string(),
+        amount: "100".to_string(),
+        slippage_tolerance: Some("0.5".to_string()),
+        ..Default::default()
+    });
+
+    match service.swap_tokens(params).await.0 {
+        SwapTokensResult::Success(resp) => {
+            assert!(!resp.wraps_eth);
+            assert!(resp.unwraps_eth);
+        }
+        SwapTokensResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_swap_tokens_falls_back_to_configured_slippage() {
+    let mut config = get_test_config().await;
+    config.trading.default_slippage = "75".to_string();
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "1".to_string(),
+        ..Default::default()
+    });
+
+    // The configured default is validated like a requested slippage, before any RPC call
+    let result = service.swap_tokens(params).await.0;
+    match result {
+        SwapTokensResult::Error {
+            error: ServiceError::InvalidAmount(message),
+        } => assert!(message.contains("75%"), "unexpected message: {message}"),
+        other => panic!("Expected InvalidAmount, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_pool_info_should_work() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(GetPoolInfoRequest {
+        token_a: "WETH".to_string(),
+        token_b: "USDC".to_string(),
+        fee_tier: None,
+    });
+
+    match service.get_pool_info(params).await.0 {
+        GetPoolInfoResult::Success(resp) => {
+            println!(
+                "✅ WETH/USDC V3 pool {} ({})",
+                resp.pool_address, resp.fee_tier
+            );
+            println!("   Price: {} USDC, tick {}", resp.price, resp.tick);
+
+            let price: f64 = resp.price.parse().unwrap();
+            assert!(price > 100.0, "WETH should be worth more than 100 USDC");
+            assert!(resp.liquidity != "0");
+        }
+        GetPoolInfoResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_get_token_prices_should_work() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(GetTokenPricesRequest {
+        tokens: vec![
+            "ETH".to_string(),
+            "UNI".to_string(),
+            "NOT_A_TOKEN".to_string(),
+        ],
+    });
+
+    let result = service.get_token_prices(params).await.0;
+    match result {
+        GetTokenPricesResult::Success(resp) => {
+            println!("✅ ETH Price: ${}", resp.eth_price_usd);
+            for entry in &resp.prices {
+                println!(
+                    "   {}: {:?} {:?}",
+                    entry.token, entry.price_usd, entry.error
+                );
+            }
+
+            // ETH is priced at the shared ETH/USD rate, unknown tokens carry an error
+            assert_eq!(resp.prices.len(), 3);
+            assert_eq!(resp.prices[0].price_usd.as_ref(), Some(&resp.eth_price_usd));
+            assert_eq!(resp.prices[0].price_eth.as_deref(), Some("1"));
+            assert!(resp.prices[1].priced);
+            assert!(!resp.prices[2].priced);
+            assert!(resp.prices[2].error.is_some());
+            assert!(!resp.all_priced);
+        }
+        GetTokenPricesResult::Error { error } => {
+            panic!("Expected success but got error: {}", error);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_get_token_prices_rejects_empty_and_oversized_lists() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    for tokens in [vec![], vec!["USDC".to_string(); 51]] {
+        let result = service
+            .get_token_prices(Parameters(GetTokenPricesRequest { tokens }))
+            .await;
+        assert!(matches!(
+            result.0,
+            GetTokenPricesResult::Error {
+                error: ServiceError::InvalidAmount(_)
+            }
+        ));
+    }
+}
+
+#[tokio::test]
+async fn test_build_permit_rejects_expired_deadline() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    // Validation fails before any RPC call
+    let req = BuildPermitRequest {
+        token: "USDC".to_string(),
+        spender: "permit2".to_string(),
+        amount: "100".to_string(),
+        deadline: Some(1),
+        owner: Some(WALLET_ADDRESS.to_string()),
+    };
+
+    let result = service.build_permit(Parameters(req)).await.0;
+    assert!(
+        matches!(
+            result,
+            BuildPermitResult::Error {
+                error: ServiceError::InvalidAmount(_)
+            }
+        ),
+        "Expected a past deadline to be rejected"
+    );
+}
+
+#[tokio::test]
+async fn test_build_permit_requires_owner_in_read_only_mode() {
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let req = BuildPermitRequest {
+        token: "USDC".to_string(),
+        spender: "permit2".to_string(),
+        amount: "100".to_string(),
+        deadline: None,
+        owner: None,
+    };
+
+    let result = service.build_permit(Parameters(req)).await.0;
+    assert!(
+        matches!(
+            result,
+            BuildPermitResult::Error {
+                error: ServiceError::InvalidWalletAddress(_)
+            }
+        ),
+        "Expected an owner to be required without a wallet"
+    );
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_build_permit_signs_usdc_permit_for_wallet() {
+    use alloy::primitives::{Address, B256, Signature};
+    use std::str::FromStr;
+
+    avoid_rate_limit().await;
+    let mut config = get_test_config().await;
+    // Well-known Anvil test key; the permit is only signed, never submitted
+    config.wallet.private_key =
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string();
+    config.wallet.allow_execution = true;
+    let service = EthereumTradingService::new(&config);
+
+    let req = BuildPermitRequest {
+        token: "USDC".to_string(),
+        spender: "permit2".to_string(),
+        amount: "max".to_string(),
+        deadline: None,
+        owner: None,
+    };
+
+    let BuildPermitResult::Success(resp) = service.build_permit(Parameters(req)).await.0 else {
+        panic!("Expected a USDC permit to be built");
+    };
+    assert!(resp.signed, "Wallet permits for Permit2 should be signed");
+    assert_eq!(resp.value, "unlimited");
+    assert!(matches!(resp.v, Some(27 | 28)));
+
+    let digest = B256::from_str(&resp.digest).unwrap();
+    let signature = Signature::from_str(resp.signature.as_deref().unwrap()).unwrap();
+    assert_eq!(
+        signature.recover_address_from_prehash(&digest).unwrap(),
+        Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap()
+    );
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_build_permit_leaves_other_owners_unsigned() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let req = BuildPermitRequest {
+        token: "UNI".to_string(),
+        spender: "v2".to_string(),
+        amount: "10".to_string(),
+        deadline: None,
+        owner: Some(WALLET_ADDRESS.to_string()),
+    };
+
+    let BuildPermitResult::Success(resp) = service.build_permit(Parameters(req)).await.0 else {
+        panic!("Expected a UNI permit to be built");
+    };
+    assert!(!resp.signed);
+    assert!(resp.signature.is_none());
+    assert!(resp.note.is_some());
+    assert_eq!(resp.value_raw, "10000000000000000000");
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_build_permit_rejects_token_without_permit() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let req = BuildPermitRequest {
+        token: "WETH".to_string(),
+        spender: "permit2".to_string(),
+        amount: "1".to_string(),
+        deadline: None,
+        owner: Some(WALLET_ADDRESS.to_string()),
+    };
+
+    let result = service.build_permit(Parameters(req)).await.0;
+    assert!(
+        matches!(
+            result,
+            BuildPermitResult::Error {
+                error: ServiceError::TokenNotFound(_)
+            }
+        ),
+        "Expected WETH to be rejected as lacking EIP-2612"
+    );
+}
+
+#[tokio::test]
+async fn test_swap_tokens_v2_quotes_from_mock_reserves() {
+    // 1000 WETH / 3,000,000 USDC
+    let service =
+        mock_service(|repo| repo.with_pair(WETH, USDC, units(1_000, 18), units(3_000_000, 6)));
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "1".to_string(),
+        ..Default::default()
+    });
+
+    match service.swap_tokens(params).await.0 {
+        SwapTokensResult::Success(response) => {
+            // 1 WETH in after the 0.3% fee, as UniswapV2Library.getAmountOut computes it
+            assert_eq!(response.estimated_output_raw, "2988020943");
+            assert_eq!(response.estimated_output, "2988.020943");
+            assert_eq!(response.route, "WETH -> USDC");
+            assert_eq!(response.uniswap_version, "v2");
+            // Slippage defaults to 0.5%
+            assert_eq!(response.minimum_output, "2973.080838");
+            // Without a from_address the typical 150k gas is priced at 20 gwei
+            assert_eq!(response.estimated_gas_raw, "150000");
+            assert!(response.price_impact.starts_with("0.1994"));
+        }
+        other => panic!("Expected a quote, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_with_config_applies_settings_to_injected_repository() {
+    let mut config = get_test_config().await;
+    config.trading.default_slippage = "1".to_string();
+    let service =
+        mock_service(|repo| repo.with_pair(WETH, USDC, units(1_000, 18), units(3_000_000, 6)))
+            .with_config(&config);
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "1".to_string(),
+        ..Default::default()
+    });
+
+    match service.swap_tokens(params).await.0 {
+        // 1% below the 2988.020943 USDC quote
+        SwapTokensResult::Success(response) => assert_eq!(response.minimum_output, "2958.140733"),
+        other => panic!("Expected a quote, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_with_repository_rejects_rpc_overrides() {
+    let service = mock_service(|repo| repo);
+
+    let params = Parameters(GetBalanceRequest {
+        wallet_address: WALLET_ADDRESS.to_string(),
+        token_contract_address: None,
+        summarize: None,
+        rpc_url: Some("https://example.org".to_string()),
+    });
+
+    assert!(matches!(
+        service.get_balance(params).await.0,
+        GetBalanceResult::Error {
+            error: ServiceError::RpcUrlNotAllowed(_)
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_swap_tokens_v2_routes_through_weth_when_better() {
+    // The direct USDC/DAI pair is shallow; both WETH pairs are deep
+    let service = mock_service(|repo| {
+        repo.with_pair(USDC, DAI, units(1_000, 6), units(1_000, 18))
+            .with_pair(USDC, WETH, units(3_000_000, 6), units(1_000, 18))
+            .with_pair(WETH, DAI, units(1_000, 18), units(3_000_000, 18))
+    });
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "USDC".to_string(),
+        to_token: "DAI".to_string(),
+        amount: "100".to_string(),
+        ..Default::default()
+    });
+
+    match service.swap_tokens(params).await.0 {
+        SwapTokensResult::Success(response) => {
+            assert_eq!(response.route, "USDC -> WETH -> DAI");
+            assert_eq!(response.path.len(), 3);
+            assert_eq!(response.estimated_output_raw, "99394303501572652144");
+        }
+        other => panic!("Expected a quote through WETH, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_swap_tokens_v2_zero_output_explains_liquidity() {
+    // A pair so lopsided that 1 USDC buys less than 1 wei of DAI
+    let service =
+        mock_service(|repo| repo.with_pair(USDC, DAI, units(1_000_000, 6), U256::from(1)));
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "USDC".to_string(),
+        to_token: "DAI".to_string(),
+        amount: "1".to_string(),
+        ..Default::default()
+    });
+
+    match service.swap_tokens(params).await.0 {
+        SwapTokensResult::Error {
+            error: ServiceError::SwapSimulationFailed(msg),
+        } => {
+            assert!(
+                msg.starts_with("Estimated output is 0 DAI for 1 USDC"),
+                "{msg}"
+            );
+            assert!(
+                msg.contains(
+                    "Insufficient liquidity (Reserve USDC: 1000000000000, Reserve DAI: 1)"
+                )
+            );
+        }
+        other => panic!("Expected SwapSimulationFailed, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_swap_tokens_v2_without_pair_fails() {
+    let service = mock_service(|repo| repo);
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "USDC".to_string(),
+        to_token: "DAI".to_string(),
+        amount: "1".to_string(),
+        ..Default::default()
+    });
+
+    match service.swap_tokens(params).await.0 {
+        SwapTokensResult::Error {
+            error: ServiceError::BlockchainError(msg),
+        } => assert!(msg.contains("No Uniswap V2 pair found"), "{msg}"),
+        other => panic!("Expected BlockchainError, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_swap_tokens_v3_picks_highest_output_fee_tier() {
+    let service = mock_service(|repo| {
+        repo.with_v3_quote(WETH, USDC, 3000, units(2_990, 6), 110_000)
+            .with_v3_quote(WETH, USDC, 500, units(2_995, 6), 100_000)
+    });
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "1".to_string(),
+        uniswap_version: Some("v3".to_string()),
+        explain: Some(true),
+        ..Default::default()
+    });
+
+    match service.swap_tokens(params).await.0 {
+        SwapTokensResult::Success(response) => {
+            assert_eq!(response.estimated_output, "2995");
+            assert!(response.transaction_data.contains("fee=500"));
+            // The quote's gas estimate is used, and without pool state the impact is unknown
+            assert_eq!(response.estimated_gas_raw, "100000");
+            assert_eq!(response.price_impact, "N/A (V3)");
+            let explanation = response.explanation.unwrap();
+            assert!(explanation.contains("1% -> no liquidity"), "{explanation}");
+        }
+        other => panic!("Expected a V3 quote, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_swap_tokens_v3_fee_tier_quotes_only_that_pool() {
+    let service = mock_service(|repo| {
+        repo.with_v3_quote(WETH, USDC, 3000, units(2_990, 6), 110_000)
+            .with_v3_quote(WETH, USDC, 500, units(2_995, 6), 100_000)
+            .with_v3_quote(WETH, USDC, 100, units(2_998, 6), 90_000)
+    });
+    let request = |fee_tier| SwapTokensRequest {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "1".to_string(),
+        uniswap_version: Some("v3".to_string()),
+        fee_tier,
+        ..Default::default()
+    };
+
+    // The 0.01% tier is part of the default scan
+    match service.swap_tokens(Parameters(request(None))).await.0 {
+        SwapTokensResult::Success(response) => assert_eq!(response.estimated_output, "2998"),
+        other => panic!("Expected a V3 quote, got {other:?}"),
+    }
+
+    match service.swap_tokens(Parameters(request(Some(3000)))).await.0 {
+        SwapTokensResult::Success(response) => {
+            assert_eq!(response.estimated_output, "2990");
+            assert!(response.transaction_data.contains("fee=3000"));
+        }
+        other => panic!("Expected a V3 quote, got {other:?}"),
+    }
+
+    assert!(matches!(
+        service.swap_tokens(Parameters(request(Some(2500)))).await.0,
+        SwapTokensResult::Error {
+            error: ServiceError::InvalidAmount(_)
+        }
+    ));
+
+    // A fee tier has no meaning for V2
+    let params = Parameters(SwapTokensRequest {
+        uniswap_version: None,
+        ..request(Some(500))
+    });
+    assert!(matches!(
+        service.swap_tokens(params).await.0,
+        SwapTokensResult::Error {
+            error: ServiceError::InvalidAmount(_)
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_swap_tokens_v3_exact_output_picks_cheapest_fee_tier() {
+    let service = mock_service(|repo| {
+        repo.with_v3_quote_exact_output(WETH, USDC, 3000, units(1, 18), 110_000)
+            .with_v3_quote_exact_output(WETH, USDC, 500, units(1, 18) / U256::from(2), 100_000)
+    });
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "1500".to_string(),
+        slippage_tolerance: Some("1".to_string()),
+        uniswap_version: Some("v3".to_string()),
+        swap_mode: Some("exact_output".to_string()),
+        ..Default::default()
+    });
+
+    match service.swap_tokens(params).await.0 {
+        SwapTokensResult::Success(response) => {
+            assert_eq!(response.estimated_output, "1500");
+            assert_eq!(response.minimum_output, "1500");
+            assert_eq!(response.estimated_input.as_deref(), Some("0.5"));
+            assert_eq!(response.maximum_input.as_deref(), Some("0.505"));
+            assert!(response.transaction_data.contains("fee=500"));
+            assert_eq!(response.estimated_gas_raw, "100000");
+        }
+        other => panic!("Expected a V3 exact-output quote, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_swap_tokens_v3_without_pool_fails() {
+    let service = mock_service(|repo| repo);
+
+    let params = Parameters(SwapTokensRequest {
+        from_token: "WETH".to_string(),
+        to_token: "DAI".to_string(),
+        amount: "1".to_string(),
+        uniswap_version: Some("v3".to_string()),
+        ..Default::default()
+    });
+
+    match service.swap_tokens(params).await.0 {
+        SwapTokensResult::Error {
+            error: ServiceError::SwapSimulationFailed(msg),
+        } => assert!(
+            msg.starts_with(
+                "No V3 liquidity pool found for WETH/DAI pair across the fee tiers \
+                 0.01%, 0.05%, 0.3%, 1%."
+            ),
+            "{msg}"
+        ),
+        other => panic!("Expected SwapSimulationFailed, got {other:?}"),
+    }
+
+    // A requested tier names only that pool
+    let params = Parameters(SwapTokensRequest {
+        from_token: "WETH".to_string(),
+        to_token: "DAI".to_string(),
+        amount: "1".to_string(),
+        uniswap_version: Some("v3".to_string()),
+        fee_tier: Some(500),
+        ..Default::default()
+    });
+
+    match service.swap_tokens(params).await.0 {
+        SwapTokensResult::Error {
+            error: ServiceError::SwapSimulationFailed(msg),
+        } => assert!(
+            msg.starts_with("No V3 liquidity pool found for WETH/DAI pair in the 0.05% pool."),
+            "{msg}"
+        ),
+        other => panic!("Expected SwapSimulationFailed, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_convert_units_between_eth_and_token_units() {
+    let service = mock_service(|repo| repo);
+    let convert = |value: &str, from: &str, to: &str| {
+        Parameters(ConvertUnitsRequest {
+            value: value.to_string(),
+            from_unit: from.to_string(),
+            to_unit: to.to_string(),
+        })
+    };
+
+    match service
+        .convert_units(convert("1.5", "ether", "gwei"))
+        .await
+        .0
+    {
+        ConvertUnitsResult::Success(response) => {
+            assert_eq!(response.value, "1500000000");
+            assert_eq!(response.raw, "1500000000000000000");
+        }
+        ConvertUnitsResult::Error { error } => panic!("Failed to convert: {error}"),
+    }
+
+    // A token symbol converts to and from its smallest unit with the token's decimals
+    match service
+        .convert_units(convert("250.5", "USDC", "wei"))
+        .await
+        .0
+    {
+        ConvertUnitsResult::Success(response) => {
+            assert_eq!(response.value, "250500000");
+            assert_eq!(response.from_unit, "USDC");
+            assert_eq!(response.from_decimals, 6);
+        }
+        ConvertUnitsResult::Error { error } => panic!("Failed to convert: {error}"),
+    }
+
+    // Different assets would need a price
+    for (from, to) in [("USDC", "ether"), ("USDC", "DAI")] {
+        assert!(
+            matches!(
+                service.convert_units(convert("1", from, to)).await.0,
+                ConvertUnitsResult::Error {
+                    error: ServiceError::InvalidAmount(_)
+                }
+            ),
+            "Expected {from} -> {to} to be rejected"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_quote_path_returns_amounts_along_path() {
+    let service = mock_service(|repo| {
+        repo.with_pair(USDC, WETH, units(3_000_000, 6), units(1_000, 18))
+            .with_pair(WETH, DAI, units(1_000, 18), units(3_000_000, 18))
+    });
+
+    let params = Parameters(QuotePathRequest {
+        path: vec!["USDC".to_string(), "WETH".to_string(), "DAI".to_string()],
+        amount: "100".to_string(),
+        dex: None,
+    });
+
+    match service.quote_path(params).await.0 {
+        QuotePathResult::Success(response) => {
+            assert_eq!(response.dex, "Uniswap V2");
+            assert_eq!(response.symbols, ["USDC", "WETH", "DAI"]);
+            assert_eq!(response.amounts_raw.len(), 3);
+            assert_eq!(response.amounts_raw[0], "100000000");
+            assert_eq!(response.amounts_raw[2], "99394303501572652144");
+            assert_eq!(response.amount_out, response.amounts[2]);
+        }
+        other => panic!("Expected amounts along the path, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_quote_path_rejects_invalid_paths() {
+    let service = mock_service(|repo| repo);
+
+    for path in [
+        vec!["USDC"],
+        vec!["USDC", "WETH", "USDC", "WETH", "USDC", "WETH"],
+        vec!["USDC", "USDC"],
+    ] {
+        let params = Parameters(QuotePathRequest {
+            path: path.iter().map(|token| token.to_string()).collect(),
+            amount: "100".to_string(),
+            dex: None,
+        });
+
+        assert!(
+            matches!(
+                service.quote_path(params).await.0,
+                QuotePathResult::Error {
+                    error: ServiceError::InvalidAmount(_)
+                }
+            ),
+            "Expected {path:?} to be rejected"
+        );
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+#[ignore]
+async fn test_quote_path_multi_hop_should_work() {
+    avoid_rate_limit().await;
+    let config = get_test_config().await;
+    let service = EthereumTradingService::new(&config);
+
+    let params = Parameters(QuotePathRequest {
+        path: vec!["USDC".to_string(), "WETH".to_string(), "DAI".to_string()],
+        amount: "1000".to_string(),
+        dex: None,
+    });
+
+    match service.quote_path(params).await.0 {
+        QuotePathResult::Success(response) => {
+            println!("✅ USDC -> WETH -> DAI: {:?}", response.amounts);
+            assert_eq!(response.amounts.len(), 3);
+            assert_ne!(response.amounts_raw[2], "0");
+        }
+        QuotePathResult::Error { error } => panic!("Failed to quote path: {error:?}"),
+    }
+}
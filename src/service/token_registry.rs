@@ -1,6 +1,15 @@
 use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
 
-/// Common ERC20 token contract addresses on Ethereum mainnet
+use alloy::primitives::Address;
+use serde::Deserialize;
+
+use crate::repository::ChainConfig;
+use crate::service::{ServiceError, ServiceResult};
+
+// Common ERC20 token contract addresses on Ethereum mainnet
 
 // Stablecoins
 const USDT_ADDRESS: &str = "0xdac17f958d2ee523a2206206994597c13d831ec7";
@@ -45,10 +54,29 @@ const ENJ_ADDRESS: &str = "0xf629cbd94d3791c9250152bd8dfbdf380e2a3b9c";
 const BAT_ADDRESS: &str = "0x0d8775f648430679a709e98d2b0cb6250d2887ef";
 const ZRX_ADDRESS: &str = "0xe41d2489571d322189246dafa5ebde1f4699f498";
 
+/// A token list in the Uniswap token-list format (https://tokenlists.org)
+#[derive(Debug, Deserialize)]
+struct TokenList {
+    tokens: Vec<TokenListEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenListEntry {
+    chain_id: u64,
+    address: String,
+    symbol: String,
+    decimals: u8,
+}
+
 /// Token registry for mapping symbols to contract addresses
 #[derive(Debug, Clone)]
 pub struct TokenRegistry {
-    registry: HashMap<String, &'static str>,
+    registry: HashMap<String, String>,
+    /// Token decimals by lowercase address, known from a token list
+    decimals: HashMap<String, u8>,
+    /// Configured decimals by lowercase address, used instead of the token's `decimals()`
+    decimals_overrides: HashMap<String, u8>,
 }
 
 impl TokenRegistry {
@@ -56,56 +84,135 @@ impl TokenRegistry {
     pub fn new() -> Self {
         Self {
             registry: Self::init_registry(),
+            decimals: HashMap::new(),
+            decimals_overrides: HashMap::new(),
+        }
+    }
+
+    /// Create a token registry for the given chain
+    ///
+    /// Mainnet gets the full token list; other networks only know ETH/WETH and USDC.
+    pub fn for_chain(chain: &ChainConfig) -> Self {
+        if *chain == ChainConfig::MAINNET {
+            return Self::new();
+        }
+
+        let mut registry = HashMap::new();
+        registry.insert("ETH".to_string(), chain.weth.to_string());
+        registry.insert("WETH".to_string(), chain.weth.to_string());
+        registry.insert("USDC".to_string(), chain.usdc.to_string());
+
+        Self {
+            registry,
+            decimals: HashMap::new(),
+            decimals_overrides: HashMap::new(),
         }
     }
 
+    /// Create a token registry for the given chain, extended with a token-list JSON file
+    ///
+    /// Only entries whose `chainId` matches the chain are merged; the built-in tokens stay as a fallback.
+    pub fn from_token_list_file(
+        path: impl AsRef<Path>,
+        chain: &ChainConfig,
+    ) -> ServiceResult<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| {
+            ServiceError::InternalError(format!(
+                "Failed to read token list {}: {e}",
+                path.display()
+            ))
+        })?;
+
+        let mut registry = Self::for_chain(chain);
+        let merged = registry.merge_token_list(&content, chain.chain_id)?;
+        tracing::info!(
+            "Loaded {merged} tokens for chain {} from {}",
+            chain.chain_id,
+            path.display()
+        );
+        Ok(registry)
+    }
+
+    /// Merge a token list (Uniswap token-list JSON) into the registry
+    ///
+    /// Entries for other chains are skipped. A listed symbol replaces the built-in entry,
+    /// except "ETH", which always stays the alias for the chain's WETH.
+    /// Returns the number of merged tokens.
+    pub fn merge_token_list(&mut self, json: &str, chain_id: u64) -> ServiceResult<usize> {
+        let list: TokenList = serde_json::from_str(json)
+            .map_err(|e| ServiceError::InternalError(format!("Invalid token list: {e}")))?;
+
+        let mut merged = 0;
+        for token in list.tokens.into_iter().filter(|t| t.chain_id == chain_id) {
+            let symbol = token.symbol.to_uppercase();
+            if symbol == "ETH" {
+                continue;
+            }
+            let Ok(address) = Address::from_str(&token.address) else {
+                tracing::warn!(
+                    "Skipping {symbol} in token list: invalid address {}",
+                    token.address
+                );
+                continue;
+            };
+
+            let address = address.to_string().to_lowercase();
+            self.decimals.insert(address.clone(), token.decimals);
+            self.registry.insert(symbol, address);
+            merged += 1;
+        }
+
+        Ok(merged)
+    }
+
     /// Initialize the token registry with common tokens
-    fn init_registry() -> HashMap<String, &'static str> {
+    fn init_registry() -> HashMap<String, String> {
         let mut registry = HashMap::new();
 
         // Native & Wrapped tokens
-        registry.insert("ETH".to_string(), WETH_ADDRESS);
-        registry.insert("WETH".to_string(), WETH_ADDRESS);
-        registry.insert("WBTC".to_string(), WBTC_ADDRESS);
+        registry.insert("ETH".to_string(), WETH_ADDRESS.to_string());
+        registry.insert("WETH".to_string(), WETH_ADDRESS.to_string());
+        registry.insert("WBTC".to_string(), WBTC_ADDRESS.to_string());
 
         // Stablecoins
-        registry.insert("USDT".to_string(), USDT_ADDRESS);
-        registry.insert("USDC".to_string(), USDC_ADDRESS);
-        registry.insert("DAI".to_string(), DAI_ADDRESS);
-        registry.insert("BUSD".to_string(), BUSD_ADDRESS);
-        registry.insert("FRAX".to_string(), FRAX_ADDRESS);
+        registry.insert("USDT".to_string(), USDT_ADDRESS.to_string());
+        registry.insert("USDC".to_string(), USDC_ADDRESS.to_string());
+        registry.insert("DAI".to_string(), DAI_ADDRESS.to_string());
+        registry.insert("BUSD".to_string(), BUSD_ADDRESS.to_string());
+        registry.insert("FRAX".to_string(), FRAX_ADDRESS.to_string());
 
         // DeFi tokens
-        registry.insert("UNI".to_string(), UNI_ADDRESS);
-        registry.insert("AAVE".to_string(), AAVE_ADDRESS);
-        registry.insert("LINK".to_string(), LINK_ADDRESS);
-        registry.insert("COMP".to_string(), COMP_ADDRESS);
-        registry.insert("MKR".to_string(), MKR_ADDRESS);
-        registry.insert("SNX".to_string(), SNX_ADDRESS);
-        registry.insert("CRV".to_string(), CRV_ADDRESS);
-        registry.insert("SUSHI".to_string(), SUSHI_ADDRESS);
-        registry.insert("LDO".to_string(), LDO_ADDRESS);
+        registry.insert("UNI".to_string(), UNI_ADDRESS.to_string());
+        registry.insert("AAVE".to_string(), AAVE_ADDRESS.to_string());
+        registry.insert("LINK".to_string(), LINK_ADDRESS.to_string());
+        registry.insert("COMP".to_string(), COMP_ADDRESS.to_string());
+        registry.insert("MKR".to_string(), MKR_ADDRESS.to_string());
+        registry.insert("SNX".to_string(), SNX_ADDRESS.to_string());
+        registry.insert("CRV".to_string(), CRV_ADDRESS.to_string());
+        registry.insert("SUSHI".to_string(), SUSHI_ADDRESS.to_string());
+        registry.insert("LDO".to_string(), LDO_ADDRESS.to_string());
 
         // Layer 2 & Scaling
-        registry.insert("MATIC".to_string(), MATIC_ADDRESS);
-        registry.insert("ARB".to_string(), ARB_ADDRESS);
-        registry.insert("OP".to_string(), OP_ADDRESS);
+        registry.insert("MATIC".to_string(), MATIC_ADDRESS.to_string());
+        registry.insert("ARB".to_string(), ARB_ADDRESS.to_string());
+        registry.insert("OP".to_string(), OP_ADDRESS.to_string());
 
         // Meme tokens
-        registry.insert("SHIB".to_string(), SHIB_ADDRESS);
-        registry.insert("PEPE".to_string(), PEPE_ADDRESS);
-        registry.insert("FLOKI".to_string(), FLOKI_ADDRESS);
+        registry.insert("SHIB".to_string(), SHIB_ADDRESS.to_string());
+        registry.insert("PEPE".to_string(), PEPE_ADDRESS.to_string());
+        registry.insert("FLOKI".to_string(), FLOKI_ADDRESS.to_string());
 
         // Exchange & Utility tokens
-        registry.insert("APE".to_string(), APE_ADDRESS);
-        registry.insert("GRT".to_string(), GRT_ADDRESS);
-        registry.insert("FTM".to_string(), FTM_ADDRESS);
-        registry.insert("SAND".to_string(), SAND_ADDRESS);
-        registry.insert("MANA".to_string(), MANA_ADDRESS);
-        registry.insert("AXS".to_string(), AXS_ADDRESS);
-        registry.insert("ENJ".to_string(), ENJ_ADDRESS);
-        registry.insert("BAT".to_string(), BAT_ADDRESS);
-        registry.insert("ZRX".to_string(), ZRX_ADDRESS);
+        registry.insert("APE".to_string(), APE_ADDRESS.to_string());
+        registry.insert("GRT".to_string(), GRT_ADDRESS.to_string());
+        registry.insert("FTM".to_string(), FTM_ADDRESS.to_string());
+        registry.insert("SAND".to_string(), SAND_ADDRESS.to_string());
+        registry.insert("MANA".to_string(), MANA_ADDRESS.to_string());
+        registry.insert("AXS".to_string(), AXS_ADDRESS.to_string());
+        registry.insert("ENJ".to_string(), ENJ_ADDRESS.to_string());
+        registry.insert("BAT".to_string(), BAT_ADDRESS.to_string());
+        registry.insert("ZRX".to_string(), ZRX_ADDRESS.to_string());
 
         registry
     }
@@ -115,7 +222,61 @@ impl TokenRegistry {
     /// Returns the contract address if found, None otherwise
     pub fn lookup(&self, symbol: &str) -> Option<&str> {
         let symbol_upper = symbol.to_uppercase();
-        self.registry.get(&symbol_upper).copied()
+        self.registry.get(&symbol_upper).map(String::as_str)
+    }
+
+    /// Lookup token symbol by contract address (case-insensitive)
+    ///
+    /// "ETH" is only an alias for the WETH contract, so that address resolves to "WETH".
+    /// Returns the symbol if found, None otherwise
+    pub fn lookup_symbol(&self, address: &str) -> Option<&str> {
+        let address = address.trim().to_lowercase();
+        self.registry
+            .iter()
+            .filter(|(symbol, token)| symbol.as_str() != "ETH" && token.to_lowercase() == address)
+            .map(|(symbol, _)| symbol.as_str())
+            .next()
+    }
+
+    /// Lookup token decimals by contract address (case-insensitive)
+    ///
+    /// Only tokens with a decimals override or loaded from a token list have known decimals;
+    /// an override wins over the token list.
+    pub fn decimals(&self, address: &str) -> Option<u8> {
+        let address = address.trim().to_lowercase();
+        self.decimals_overrides
+            .get(&address)
+            .or_else(|| self.decimals.get(&address))
+            .copied()
+    }
+
+    /// Assign decimals to a token (symbol or address), for tokens whose `decimals()` is
+    /// missing or wrong
+    ///
+    /// Fails for symbols the registry does not know.
+    pub fn override_decimals(&mut self, token: &str, decimals: u8) -> ServiceResult<()> {
+        let address = match Address::from_str(token.trim()) {
+            Ok(address) => address,
+            Err(_) => self
+                .lookup(token.trim())
+                .and_then(|address| Address::from_str(address).ok())
+                .ok_or_else(|| {
+                    ServiceError::TokenNotFound(format!(
+                        "{token} in decimals_override is neither an address nor a known symbol"
+                    ))
+                })?,
+        };
+        self.decimals_overrides
+            .insert(address.to_string().to_lowercase(), decimals);
+        Ok(())
+    }
+
+    /// Configured decimals overrides by token address
+    pub fn decimals_overrides(&self) -> HashMap<Address, u8> {
+        self.decimals_overrides
+            .iter()
+            .filter_map(|(address, decimals)| Some((Address::from_str(address).ok()?, *decimals)))
+            .collect()
     }
 
     /// Get list of all supported token symbols (sorted alphabetically)
@@ -125,6 +286,37 @@ impl TokenRegistry {
         tokens
     }
 
+    /// Search token symbols containing `query` (case-insensitive)
+    ///
+    /// Results are ordered by closeness: exact match first, then symbols starting with the
+    /// query, then the rest by where the query occurs; ties go to the shorter symbol, then
+    /// alphabetically. Returns (symbol, address) pairs.
+    pub fn search(&self, query: &str) -> Vec<(String, &str)> {
+        let query = query.trim().to_uppercase();
+        let mut matches: Vec<(usize, &String, &String)> = self
+            .registry
+            .iter()
+            .filter_map(|(symbol, address)| {
+                symbol
+                    .find(&query)
+                    .map(|position| (position, symbol, address))
+            })
+            .collect();
+
+        matches.sort_by(|(pos_a, sym_a, _), (pos_b, sym_b, _)| {
+            (**sym_a != query, pos_a, sym_a.len(), sym_a).cmp(&(
+                **sym_b != query,
+                pos_b,
+                sym_b.len(),
+                sym_b,
+            ))
+        });
+        matches
+            .into_iter()
+            .map(|(_, symbol, address)| (symbol.clone(), address.as_str()))
+            .collect()
+    }
+
     /// Check if a token symbol is supported
     pub fn contains(&self, symbol: &str) -> bool {
         let symbol_upper = symbol.to_uppercase();
@@ -141,7 +333,7 @@ impl TokenRegistry {
         self.registry.is_empty()
     }
 
-    /// Get WETH address
+    /// Get WETH address on Ethereum mainnet
     pub fn weth_address() -> &'static str {
         WETH_ADDRESS
     }
@@ -167,6 +359,30 @@ mod tests {
         assert_eq!(registry.lookup("WETH"), Some(WETH_ADDRESS));
     }
 
+    #[test]
+    fn test_lookup_symbol_existing_address() {
+        let registry = TokenRegistry::new();
+
+        assert_eq!(registry.lookup_symbol(USDT_ADDRESS), Some("USDT"));
+        assert_eq!(
+            registry.lookup_symbol("0xdAC17F958D2ee523a2206206994597C13D831ec7"),
+            Some("USDT")
+        );
+        // ETH and WETH share an address; the contract is WETH
+        assert_eq!(registry.lookup_symbol(WETH_ADDRESS), Some("WETH"));
+    }
+
+    #[test]
+    fn test_lookup_symbol_unknown_address() {
+        let registry = TokenRegistry::new();
+
+        assert_eq!(
+            registry.lookup_symbol("0x0000000000000000000000000000000000000001"),
+            None
+        );
+        assert_eq!(registry.lookup_symbol("not an address"), None);
+    }
+
     #[test]
     fn test_lookup_non_existing_token() {
         let registry = TokenRegistry::new();
@@ -200,10 +416,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_search_orders_by_closeness() {
+        let mut registry = TokenRegistry::new();
+        registry
+            .merge_token_list(
+                r#"{"tokens": [
+                    {"chainId": 1, "address": "0xC18360217D8F7Ab5e7c516566761Ea12Ce7F9D72", "symbol": "UNIX", "decimals": 18},
+                    {"chainId": 1, "address": "0x0000000000000000000000000000000000000002", "symbol": "XUNI", "decimals": 18}
+                ]}"#,
+                1,
+            )
+            .unwrap();
+
+        let symbols: Vec<String> = registry
+            .search("uni")
+            .into_iter()
+            .map(|(symbol, _)| symbol)
+            .collect();
+        assert_eq!(symbols, vec!["UNI", "UNIX", "XUNI"]);
+        assert_eq!(
+            registry.search("USDT"),
+            vec![("USDT".to_string(), USDT_ADDRESS)]
+        );
+
+        // Exact match, then prefix, then shorter symbols before longer ones
+        let eth: Vec<String> = registry
+            .search("eth")
+            .into_iter()
+            .map(|(symbol, _)| symbol)
+            .collect();
+        assert_eq!(eth, vec!["ETH", "WETH"]);
+        assert!(registry.search("nothing").is_empty());
+    }
+
     #[test]
     fn test_len() {
         let registry = TokenRegistry::new();
-        assert!(registry.len() > 0);
+        assert_ne!(registry.len(), 0);
         assert!(!registry.is_empty());
     }
 
@@ -211,4 +461,114 @@ mod tests {
     fn test_weth_address() {
         assert_eq!(TokenRegistry::weth_address(), WETH_ADDRESS);
     }
+
+    #[test]
+    fn test_for_chain_uses_chain_addresses() {
+        let mainnet = TokenRegistry::for_chain(&ChainConfig::MAINNET);
+        assert_eq!(mainnet.len(), TokenRegistry::new().len());
+
+        let base = TokenRegistry::for_chain(&ChainConfig::BASE);
+        let weth = ChainConfig::BASE.weth.to_string();
+        assert_eq!(base.lookup("eth"), Some(weth.as_str()));
+        assert_eq!(base.lookup("WETH"), Some(weth.as_str()));
+        assert!(base.contains("USDC"));
+        assert!(!base.contains("USDT"));
+    }
+
+    const TOKEN_LIST: &str = r#"{
+        "name": "Test List",
+        "tokens": [
+            {"chainId": 1, "address": "0xC18360217D8F7Ab5e7c516566761Ea12Ce7F9D72", "name": "Ethereum Name Service", "symbol": "ENS", "decimals": 18},
+            {"chainId": 1, "address": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", "name": "USD Coin", "symbol": "usdc", "decimals": 6},
+            {"chainId": 1, "address": "0x0000000000000000000000000000000000000001", "name": "Fake Ether", "symbol": "ETH", "decimals": 18},
+            {"chainId": 1, "address": "not an address", "name": "Broken", "symbol": "BRK", "decimals": 18},
+            {"chainId": 10, "address": "0x4200000000000000000000000000000000000042", "name": "Optimism", "symbol": "OPT", "decimals": 18}
+        ]
+    }"#;
+
+    #[test]
+    fn test_merge_token_list_filters_by_chain() {
+        let mut registry = TokenRegistry::new();
+        let merged = registry.merge_token_list(TOKEN_LIST, 1).unwrap();
+
+        assert_eq!(merged, 2);
+        assert_eq!(
+            registry.lookup("ens"),
+            Some("0xc18360217d8f7ab5e7c516566761ea12ce7f9d72")
+        );
+        assert_eq!(registry.lookup("USDC"), Some(USDC_ADDRESS));
+        // Other chains, invalid addresses and ETH overrides are skipped
+        assert!(!registry.contains("OPT"));
+        assert!(!registry.contains("BRK"));
+        assert_eq!(registry.lookup("ETH"), Some(WETH_ADDRESS));
+        // Built-in tokens remain
+        assert!(registry.contains("USDT"));
+    }
+
+    #[test]
+    fn test_merge_token_list_caches_decimals() {
+        let mut registry = TokenRegistry::new();
+        registry.merge_token_list(TOKEN_LIST, 1).unwrap();
+
+        assert_eq!(
+            registry.decimals("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+            Some(6)
+        );
+        assert_eq!(
+            registry.decimals("0xc18360217d8f7ab5e7c516566761ea12ce7f9d72"),
+            Some(18)
+        );
+        assert_eq!(registry.decimals(USDT_ADDRESS), None);
+    }
+
+    #[test]
+    fn test_merge_token_list_invalid_json() {
+        let mut registry = TokenRegistry::new();
+        assert!(registry.merge_token_list("{\"tokens\": 1}", 1).is_err());
+        assert!(
+            TokenRegistry::from_token_list_file("missing.json", &ChainConfig::MAINNET).is_err()
+        );
+    }
+
+    #[test]
+    fn test_override_decimals_by_symbol_or_address() {
+        let mut registry = TokenRegistry::new();
+        let token = "0x0000000000000000000000000000000000000001";
+
+        registry.override_decimals("usdt", 8).unwrap();
+        registry.override_decimals(token, 9).unwrap();
+
+        // Overrides resolve symbols and win over built-in knowledge
+        assert_eq!(registry.decimals(USDT_ADDRESS), Some(8));
+        assert_eq!(registry.decimals(token), Some(9));
+        let overrides = registry.decimals_overrides();
+        assert_eq!(overrides.len(), 2);
+        assert_eq!(overrides[&Address::from_str(token).unwrap()], 9);
+    }
+
+    #[test]
+    fn test_override_decimals_wins_over_token_list() {
+        let mut registry = TokenRegistry::new();
+        let json = r#"{"tokens": [
+            {"chainId": 1, "address": "0x0000000000000000000000000000000000000001", "symbol": "ODD", "decimals": 18}
+        ]}"#;
+        registry.merge_token_list(json, 1).unwrap();
+
+        registry.override_decimals("ODD", 0).unwrap();
+
+        assert_eq!(
+            registry.decimals("0x0000000000000000000000000000000000000001"),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_override_decimals_rejects_unknown_symbol() {
+        let mut registry = TokenRegistry::new();
+
+        let err = registry.override_decimals("NOPE", 6).unwrap_err();
+
+        assert!(matches!(err, ServiceError::TokenNotFound(_)));
+        assert!(registry.decimals_overrides().is_empty());
+    }
 }
@@ -1,54 +1,303 @@
+use std::collections::HashMap;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use alloy::primitives::{Address, U256};
-use alloy::providers::ProviderBuilder;
-use rmcp::handler::server::tool::ToolRouter;
+use alloy::primitives::{Address, B256, Bytes, U256, address};
+use alloy::providers::DynProvider;
+use rmcp::handler::server::tool::{ToolCallContext, ToolRouter};
 use rmcp::handler::server::wrapper::Parameters;
-use rmcp::{Json, ServerHandler, tool, tool_handler, tool_router};
+use rmcp::model::{
+    CallToolRequestParam, CallToolResult, Content, ErrorCode, ListToolsResult,
+    PaginatedRequestParam,
+};
+use rmcp::service::RequestContext;
+use rmcp::{ErrorData, Json, RoleServer, ServerHandler, tool, tool_router};
 use rust_decimal::Decimal;
+use serde_json::Value;
 use tracing::instrument;
 
-use crate::config::Config;
-use crate::repository::{AlloyEthereumRepository, EthereumRepository};
+use crate::config::{
+    ChainlinkConfig, Config, EthPriceSource, NumberFormat, RpcConfig, ServerConfig, TradingConfig,
+};
+use crate::repository::{
+    AlloyEthereumRepository, ChainConfig, CircuitBreaker, CircuitBreakerRepository,
+    EthereumRepository, NativeEth, Network, RepoResult, RepositoryError, SwapExecution, SwapIntent,
+    TimeoutRepository, TokenBalance, V2Dex, V3PoolState, connect_http_provider,
+};
+use crate::service::argument_hints::{DESERIALIZE_ERROR_PREFIX, describe_argument_error};
+use crate::service::number_format::{allow_numbers_in_schema, numeric_strings_to_numbers};
+use crate::service::price_feed::CoinGeckoPriceFeed;
 use crate::service::token_registry::TokenRegistry;
 use crate::service::types::{
-    GetBalanceRequest, GetBalanceResponse, GetBalanceResult, GetTokenPriceRequest,
-    GetTokenPriceResponse, GetTokenPriceResult, SwapTokensRequest, SwapTokensResponse,
-    SwapTokensResult,
+    ApproveTokenRequest, ApproveTokenResponse, ApproveTokenResult, BalanceEntry,
+    BuildPermitRequest, BuildPermitResponse, BuildPermitResult, CheckAllowanceRequest,
+    CheckAllowanceResponse, CheckAllowanceResult, CompareGasTimingRequest,
+    CompareGasTimingResponse, CompareGasTimingResult, ConvertUnitsRequest, ConvertUnitsResponse,
+    ConvertUnitsResult, DecodedSwap, DetectRebasingRequest, DetectRebasingResponse,
+    DetectRebasingResult, EstimateNetYieldRequest, EstimateNetYieldResponse,
+    EstimateNetYieldResult, EstimateRoundTripRequest, EstimateRoundTripResponse,
+    EstimateRoundTripResult, EstimateSwapGasRequest, EstimateSwapGasResponse,
+    EstimateSwapGasResult, ExcludedRoute, ExecuteSwapResponse, ExecuteSwapResult,
+    FindBestRouteRequest, FindBestRouteResponse, FindBestRouteResult, GetBalanceRequest,
+    GetBalanceResponse, GetBalanceResult, GetBalancesRequest, GetBalancesResponse,
+    GetBalancesResult, GetBestSwapRequest, GetBestSwapResponse, GetBestSwapResult,
+    GetChainStatusResponse, GetChainStatusResult, GetContractAddressesResponse,
+    GetEthPriceResponse, GetEthPriceResult, GetEthStablecoinPricesResponse,
+    GetEthStablecoinPricesResult, GetMyApprovalRequest, GetMyApprovalResponse, GetMyApprovalResult,
+    GetPoolCapacityRequest, GetPoolCapacityResponse, GetPoolCapacityResult, GetPoolInfoRequest,
+    GetPoolInfoResponse, GetPoolInfoResult, GetPoolVolumeRequest, GetPoolVolumeResponse,
+    GetPoolVolumeResult, GetPortfolioValueRequest, GetPortfolioValueResponse,
+    GetPortfolioValueResult, GetRecentTransactionsRequest, GetRecentTransactionsResponse,
+    GetRecentTransactionsResult, GetSwapStatusRequest, GetSwapStatusResponse, GetSwapStatusResult,
+    GetTokenInfoRequest, GetTokenInfoResponse, GetTokenInfoResult, GetTokenPriceRequest,
+    GetTokenPriceResponse, GetTokenPriceResult, GetTokenPricesRequest, GetTokenPricesResponse,
+    GetTokenPricesResult, GetTransactionRequest, GetTransactionResponse, GetTransactionResult,
+    GetWalletAddressResponse, GetWalletSnapshotResponse, GetWalletSnapshotResult,
+    ImpliedSlippageRequest, ImpliedSlippageResponse, ImpliedSlippageResult,
+    ListSupportedTokensRequest, ListSupportedTokensResponse, PortfolioHolding,
+    PriceSourceComparison, QuotePathRequest, QuotePathResponse, QuotePathResult, RecentTransaction,
+    ResolveTokensRequest, ResolveTokensResponse, ResolveTokensResult, ResolvedToken, RouteQuote,
+    SimulatePendingSwapRequest, SimulatePendingSwapResponse, SimulatePendingSwapResult,
+    SourcePrice, StablecoinPrice, StopLossPlanRequest, StopLossPlanResponse, StopLossPlanResult,
+    SupportedToken, SwapTokensRequest, SwapTokensResponse, SwapTokensResult, TokenPriceEntry,
+    VerifyQuoteRequest, VerifyQuoteResponse, VerifyQuoteResult,
 };
 use crate::service::utils::{
-    calculate_exchange_rate, calculate_minimum_output, calculate_price, calculate_price_impact,
-    format_balance, parse_amount,
+    apply_gas_buffer, calculate_exchange_rate, calculate_gas_cost, calculate_implied_slippage,
+    calculate_maximum_input, calculate_minimum_output, calculate_net_yield,
+    calculate_output_after_pending_swap, calculate_pool_capacity, calculate_pool_liquidity_usd,
+    calculate_price, calculate_price_impact, calculate_price_spread, calculate_probe_slippage,
+    calculate_quote_change, calculate_round_trip, calculate_stop_loss_proceeds,
+    calculate_v3_price_impact, combine_price_impacts, convert_amount, format_balance,
+    format_balance_rounded, format_decimal, is_revert_error, parse_amount, parse_slippage,
+    permit_digest, simulate_v2_swap, sum_swap_volume, u256_to_decimal, v3_spot_price,
+    validate_positive_amount,
 };
 use crate::service::{ServiceError, ServiceResult};
+use crate::shutdown::Drain;
+use crate::telemetry::record_tool_call;
 
 /// ETH decimals - Ethereum uses 18 decimal places (1 ETH = 10^18 wei)
 const ETH_DECIMALS: u8 = 18;
+/// USDC decimals, the same on every supported chain
+const USDC_DECIMALS: u8 = 6;
+const GWEI_DECIMALS: u8 = 9;
+
+/// Fee tier of the Uniswap V3 USDC/WETH pool read for `eth_price_source: uniswap_v3`, the
+/// deepest one on every supported chain
+const ETH_USD_V3_FEE: u32 = 500;
+
+/// Uniswap V3 fee tiers quoted and inspected, in hundredths of a bip
+const V3_FEE_TIERS: [u32; 4] = [100, 500, 3000, 10000];
+
+/// Placeholder address wallets and DEX aggregators use for native ETH
+const NATIVE_ETH_ADDRESS: Address = address!("0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE");
+
+/// Uniswap Permit2, deployed at the same address on every supported chain
+const PERMIT2_ADDRESS: Address = address!("0x000000000022D473030F116dDEE9F6B43aC78BA3");
+
+/// Allowances from this value up count as unlimited: max-uint approvals stay above it even
+/// for tokens that decrement them on every transfer
+const UNLIMITED_ALLOWANCE_THRESHOLD: U256 = U256::from_limbs([0, 0, 0, 1 << 63]);
+
+/// Share of a Uniswap V2 round trip paid as LP fees: 1 - 0.997² (0.5991%)
+const V2_ROUND_TRIP_FEE_PERCENT: Decimal = Decimal::from_parts(5991, 0, 0, false, 4);
+
+/// Default and maximum block range for `get_recent_transactions`, which fetches every block in full
+const DEFAULT_RECENT_TX_BLOCKS: u64 = 20;
+const MAX_RECENT_TX_BLOCKS: u64 = 100;
+
+/// Upper bound on RPC retries per route in `find_best_route`
+const MAX_ROUTE_RPC_RETRIES: u32 = 3;
+
+/// Most tokens a batch tool such as `get_token_prices` or `get_balances` accepts
+const MAX_BATCH_TOKENS: usize = 50;
+
+/// Most tokens a `quote_path` path may list (four hops)
+const MAX_QUOTE_PATH_TOKENS: usize = 5;
+
+/// Stablecoins ETH is priced against by `get_eth_stablecoin_prices`
+const ETH_PRICE_STABLECOINS: [&str; 4] = ["USDC", "USDT", "DAI", "FRAX"];
+/// Spread between stablecoin ETH prices above which the USD price is flagged as unreliable
+const STABLECOIN_SPREAD_WARNING_PERCENT: u32 = 1;
+
+/// Base delay between retries of a V3 fee-tier quote, multiplied by the attempt number
+const V3_SCAN_RETRY_DELAY: Duration = Duration::from_millis(250);
+
+/// WETH sold in the quote a V3 spot price is derived from (0.01 WETH)
+const PRICE_QUOTE_WEI: u64 = 10_000_000_000_000_000;
+/// WETH sold in the larger quote that measures a pool's depth against its spot price (1 WETH)
+const DEPTH_PROBE_WEI: u64 = 1_000_000_000_000_000_000;
 
 pub struct EthereumTradingService {
     tool_router: ToolRouter<Self>,
     repository: Box<dyn EthereumRepository>,
+    /// Repositories for the V2 forks deployed on this network, selected by a request's `dex`
+    v2_forks: Vec<(V2Dex, Box<dyn EthereumRepository>)>,
     token_registry: TokenRegistry,
+    network: Network,
+    chain: ChainConfig,
+    allow_execution: bool,
+    display_precision: Option<u32>,
+    balance_decimals: Option<u8>,
+    number_format: NumberFormat,
+    argument_hints: bool,
+    v3_scan_retries: u32,
+    gas_buffer_percent: u32,
+    eth_price_source: EthPriceSource,
+    /// Chainlink USD feeds by token address, from `chainlink.feeds`
+    chainlink_feeds: HashMap<Address, Address>,
+    chainlink_max_age_secs: u64,
+    default_slippage: String,
+    deadline_secs: u64,
+    min_liquidity_usd: Option<Decimal>,
+    price_feed: Option<Arc<CoinGeckoPriceFeed>>,
+    rpc_overrides: RpcOverrides,
+    /// Shutdown drain this service's tool calls report to; calls are untracked without one
+    drain: Option<Drain>,
+}
+
+/// Read-only services for the endpoints in `rpc.allowed_urls`, selected by a request's `rpc_url`
+struct RpcOverrides {
+    /// Configuration the override services are derived from; without one, overrides are
+    /// rejected
+    config: Option<Config>,
+    /// Services created so far by URL, so each endpoint keeps one provider
+    services: Mutex<HashMap<String, Arc<EthereumTradingService>>>,
+}
+
+/// A candidate swap route compared by `find_best_route`
+enum RouteCandidate {
+    /// Uniswap V2 route through the given token path
+    V2(Vec<Address>),
+    /// Uniswap V3 single-hop route through the pool with the given fee tier
+    V3(u32),
+}
+
+/// On-chain source for `get_token_price`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PriceSource {
+    /// Uniswap V2 pair reserves
+    V2,
+    /// Uniswap V3 QuoterV2 quotes
+    V3,
+    /// Both, reporting the more liquid one
+    Best,
+    /// The token's Chainlink USD feed
+    Chainlink,
+}
+
+impl PriceSource {
+    fn label(self) -> &'static str {
+        match self {
+            Self::V2 => "v2",
+            Self::V3 => "v3",
+            Self::Best => "best",
+            Self::Chainlink => "chainlink",
+        }
+    }
+}
+
+/// A token's price on one Uniswap version and how deep the pool behind it is
+struct SourceQuote {
+    price_eth: Decimal,
+    price_usd: Decimal,
+    /// V3 fee tier the price was quoted on
+    fee_tier: Option<u32>,
+    /// Percentage by which a 1 WETH buy fills worse than the spot price
+    probe_slippage: Decimal,
+}
+
+/// The fixed side of a Uniswap V3 quote
+#[derive(Debug, Clone, Copy)]
+enum V3QuoteAmount {
+    /// Sell this input; the tier with the highest output wins
+    ExactInput(U256),
+    /// Buy this output; the tier needing the lowest input wins
+    ExactOutput(U256),
+}
+
+/// Quotes collected while scanning the Uniswap V3 fee tiers for a token pair
+struct V3TierScan {
+    /// Best quote found as (quoted amount, gas_estimate, fee); the quoted amount is the output
+    /// for exact-input scans and the required input for exact-output scans
+    best: Option<(U256, u64, u32)>,
+    /// Quoted amount per scanned fee tier (`None` when the quote failed)
+    results: Vec<(u32, Option<U256>)>,
+    /// Last RPC-level failure of a tier that still failed after retrying
+    rpc_error: Option<RepositoryError>,
+}
+
+impl V3TierScan {
+    /// The best quote, or why there is none: an RPC failure when a tier could not be
+    /// quoted at all, otherwise "no pool"
+    fn selected(&self, from_symbol: &str, to_symbol: &str) -> ServiceResult<(U256, u64, u32)> {
+        match (self.best, &self.rpc_error) {
+            (Some(best), _) => Ok(best),
+            (None, Some(e)) => Err(e.clone().into()),
+            (None, None) => {
+                let tiers: Vec<u32> = self.results.iter().map(|(fee, _)| *fee).collect();
+                Err(EthereumTradingService::no_v3_pool_error(
+                    from_symbol,
+                    to_symbol,
+                    &tiers,
+                ))
+            }
+        }
+    }
 }
 
 // MCP Tool Layer
 #[tool_router]
 impl EthereumTradingService {
+    /// Creates a service with its own HTTP provider, circuit breaker and price feed for the
+    /// configured RPC URL
     pub fn new(config: &Config) -> Self {
-        // Use RPC URL from configuration
-        let rpc_url = &config.rpc.url;
+        Self::with_provider(
+            config,
+            connect_http_provider(&config.rpc.url),
+            config.rpc.circuit_breaker.breaker(),
+        )
+        .with_price_feed(CoinGeckoPriceFeed::from_config(config))
+    }
+
+    /// Creates a service on an existing provider, e.g. a WebSocket connection shared by all
+    /// sessions (see [`connect_provider`](crate::repository::connect_provider)), guarded by a
+    /// circuit breaker that should be shared along with it
+    pub fn with_provider(config: &Config, provider: DynProvider, breaker: CircuitBreaker) -> Self {
+        let chain = config.network.chain_config();
+        tracing::info!(
+            "Using network {:?} (chain id {})",
+            config.network,
+            chain.chain_id
+        );
+
+        let mut token_registry = match &config.token_list_path {
+            Some(path) => TokenRegistry::from_token_list_file(path, &chain).unwrap_or_else(|e| {
+                tracing::warn!("{e}. Using the built-in token registry.");
+                TokenRegistry::for_chain(&chain)
+            }),
+            None => TokenRegistry::for_chain(&chain),
+        };
 
-        let provider =
-            ProviderBuilder::new().connect_http(rpc_url.parse().expect("Invalid RPC URL"));
+        for (token, decimals) in &config.decimals_override {
+            if let Err(e) = token_registry.override_decimals(token, *decimals) {
+                tracing::warn!("Ignoring decimals override: {e}");
+            }
+        }
+        let decimals_overrides = token_registry.decimals_overrides();
 
         // Create repository with wallet if private key is provided
         let repository: Box<dyn EthereumRepository> = if !config.wallet.private_key.is_empty() {
             match AlloyEthereumRepository::new_with_wallet(
-                Arc::new(provider),
+                Arc::new(provider.clone()),
                 &config.wallet.private_key,
             ) {
                 Ok(repo) => {
+                    let repo = repo
+                        .with_chain(chain)
+                        .with_decimals_overrides(decimals_overrides.clone());
                     if let Some(address) = repo.wallet_address() {
                         tracing::info!("Initialized with wallet address: {address}");
                     }
@@ -56,24 +305,135 @@ impl EthereumTradingService {
                 }
                 Err(e) => {
                     tracing::warn!("Failed to initialize wallet: {e}. Using read-only mode.");
-                    Box::new(AlloyEthereumRepository::new(Arc::new(
-                        ProviderBuilder::new()
-                            .connect_http(rpc_url.parse().expect("Invalid RPC URL")),
-                    )))
+                    Box::new(
+                        AlloyEthereumRepository::new(Arc::new(provider.clone()))
+                            .with_chain(chain)
+                            .with_decimals_overrides(decimals_overrides.clone()),
+                    )
                 }
             }
         } else {
             tracing::info!("No private key provided. Running in read-only mode.");
-            Box::new(AlloyEthereumRepository::new(Arc::new(provider)))
+            Box::new(
+                AlloyEthereumRepository::new(Arc::new(provider.clone()))
+                    .with_chain(chain)
+                    .with_decimals_overrides(decimals_overrides.clone()),
+            )
         };
+        let rpc_timeout = Duration::from_millis(config.rpc.timeout_ms);
+        // The breaker wraps the timeout so that stalled calls count as failures
+        let repository = Box::new(CircuitBreakerRepository::new(
+            Box::new(TimeoutRepository::new(repository, rpc_timeout)),
+            breaker.clone(),
+        ));
+
+        // Forks share the wallet; a key rejected above falls back to read-only here as well
+        let v2_forks = V2Dex::FORKS
+            .into_iter()
+            .filter(|dex| chain.v2_deployment(*dex).is_some())
+            .map(|dex| {
+                let provider = Arc::new(provider.clone());
+                let fork = AlloyEthereumRepository::new_with_wallet(
+                    provider.clone(),
+                    &config.wallet.private_key,
+                )
+                .unwrap_or_else(|_| AlloyEthereumRepository::new(provider))
+                .with_chain(chain)
+                .with_decimals_overrides(decimals_overrides.clone())
+                .with_v2_dex(dex);
+                let fork: Box<dyn EthereumRepository> = Box::new(CircuitBreakerRepository::new(
+                    Box::new(TimeoutRepository::new(Box::new(fork), rpc_timeout)),
+                    breaker.clone(),
+                ));
+                (dex, fork)
+            })
+            .collect();
+
+        let mut service = Self::with_repository(repository, token_registry).with_config(config);
+        service.v2_forks = v2_forks;
+        service
+    }
 
+    /// Creates a service on any repository, e.g. an in-memory one in tests or another data
+    /// source, resolving token symbols through `token_registry`
+    ///
+    /// The repository is used as is, without the timeout and circuit breaker that guard the RPC
+    /// repository, and only Uniswap V2 itself is available among the V2 DEXes. The service runs
+    /// on mainnet, read-only, with the default settings until [`with_config`](Self::with_config)
+    /// applies others.
+    pub fn with_repository(
+        repository: Box<dyn EthereumRepository>,
+        token_registry: TokenRegistry,
+    ) -> Self {
+        let network = Network::default();
+        let trading = TradingConfig::default();
         Self {
             tool_router: Self::tool_router(),
             repository,
-            token_registry: TokenRegistry::new(),
+            v2_forks: vec![],
+            token_registry,
+            network,
+            chain: network.chain_config(),
+            allow_execution: false,
+            display_precision: None,
+            balance_decimals: None,
+            number_format: NumberFormat::default(),
+            argument_hints: ServerConfig::default_argument_hints(),
+            v3_scan_retries: RpcConfig::default_v3_scan_retries(),
+            gas_buffer_percent: Config::default_gas_buffer_percent(),
+            eth_price_source: EthPriceSource::default(),
+            chainlink_feeds: HashMap::new(),
+            chainlink_max_age_secs: ChainlinkConfig::default_max_age_secs(),
+            default_slippage: trading.default_slippage,
+            deadline_secs: trading.deadline_secs,
+            min_liquidity_usd: None,
+            price_feed: None,
+            rpc_overrides: RpcOverrides {
+                config: None,
+                services: Mutex::default(),
+            },
+            drain: None,
         }
     }
 
+    /// Applies the network and the settings of `config`
+    ///
+    /// The repository and token registry are kept, so they should belong to the configured
+    /// network.
+    pub fn with_config(mut self, config: &Config) -> Self {
+        let chain = config.network.chain_config();
+        self.network = config.network;
+        self.chain = chain;
+        self.allow_execution = config.wallet.allow_execution;
+        self.display_precision = config.display.precision;
+        self.balance_decimals = config.display.balance_decimals;
+        self.number_format = config.display.numbers;
+        self.argument_hints = config.server.argument_hints;
+        self.v3_scan_retries = config.rpc.v3_scan_retries;
+        self.gas_buffer_percent = config.gas_buffer_percent;
+        self.eth_price_source = config.eth_price_source;
+        self.chainlink_feeds = self.resolve_chainlink_feeds(&config.chainlink.feeds);
+        self.chainlink_max_age_secs = config.chainlink.max_age_secs;
+        self.default_slippage = config.trading.default_slippage.clone();
+        self.deadline_secs = config.trading.deadline_secs;
+        self.min_liquidity_usd = config.trading.min_liquidity_usd.map(Decimal::from);
+        self.rpc_overrides.config = Some(config.clone());
+        self
+    }
+
+    /// Uses `price_feed` for USD prices, e.g. one shared by all sessions so its circuit breaker
+    /// sees every failure (see [`CoinGeckoPriceFeed::from_config`])
+    pub fn with_price_feed(mut self, price_feed: Option<Arc<CoinGeckoPriceFeed>>) -> Self {
+        self.price_feed = price_feed;
+        self
+    }
+
+    /// Reports tool calls to `drain`, which refuses new ones once shutdown has started
+    pub fn with_drain(mut self, drain: Drain) -> Self {
+        self.drain = Some(drain);
+        self
+    }
+
     #[instrument(skip(self))]
     #[tool(description = "Query ETH and ERC20 token balances")]
     pub async fn get_balance(
@@ -89,6 +449,57 @@ impl EthereumTradingService {
         }
     }
 
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Query the native ETH balance and several ERC20 token balances (symbols or addresses) of a wallet at once"
+    )]
+    pub async fn get_balances(
+        &self,
+        Parameters(req): Parameters<GetBalancesRequest>,
+    ) -> Json<GetBalancesResult> {
+        match self.get_balances_impl(req).await {
+            Ok(response) => Json(GetBalancesResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to get balances: {e}");
+                Json(GetBalancesResult::Error { error: e })
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Read the native ETH balance and several ERC20 balances of a wallet atomically, at one block, in a single Multicall3 round trip"
+    )]
+    pub async fn get_wallet_snapshot(
+        &self,
+        Parameters(req): Parameters<GetBalancesRequest>,
+    ) -> Json<GetWalletSnapshotResult> {
+        match self.get_wallet_snapshot_impl(req).await {
+            Ok(response) => Json(GetWalletSnapshotResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to get wallet snapshot: {e}");
+                Json(GetWalletSnapshotResult::Error { error: e })
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Value a wallet in USD: ETH and token balances priced via Uniswap, with a grand total. Tokens that cannot be priced are flagged instead of failing the call."
+    )]
+    pub async fn get_portfolio_value(
+        &self,
+        Parameters(req): Parameters<GetPortfolioValueRequest>,
+    ) -> Json<GetPortfolioValueResult> {
+        match self.get_portfolio_value_impl(req).await {
+            Ok(response) => Json(GetPortfolioValueResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to get portfolio value: {e}");
+                Json(GetPortfolioValueResult::Error { error: e })
+            }
+        }
+    }
+
     #[instrument(skip(self))]
     #[tool(description = "Get current token price in USD or ETH")]
     pub async fn get_token_price(
@@ -96,7 +507,7 @@ impl EthereumTradingService {
         Parameters(req): Parameters<GetTokenPriceRequest>,
     ) -> Json<GetTokenPriceResult> {
         match self.get_token_price_impl(req).await {
-            Ok(response) => Json(GetTokenPriceResult::Success(response)),
+            Ok(response) => Json(GetTokenPriceResult::Success(Box::new(response))),
             Err(e) => {
                 tracing::error!("Failed to get token price: {e}");
                 Json(GetTokenPriceResult::Error { error: e })
@@ -111,432 +522,4920 @@ impl EthereumTradingService {
         Parameters(req): Parameters<SwapTokensRequest>,
     ) -> Json<SwapTokensResult> {
         match self.swap_tokens_impl(req).await {
-            Ok(response) => Json(SwapTokensResult::Success(response)),
+            Ok(response) => Json(SwapTokensResult::Success(Box::new(response))),
             Err(e) => {
                 tracing::error!("Failed to simulate swap: {e}");
                 Json(SwapTokensResult::Error { error: e })
             }
         }
     }
-}
 
-// Business Logic - Core implementation
-impl EthereumTradingService {
-    #[instrument(skip(self), err)]
-    async fn get_balance_impl(&self, req: GetBalanceRequest) -> ServiceResult<GetBalanceResponse> {
-        let address = Address::from_str(&req.wallet_address)
-            .map_err(|e| ServiceError::InvalidWalletAddress(e.to_string()))?;
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Execute a real token swap on Uniswap V2 or V3 using the configured wallet. Requires wallet.allow_execution to be enabled. With dry_run, returns the simulated quote instead and sends nothing."
+    )]
+    pub async fn execute_swap(
+        &self,
+        Parameters(req): Parameters<SwapTokensRequest>,
+    ) -> Json<ExecuteSwapResult> {
+        let result = if req.dry_run.unwrap_or(false) {
+            self.dry_run_swap_impl(req)
+                .await
+                .map(|response| ExecuteSwapResult::Simulated(Box::new(response)))
+        } else {
+            self.execute_swap_impl(req)
+                .await
+                .map(|response| ExecuteSwapResult::Success(Box::new(response)))
+        };
 
-        tracing::info!("Querying balance for address: {}", address);
+        match result {
+            Ok(result) => Json(result),
+            Err(e) => {
+                tracing::error!("Failed to execute swap: {e}");
+                Json(ExecuteSwapResult::Error { error: e })
+            }
+        }
+    }
 
-        match req.token_contract_address {
-            Some(token_address) => {
-                // ERC20 token balance
-                let token_addr = Address::from_str(&token_address)
-                    .map_err(|e| ServiceError::InvalidWalletAddress(e.to_string()))?;
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Check whether a spender (defaults to the Uniswap router) is approved to move an owner's tokens"
+    )]
+    pub async fn check_allowance(
+        &self,
+        Parameters(req): Parameters<CheckAllowanceRequest>,
+    ) -> Json<CheckAllowanceResult> {
+        match self.check_allowance_impl(req).await {
+            Ok(response) => Json(CheckAllowanceResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to check allowance: {e}");
+                Json(CheckAllowanceResult::Error { error: e })
+            }
+        }
+    }
 
-                let token_balance = self
-                    .repository
-                    .get_erc20_balance(token_addr, address)
-                    .await?;
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Approve the Uniswap V2 or V3 router to spend a token from the configured wallet. Requires wallet.allow_execution to be enabled."
+    )]
+    pub async fn approve_token(
+        &self,
+        Parameters(req): Parameters<ApproveTokenRequest>,
+    ) -> Json<ApproveTokenResult> {
+        match self.approve_token_impl(req).await {
+            Ok(response) => Json(ApproveTokenResult::Success(Box::new(response))),
+            Err(e) => {
+                tracing::error!("Failed to approve token: {e}");
+                Json(ApproveTokenResult::Error { error: e })
+            }
+        }
+    }
 
-                let formatted_balance =
-                    format_balance(token_balance.balance, token_balance.decimals);
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Build an EIP-2612 permit (gasless approval) for a token that supports it: returns the EIP-712 digest and, when the configured wallet is the owner and wallet.allow_execution is enabled, its signature as v/r/s. Nothing is sent on-chain"
+    )]
+    pub async fn build_permit(
+        &self,
+        Parameters(req): Parameters<BuildPermitRequest>,
+    ) -> Json<BuildPermitResult> {
+        match self.build_permit_impl(req).await {
+            Ok(response) => Json(BuildPermitResult::Success(Box::new(response))),
+            Err(e) => {
+                tracing::error!("Failed to build permit: {e}");
+                Json(BuildPermitResult::Error { error: e })
+            }
+        }
+    }
 
-                Ok(GetBalanceResponse {
-                    balance: token_balance.balance.to_string(),
-                    formatted_balance,
-                    decimals: token_balance.decimals,
-                    symbol: token_balance.symbol,
-                })
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Quote an arbitrary Uniswap V2 path: takes token symbols or addresses from input to output and an input amount, and returns the amount of every token along the path from the router's getAmountsOut. No slippage, price impact or gas estimates; use swap_tokens for those"
+    )]
+    pub async fn quote_path(
+        &self,
+        Parameters(req): Parameters<QuotePathRequest>,
+    ) -> Json<QuotePathResult> {
+        match self.quote_path_impl(req).await {
+            Ok(response) => Json(QuotePathResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to quote path: {e}");
+                Json(QuotePathResult::Error { error: e })
             }
-            None => {
-                // Native ETH balance
-                let balance = self.repository.get_eth_balance(address).await?;
-                let formatted_balance = format_balance(balance, ETH_DECIMALS);
-
-                Ok(GetBalanceResponse {
-                    balance: balance.to_string(),
-                    formatted_balance,
-                    decimals: ETH_DECIMALS,
-                    symbol: "ETH".to_string(),
-                })
+        }
+    }
+
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Convert an amount between wei, gwei and ether, or between a token's human-readable amount (by symbol or address) and its smallest unit (\"wei\"). Exact decimal arithmetic; only token units read the token's decimals from the chain"
+    )]
+    pub async fn convert_units(
+        &self,
+        Parameters(req): Parameters<ConvertUnitsRequest>,
+    ) -> Json<ConvertUnitsResult> {
+        match self.convert_units_impl(req).await {
+            Ok(response) => Json(ConvertUnitsResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to convert units: {e}");
+                Json(ConvertUnitsResult::Error { error: e })
             }
         }
     }
 
-    #[instrument(skip(self), err)]
-    async fn get_token_price_impl(
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Detect whether a token rebases (changes holder balances without transfers, like AMPL or stETH)"
+    )]
+    pub async fn detect_rebasing(
         &self,
-        req: GetTokenPriceRequest,
-    ) -> ServiceResult<GetTokenPriceResponse> {
-        // Lookup token address from registry or dynamic sources
-        let (token_address, symbol) = match req {
-            GetTokenPriceRequest::Symbol { symbol } => {
-                let addr = self.lookup_token_address(&symbol)?;
-                (addr, symbol)
+        Parameters(req): Parameters<DetectRebasingRequest>,
+    ) -> Json<DetectRebasingResult> {
+        match self.detect_rebasing_impl(req).await {
+            Ok(response) => Json(DetectRebasingResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to detect rebasing: {e}");
+                Json(DetectRebasingResult::Error { error: e })
             }
-            GetTokenPriceRequest::ContractAddress { contract_address } => {
-                let addr = Address::from_str(&contract_address)
-                    .map_err(|e| ServiceError::InvalidWalletAddress(e.to_string()))?;
-                let metadata = self.repository.get_token_metadata(addr).await?;
-                (contract_address, metadata.symbol)
+        }
+    }
+
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Plan a stop-loss: compute the trigger price and expected net proceeds (after price impact and gas) for selling a holding"
+    )]
+    pub async fn stop_loss_plan(
+        &self,
+        Parameters(req): Parameters<StopLossPlanRequest>,
+    ) -> Json<StopLossPlanResult> {
+        match self.stop_loss_plan_impl(req).await {
+            Ok(response) => Json(StopLossPlanResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to plan stop-loss: {e}");
+                Json(StopLossPlanResult::Error { error: e })
             }
-        };
+        }
+    }
 
-        let token_addr = Address::from_str(&token_address)
-            .map_err(|e| ServiceError::InvalidWalletAddress(e.to_string()))?;
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Compare the gas cost of a swap at the current gas price with its cost at a target gas price, showing the USD saved by waiting"
+    )]
+    pub async fn compare_gas_timing(
+        &self,
+        Parameters(req): Parameters<CompareGasTimingRequest>,
+    ) -> Json<CompareGasTimingResult> {
+        match self.compare_gas_timing_impl(req).await {
+            Ok(response) => Json(CompareGasTimingResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to compare gas timing: {e}");
+                Json(CompareGasTimingResult::Error { error: e })
+            }
+        }
+    }
 
-        // Special handling for ETH/WETH - return ETH USD price directly
-        let weth_address = Address::from_str(TokenRegistry::weth_address())
-            .map_err(|e| ServiceError::InvalidWalletAddress(e.to_string()))?;
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Get the approximate 24h trading volume of a Uniswap V2 pair in USD, summed from on-chain swap events"
+    )]
+    pub async fn get_pool_volume(
+        &self,
+        Parameters(req): Parameters<GetPoolVolumeRequest>,
+    ) -> Json<GetPoolVolumeResult> {
+        match self.get_pool_volume_impl(req).await {
+            Ok(response) => Json(GetPoolVolumeResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to get pool volume: {e}");
+                Json(GetPoolVolumeResult::Error { error: e })
+            }
+        }
+    }
 
-        tracing::info!("Getting price for token: {} ({})", symbol, token_address);
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Estimate the largest trade a Uniswap V2 pool can absorb before its output reserve is drained past a threshold (default 50%)"
+    )]
+    pub async fn get_pool_capacity(
+        &self,
+        Parameters(req): Parameters<GetPoolCapacityRequest>,
+    ) -> Json<GetPoolCapacityResult> {
+        match self.get_pool_capacity_impl(req).await {
+            Ok(response) => Json(GetPoolCapacityResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to get pool capacity: {e}");
+                Json(GetPoolCapacityResult::Error { error: e })
+            }
+        }
+    }
 
-        let (price_eth, price_usd) = if token_addr == weth_address {
-            // For ETH/WETH, price in ETH is 1.0, and get USD price from USDC pair
-            let eth_usd = self.repository.get_eth_usd_price().await?;
-            ("1.0".to_string(), eth_usd.to_string())
-        } else {
-            // For other tokens, get price from Uniswap V2 WETH pair
-            self.get_price_from_uniswap(token_addr, weth_address)
-                .await?
-        };
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Estimate a swap's output on Uniswap V2 if a known pending swap on the same pool (same or opposite direction) executes first"
+    )]
+    pub async fn simulate_pending_swap(
+        &self,
+        Parameters(req): Parameters<SimulatePendingSwapRequest>,
+    ) -> Json<SimulatePendingSwapResult> {
+        match self.simulate_pending_swap_impl(req).await {
+            Ok(response) => Json(SimulatePendingSwapResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to simulate pending swap: {e}");
+                Json(SimulatePendingSwapResult::Error { error: e })
+            }
+        }
+    }
 
-        Ok(GetTokenPriceResponse {
-            symbol,
-            address: token_address.to_string(),
-            price_usd,
-            price_eth,
-            timestamp: chrono::Utc::now().timestamp(),
-        })
+    #[instrument(skip(self))]
+    #[tool(
+        description = "List a wallet's transactions in the most recent blocks (default 20, maximum 100), decoding Uniswap V2/V3 router swaps"
+    )]
+    pub async fn get_recent_transactions(
+        &self,
+        Parameters(req): Parameters<GetRecentTransactionsRequest>,
+    ) -> Json<GetRecentTransactionsResult> {
+        match self.get_recent_transactions_impl(req).await {
+            Ok(response) => Json(GetRecentTransactionsResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to get recent transactions: {e}");
+                Json(GetRecentTransactionsResult::Error { error: e })
+            }
+        }
     }
 
-    #[instrument(skip(self), err)]
-    async fn swap_tokens_impl(&self, req: SwapTokensRequest) -> ServiceResult<SwapTokensResponse> {
-        // Determine which Uniswap version to use (default to V2)
-        let uniswap_version = req.uniswap_version.as_deref().unwrap_or("v2");
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Estimate the cost of buying a token on Uniswap V2 and selling it straight back in the same block: the round-trip spread a taker pays, split into LP fees and the remaining price impact"
+    )]
+    pub async fn estimate_round_trip(
+        &self,
+        Parameters(req): Parameters<EstimateRoundTripRequest>,
+    ) -> Json<EstimateRoundTripResult> {
+        match self.estimate_round_trip_impl(req).await {
+            Ok(response) => Json(EstimateRoundTripResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to estimate round trip: {e}");
+                Json(EstimateRoundTripResult::Error { error: e })
+            }
+        }
+    }
 
-        match uniswap_version.to_lowercase().as_str() {
-            "v2" => self.swap_tokens_v2(req).await,
-            "v3" => self.swap_tokens_v3(req).await,
-            _ => Err(ServiceError::InvalidAmount(format!(
-                "Invalid Uniswap version: {}. Must be 'v2' or 'v3'",
-                uniswap_version
-            ))),
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Estimate only the gas of a Uniswap V2 swap and its cost in ETH, without quoting output, price impact or exchange rate"
+    )]
+    pub async fn estimate_swap_gas(
+        &self,
+        Parameters(req): Parameters<EstimateSwapGasRequest>,
+    ) -> Json<EstimateSwapGasResult> {
+        match self.estimate_swap_gas_impl(req).await {
+            Ok(response) => Json(EstimateSwapGasResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to estimate swap gas: {e}");
+                Json(EstimateSwapGasResult::Error { error: e })
+            }
         }
     }
 
-    #[instrument(skip(self), err)]
-    async fn swap_tokens_v2(&self, req: SwapTokensRequest) -> ServiceResult<SwapTokensResponse> {
-        let from_token = self.parse_token_address_or_symbol(&req.from_token).await?;
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Describe an ERC20 token by symbol or address: name, symbol, decimals, total supply and contract address"
+    )]
+    pub async fn get_token_info(
+        &self,
+        Parameters(req): Parameters<GetTokenInfoRequest>,
+    ) -> Json<GetTokenInfoResult> {
+        match self.get_token_info_impl(req).await {
+            Ok(response) => Json(GetTokenInfoResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to get token info: {e}");
+                Json(GetTokenInfoResult::Error { error: e })
+            }
+        }
+    }
 
-        let to_token = self.parse_token_address_or_symbol(&req.to_token).await?;
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Show the configured wallet's current allowance for a token and spender (the Uniswap V2 router by default). Check it before execute_swap. Requires a configured wallet."
+    )]
+    pub async fn get_my_approval(
+        &self,
+        Parameters(req): Parameters<GetMyApprovalRequest>,
+    ) -> Json<GetMyApprovalResult> {
+        match self.get_my_approval_impl(req).await {
+            Ok(response) => Json(GetMyApprovalResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to get own approval: {e}");
+                Json(GetMyApprovalResult::Error { error: e })
+            }
+        }
+    }
 
-        // Get from_token metadata to know its decimals
-        let from_metadata = self.repository.get_token_metadata(from_token).await?;
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Price ETH against each registered stablecoin (USDC, USDT, DAI, FRAX) side by side with the spread, to check whether the USD price is reliable or a stablecoin has depegged"
+    )]
+    pub async fn get_eth_stablecoin_prices(&self) -> Json<GetEthStablecoinPricesResult> {
+        match self.get_eth_stablecoin_prices_impl().await {
+            Ok(response) => Json(GetEthStablecoinPricesResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to get ETH stablecoin prices: {e}");
+                Json(GetEthStablecoinPricesResult::Error { error: e })
+            }
+        }
+    }
 
-        // Parse amount with proper decimals (converts human-readable amount to smallest unit)
-        let amount_in = parse_amount(&req.amount, from_metadata.decimals)
-            .map_err(|e| ServiceError::InvalidAmount(e))?;
-        tracing::info!(
-            "Amount in (parsed): {} ({})",
-            amount_in,
-            format_balance(amount_in, from_metadata.decimals)
-        );
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Quote a swap and report the slippage tolerance a desired minimum output corresponds to, and whether that minimum is achievable at the current price"
+    )]
+    pub async fn calculate_implied_slippage(
+        &self,
+        Parameters(req): Parameters<ImpliedSlippageRequest>,
+    ) -> Json<ImpliedSlippageResult> {
+        match self.calculate_implied_slippage_impl(req).await {
+            Ok(response) => Json(ImpliedSlippageResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to calculate implied slippage: {e}");
+                Json(ImpliedSlippageResult::Error { error: e })
+            }
+        }
+    }
 
-        let slippage = Decimal::from_str(&req.slippage_tolerance)
-            .map_err(|e| ServiceError::InvalidAmount(format!("Invalid slippage: {e}")))?;
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Quote a swap on Uniswap V2 (best path) and V3 (best fee tier) in one call and return the version with the larger output, with both quotes"
+    )]
+    pub async fn get_best_swap(
+        &self,
+        Parameters(req): Parameters<GetBestSwapRequest>,
+    ) -> Json<GetBestSwapResult> {
+        match self.get_best_swap_impl(req).await {
+            Ok(response) => Json(GetBestSwapResult::Success(Box::new(response))),
+            Err(e) => {
+                tracing::error!("Failed to get best swap: {e}");
+                Json(GetBestSwapResult::Error { error: e })
+            }
+        }
+    }
 
-        // Build swap path
-        let path = vec![from_token, to_token];
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Follow up on a broadcast transaction (e.g. from execute_swap): pending until its receipt is available, then success or reverted with block, confirmations and gas paid"
+    )]
+    pub async fn get_swap_status(
+        &self,
+        Parameters(req): Parameters<GetSwapStatusRequest>,
+    ) -> Json<GetSwapStatusResult> {
+        match self.get_swap_status_impl(req).await {
+            Ok(response) => Json(GetSwapStatusResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to get swap status: {e}");
+                Json(GetSwapStatusResult::Error { error: e })
+            }
+        }
+    }
 
-        // Get expected output and calculate minimum with slippage
-        let amount_out = self.get_swap_output_amount(amount_in, &path).await?;
-        tracing::info!("Amount out: {}", amount_out);
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Look up a transaction by hash: sender, recipient, ETH value, gas limit, nonce, function selector and decoded Uniswap swap, plus its receipt status once mined. Unknown hashes report status not_found"
+    )]
+    pub async fn get_transaction(
+        &self,
+        Parameters(req): Parameters<GetTransactionRequest>,
+    ) -> Json<GetTransactionResult> {
+        match self.get_transaction_impl(req).await {
+            Ok(response) => Json(GetTransactionResult::Success(Box::new(response))),
+            Err(e) => {
+                tracing::error!("Failed to get transaction: {e}");
+                Json(GetTransactionResult::Error { error: e })
+            }
+        }
+    }
 
-        // Check if amount_out is zero and provide helpful error
-        if amount_out.is_zero() {
-            // Get to_token metadata for better error messages
-            let to_metadata = self.repository.get_token_metadata(to_token).await.ok();
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Get the current ETH price in USD from the Uniswap V2 USDC/WETH pair, with the reserves it was computed from. Cheaper than get_token_price for ETH: no token metadata lookups"
+    )]
+    pub async fn get_eth_price(&self) -> Json<GetEthPriceResult> {
+        match self.get_eth_price_impl().await {
+            Ok(response) => Json(GetEthPriceResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to get ETH price: {e}");
+                Json(GetEthPriceResult::Error { error: e })
+            }
+        }
+    }
 
-            let from_symbol = &from_metadata.symbol;
-            let to_symbol = to_metadata
-                .as_ref()
-                .map(|m| m.symbol.as_str())
-                .unwrap_or("Unknown");
-            let from_decimals = from_metadata.decimals;
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Read a Uniswap V3 pool's current state: pool address, fee tier, price, sqrtPriceX96, tick and in-range liquidity. Without fee_tier, the tier with the most in-range liquidity is used"
+    )]
+    pub async fn get_pool_info(
+        &self,
+        Parameters(req): Parameters<GetPoolInfoRequest>,
+    ) -> Json<GetPoolInfoResult> {
+        match self.get_pool_info_impl(req).await {
+            Ok(response) => Json(GetPoolInfoResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to get pool info: {e}");
+                Json(GetPoolInfoResult::Error { error: e })
+            }
+        }
+    }
 
-            // Try to get reserves to provide more context
-            match self
-                .repository
-                .get_uniswap_pair_reserves(from_token, to_token)
-                .await
-            {
-                Ok((reserve_in, reserve_out, _, _)) => {
-                    return Err(ServiceError::SwapSimulationFailed(format!(
-                        "Estimated output is 0 {} for {} {}. This could be due to:\n\
-                         1. Insufficient liquidity (Reserve {}: {}, Reserve {}: {})\n\
-                         2. Input amount too small (try a larger amount)\n\
-                         3. The swap path may need intermediate tokens\n\
-                         \n\
-                         Suggestion: Try using WETH as an intermediate token, or increase the swap amount.",
-                        to_symbol,
-                        format_balance(amount_in, from_decimals),
-                        from_symbol,
-                        from_symbol,
-                        reserve_in,
-                        to_symbol,
-                        reserve_out
-                    )));
-                }
-                Err(_) => {
-                    return Err(ServiceError::SwapSimulationFailed(format!(
-                        "No liquidity pool found for {}/{} pair. The trading pair may not exist on Uniswap V2.\n\
-                         \n\
-                         Suggestions:\n\
-                         - Use a different DEX or token pair\n\
-                         - Try routing through WETH (e.g., {} -> WETH -> {})",
-                        from_symbol, to_symbol, from_symbol, to_symbol
-                    )));
-                }
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Get USD and ETH prices for a list of token symbols or addresses in one call. ETH/USD is read once and shared; a token without a Uniswap V2 WETH pair is reported with priced: false instead of failing the call"
+    )]
+    pub async fn get_token_prices(
+        &self,
+        Parameters(req): Parameters<GetTokenPricesRequest>,
+    ) -> Json<GetTokenPricesResult> {
+        match self.get_token_prices_impl(req).await {
+            Ok(response) => Json(GetTokenPricesResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to get token prices: {e}");
+                Json(GetTokenPricesResult::Error { error: e })
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Compare Uniswap V2 (direct and via WETH) and V3 (all fee tiers) routes for a swap and rank them by output. Routes that revert are excluded with the reason."
+    )]
+    pub async fn find_best_route(
+        &self,
+        Parameters(req): Parameters<FindBestRouteRequest>,
+    ) -> Json<FindBestRouteResult> {
+        match self.find_best_route_impl(req).await {
+            Ok(response) => Json(FindBestRouteResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to find best route: {e}");
+                Json(FindBestRouteResult::Error { error: e })
             }
         }
+    }
 
-        let minimum_output = calculate_minimum_output(amount_out, slippage);
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Resolve a list of token symbols, contract addresses and ENS names (e.g. \"vitalik.eth\") to addresses. Each item reports its own error."
+    )]
+    pub async fn resolve_tokens(
+        &self,
+        Parameters(req): Parameters<ResolveTokensRequest>,
+    ) -> Json<ResolveTokensResult> {
+        match self.resolve_tokens_impl(req).await {
+            Ok(response) => Json(ResolveTokensResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to resolve tokens: {e}");
+                Json(ResolveTokensResult::Error { error: e })
+            }
+        }
+    }
 
-        // Get to_token metadata for proper decimal formatting
-        let to_metadata = self.repository.get_token_metadata(to_token).await?;
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Estimate the net yield of holding a yield-bearing token (e.g. stETH) for a period: the supplied APY minus entry/exit price impact and gas"
+    )]
+    pub async fn estimate_net_yield(
+        &self,
+        Parameters(req): Parameters<EstimateNetYieldRequest>,
+    ) -> Json<EstimateNetYieldResult> {
+        match self.estimate_net_yield_impl(req).await {
+            Ok(response) => Json(EstimateNetYieldResult::Success(Box::new(response))),
+            Err(e) => {
+                tracing::error!("Failed to estimate net yield: {e}");
+                Json(EstimateNetYieldResult::Error { error: e })
+            }
+        }
+    }
 
-        // Get reserves for price impact calculation
-        let (reserve_in, reserve_out, _, _) = self
-            .repository
-            .get_uniswap_pair_reserves(from_token, to_token)
-            .await?;
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Re-quote a previously issued swap and check whether its output has moved beyond the slippage tolerance. Use right before execution to confirm the quote is still fresh."
+    )]
+    pub async fn verify_quote(
+        &self,
+        Parameters(req): Parameters<VerifyQuoteRequest>,
+    ) -> Json<VerifyQuoteResult> {
+        match self.verify_quote_impl(req).await {
+            Ok(response) => Json(VerifyQuoteResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to verify quote: {e}");
+                Json(VerifyQuoteResult::Error { error: e })
+            }
+        }
+    }
 
-        // Estimate gas cost
-        let (estimated_gas, gas_cost_eth) = self
-            .estimate_swap_gas(&req.from_address, amount_in, minimum_output, path)
-            .await?;
+    #[instrument(skip(self))]
+    #[tool(
+        description = "List the token symbols this server resolves, with their contract addresses. Pass query to search symbols (case-insensitive substring) and confirm a token is supported before building a swap"
+    )]
+    pub async fn list_supported_tokens(
+        &self,
+        Parameters(req): Parameters<ListSupportedTokensRequest>,
+    ) -> Json<ListSupportedTokensResponse> {
+        let tokens = match req.query.as_deref() {
+            Some(query) => self
+                .token_registry
+                .search(query)
+                .into_iter()
+                .map(|(symbol, address)| SupportedToken {
+                    symbol,
+                    contract_address: address.to_string(),
+                })
+                .collect(),
+            None => self
+                .token_registry
+                .supported_tokens()
+                .into_iter()
+                .filter_map(|symbol| {
+                    let contract_address = self.token_registry.lookup(&symbol)?.to_string();
+                    Some(SupportedToken {
+                        symbol,
+                        contract_address,
+                    })
+                })
+                .collect(),
+        };
 
-        // Calculate metrics
-        let price_impact = calculate_price_impact(amount_in, amount_out, reserve_in, reserve_out);
-        let exchange_rate = calculate_exchange_rate(
-            amount_in,
-            amount_out,
-            from_metadata.decimals,
-            to_metadata.decimals,
+        Json(ListSupportedTokensResponse {
+            network: self.network.as_str().to_string(),
+            tokens,
+        })
+    }
+
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Get the chain head: latest block number and timestamp, the RPC's chain id (and whether it matches the configured network) and the next block's base fee. Use it to set deadlines and to check the network before executing trades"
+    )]
+    pub async fn get_chain_status(&self) -> Json<GetChainStatusResult> {
+        match self.get_chain_status_impl().await {
+            Ok(response) => Json(GetChainStatusResult::Success(response)),
+            Err(e) => {
+                tracing::error!("Failed to get chain status: {e}");
+                Json(GetChainStatusResult::Error { error: e })
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Get the address of the server's own wallet, to use as wallet_address, from_address or swap recipient. Reports read-only mode when no private key is configured"
+    )]
+    pub async fn get_wallet_address(&self) -> Json<GetWalletAddressResponse> {
+        let wallet_address = self.repository.wallet_address();
+        let execution_allowed = wallet_address.is_some() && self.allow_execution;
+        let message = match wallet_address {
+            None => "Read-only mode: no wallet private key is configured, so swaps can only be \
+                     simulated. Pass an explicit wallet address to the balance tools"
+                .to_string(),
+            Some(address) if execution_allowed => {
+                format!("Wallet {address} is configured and may execute swaps")
+            }
+            Some(address) => format!(
+                "Wallet {address} is configured, but wallet.allow_execution is false, so swaps \
+                 can only be simulated"
+            ),
+        };
+
+        Json(GetWalletAddressResponse {
+            wallet_address: wallet_address.map(|address| address.to_string()),
+            read_only: wallet_address.is_none(),
+            execution_allowed,
+            message,
+        })
+    }
+
+    #[instrument(skip(self))]
+    #[tool(
+        description = "Show the network and the Uniswap V2/V3, WETH, USDC and ENS contract addresses the server is using"
+    )]
+    pub async fn get_contract_addresses(&self) -> Json<GetContractAddressesResponse> {
+        let chain = &self.chain;
+        Json(GetContractAddressesResponse {
+            network: self.network.as_str().to_string(),
+            chain_id: chain.chain_id,
+            uniswap_v2_factory: chain.uniswap_v2_factory.to_string(),
+            uniswap_v2_router: chain.uniswap_v2_router.to_string(),
+            uniswap_v3_quoter: chain.uniswap_v3_quoter.to_string(),
+            uniswap_v3_swap_router: chain.uniswap_v3_swap_router.map(|a| a.to_string()),
+            weth: chain.weth.to_string(),
+            usdc: chain.usdc.to_string(),
+            ens_registry: chain.ens_registry.map(|a| a.to_string()),
+        })
+    }
+}
+
+// Business Logic - Core implementation
+impl EthereumTradingService {
+    /// Check that the RPC endpoint serves the chain of the configured network
+    ///
+    /// Fails with `BlockchainError` when the node cannot be reached, and with `InternalError`
+    /// when it reports a different chain id, since every preset contract address would be wrong.
+    pub async fn verify_network(&self) -> ServiceResult<()> {
+        let chain_id = self.repository.get_chain_id().await?;
+        if chain_id != self.chain.chain_id {
+            return Err(ServiceError::InternalError(format!(
+                "Configured network {} expects chain id {}, but the RPC endpoint reports chain id {}",
+                self.network.as_str(),
+                self.chain.chain_id,
+                chain_id
+            )));
+        }
+
+        tracing::info!(
+            "RPC endpoint matches network {} (chain id {chain_id})",
+            self.network.as_str()
         );
+        Ok(())
+    }
 
-        let response = SwapTokensResponse {
-            estimated_output: format_balance(amount_out, to_metadata.decimals),
-            estimated_output_raw: amount_out.to_string(),
-            minimum_output: format_balance(minimum_output, to_metadata.decimals),
-            estimated_gas,
-            estimated_gas_eth: gas_cost_eth,
-            price_impact: price_impact.clone(),
-            exchange_rate: exchange_rate.clone(),
-            transaction_data: format!("Swap simulation (V2): {from_token} -> {to_token}"),
+    /// The service for an allowlisted RPC endpoint, created on first use
+    ///
+    /// It serves the network listed with the URL and never signs, whatever the main wallet
+    /// configuration.
+    fn rpc_override(&self, url: &str) -> ServiceResult<Arc<EthereumTradingService>> {
+        let url = url.trim();
+        let Some((config, allowed)) = self.rpc_overrides.config.as_ref().and_then(|config| {
+            let allowed = config.rpc.allowed_urls.iter().find(|a| a.url == url)?;
+            Some((config, allowed))
+        }) else {
+            return Err(ServiceError::RpcUrlNotAllowed(format!(
+                "{url} is not listed in rpc.allowed_urls"
+            )));
         };
 
+        if let Some(service) = self
+            .rpc_overrides
+            .services
+            .lock()
+            .unwrap()
+            .get(&allowed.url)
+        {
+            return Ok(service.clone());
+        }
+
+        // Built outside the lock, so a failure here cannot poison it for later requests
         tracing::info!(
-            "V2 swap simulation complete: output={}, impact={}, rate={}",
-            response.estimated_output,
-            price_impact,
-            exchange_rate
+            "Connecting to RPC override {url} ({})",
+            allowed.network.as_str()
         );
+        let mut config = config.clone();
+        config.network = allowed.network;
+        config.rpc.url = allowed.url.clone();
+        config.rpc.allowed_urls.clear();
+        config.wallet.private_key.clear();
+        config.wallet.allow_execution = false;
+        let service = Arc::new(EthereumTradingService::new(&config));
 
-        Ok(response)
+        // A concurrent request may have connected first; keep its service
+        let mut services = self.rpc_overrides.services.lock().unwrap();
+        Ok(services
+            .entry(allowed.url.clone())
+            .or_insert(service)
+            .clone())
     }
 
     #[instrument(skip(self), err)]
-    async fn swap_tokens_v3(&self, req: SwapTokensRequest) -> ServiceResult<SwapTokensResponse> {
-        let from_token = self.parse_token_address_or_symbol(&req.from_token).await?;
-        let to_token = self.parse_token_address_or_symbol(&req.to_token).await?;
+    async fn get_balance_impl(
+        &self,
+        mut req: GetBalanceRequest,
+    ) -> ServiceResult<GetBalanceResponse> {
+        if let Some(url) = req.rpc_url.take() {
+            let service = self.rpc_override(&url)?;
+            return Box::pin(service.get_balance_impl(req)).await;
+        }
 
-        // Get token metadata
-        let from_metadata = self.repository.get_token_metadata(from_token).await?;
-        let to_metadata = self.repository.get_token_metadata(to_token).await?;
+        let address = self.parse_wallet_address(&req.wallet_address).await?;
+
+        tracing::info!("Querying balance for address: {}", address);
+
+        let mut response = match req.token_contract_address {
+            Some(token_address) => {
+                // ERC20 token balance
+                let token_addr = Address::from_str(&token_address)
+                    .map_err(|e| ServiceError::InvalidWalletAddress(e.to_string()))?;
+
+                self.erc20_balance(token_addr, address).await
+            }
+            None => self.eth_balance(address).await,
+        }?;
+
+        if req.summarize.unwrap_or(false) {
+            response.summary = Some(format!(
+                "{} holds {} {}",
+                req.wallet_address, response.formatted_balance, response.symbol
+            ));
+        }
+        Ok(response)
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_balances_impl(
+        &self,
+        req: GetBalancesRequest,
+    ) -> ServiceResult<GetBalancesResponse> {
+        let address = self.parse_wallet_address(&req.wallet_address).await?;
+        if req.tokens.len() > MAX_BATCH_TOKENS {
+            return Err(ServiceError::InvalidAmount(format!(
+                "Too many tokens: {} (maximum {MAX_BATCH_TOKENS})",
+                req.tokens.len()
+            )));
+        }
 
-        // Parse amount with proper decimals
-        let amount_in = parse_amount(&req.amount, from_metadata.decimals)
-            .map_err(|e| ServiceError::InvalidAmount(e))?;
         tracing::info!(
-            "V3 Amount in (parsed): {} ({})",
-            amount_in,
-            format_balance(amount_in, from_metadata.decimals)
+            "Querying ETH and {} token balances for address: {}",
+            req.tokens.len(),
+            address
         );
 
-        let slippage = Decimal::from_str(&req.slippage_tolerance)
-            .map_err(|e| ServiceError::InvalidAmount(format!("Invalid slippage: {e}")))?;
+        // Native ETH always comes first; "ETH" in the list refers to it rather than WETH
+        let mut balances = vec![BalanceEntry::from_result(
+            "ETH".to_string(),
+            None,
+            self.eth_balance(address).await,
+        )];
+        let mut seen = vec![];
+
+        for token in req.tokens {
+            let token = token.trim().to_string();
+            if token.eq_ignore_ascii_case("ETH") {
+                continue;
+            }
+
+            let token_addr = match self.parse_token_address_or_symbol(&token).await {
+                Ok(token_addr) => token_addr,
+                Err(e) => {
+                    balances.push(BalanceEntry::from_result(token, None, Err(e)));
+                    continue;
+                }
+            };
+            if seen.contains(&token_addr) {
+                continue;
+            }
+            seen.push(token_addr);
+
+            let result = self.erc20_balance(token_addr, address).await;
+            balances.push(BalanceEntry::from_result(token, Some(token_addr), result));
+        }
+
+        Ok(GetBalancesResponse {
+            wallet_address: address.to_string(),
+            balances,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_token_price_impl(
+        &self,
+        mut req: GetTokenPriceRequest,
+    ) -> ServiceResult<GetTokenPriceResponse> {
+        if let Some(url) = req.options_mut().rpc_url.take() {
+            let service = self.rpc_override(&url)?;
+            return Box::pin(service.get_token_price_impl(req)).await;
+        }
+
+        let explain = req.options().explain.unwrap_or(false);
+        let summarize = req.options().summarize.unwrap_or(false);
+        let block = req.options().block_number;
+        let source = match req
+            .options()
+            .source
+            .as_deref()
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            None => None,
+            Some("v2") => Some(PriceSource::V2),
+            Some("v3") => Some(PriceSource::V3),
+            Some("best") => Some(PriceSource::Best),
+            Some("chainlink") => Some(PriceSource::Chainlink),
+            Some(other) => {
+                return Err(ServiceError::InvalidAmount(format!(
+                    "Invalid price source: {other}. Must be 'v2', 'v3', 'best' or 'chainlink'"
+                )));
+            }
+        };
+        if let (Some(_), Some(source @ (PriceSource::V3 | PriceSource::Best))) = (block, source) {
+            return Err(ServiceError::InvalidAmount(format!(
+                "block_number is only supported with source 'v2'; V3 quotes are always current \
+                 (source was '{}')",
+                source.label()
+            )));
+        }
+        let max_staleness = req.options().max_staleness_secs;
+        if max_staleness.is_some()
+            && (block.is_some() || source.is_some_and(|s| s != PriceSource::V2))
+        {
+            return Err(ServiceError::InvalidAmount(
+                "max_staleness_secs only applies to current prices from source 'v2'".to_string(),
+            ));
+        }
+        if let Some(block) = block {
+            let latest = self.repository.get_block_number().await?;
+            if block > latest {
+                return Err(ServiceError::InvalidAmount(format!(
+                    "Block {block} is in the future (latest block is {latest})"
+                )));
+            }
+        }
+        // Resolve the quote token before pricing, so a typo fails without RPC calls for the price
+        let quote = match req.options().quote.as_deref() {
+            Some(quote) => Some(self.resolve_quote_token(quote).await?),
+            None => None,
+        };
+
+        // Lookup token address from registry or dynamic sources.
+        // ETH is priced as WETH, so "ETH" and the native ETH placeholder address both resolve to it.
+        let (token_address, symbol) = match req {
+            GetTokenPriceRequest::Symbol { symbol, .. } if symbol.eq_ignore_ascii_case("ETH") => {
+                (self.chain.weth.to_string(), "ETH".to_string())
+            }
+            GetTokenPriceRequest::Symbol { symbol, .. } => {
+                let addr = self.lookup_token_address(&symbol)?;
+                (addr, symbol)
+            }
+            GetTokenPriceRequest::ContractAddress {
+                contract_address, ..
+            } => {
+                let addr = Address::from_str(&contract_address)
+                    .map_err(|e| ServiceError::InvalidWalletAddress(e.to_string()))?;
+                if addr == NATIVE_ETH_ADDRESS {
+                    (self.chain.weth.to_string(), "ETH".to_string())
+                } else {
+                    let symbol = match self.token_registry.lookup_symbol(&contract_address) {
+                        Some(symbol) => symbol.to_string(),
+                        None => self.repository.get_token_metadata(addr).await?.symbol,
+                    };
+                    (contract_address, symbol)
+                }
+            }
+        };
+
+        let token_addr = Address::from_str(&token_address)
+            .map_err(|e| ServiceError::InvalidWalletAddress(e.to_string()))?;
+
+        // Special handling for ETH/WETH - return ETH USD price directly
+        let weth_address = self.chain.weth;
+
+        tracing::info!("Getting price for token: {} ({})", symbol, token_address);
+
+        let is_weth = token_addr == weth_address;
+        let mut feed_updated_at = None;
+        let (price_eth, eth_value, onchain_usd, onchain_source, comparison, reserves_updated_at) =
+            match source {
+                None | Some(PriceSource::V2) if is_weth => {
+                    // For ETH/WETH, price in ETH is 1.0, and get USD price from USDC pair
+                    let (eth_usd, updated_at) = self.eth_usd_price_with_update(block).await?;
+                    (
+                        "1.0".to_string(),
+                        Decimal::ONE,
+                        Some(eth_usd),
+                        PriceSource::V2,
+                        None,
+                        updated_at,
+                    )
+                }
+                None | Some(PriceSource::V2) => {
+                    // For other tokens, get price from Uniswap V2 WETH pair
+                    let (price_eth, price_usd, updated_at) = self
+                        .get_price_from_uniswap(token_addr, weth_address, block)
+                        .await?;
+                    (
+                        self.display(price_eth),
+                        price_eth,
+                        price_usd,
+                        PriceSource::V2,
+                        None,
+                        Some(updated_at),
+                    )
+                }
+                Some(PriceSource::V3) => {
+                    let quote = self.source_quote(PriceSource::V3, token_addr).await?;
+                    (
+                        self.display(quote.price_eth),
+                        quote.price_eth,
+                        Some(quote.price_usd),
+                        PriceSource::V3,
+                        None,
+                        None,
+                    )
+                }
+                Some(PriceSource::Chainlink) => {
+                    let (price_eth, price_usd, updated_at) = self
+                        .chainlink_token_price(token_addr, &symbol, block)
+                        .await?;
+                    feed_updated_at = Some(updated_at);
+                    (
+                        self.display(price_eth),
+                        price_eth,
+                        Some(price_usd),
+                        PriceSource::Chainlink,
+                        None,
+                        None,
+                    )
+                }
+                Some(PriceSource::Best) => {
+                    let (quote, chosen, comparison) =
+                        self.compare_price_sources(token_addr).await?;
+                    (
+                        self.display(quote.price_eth),
+                        quote.price_eth,
+                        Some(quote.price_usd),
+                        chosen,
+                        Some(comparison),
+                        None,
+                    )
+                }
+            };
+
+        // Thin or abandoned pairs keep reserves from their last trade, whatever the market did since
+        if let (Some(max_staleness), Some(updated_at)) = (max_staleness, reserves_updated_at) {
+            let age = chrono::Utc::now().timestamp() - updated_at as i64;
+            if age > max_staleness as i64 {
+                return Err(ServiceError::StalePrice(format!(
+                    "the Uniswap V2 pair behind the {symbol} price last changed {age}s ago, more \
+                     than max_staleness_secs ({max_staleness}s)"
+                )));
+            }
+        }
+
+        let price_in_quote = match &quote {
+            Some((quote, quote_symbol)) => {
+                let quote_eth = self
+                    .quote_price_in_eth(*quote, quote_symbol, onchain_source, block)
+                    .await?;
+                let price = eth_value.checked_div(quote_eth).ok_or_else(|| {
+                    ServiceError::InsufficientLiquidity(format!(
+                        "{quote_symbol} has no usable price in ETH to quote {symbol} against"
+                    ))
+                })?;
+                // Both ETH prices carry rounding in their last digits; keep 18 decimals, like ETH
+                Some(self.display(price.round_dp(18)))
+            }
+            None => None,
+        };
+
+        // Prefer the external API for USD when enabled; fall back to on-chain when it is down.
+        // The API only knows current prices, so historical lookups stay on-chain, and so does
+        // an explicitly requested on-chain source.
+        let (price_usd, price_source, fallback_reason) = match &self.price_feed {
+            Some(feed) if block.is_none() && source.is_none() => {
+                match feed.token_usd_price(token_addr).await {
+                    Ok(price) => (Some(price), "coingecko", None),
+                    Err(e) => {
+                        tracing::warn!("Falling back to on-chain USD price for {symbol}: {e}");
+                        (onchain_usd, "onchain", Some(e.to_string()))
+                    }
+                }
+            }
+            _ => (onchain_usd, "onchain", None),
+        };
+
+        let explanation = explain.then(|| {
+            if onchain_source == PriceSource::Chainlink {
+                let eth = if is_weth {
+                    "price_eth is 1.0, as WETH is 1:1 with ETH."
+                } else {
+                    "price_eth divides it by the Chainlink ETH/USD answer."
+                };
+                format!(
+                    "price_usd is the latest answer of the Chainlink {symbol}/USD feed, updated \
+                     at feed_updated_at. {eth}"
+                )
+            } else if onchain_source == PriceSource::V3 {
+                let chosen = if comparison.is_some() {
+                    "The V3 pool was deeper than the V2 pair, so its price is reported. "
+                } else {
+                    ""
+                };
+                format!(
+                    "{chosen}price_eth is derived from a Uniswap V3 QuoterV2 quote selling 0.01 \
+                     WETH for {symbol} on the best fee tier, with the pool fee added back. \
+                     price_usd uses the ETH/USD price quoted the same way against USDC."
+                )
+            } else if is_weth {
+                let usd = if price_source == "coingecko" {
+                    "CoinGecko"
+                } else {
+                    self.eth_usd_source_label()
+                };
+                format!(
+                    "{symbol} is priced as WETH, which is 1:1 with ETH, so price_eth is 1.0. \
+                     The USD price comes from {usd}."
+                )
+            } else {
+                let usd = if price_source == "coingecko" {
+                    "price_usd is CoinGecko's USD price for the token, not derived from price_eth."
+                        .to_string()
+                } else {
+                    format!(
+                        "price_usd multiplies it by the ETH/USD price from {}.",
+                        self.eth_usd_source_label()
+                    )
+                };
+                format!(
+                    "price_eth is the ratio of the Uniswap V2 {symbol}/WETH pair reserves, adjusted \
+                     for token decimals. {usd}"
+                )
+            }
+        });
+        let explanation = match block {
+            Some(block) => {
+                explanation.map(|text| format!("{text} Reserves are read at block {block}."))
+            }
+            None => explanation,
+        };
+
+        let explanation = match (&quote, &price_in_quote) {
+            (Some((_, quote_symbol)), Some(_)) => explanation.map(|text| {
+                format!(
+                    "{text} price_in_quote divides price_eth by the {quote_symbol} price in ETH \
+                     from the same source."
+                )
+            }),
+            _ => explanation,
+        };
+
+        let explanation = match price_usd {
+            Some(_) => explanation,
+            None => explanation.map(|text| {
+                format!("{text} The ETH/USD price could not be read, so price_usd is null.")
+            }),
+        };
+
+        let price_usd = price_usd.map(|price| self.display(price));
+        let summary = summarize.then(|| {
+            let source = match (price_source, onchain_source) {
+                ("coingecko", _) => "CoinGecko",
+                (_, PriceSource::V3) => "Uniswap V3 quotes",
+                (_, PriceSource::Chainlink) => "Chainlink feeds",
+                _ => "Uniswap V2 reserves",
+            };
+            let at_block = block.map(|b| format!(" at block {b}")).unwrap_or_default();
+            let price_eth = match (&quote, &price_in_quote) {
+                (Some((_, quote_symbol)), Some(price)) => {
+                    format!("{price_eth} ETH, {price} {quote_symbol}")
+                }
+                _ => format!("{price_eth} ETH"),
+            };
+            match &price_usd {
+                Some(price_usd) => {
+                    format!("{symbol} is ${price_usd} ({price_eth}) per {source}{at_block}")
+                }
+                None => format!(
+                    "{symbol} is {price_eth} per {source}{at_block}; its USD price is unavailable"
+                ),
+            }
+        });
+
+        Ok(GetTokenPriceResponse {
+            symbol,
+            address: token_address.to_string(),
+            usd_price_available: price_usd.is_some(),
+            price_usd,
+            price_eth,
+            quote_symbol: quote.map(|(_, quote_symbol)| quote_symbol),
+            price_in_quote,
+            price_source: price_source.to_string(),
+            onchain_source: onchain_source.label().to_string(),
+            comparison,
+            fallback_reason,
+            block_number: block,
+            reserves_updated_at,
+            feed_updated_at,
+            timestamp: chrono::Utc::now().timestamp(),
+            explanation,
+            summary,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_eth_stablecoin_prices_impl(
+        &self,
+    ) -> ServiceResult<GetEthStablecoinPricesResponse> {
+        let mut prices = Vec::with_capacity(ETH_PRICE_STABLECOINS.len());
+        let mut values = vec![];
+        let mut last_error = None;
+
+        for symbol in ETH_PRICE_STABLECOINS {
+            let token = match self.lookup_token_address(symbol).and_then(|addr| {
+                Address::from_str(&addr)
+                    .map_err(|e| ServiceError::InvalidWalletAddress(e.to_string()))
+            }) {
+                Ok(token) => token,
+                Err(e) => {
+                    prices.push(StablecoinPrice {
+                        symbol: symbol.to_string(),
+                        token_address: None,
+                        eth_price: None,
+                        error: Some(e.to_string()),
+                    });
+                    continue;
+                }
+            };
+
+            let (eth_price, error) = match self.eth_price_in(token).await {
+                Ok(price) => {
+                    values.push(price);
+                    (Some(self.display(price)), None)
+                }
+                Err(e) => {
+                    tracing::warn!("Could not price ETH in {symbol}: {e}");
+                    let message = e.to_string();
+                    last_error = Some(e);
+                    (None, Some(message))
+                }
+            };
+            prices.push(StablecoinPrice {
+                symbol: symbol.to_string(),
+                token_address: Some(token.to_string()),
+                eth_price,
+                error,
+            });
+        }
+
+        let Some((min, max, spread)) = calculate_price_spread(&values) else {
+            return Err(last_error.unwrap_or_else(|| {
+                ServiceError::InsufficientLiquidity(
+                    "No stablecoin could be priced against ETH".to_string(),
+                )
+            }));
+        };
+
+        let consistent = spread <= Decimal::from(STABLECOIN_SPREAD_WARNING_PERCENT);
+        let warning = (!consistent).then(|| {
+            format!(
+                "ETH prices differ by {}% across stablecoins (more than \
+                 {STABLECOIN_SPREAD_WARNING_PERCENT}%); a stablecoin may have depegged or its \
+                 pair is thin, so USD prices derived from USDC may be off",
+                format_decimal(spread, Some(2))
+            )
+        });
+
+        Ok(GetEthStablecoinPricesResponse {
+            prices,
+            min_price: self.display(min),
+            max_price: self.display(max),
+            spread_percent: format_decimal(spread, Some(4)),
+            consistent,
+            warning,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn swap_tokens_impl(&self, req: SwapTokensRequest) -> ServiceResult<SwapTokensResponse> {
+        // Determine which Uniswap version to use (default to V2)
+        let uniswap_version = req.uniswap_version.as_deref().unwrap_or("v2");
+        let exact_output = Self::is_exact_output(req.swap_mode.as_deref())?;
+        let dex = Self::parse_v2_dex(req.dex.as_deref(), uniswap_version)?;
+        Self::parse_fee_tier(req.fee_tier, uniswap_version)?;
+        validate_positive_amount(&req.amount).map_err(ServiceError::InvalidAmount)?;
+        self.slippage(req.slippage_tolerance.as_deref())?;
+        let max_price_impact = req
+            .max_price_impact
+            .as_deref()
+            .map(|max| match Decimal::from_str(max.trim()) {
+                Ok(value) if !value.is_sign_negative() => Ok(value),
+                Ok(_) => Err(ServiceError::InvalidAmount(format!(
+                    "max_price_impact cannot be negative: {max}%"
+                ))),
+                Err(e) => Err(ServiceError::InvalidAmount(format!(
+                    "Invalid max_price_impact: {e}"
+                ))),
+            })
+            .transpose()?;
+
+        let tokens = [req.from_token.clone(), req.to_token.clone()];
+        let summarize = req.summarize.unwrap_or(false);
+        let amount = req.amount.clone();
+        let venue = match uniswap_version.to_lowercase().as_str() {
+            "v2" => dex.label().to_string(),
+            _ => format!("Uniswap {}", uniswap_version.to_uppercase()),
+        };
+        let mut response = match uniswap_version.to_lowercase().as_str() {
+            "v2" if exact_output => self.swap_tokens_v2_exact_output(req).await,
+            "v3" if exact_output => self.swap_tokens_v3_exact_output(req).await,
+            "v2" => self.swap_tokens_v2(req).await,
+            "v3" => self.swap_tokens_v3(req).await,
+            _ => Err(ServiceError::InvalidAmount(format!(
+                "Invalid Uniswap version: {}. Must be 'v2' or 'v3'",
+                uniswap_version
+            ))),
+        }?;
+
+        // A V3 impact that could not be computed ("N/A (V3)") does not trip the guard
+        if let (Some(max), Ok(impact)) =
+            (max_price_impact, Decimal::from_str(&response.price_impact))
+            && impact > max
+        {
+            return Err(ServiceError::PriceImpactTooHigh {
+                impact: response.price_impact,
+                max: format_decimal(max, None),
+            });
+        }
+
+        response.warning = self.rebasing_warning(&tokens).await;
+
+        if summarize {
+            let [from, to] = &tokens;
+            let (input, output) = match &response.estimated_input {
+                Some(input) => (format!("~{input}"), amount),
+                None => (amount, format!("~{}", response.estimated_output)),
+            };
+            response.summary = Some(format!(
+                "Swapping {input} {from} → {output} {to} on {venue}, {}% impact, {} gas",
+                response.price_impact,
+                self.gas_cost_label(&response.estimated_gas_eth).await
+            ));
+        }
+        Ok(response)
+    }
+
+    /// Warn about rebasing tokens among `tokens`; detection failures are not reported
+    async fn rebasing_warning(&self, tokens: &[String]) -> Option<String> {
+        let mut warnings = vec![];
+        for token in tokens {
+            let Ok(address) = self.parse_token_address_or_symbol(token).await else {
+                continue;
+            };
+            match self.repository.detect_rebasing(address).await {
+                Ok(Some(mechanism)) => warnings.push(format!(
+                    "{token} is a rebasing token ({mechanism}): its balances change without \
+                     transfers, so received or remaining amounts may differ from this quote"
+                )),
+                Ok(None) => {}
+                Err(e) => tracing::debug!("Rebasing check failed for {token}: {e}"),
+            }
+        }
+        (!warnings.is_empty()).then(|| warnings.join("; "))
+    }
+
+    #[instrument(skip(self), err)]
+    async fn swap_tokens_v2(&self, req: SwapTokensRequest) -> ServiceResult<SwapTokensResponse> {
+        let dex = Self::parse_v2_dex(req.dex.as_deref(), "v2")?;
+        let repository = self.v2_repository(dex)?;
+        let from_token = self.parse_token_address_or_symbol(&req.from_token).await?;
+
+        let to_token = self.parse_token_address_or_symbol(&req.to_token).await?;
+        let native = Self::native_eth(&req.from_token, &req.to_token, false)?;
+
+        // Get from_token metadata to know its decimals
+        let from_metadata = self.repository.get_token_metadata(from_token).await?;
+
+        // Parse amount with proper decimals (converts human-readable amount to smallest unit)
+        let amount_in = parse_amount(&req.amount, from_metadata.decimals)
+            .map_err(ServiceError::InvalidAmount)?;
+        tracing::info!(
+            "Amount in (parsed): {} ({})",
+            amount_in,
+            format_balance(amount_in, from_metadata.decimals)
+        );
+
+        let slippage = self.slippage(req.slippage_tolerance.as_deref())?;
+
+        // Pick the direct path or the route through WETH, whichever yields more
+        let (path, amounts) = self
+            .find_best_path(dex, from_token, to_token, amount_in)
+            .await?;
+        let amount_out = amounts.last().copied().unwrap_or_default();
+        tracing::info!("Amount out: {} (path of {} tokens)", amount_out, path.len());
+
+        // Check if amount_out is zero and provide helpful error
+        if amount_out.is_zero() {
+            // Get to_token metadata for better error messages
+            let to_metadata = self.repository.get_token_metadata(to_token).await.ok();
+
+            let from_symbol = &from_metadata.symbol;
+            let to_symbol = to_metadata
+                .as_ref()
+                .map(|m| m.symbol.as_str())
+                .unwrap_or("Unknown");
+            let from_decimals = from_metadata.decimals;
+
+            // Try to get reserves to provide more context
+            match repository
+                .get_uniswap_pair_reserves(from_token, to_token, None)
+                .await
+            {
+                Ok((reserve_in, reserve_out, _, _)) => {
+                    return Err(ServiceError::SwapSimulationFailed(format!(
+                        "Estimated output is 0 {} for {} {}. This could be due to:\n\
+                         1. Insufficient liquidity (Reserve {}: {}, Reserve {}: {})\n\
+                         2. Input amount too small (try a larger amount)\n\
+                         3. The route through WETH gave no output either\n\
+                         \n\
+                         Suggestion: Increase the swap amount, or try Uniswap V3.",
+                        to_symbol,
+                        format_balance(amount_in, from_decimals),
+                        from_symbol,
+                        from_symbol,
+                        reserve_in,
+                        to_symbol,
+                        reserve_out
+                    )));
+                }
+                Err(_) => {
+                    return Err(ServiceError::SwapSimulationFailed(format!(
+                        "No liquidity pool found for {}/{} pair, and routing through WETH gave no output.\n\
+                         \n\
+                         Suggestions:\n\
+                         - Use a different DEX or token pair\n\
+                         - Try Uniswap V3 (set uniswap_version to 'v3')",
+                        from_symbol, to_symbol
+                    )));
+                }
+            }
+        }
+
+        let minimum_output = calculate_minimum_output(amount_out, slippage)?;
+
+        // Get to_token metadata for proper decimal formatting
+        let to_metadata = self.repository.get_token_metadata(to_token).await?;
+
+        // Price impact compounds over every hop of the path
+        let mut hop_impacts = Vec::with_capacity(path.len() - 1);
+        let mut direct_reserves = None;
+        for (hop, pair) in path.windows(2).enumerate() {
+            let (reserve_in, reserve_out, _, _) = repository
+                .get_uniswap_pair_reserves(pair[0], pair[1], None)
+                .await?;
+            let impact =
+                calculate_price_impact(amounts[hop], amounts[hop + 1], reserve_in, reserve_out);
+            hop_impacts.push(Decimal::from_str(&impact).unwrap_or(Decimal::ZERO));
+            direct_reserves = Some((reserve_in, reserve_out));
+        }
+        let price_impact = format_decimal(combine_price_impacts(&hop_impacts), None);
+        let route = self.path_label(&path).await;
+
+        // Estimate gas cost
+        let (estimated_gas, estimated_gas_raw, gas_cost_eth) = self
+            .estimate_v2_swap_gas(
+                dex,
+                &req.from_address,
+                amount_in,
+                minimum_output,
+                path.clone(),
+                native,
+            )
+            .await?;
+
+        // Calculate metrics
+        let exchange_rate = calculate_exchange_rate(
+            amount_in,
+            amount_out,
+            from_metadata.decimals,
+            to_metadata.decimals,
+            self.display_precision,
+        );
+
+        let explanation = req.explain.unwrap_or(false).then(|| {
+            let pool = match (path.len(), direct_reserves) {
+                (2, Some((reserve_in, reserve_out))) => format!(
+                    "the direct {from}/{to} pair (reserves: {} {from} / {} {to})",
+                    format_balance(reserve_in, from_metadata.decimals),
+                    format_balance(reserve_out, to_metadata.decimals),
+                    from = from_metadata.symbol,
+                    to = to_metadata.symbol,
+                ),
+                _ => format!("{route}, which gave more output than the direct pair"),
+            };
+            format!(
+                "Quoted on {dex} through {pool}. Trading {amount} {from} moves the pool price by {price_impact}%. \
+                 The minimum output applies the requested {slippage}% slippage tolerance to the quoted {out} {to}.",
+                from = from_metadata.symbol,
+                to = to_metadata.symbol,
+                dex = dex.label(),
+                amount = format_balance(amount_in, from_metadata.decimals),
+                out = format_balance(amount_out, to_metadata.decimals),
+            )
+        });
+
+        let response = SwapTokensResponse {
+            estimated_output: format_balance(amount_out, to_metadata.decimals),
+            estimated_output_raw: amount_out.to_string(),
+            minimum_output: format_balance(minimum_output, to_metadata.decimals),
+            estimated_input: None,
+            maximum_input: None,
+            estimated_gas,
+            estimated_gas_raw,
+            gas_buffer_percent: self.gas_buffer_percent,
+            estimated_gas_eth: gas_cost_eth,
+            price_impact: price_impact.clone(),
+            exchange_rate: exchange_rate.clone(),
+            transaction_data: format!(
+                "Swap simulation (V2): {}",
+                path.iter()
+                    .map(Address::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" -> ")
+            ),
+            route,
+            path: path.iter().map(Address::to_string).collect(),
+            amounts: amounts.iter().map(U256::to_string).collect(),
+            uniswap_version: "v2".to_string(),
+            wraps_eth: native == NativeEth::Wrap,
+            unwraps_eth: native == NativeEth::Unwrap,
+            explanation,
+            warning: None,
+            summary: None,
+        };
+
+        tracing::info!(
+            "V2 swap simulation complete: output={}, impact={}, rate={}",
+            response.estimated_output,
+            price_impact,
+            exchange_rate
+        );
+
+        Ok(response)
+    }
+
+    #[instrument(skip(self), err)]
+    async fn swap_tokens_v2_exact_output(
+        &self,
+        req: SwapTokensRequest,
+    ) -> ServiceResult<SwapTokensResponse> {
+        let dex = Self::parse_v2_dex(req.dex.as_deref(), "v2")?;
+        let repository = self.v2_repository(dex)?;
+        let from_token = self.parse_token_address_or_symbol(&req.from_token).await?;
+        let to_token = self.parse_token_address_or_symbol(&req.to_token).await?;
+        let native = Self::native_eth(&req.from_token, &req.to_token, false)?;
+
+        let from_metadata = self.repository.get_token_metadata(from_token).await?;
+        let to_metadata = self.repository.get_token_metadata(to_token).await?;
+
+        // In exact-output mode the amount is denominated in the destination token
+        let amount_out =
+            parse_amount(&req.amount, to_metadata.decimals).map_err(ServiceError::InvalidAmount)?;
+
+        let slippage = self.slippage(req.slippage_tolerance.as_deref())?;
+
+        let path = vec![from_token, to_token];
+        let amounts = repository
+            .get_swap_amounts_in(amount_out, path.clone())
+            .await
+            .map_err(|e| {
+                ServiceError::SwapSimulationFailed(format!(
+                    "Cannot buy {} {} with {}: {e}. The pair may not exist or lack liquidity.",
+                    req.amount, to_metadata.symbol, from_metadata.symbol
+                ))
+            })?;
+        let amount_in = amounts.first().copied().ok_or_else(|| {
+            ServiceError::SwapSimulationFailed("No input amount returned".to_string())
+        })?;
+        let maximum_input = calculate_maximum_input(amount_in, slippage)?;
+        tracing::info!("Amount in required: {} (max {})", amount_in, maximum_input);
+
+        let (reserve_in, reserve_out, _, _) = repository
+            .get_uniswap_pair_reserves(from_token, to_token, None)
+            .await?;
+
+        // Gas is estimated on the equivalent exact-input swap of the quoted input
+        let (estimated_gas, estimated_gas_raw, gas_cost_eth) = self
+            .estimate_v2_swap_gas(
+                dex,
+                &req.from_address,
+                amount_in,
+                calculate_minimum_output(amount_out, slippage)?,
+                path,
+                native,
+            )
+            .await?;
+
+        let price_impact = calculate_price_impact(amount_in, amount_out, reserve_in, reserve_out);
+        let exchange_rate = calculate_exchange_rate(
+            amount_in,
+            amount_out,
+            from_metadata.decimals,
+            to_metadata.decimals,
+            self.display_precision,
+        );
+
+        let explanation = req.explain.unwrap_or(false).then(|| {
+            format!(
+                "Quoted on {dex} through the direct {from}/{to} pair as an exact-output swap. Buying \
+                 {out} {to} requires about {input} {from}, moving the pool price by {price_impact}%. \
+                 The maximum input applies the requested {slippage}% slippage tolerance to that estimate.",
+                dex = dex.label(),
+                from = from_metadata.symbol,
+                to = to_metadata.symbol,
+                out = format_balance(amount_out, to_metadata.decimals),
+                input = format_balance(amount_in, from_metadata.decimals),
+            )
+        });
+
+        let response = SwapTokensResponse {
+            estimated_output: format_balance(amount_out, to_metadata.decimals),
+            estimated_output_raw: amount_out.to_string(),
+            minimum_output: format_balance(amount_out, to_metadata.decimals),
+            estimated_input: Some(format_balance(amount_in, from_metadata.decimals)),
+            maximum_input: Some(format_balance(maximum_input, from_metadata.decimals)),
+            estimated_gas,
+            estimated_gas_raw,
+            gas_buffer_percent: self.gas_buffer_percent,
+            estimated_gas_eth: gas_cost_eth,
+            price_impact: price_impact.clone(),
+            exchange_rate: exchange_rate.clone(),
+            transaction_data: format!(
+                "Swap simulation (V2, exact output): {from_token} -> {to_token}"
+            ),
+            route: format!("{} -> {}", from_metadata.symbol, to_metadata.symbol),
+            path: vec![from_token.to_string(), to_token.to_string()],
+            amounts: amounts.iter().map(U256::to_string).collect(),
+            uniswap_version: "v2".to_string(),
+            wraps_eth: native == NativeEth::Wrap,
+            unwraps_eth: native == NativeEth::Unwrap,
+            explanation,
+            warning: None,
+            summary: None,
+        };
+
+        tracing::info!(
+            "V2 exact-output simulation complete: input={}, impact={}, rate={}",
+            response.estimated_input.as_deref().unwrap_or_default(),
+            price_impact,
+            exchange_rate
+        );
+
+        Ok(response)
+    }
+
+    #[instrument(skip(self), err)]
+    async fn swap_tokens_v3(&self, req: SwapTokensRequest) -> ServiceResult<SwapTokensResponse> {
+        let from_token = self.parse_token_address_or_symbol(&req.from_token).await?;
+        let to_token = self.parse_token_address_or_symbol(&req.to_token).await?;
+        let native = Self::native_eth(&req.from_token, &req.to_token, false)?;
+
+        // Get token metadata
+        let from_metadata = self.repository.get_token_metadata(from_token).await?;
+        let to_metadata = self.repository.get_token_metadata(to_token).await?;
+
+        // Parse amount with proper decimals
+        let amount_in = parse_amount(&req.amount, from_metadata.decimals)
+            .map_err(ServiceError::InvalidAmount)?;
+        tracing::info!(
+            "V3 Amount in (parsed): {} ({})",
+            amount_in,
+            format_balance(amount_in, from_metadata.decimals)
+        );
+
+        let slippage = self.slippage(req.slippage_tolerance.as_deref())?;
+        let fee_tiers = Self::parse_fee_tier(req.fee_tier, "v3")?;
+
+        let scan = self
+            .scan_v3_fee_tiers(from_token, to_token, amount_in, fee_tiers)
+            .await;
+        let tier_results = &scan.results;
+
+        // Check if we got any valid quote
+        let (amount_out, gas_estimate, selected_fee) =
+            scan.selected(&from_metadata.symbol, &to_metadata.symbol)?;
+
+        tracing::info!(
+            "Selected V3 pool with fee tier {} ({}%)",
+            selected_fee,
+            selected_fee as f64 / 10000.0
+        );
+
+        let minimum_output = calculate_minimum_output(amount_out, slippage)?;
+
+        // Compare the quote with the selected pool's spot price; without pool state the impact
+        // stays unknown rather than failing the quote
+        let price_impact = match self
+            .repository
+            .get_v3_pool_state(from_token, to_token, selected_fee)
+            .await
+            .map_err(ServiceError::from)
+            .and_then(|state| {
+                let spot = v3_spot_price(
+                    state.sqrt_price_x96,
+                    from_token == state.token0,
+                    from_metadata.decimals,
+                    to_metadata.decimals,
+                )?;
+                calculate_v3_price_impact(
+                    spot,
+                    amount_in,
+                    amount_out,
+                    from_metadata.decimals,
+                    to_metadata.decimals,
+                    selected_fee,
+                )
+            }) {
+            Ok(impact) => format_decimal(impact, None),
+            Err(e) => {
+                tracing::warn!("Could not compute V3 price impact: {e}");
+                "N/A (V3)".to_string()
+            }
+        };
+
+        // Estimate gas cost
+        let mut gas_simulated = false;
+        let (estimated_gas, estimated_gas_raw, gas_cost_eth) =
+            if let Some(addr_str) = &req.from_address {
+                let from_address = Address::from_str(addr_str)
+                    .map_err(|e| ServiceError::InvalidWalletAddress(e.to_string()))?;
+                let deadline = self.deadline();
+
+                match self
+                    .repository
+                    .simulate_v3_swap(
+                        from_address,
+                        from_token,
+                        to_token,
+                        amount_in,
+                        minimum_output,
+                        selected_fee,
+                        deadline,
+                        native,
+                    )
+                    .await
+                {
+                    Ok(gas) => {
+                        gas_simulated = true;
+                        self.format_gas_cost(gas).await?
+                    }
+                    Err(_) => {
+                        // Use the gas estimate from the quote
+                        self.format_gas_cost(gas_estimate).await?
+                    }
+                }
+            } else {
+                // Use the gas estimate from the quote
+                self.format_gas_cost(gas_estimate).await?
+            };
+
+        let exchange_rate = calculate_exchange_rate(
+            amount_in,
+            amount_out,
+            from_metadata.decimals,
+            to_metadata.decimals,
+            self.display_precision,
+        );
+
+        tracing::info!(
+            "V3 swap simulation complete: fee={}%, output={}, gas={}",
+            selected_fee as f64 / 10000.0,
+            format_balance(amount_out, to_metadata.decimals),
+            estimated_gas
+        );
+
+        let explanation = req.explain.unwrap_or(false).then(|| {
+            let scanned = tier_results
+                .iter()
+                .map(|(fee, out)| match out {
+                    Some(out) if !out.is_zero() => format!(
+                        "{}% -> {} {}",
+                        *fee as f64 / 10000.0,
+                        format_balance(*out, to_metadata.decimals),
+                        to_metadata.symbol
+                    ),
+                    _ => format!("{}% -> no liquidity", *fee as f64 / 10000.0),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let gas_source = if gas_simulated {
+                "an eth_call simulation from the provided address"
+            } else {
+                "the QuoterV2 gas estimate"
+            };
+
+            format!(
+                "Scanned Uniswap V3 fee tiers ({scanned}). Chose the {}% tier because it gave the \
+                 highest output. Gas is taken from {gas_source}. The minimum output applies the \
+                 requested {slippage}% slippage tolerance.",
+                selected_fee as f64 / 10000.0
+            )
+        });
+
+        Ok(SwapTokensResponse {
+            estimated_output: format_balance(amount_out, to_metadata.decimals),
+            estimated_output_raw: amount_out.to_string(),
+            minimum_output: format_balance(minimum_output, to_metadata.decimals),
+            estimated_input: None,
+            maximum_input: None,
+            estimated_gas,
+            estimated_gas_raw,
+            gas_buffer_percent: self.gas_buffer_percent,
+            estimated_gas_eth: gas_cost_eth,
+            price_impact,
+            exchange_rate,
+            transaction_data: format!(
+                "Swap simulation (V3, fee={}): {from_token} -> {to_token}",
+                selected_fee
+            ),
+            route: format!("{} -> {}", from_metadata.symbol, to_metadata.symbol),
+            path: vec![from_token.to_string(), to_token.to_string()],
+            amounts: vec![amount_in.to_string(), amount_out.to_string()],
+            uniswap_version: "v3".to_string(),
+            wraps_eth: native == NativeEth::Wrap,
+            unwraps_eth: native == NativeEth::Unwrap,
+            explanation,
+            warning: None,
+            summary: None,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn swap_tokens_v3_exact_output(
+        &self,
+        req: SwapTokensRequest,
+    ) -> ServiceResult<SwapTokensResponse> {
+        let from_token = self.parse_token_address_or_symbol(&req.from_token).await?;
+        let to_token = self.parse_token_address_or_symbol(&req.to_token).await?;
+        let native = Self::native_eth(&req.from_token, &req.to_token, false)?;
+
+        let from_metadata = self.repository.get_token_metadata(from_token).await?;
+        let to_metadata = self.repository.get_token_metadata(to_token).await?;
+
+        // In exact-output mode the amount is denominated in the destination token
+        let amount_out =
+            parse_amount(&req.amount, to_metadata.decimals).map_err(ServiceError::InvalidAmount)?;
+
+        let slippage = self.slippage(req.slippage_tolerance.as_deref())?;
+        let fee_tiers = Self::parse_fee_tier(req.fee_tier, "v3")?;
+
+        let scan = self
+            .scan_v3_quotes(
+                from_token,
+                to_token,
+                V3QuoteAmount::ExactOutput(amount_out),
+                fee_tiers,
+            )
+            .await;
+        let (amount_in, gas_estimate, selected_fee) =
+            scan.selected(&from_metadata.symbol, &to_metadata.symbol)?;
+        let maximum_input = calculate_maximum_input(amount_in, slippage)?;
+        tracing::info!(
+            "Selected V3 fee tier {} for exact output: amount in required {} (max {})",
+            selected_fee,
+            amount_in,
+            maximum_input
+        );
+
+        let price_impact = match self
+            .repository
+            .get_v3_pool_state(from_token, to_token, selected_fee)
+            .await
+            .map_err(ServiceError::from)
+            .and_then(|state| {
+                let spot = v3_spot_price(
+                    state.sqrt_price_x96,
+                    from_token == state.token0,
+                    from_metadata.decimals,
+                    to_metadata.decimals,
+                )?;
+                calculate_v3_price_impact(
+                    spot,
+                    amount_in,
+                    amount_out,
+                    from_metadata.decimals,
+                    to_metadata.decimals,
+                    selected_fee,
+                )
+            }) {
+            Ok(impact) => format_decimal(impact, None),
+            Err(e) => {
+                tracing::warn!("Could not compute V3 price impact: {e}");
+                "N/A (V3)".to_string()
+            }
+        };
+
+        // Gas is estimated on the equivalent exact-input swap of the quoted input
+        let simulated_gas = match &req.from_address {
+            Some(addr_str) => {
+                let from_address = Address::from_str(addr_str)
+                    .map_err(|e| ServiceError::InvalidWalletAddress(e.to_string()))?;
+                self.repository
+                    .simulate_v3_swap(
+                        from_address,
+                        from_token,
+                        to_token,
+                        amount_in,
+                        calculate_minimum_output(amount_out, slippage)?,
+                        selected_fee,
+                        self.deadline(),
+                        native,
+                    )
+                    .await
+                    .ok()
+            }
+            None => None,
+        };
+        let (estimated_gas, estimated_gas_raw, gas_cost_eth) = self
+            .format_gas_cost(simulated_gas.unwrap_or(gas_estimate))
+            .await?;
+
+        let exchange_rate = calculate_exchange_rate(
+            amount_in,
+            amount_out,
+            from_metadata.decimals,
+            to_metadata.decimals,
+            self.display_precision,
+        );
+
+        let explanation = req.explain.unwrap_or(false).then(|| {
+            let scanned = scan
+                .results
+                .iter()
+                .map(|(fee, input)| match input {
+                    Some(input) if !input.is_zero() => format!(
+                        "{}% -> {} {}",
+                        *fee as f64 / 10000.0,
+                        format_balance(*input, from_metadata.decimals),
+                        from_metadata.symbol
+                    ),
+                    _ => format!("{}% -> no liquidity", *fee as f64 / 10000.0),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "Quoted buying {out} {to} as an exact-output swap on Uniswap V3 fee tiers \
+                 ({scanned}). Chose the {fee}% tier because it needs the least {from}. The \
+                 maximum input applies the requested {slippage}% slippage tolerance to that \
+                 estimate.",
+                out = format_balance(amount_out, to_metadata.decimals),
+                to = to_metadata.symbol,
+                from = from_metadata.symbol,
+                fee = selected_fee as f64 / 10000.0,
+            )
+        });
+
+        let response = SwapTokensResponse {
+            estimated_output: format_balance(amount_out, to_metadata.decimals),
+            estimated_output_raw: amount_out.to_string(),
+            minimum_output: format_balance(amount_out, to_metadata.decimals),
+            estimated_input: Some(format_balance(amount_in, from_metadata.decimals)),
+            maximum_input: Some(format_balance(maximum_input, from_metadata.decimals)),
+            estimated_gas,
+            estimated_gas_raw,
+            gas_buffer_percent: self.gas_buffer_percent,
+            estimated_gas_eth: gas_cost_eth,
+            price_impact: price_impact.clone(),
+            exchange_rate: exchange_rate.clone(),
+            transaction_data: format!(
+                "Swap simulation (V3, exact output, fee={selected_fee}): {from_token} -> \
+                 {to_token}"
+            ),
+            route: format!("{} -> {}", from_metadata.symbol, to_metadata.symbol),
+            path: vec![from_token.to_string(), to_token.to_string()],
+            amounts: vec![amount_in.to_string(), amount_out.to_string()],
+            uniswap_version: "v3".to_string(),
+            wraps_eth: native == NativeEth::Wrap,
+            unwraps_eth: native == NativeEth::Unwrap,
+            explanation,
+            warning: None,
+            summary: None,
+        };
+
+        tracing::info!(
+            "V3 exact-output simulation complete: input={}, impact={}, rate={}",
+            response.estimated_input.as_deref().unwrap_or_default(),
+            price_impact,
+            exchange_rate
+        );
+
+        Ok(response)
+    }
+
+    #[instrument(skip(self), err)]
+    async fn check_allowance_impl(
+        &self,
+        req: CheckAllowanceRequest,
+    ) -> ServiceResult<CheckAllowanceResponse> {
+        let token = self.parse_token_address_or_symbol(&req.token).await?;
+        let owner = self.parse_wallet_address(&req.owner_address).await?;
+        let spender = self.resolve_spender(req.spender.as_deref())?;
+
+        let metadata = self.repository.get_token_metadata(token).await?;
+        let required =
+            parse_amount(&req.amount, metadata.decimals).map_err(ServiceError::InvalidAmount)?;
+
+        let allowance = self
+            .repository
+            .get_erc20_allowance(token, owner, spender)
+            .await?;
+
+        tracing::info!(
+            "Allowance of {} for {} on {}: {}",
+            spender,
+            owner,
+            metadata.symbol,
+            allowance
+        );
+
+        Ok(CheckAllowanceResponse {
+            symbol: metadata.symbol,
+            token_address: token.to_string(),
+            spender: spender.to_string(),
+            allowance: allowance.to_string(),
+            formatted_allowance: format_balance(allowance, metadata.decimals),
+            required_amount: format_balance(required, metadata.decimals),
+            sufficient: allowance >= required,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_my_approval_impl(
+        &self,
+        req: GetMyApprovalRequest,
+    ) -> ServiceResult<GetMyApprovalResponse> {
+        let owner = self.repository.wallet_address().ok_or_else(|| {
+            ServiceError::ExecutionNotAllowed(
+                "no wallet private key is configured; use check_allowance with an owner address"
+                    .to_string(),
+            )
+        })?;
+        let token = self.parse_token_address_or_symbol(&req.token).await?;
+        let spender = self.resolve_spender(req.spender.as_deref())?;
+
+        let metadata = self.repository.get_token_metadata(token).await?;
+        let allowance = self
+            .repository
+            .get_erc20_allowance(token, owner, spender)
+            .await?;
+        let unlimited = allowance >= UNLIMITED_ALLOWANCE_THRESHOLD;
+
+        Ok(GetMyApprovalResponse {
+            symbol: metadata.symbol,
+            token_address: token.to_string(),
+            owner: owner.to_string(),
+            spender: spender.to_string(),
+            allowance: allowance.to_string(),
+            formatted_allowance: if unlimited {
+                "unlimited".to_string()
+            } else {
+                format_balance(allowance, metadata.decimals)
+            },
+            unlimited,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn stop_loss_plan_impl(
+        &self,
+        req: StopLossPlanRequest,
+    ) -> ServiceResult<StopLossPlanResponse> {
+        let token = self.parse_token_address_or_symbol(&req.token).await?;
+        let metadata = self.repository.get_token_metadata(token).await?;
+
+        let amount_in =
+            parse_amount(&req.amount, metadata.decimals).map_err(ServiceError::InvalidAmount)?;
+        let amount = u256_to_decimal(amount_in, metadata.decimals)?;
+
+        let stop = Decimal::from_str(&req.stop_percentage)
+            .map_err(|e| ServiceError::InvalidAmount(format!("Invalid stop percentage: {e}")))?;
+        if stop <= Decimal::ZERO || stop >= Decimal::from(100) {
+            return Err(ServiceError::InvalidAmount(format!(
+                "Stop percentage must be between 0 and 100, got {stop}"
+            )));
+        }
+
+        let weth_address = self.chain.weth;
+        let eth_usd = self.eth_usd_price(None).await?;
+
+        let current_price = match req.current_price_usd {
+            Some(price) => Decimal::from_str(&price)
+                .map_err(|e| ServiceError::InvalidAmount(format!("Invalid current price: {e}")))?,
+            None => self.get_token_usd_price(token).await?,
+        };
+
+        // Sell into WETH, or into USDC when the holding is WETH itself
+        let quote_token = if token == weth_address {
+            self.parse_token_address_or_symbol("USDC").await?
+        } else {
+            weth_address
+        };
+
+        let amount_out = self
+            .get_swap_output_amount(amount_in, &[token, quote_token])
+            .await?;
+        let (reserve_in, reserve_out, _, _) = self
+            .repository
+            .get_uniswap_pair_reserves(token, quote_token, None)
+            .await?;
+        let price_impact = Decimal::from_str(&calculate_price_impact(
+            amount_in,
+            amount_out,
+            reserve_in,
+            reserve_out,
+        ))
+        .unwrap_or(Decimal::ZERO);
+
+        let (_, _, gas_cost_eth) = self.get_typical_gas_cost().await?;
+        let gas_usd = Decimal::from_str(&gas_cost_eth)
+            .map_err(|e| ServiceError::InternalError(format!("Invalid gas cost: {e}")))?
+            * eth_usd;
+
+        let trigger_price = current_price * (Decimal::from(100) - stop) / Decimal::from(100);
+        let (gross, net) =
+            calculate_stop_loss_proceeds(amount, trigger_price, price_impact, gas_usd);
+
+        tracing::info!(
+            "Stop-loss plan for {} {}: trigger=${}, net=${}",
+            amount,
+            metadata.symbol,
+            trigger_price,
+            net
+        );
+
+        Ok(StopLossPlanResponse {
+            symbol: metadata.symbol,
+            amount: format_decimal(amount, None),
+            current_price_usd: self.display(current_price),
+            trigger_price_usd: self.display(trigger_price),
+            gross_proceeds_usd: self.display(gross),
+            estimated_price_impact: format_decimal(price_impact, None),
+            estimated_gas_usd: self.display(gas_usd),
+            net_proceeds_usd: self.display(net),
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn compare_gas_timing_impl(
+        &self,
+        req: CompareGasTimingRequest,
+    ) -> ServiceResult<CompareGasTimingResponse> {
+        let target_gas_price = parse_amount(&req.target_gas_price_gwei, GWEI_DECIMALS)
+            .map_err(|e| ServiceError::InvalidAmount(format!("Invalid target gas price: {e}")))?;
+        if target_gas_price.is_zero() {
+            return Err(ServiceError::InvalidAmount(
+                "Target gas price must be greater than zero".to_string(),
+            ));
+        }
+
+        // Reuse the swap simulation for its gas estimate; slippage does not affect gas
+        let quote = self
+            .swap_tokens_impl(SwapTokensRequest {
+                from_token: req.from_token,
+                to_token: req.to_token,
+                amount: req.amount,
+                uniswap_version: req.uniswap_version,
+                from_address: req.from_address,
+                ..Default::default()
+            })
+            .await?;
+        let gas = u64::from_str(&quote.estimated_gas)
+            .map_err(|e| ServiceError::InternalError(format!("Invalid gas estimate: {e}")))?;
+
+        let current_gas_price = U256::from(self.fee_per_gas().await?);
+        let eth_usd = self.eth_usd_price(None).await?;
+
+        let (current_eth, current_usd) = calculate_gas_cost(gas, current_gas_price, eth_usd)?;
+        let (target_eth, target_usd) = calculate_gas_cost(gas, target_gas_price, eth_usd)?;
+
+        let already_at_target = current_gas_price <= target_gas_price;
+        let savings = (current_usd - target_usd).max(Decimal::ZERO);
+        let savings_percentage = if current_usd.is_zero() {
+            Decimal::ZERO
+        } else {
+            savings / current_usd * Decimal::from(100)
+        };
+
+        tracing::info!(
+            "Gas timing: {} gas costs ${} now vs ${} at target, saving ${}",
+            gas,
+            current_usd,
+            target_usd,
+            savings
+        );
+
+        Ok(CompareGasTimingResponse {
+            estimated_gas: gas.to_string(),
+            current_gas_price_gwei: format_balance(current_gas_price, GWEI_DECIMALS),
+            target_gas_price_gwei: format_balance(target_gas_price, GWEI_DECIMALS),
+            current_cost_eth: format_decimal(current_eth, None),
+            target_cost_eth: format_decimal(target_eth, None),
+            current_cost_usd: self.display(current_usd),
+            target_cost_usd: self.display(target_usd),
+            savings_usd: self.display(savings),
+            savings_percentage: format_decimal(savings_percentage, Some(2)),
+            already_at_target,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn verify_quote_impl(
+        &self,
+        req: VerifyQuoteRequest,
+    ) -> ServiceResult<VerifyQuoteResponse> {
+        let from_token = self.parse_token_address_or_symbol(&req.from_token).await?;
+        let to_token = self.parse_token_address_or_symbol(&req.to_token).await?;
+        let from_metadata = self.repository.get_token_metadata(from_token).await?;
+        let to_metadata = self.repository.get_token_metadata(to_token).await?;
+
+        let amount_in = parse_amount(&req.amount, from_metadata.decimals)
+            .map_err(ServiceError::InvalidAmount)?;
+        let quoted_output = parse_amount(&req.quoted_output, to_metadata.decimals)
+            .map_err(ServiceError::InvalidAmount)?;
+        let slippage =
+            parse_slippage(&req.slippage_tolerance).map_err(ServiceError::InvalidAmount)?;
+
+        // Re-quote the same route the original quote used
+        let candidate = match req
+            .uniswap_version
+            .as_deref()
+            .unwrap_or("v2")
+            .to_lowercase()
+            .as_str()
+        {
+            "v2" => RouteCandidate::V2(vec![from_token, to_token]),
+            "v3" => match req.v3_fee {
+                Some(fee) => {
+                    Self::parse_fee_tier(Some(fee), "v3")?;
+                    RouteCandidate::V3(fee)
+                }
+                None => {
+                    let scan = self
+                        .scan_v3_fee_tiers(from_token, to_token, amount_in, &V3_FEE_TIERS)
+                        .await;
+                    let (_, _, fee) = scan.selected(&from_metadata.symbol, &to_metadata.symbol)?;
+                    RouteCandidate::V3(fee)
+                }
+            },
+            other => {
+                return Err(ServiceError::InvalidAmount(format!(
+                    "Invalid Uniswap version: {other}. Must be 'v2' or 'v3'"
+                )));
+            }
+        };
+
+        let (current_output, _) = self
+            .quote_route(&candidate, from_token, to_token, amount_in)
+            .await
+            .map_err(|e| ServiceError::SwapSimulationFailed(format!("Failed to re-quote: {e}")))?;
+
+        let change = calculate_quote_change(quoted_output, current_output)?;
+        let minimum_output = calculate_minimum_output(quoted_output, slippage)?;
+        let within_tolerance = current_output >= minimum_output;
+
+        tracing::info!(
+            "Re-quoted {} {} -> {}: {} (quoted {}, change {}%)",
+            req.amount,
+            from_metadata.symbol,
+            to_metadata.symbol,
+            current_output,
+            quoted_output,
+            change
+        );
+
+        Ok(VerifyQuoteResponse {
+            route: self.route_label(&candidate, from_token, to_token).await,
+            quoted_output: format_balance(quoted_output, to_metadata.decimals),
+            current_output: format_balance(current_output, to_metadata.decimals),
+            current_output_raw: current_output.to_string(),
+            change_percentage: format_decimal(change.round_dp(4), None),
+            minimum_output: format_balance(minimum_output, to_metadata.decimals),
+            within_tolerance,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn calculate_implied_slippage_impl(
+        &self,
+        req: ImpliedSlippageRequest,
+    ) -> ServiceResult<ImpliedSlippageResponse> {
+        validate_positive_amount(&req.amount).map_err(ServiceError::InvalidAmount)?;
+        validate_positive_amount(&req.minimum_output).map_err(ServiceError::InvalidAmount)?;
+
+        let from_token = self.parse_token_address_or_symbol(&req.from_token).await?;
+        let to_token = self.parse_token_address_or_symbol(&req.to_token).await?;
+        let from_metadata = self.repository.get_token_metadata(from_token).await?;
+        let to_metadata = self.repository.get_token_metadata(to_token).await?;
+
+        let amount_in = parse_amount(&req.amount, from_metadata.decimals)
+            .map_err(ServiceError::InvalidAmount)?;
+        let minimum_output = parse_amount(&req.minimum_output, to_metadata.decimals)
+            .map_err(ServiceError::InvalidAmount)?;
+
+        // Quote the route swap_tokens would use for the same version
+        let (candidate, amount_out) = match req
+            .uniswap_version
+            .as_deref()
+            .unwrap_or("v2")
+            .to_lowercase()
+            .as_str()
+        {
+            "v2" => {
+                let (path, amounts) = self
+                    .find_best_path(V2Dex::UniswapV2, from_token, to_token, amount_in)
+                    .await?;
+                let amount_out = amounts.last().copied().unwrap_or_default();
+                (RouteCandidate::V2(path), amount_out)
+            }
+            "v3" => {
+                let scan = self
+                    .scan_v3_fee_tiers(from_token, to_token, amount_in, &V3_FEE_TIERS)
+                    .await;
+                let (amount_out, _, fee) =
+                    scan.selected(&from_metadata.symbol, &to_metadata.symbol)?;
+                (RouteCandidate::V3(fee), amount_out)
+            }
+            other => {
+                return Err(ServiceError::InvalidAmount(format!(
+                    "Invalid Uniswap version: {other}. Must be 'v2' or 'v3'"
+                )));
+            }
+        };
+
+        let slippage = calculate_implied_slippage(amount_out, minimum_output)?;
+        let reason = if minimum_output > amount_out {
+            Some(format!(
+                "The current quote of {} {} is below the minimum",
+                format_balance(amount_out, to_metadata.decimals),
+                to_metadata.symbol
+            ))
+        } else {
+            parse_slippage(&slippage.to_string()).err()
+        };
+
+        Ok(ImpliedSlippageResponse {
+            route: self.route_label(&candidate, from_token, to_token).await,
+            expected_output: format_balance(amount_out, to_metadata.decimals),
+            expected_output_raw: amount_out.to_string(),
+            minimum_output: format_balance(minimum_output, to_metadata.decimals),
+            implied_slippage: format_decimal(slippage, None),
+            achievable: reason.is_none(),
+            reason,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_best_swap_impl(
+        &self,
+        req: GetBestSwapRequest,
+    ) -> ServiceResult<GetBestSwapResponse> {
+        validate_positive_amount(&req.amount).map_err(ServiceError::InvalidAmount)?;
+        parse_slippage(&req.slippage_tolerance).map_err(ServiceError::InvalidAmount)?;
+
+        let request = |version: &str| SwapTokensRequest {
+            from_token: req.from_token.clone(),
+            to_token: req.to_token.clone(),
+            amount: req.amount.clone(),
+            slippage_tolerance: Some(req.slippage_tolerance.clone()),
+            uniswap_version: Some(version.to_string()),
+            from_address: req.from_address.clone(),
+            ..Default::default()
+        };
+        let v2 = self.swap_tokens_v2(request("v2")).await;
+        let v3 = self.swap_tokens_v3(request("v3")).await;
+
+        let (v2_quote, v2_error) = match v2 {
+            Ok(quote) => (Some(quote), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        let (v3_quote, v3_error) = match v3 {
+            Ok(quote) => (Some(quote), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        let output = |quote: &SwapTokensResponse| U256::from_str(&quote.estimated_output_raw).ok();
+        let (chosen_version, chosen) = match (&v2_quote, &v3_quote) {
+            (Some(v2), Some(v3)) if output(v3) > output(v2) => ("v3", v3),
+            (Some(v2), _) => ("v2", v2),
+            (None, Some(v3)) => ("v3", v3),
+            (None, None) => {
+                return Err(ServiceError::SwapSimulationFailed(format!(
+                    "Neither Uniswap version could quote the swap. V2: {}. V3: {}",
+                    v2_error.unwrap_or_default(),
+                    v3_error.unwrap_or_default()
+                )));
+            }
+        };
+        let estimated_output = chosen.estimated_output.clone();
+        let minimum_output = chosen.minimum_output.clone();
+
+        // Formatted outputs share the destination token's decimals, so they compare directly
+        let advantage_percent = match (&v2_quote, &v3_quote) {
+            (Some(v2), Some(v3)) => [&v2.estimated_output, &v3.estimated_output]
+                .into_iter()
+                .map(|output| Decimal::from_str(output).ok())
+                .collect::<Option<Vec<_>>>()
+                .and_then(|outputs| calculate_price_spread(&outputs))
+                .map(|(_, _, spread)| format_decimal(spread, Some(4))),
+            _ => None,
+        };
+
+        Ok(GetBestSwapResponse {
+            chosen_version: chosen_version.to_string(),
+            estimated_output,
+            minimum_output,
+            advantage_percent,
+            v2_quote,
+            v3_quote,
+            v2_error,
+            v3_error,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_swap_status_impl(
+        &self,
+        req: GetSwapStatusRequest,
+    ) -> ServiceResult<GetSwapStatusResponse> {
+        let hash = B256::from_str(req.transaction_hash.trim())
+            .map_err(|e| ServiceError::InvalidTransactionHash(format!("{e}")))?;
+
+        let Some(execution) = self.repository.get_transaction_receipt(hash).await? else {
+            return Ok(GetSwapStatusResponse {
+                transaction_hash: hash.to_string(),
+                status: "pending".to_string(),
+                block_number: None,
+                confirmations: None,
+                gas_used: None,
+                effective_gas_price: None,
+                gas_cost_eth: None,
+            });
+        };
+
+        let confirmations = match execution.block_number {
+            Some(block) => {
+                let latest = self.repository.get_block_number().await?;
+                Some(latest.saturating_sub(block) + 1)
+            }
+            None => None,
+        };
+        let gas_cost_wei =
+            U256::from(execution.gas_used) * U256::from(execution.effective_gas_price);
+
+        Ok(GetSwapStatusResponse {
+            transaction_hash: hash.to_string(),
+            status: if execution.success {
+                "success".to_string()
+            } else {
+                "reverted".to_string()
+            },
+            block_number: execution.block_number,
+            confirmations,
+            gas_used: Some(execution.gas_used.to_string()),
+            effective_gas_price: Some(execution.effective_gas_price.to_string()),
+            gas_cost_eth: Some(format_balance(gas_cost_wei, ETH_DECIMALS)),
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_transaction_impl(
+        &self,
+        req: GetTransactionRequest,
+    ) -> ServiceResult<GetTransactionResponse> {
+        let hash = B256::from_str(req.transaction_hash.trim())
+            .map_err(|e| ServiceError::InvalidTransactionHash(format!("{e}")))?;
+
+        let Some(tx) = self.repository.get_transaction(hash).await? else {
+            return Ok(GetTransactionResponse {
+                transaction_hash: hash.to_string(),
+                status: "not_found".to_string(),
+                from: None,
+                to: None,
+                value_eth: None,
+                gas_limit: None,
+                nonce: None,
+                input_selector: None,
+                input_size: None,
+                swap: None,
+                block_number: None,
+                gas_used: None,
+            });
+        };
+
+        // A mined transaction's receipt can lag its block on some nodes; report it as pending
+        let receipt = match tx.block_number {
+            Some(_) => self.repository.get_transaction_receipt(hash).await?,
+            None => None,
+        };
+        let status = match &receipt {
+            None => "pending",
+            Some(receipt) if receipt.success => "success",
+            Some(_) => "reverted",
+        };
+        let swap = match &tx.swap {
+            Some(intent) => Some(self.describe_swap(intent).await),
+            None => None,
+        };
+
+        Ok(GetTransactionResponse {
+            transaction_hash: hash.to_string(),
+            status: status.to_string(),
+            from: Some(tx.from.to_string()),
+            to: tx.to.map(|a| a.to_string()),
+            value_eth: Some(format_balance(tx.value, ETH_DECIMALS)),
+            gas_limit: Some(tx.gas_limit),
+            nonce: Some(tx.nonce),
+            input_selector: tx
+                .input
+                .get(..4)
+                .map(|selector| Bytes::copy_from_slice(selector).to_string()),
+            input_size: Some(tx.input.len()),
+            swap,
+            block_number: receipt.as_ref().and(tx.block_number),
+            gas_used: receipt.map(|receipt| receipt.gas_used.to_string()),
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_eth_price_impl(&self) -> ServiceResult<GetEthPriceResponse> {
+        // Same computation as get_eth_usd_price, but reading the reserves here so the price and
+        // the reserves returned come from one call
+        let (reserve_usdc, reserve_weth, _, _) = self
+            .repository
+            .get_uniswap_pair_reserves(self.chain.usdc, self.chain.weth, None)
+            .await?;
+        if reserve_usdc.is_zero() || reserve_weth.is_zero() {
+            return Err(ServiceError::InsufficientLiquidity(
+                "No liquidity in USDC/WETH pair".to_string(),
+            ));
+        }
+
+        let price = calculate_price(reserve_usdc, reserve_weth, USDC_DECIMALS, ETH_DECIMALS)?;
+
+        Ok(GetEthPriceResponse {
+            price_usd: self.display(price),
+            reserve_usdc: format_balance(reserve_usdc, USDC_DECIMALS),
+            reserve_weth: format_balance(reserve_weth, ETH_DECIMALS),
+            timestamp: chrono::Utc::now().timestamp(),
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_chain_status_impl(&self) -> ServiceResult<GetChainStatusResponse> {
+        let chain_id = self.repository.get_chain_id().await?;
+        let block_number = self.repository.get_block_number().await?;
+        let block_timestamp = self.repository.get_block_timestamp().await?;
+        let fees = self.repository.get_fee_estimates().await?;
+
+        Ok(GetChainStatusResponse {
+            network: self.network.as_str().to_string(),
+            chain_id,
+            chain_id_matches: chain_id == self.chain.chain_id,
+            block_number,
+            block_timestamp,
+            block_age_secs: chrono::Utc::now().timestamp() - block_timestamp as i64,
+            base_fee_gwei: format_balance(U256::from(fees.base_fee), GWEI_DECIMALS),
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_pool_info_impl(
+        &self,
+        req: GetPoolInfoRequest,
+    ) -> ServiceResult<GetPoolInfoResponse> {
+        let token_a = self.parse_token_address_or_symbol(&req.token_a).await?;
+        let token_b = self.parse_token_address_or_symbol(&req.token_b).await?;
+        if token_a == token_b {
+            return Err(ServiceError::InvalidAmount(
+                "token_a and token_b must be different tokens".to_string(),
+            ));
+        }
+
+        let state = match req.fee_tier {
+            Some(fee) => {
+                Self::parse_fee_tier(Some(fee), "v3")?;
+                self.repository
+                    .get_v3_pool_state(token_a, token_b, fee)
+                    .await?
+            }
+            None => {
+                // Tiers without a pool are skipped; the deepest remaining pool is reported
+                let mut deepest: Option<V3PoolState> = None;
+                let mut last_error = None;
+                for fee in V3_FEE_TIERS {
+                    match self
+                        .repository
+                        .get_v3_pool_state(token_a, token_b, fee)
+                        .await
+                    {
+                        Ok(state) => {
+                            if deepest
+                                .as_ref()
+                                .is_none_or(|best| state.liquidity > best.liquidity)
+                            {
+                                deepest = Some(state);
+                            }
+                        }
+                        Err(e) => last_error = Some(e),
+                    }
+                }
+                match deepest {
+                    Some(state) => state,
+                    None => {
+                        return Err(last_error.map(ServiceError::from).unwrap_or_else(|| {
+                            ServiceError::InsufficientLiquidity(format!(
+                                "No Uniswap V3 pool found for {} and {}",
+                                req.token_a, req.token_b
+                            ))
+                        }));
+                    }
+                }
+            }
+        };
+
+        let decimals_a = self.repository.get_token_metadata(token_a).await?.decimals;
+        let decimals_b = self.repository.get_token_metadata(token_b).await?.decimals;
+        let price = v3_spot_price(
+            state.sqrt_price_x96,
+            token_a == state.token0,
+            decimals_a,
+            decimals_b,
+        )?;
+
+        Ok(GetPoolInfoResponse {
+            pool_address: state.pool.to_string(),
+            fee_tier: state.fee,
+            token0: state.token0.to_string(),
+            token1: state.token1.to_string(),
+            price: self.display(price),
+            sqrt_price_x96: state.sqrt_price_x96.to_string(),
+            tick: state.tick,
+            liquidity: state.liquidity.to_string(),
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_token_prices_impl(
+        &self,
+        req: GetTokenPricesRequest,
+    ) -> ServiceResult<GetTokenPricesResponse> {
+        if req.tokens.is_empty() {
+            return Err(ServiceError::InvalidAmount(
+                "No tokens to price".to_string(),
+            ));
+        }
+        if req.tokens.len() > MAX_BATCH_TOKENS {
+            return Err(ServiceError::InvalidAmount(format!(
+                "Too many tokens: {} (maximum {MAX_BATCH_TOKENS})",
+                req.tokens.len()
+            )));
+        }
+
+        tracing::info!("Pricing {} tokens", req.tokens.len());
+
+        // Without ETH/USD no token has a USD price, so this one failure fails the call
+        let eth_usd = self.eth_usd_price(None).await?;
+
+        let mut prices = Vec::with_capacity(req.tokens.len());
+        for token in req.tokens {
+            let token = token.trim().to_string();
+            let address = match self.parse_token_address_or_symbol(&token).await {
+                Ok(address) => address,
+                Err(e) => {
+                    prices.push(TokenPriceEntry {
+                        token,
+                        contract_address: None,
+                        symbol: None,
+                        price_usd: None,
+                        price_eth: None,
+                        reserves_updated_at: None,
+                        priced: false,
+                        error: Some(e.to_string()),
+                    });
+                    continue;
+                }
+            };
+
+            let symbol = if token.eq_ignore_ascii_case("ETH") {
+                Some("ETH".to_string())
+            } else {
+                match self.token_registry.lookup_symbol(&address.to_string()) {
+                    Some(symbol) => Some(symbol.to_string()),
+                    None => self
+                        .repository
+                        .get_token_metadata(address)
+                        .await
+                        .ok()
+                        .map(|metadata| metadata.symbol),
+                }
+            };
+
+            let price_eth = if address == self.chain.weth {
+                Ok((Decimal::ONE, None))
+            } else {
+                self.token_price_in_eth(address, self.chain.weth, Some(eth_usd), None)
+                    .await
+                    .map(|(price_eth, updated_at)| (price_eth, Some(updated_at)))
+            };
+
+            prices.push(match price_eth {
+                Ok((price_eth, reserves_updated_at)) => TokenPriceEntry {
+                    token,
+                    contract_address: Some(address.to_string()),
+                    symbol,
+                    price_usd: Some(self.display(price_eth * eth_usd)),
+                    price_eth: Some(self.display(price_eth)),
+                    reserves_updated_at,
+                    priced: true,
+                    error: None,
+                },
+                Err(e) => {
+                    tracing::warn!("Could not price {token}: {e}");
+                    TokenPriceEntry {
+                        token,
+                        contract_address: Some(address.to_string()),
+                        symbol,
+                        price_usd: None,
+                        price_eth: None,
+                        reserves_updated_at: None,
+                        priced: false,
+                        error: Some(e.to_string()),
+                    }
+                }
+            });
+        }
+
+        let all_priced = prices.iter().all(|entry| entry.priced);
+        Ok(GetTokenPricesResponse {
+            eth_price_usd: self.display(eth_usd),
+            prices,
+            all_priced,
+            timestamp: chrono::Utc::now().timestamp(),
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_wallet_snapshot_impl(
+        &self,
+        req: GetBalancesRequest,
+    ) -> ServiceResult<GetWalletSnapshotResponse> {
+        let address = self.parse_wallet_address(&req.wallet_address).await?;
+        if req.tokens.len() > MAX_BATCH_TOKENS {
+            return Err(ServiceError::InvalidAmount(format!(
+                "Too many tokens: {} (maximum {MAX_BATCH_TOKENS})",
+                req.tokens.len()
+            )));
+        }
+
+        // Resolve every input first so all balances go into one multicall
+        let mut resolved: Vec<(String, ServiceResult<Address>)> = vec![];
+        let mut contracts = vec![];
+        for token in req.tokens {
+            let token = token.trim().to_string();
+            if token.eq_ignore_ascii_case("ETH") {
+                continue;
+            }
+            let result = self.parse_token_address_or_symbol(&token).await;
+            if let Ok(contract) = result {
+                if contracts.contains(&contract) {
+                    continue;
+                }
+                contracts.push(contract);
+            }
+            resolved.push((token, result));
+        }
+
+        let snapshot = self
+            .repository
+            .get_wallet_snapshot(address, contracts)
+            .await?;
+        tracing::info!(
+            "Wallet snapshot of {} at block {}: ETH and {} tokens",
+            address,
+            snapshot.block_number,
+            snapshot.tokens.len()
+        );
+
+        let eth_balance = GetBalanceResponse {
+            balance: snapshot.eth_balance.to_string(),
+            formatted_balance: format_balance(snapshot.eth_balance, ETH_DECIMALS),
+            decimals: ETH_DECIMALS,
+            symbol: "ETH".to_string(),
+            summary: None,
+        };
+        let mut balances = vec![BalanceEntry::from_result(
+            "ETH".to_string(),
+            None,
+            Ok(eth_balance),
+        )];
+
+        let mut token_balances = snapshot.tokens.into_iter();
+        for (token, result) in resolved {
+            balances.push(match result {
+                Ok(contract) => {
+                    let balance = token_balances
+                        .next()
+                        .ok_or_else(|| {
+                            ServiceError::InternalError("Missing multicall result".to_string())
+                        })?
+                        .map(|balance| self.token_balance_response(contract, balance))
+                        .map_err(ServiceError::from);
+                    BalanceEntry::from_result(token, Some(contract), balance)
+                }
+                Err(e) => BalanceEntry::from_result(token, None, Err(e)),
+            });
+        }
+
+        Ok(GetWalletSnapshotResponse {
+            wallet_address: address.to_string(),
+            block_number: snapshot.block_number,
+            balances,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_portfolio_value_impl(
+        &self,
+        req: GetPortfolioValueRequest,
+    ) -> ServiceResult<GetPortfolioValueResponse> {
+        let balances = self
+            .get_balances_impl(GetBalancesRequest {
+                wallet_address: req.wallet_address,
+                tokens: req.tokens,
+            })
+            .await?;
+
+        let weth_address = self.chain.weth;
+        let eth_usd = self.eth_usd_price(None).await?;
+
+        let mut total = Decimal::ZERO;
+        let mut holdings = Vec::with_capacity(balances.balances.len());
+        for entry in balances.balances {
+            let Some(balance) = entry.balance else {
+                holdings.push(PortfolioHolding {
+                    token: entry.token,
+                    contract_address: entry.contract_address,
+                    symbol: None,
+                    balance: None,
+                    price_usd: None,
+                    value_usd: None,
+                    priced: false,
+                    error: entry.error,
+                });
+                continue;
+            };
+
+            let contract = entry
+                .contract_address
+                .as_deref()
+                .map(Address::from_str)
+                .transpose()
+                .map_err(|e| ServiceError::InternalError(e.to_string()))?;
+            let raw = U256::from_str(&balance.balance)
+                .map_err(|e| ServiceError::InternalError(format!("Invalid balance: {e}")))?;
+            let amount = u256_to_decimal(raw, balance.decimals)?;
+
+            // Native ETH and WETH use the ETH/USD price; other tokens go through their WETH pair
+            let price = match contract {
+                None => Ok(eth_usd),
+                Some(token) if token == weth_address => Ok(eth_usd),
+                Some(token) => self
+                    .token_price_in_eth(token, weth_address, Some(eth_usd), None)
+                    .await
+                    .map(|(price_eth, _)| price_eth * eth_usd),
+            };
+
+            holdings.push(match price {
+                Ok(price) => {
+                    let value = amount * price;
+                    total += value;
+                    PortfolioHolding {
+                        token: entry.token,
+                        contract_address: entry.contract_address,
+                        symbol: Some(balance.symbol),
+                        balance: Some(balance.formatted_balance),
+                        price_usd: Some(self.display(price)),
+                        value_usd: Some(self.display(value)),
+                        priced: true,
+                        error: None,
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Could not price {}: {e}", entry.token);
+                    PortfolioHolding {
+                        token: entry.token,
+                        contract_address: entry.contract_address,
+                        symbol: Some(balance.symbol),
+                        balance: Some(balance.formatted_balance),
+                        price_usd: None,
+                        value_usd: None,
+                        priced: false,
+                        error: Some(e.to_string()),
+                    }
+                }
+            });
+        }
+
+        let all_priced = holdings.iter().all(|holding| holding.priced);
+        Ok(GetPortfolioValueResponse {
+            wallet_address: balances.wallet_address,
+            holdings,
+            total_value_usd: self.display(total),
+            all_priced,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn estimate_net_yield_impl(
+        &self,
+        req: EstimateNetYieldRequest,
+    ) -> ServiceResult<EstimateNetYieldResponse> {
+        let token = self.parse_token_address_or_symbol(&req.token).await?;
+        let metadata = self.repository.get_token_metadata(token).await?;
+
+        let amount_raw =
+            parse_amount(&req.amount, metadata.decimals).map_err(ServiceError::InvalidAmount)?;
+        let amount = u256_to_decimal(amount_raw, metadata.decimals)?;
+
+        let apy = Decimal::from_str(&req.apy)
+            .map_err(|e| ServiceError::InvalidAmount(format!("Invalid APY: {e}")))?;
+        if apy < Decimal::ZERO {
+            return Err(ServiceError::InvalidAmount(format!(
+                "APY cannot be negative, got {apy}"
+            )));
+        }
+        let holding_days = Decimal::from_str(&req.holding_days)
+            .map_err(|e| ServiceError::InvalidAmount(format!("Invalid holding period: {e}")))?;
+        if holding_days <= Decimal::ZERO {
+            return Err(ServiceError::InvalidAmount(format!(
+                "Holding period must be positive, got {holding_days} days"
+            )));
+        }
+
+        let current_price = match req.current_price_usd {
+            Some(price) => Decimal::from_str(&price)
+                .map_err(|e| ServiceError::InvalidAmount(format!("Invalid current price: {e}")))?,
+            None => self.get_token_usd_price(token).await?,
+        };
+        let value = amount * current_price;
+
+        // Enter from and exit to WETH, or USDC when the holding is WETH itself
+        let weth_address = self.chain.weth;
+        let quote_token = if token == weth_address {
+            self.parse_token_address_or_symbol("USDC").await?
+        } else {
+            weth_address
+        };
+        let (reserve_token, reserve_quote, _, _) = self
+            .repository
+            .get_uniswap_pair_reserves(token, quote_token, None)
+            .await?;
+
+        // Entry: buy the amount with the quote token; exit: sell it back
+        let entry_input = self
+            .repository
+            .get_swap_amounts_in(amount_raw, vec![quote_token, token])
+            .await?
+            .first()
+            .copied()
+            .ok_or_else(|| {
+                ServiceError::SwapSimulationFailed("No input amount returned".to_string())
+            })?;
+        let exit_output = self
+            .get_swap_output_amount(amount_raw, &[token, quote_token])
+            .await?;
+        let entry_impact = Decimal::from_str(&calculate_price_impact(
+            entry_input,
+            amount_raw,
+            reserve_quote,
+            reserve_token,
+        ))
+        .unwrap_or(Decimal::ZERO);
+        let exit_impact = Decimal::from_str(&calculate_price_impact(
+            amount_raw,
+            exit_output,
+            reserve_token,
+            reserve_quote,
+        ))
+        .unwrap_or(Decimal::ZERO);
+
+        let eth_usd = self.eth_usd_price(None).await?;
+        let (_, _, gas_cost_eth) = self.get_typical_gas_cost().await?;
+        let gas_usd = Decimal::from_str(&gas_cost_eth)
+            .map_err(|e| ServiceError::InternalError(format!("Invalid gas cost: {e}")))?
+            * eth_usd;
+
+        let entry_cost = value * entry_impact / Decimal::from(100) + gas_usd;
+        let exit_cost = value * exit_impact / Decimal::from(100) + gas_usd;
+        let (gross, net, net_apy) =
+            calculate_net_yield(value, apy, holding_days, entry_cost + exit_cost);
+
+        // Days of yield needed to pay back the entry and exit costs
+        let daily_yield = value * apy / Decimal::from(100) / Decimal::from(365);
+        let break_even_days = (!daily_yield.is_zero())
+            .then(|| format_decimal(((entry_cost + exit_cost) / daily_yield).round_dp(1), None));
+
+        tracing::info!(
+            "Net yield for {} {} over {} days at {}%: net=${}, net APY={}%",
+            amount,
+            metadata.symbol,
+            holding_days,
+            apy,
+            net,
+            net_apy
+        );
+
+        Ok(EstimateNetYieldResponse {
+            symbol: metadata.symbol,
+            amount: format_decimal(amount, None),
+            current_price_usd: self.display(current_price),
+            position_value_usd: self.display(value),
+            apy: format_decimal(apy, None),
+            holding_days: format_decimal(holding_days, None),
+            gross_yield_usd: self.display(gross),
+            entry_cost_usd: self.display(entry_cost),
+            exit_cost_usd: self.display(exit_cost),
+            net_yield_usd: self.display(net),
+            net_apy: format_decimal(net_apy.round_dp(4), None),
+            break_even_days,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_pool_volume_impl(
+        &self,
+        req: GetPoolVolumeRequest,
+    ) -> ServiceResult<GetPoolVolumeResponse> {
+        let token_a = self.parse_token_address_or_symbol(&req.token_a).await?;
+        let token_b = self.parse_token_address_or_symbol(&req.token_b).await?;
+        let metadata_a = self.repository.get_token_metadata(token_a).await?;
+        let metadata_b = self.repository.get_token_metadata(token_b).await?;
+
+        let blocks_per_day = self.chain.blocks_per_day;
+        let requested = req.blocks.unwrap_or(blocks_per_day);
+        if requested == 0 {
+            return Err(ServiceError::InvalidAmount(
+                "blocks must be greater than 0".to_string(),
+            ));
+        }
+        let blocks = requested.min(blocks_per_day);
+        let warning = (requested > blocks).then(|| {
+            format!("Requested {requested} blocks; scanned the maximum of {blocks_per_day}")
+        });
+
+        let to_block = self.repository.get_block_number().await?;
+        let from_block = to_block.saturating_sub(blocks - 1);
+
+        let events = self
+            .repository
+            .get_v2_swap_events(token_a, token_b, from_block, to_block)
+            .await?;
+        let (raw_volume_a, raw_volume_b) = sum_swap_volume(&events);
+        let volume_a = u256_to_decimal(raw_volume_a, metadata_a.decimals)?;
+        let volume_b = u256_to_decimal(raw_volume_b, metadata_b.decimals)?;
+
+        // Price from token A, falling back to token B if A has no WETH pair
+        let volume_usd = match self.get_token_usd_price(token_a).await {
+            Ok(price) => volume_a * price,
+            Err(e) => {
+                tracing::debug!("No USD price for {}: {e}", metadata_a.symbol);
+                volume_b * self.get_token_usd_price(token_b).await?
+            }
+        };
+
+        tracing::info!(
+            "{}/{} volume over blocks {}..={}: {} swaps, ${}",
+            metadata_a.symbol,
+            metadata_b.symbol,
+            from_block,
+            to_block,
+            events.len(),
+            volume_usd
+        );
+
+        Ok(GetPoolVolumeResponse {
+            pair: format!("{}/{}", metadata_a.symbol, metadata_b.symbol),
+            from_block,
+            to_block,
+            swap_count: events.len(),
+            volume_token_a: format_decimal(volume_a, None),
+            volume_token_b: format_decimal(volume_b, None),
+            volume_usd: self.display(volume_usd.round_dp(2)),
+            warning,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_pool_capacity_impl(
+        &self,
+        req: GetPoolCapacityRequest,
+    ) -> ServiceResult<GetPoolCapacityResponse> {
+        let from_token = self.parse_token_address_or_symbol(&req.from_token).await?;
+        let to_token = self.parse_token_address_or_symbol(&req.to_token).await?;
+        let from_metadata = self.repository.get_token_metadata(from_token).await?;
+        let to_metadata = self.repository.get_token_metadata(to_token).await?;
+
+        let drain = Decimal::from_str(req.drain_percentage.as_deref().unwrap_or("50"))
+            .map_err(|e| ServiceError::InvalidAmount(format!("Invalid drain percentage: {e}")))?;
+
+        let (reserve_in, reserve_out, _, _) = self
+            .repository
+            .get_uniswap_pair_reserves(from_token, to_token, None)
+            .await?;
+        if reserve_in.is_zero() || reserve_out.is_zero() {
+            return Err(ServiceError::InsufficientLiquidity(format!(
+                "No liquidity in Uniswap pair for {} and {}",
+                from_metadata.symbol, to_metadata.symbol
+            )));
+        }
+
+        let (max_input, output_at_max) = calculate_pool_capacity(reserve_in, reserve_out, drain)?;
+
+        tracing::info!(
+            "{}/{} capacity at {}% drain: {} in -> {} out",
+            from_metadata.symbol,
+            to_metadata.symbol,
+            drain,
+            max_input,
+            output_at_max
+        );
+
+        Ok(GetPoolCapacityResponse {
+            pair: format!("{}/{}", from_metadata.symbol, to_metadata.symbol),
+            reserve_in: format_balance(reserve_in, from_metadata.decimals),
+            reserve_out: format_balance(reserve_out, to_metadata.decimals),
+            drain_percentage: drain.to_string(),
+            max_input: format_balance(max_input, from_metadata.decimals),
+            max_input_raw: max_input.to_string(),
+            output_at_max: format_balance(output_at_max, to_metadata.decimals),
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn simulate_pending_swap_impl(
+        &self,
+        req: SimulatePendingSwapRequest,
+    ) -> ServiceResult<SimulatePendingSwapResponse> {
+        let same_direction = match req.pending_direction.as_deref().unwrap_or("same") {
+            "same" => true,
+            "opposite" => false,
+            other => {
+                return Err(ServiceError::InvalidAmount(format!(
+                    "Pending direction must be \"same\" or \"opposite\", got \"{other}\""
+                )));
+            }
+        };
+
+        let from_token = self.parse_token_address_or_symbol(&req.from_token).await?;
+        let to_token = self.parse_token_address_or_symbol(&req.to_token).await?;
+        let from_metadata = self.repository.get_token_metadata(from_token).await?;
+        let to_metadata = self.repository.get_token_metadata(to_token).await?;
+
+        let amount_in = parse_amount(&req.amount, from_metadata.decimals)
+            .map_err(ServiceError::InvalidAmount)?;
+        let (pending_in_decimals, pending_out_decimals) = if same_direction {
+            (from_metadata.decimals, to_metadata.decimals)
+        } else {
+            (to_metadata.decimals, from_metadata.decimals)
+        };
+        let pending_amount_in = parse_amount(&req.pending_amount, pending_in_decimals)
+            .map_err(ServiceError::InvalidAmount)?;
+
+        let (reserve_in, reserve_out, _, _) = self
+            .repository
+            .get_uniswap_pair_reserves(from_token, to_token, None)
+            .await?;
+
+        let (expected_out, pending_out, amount_out) = calculate_output_after_pending_swap(
+            amount_in,
+            pending_amount_in,
+            same_direction,
+            reserve_in,
+            reserve_out,
+        )?;
+        let change = calculate_quote_change(expected_out, amount_out)?;
+
+        tracing::info!(
+            "{}/{} output {} -> {} after pending {} swap ({}%)",
+            from_metadata.symbol,
+            to_metadata.symbol,
+            expected_out,
+            amount_out,
+            if same_direction { "same" } else { "opposite" },
+            change.round_dp(2)
+        );
+
+        Ok(SimulatePendingSwapResponse {
+            pair: format!("{}/{}", from_metadata.symbol, to_metadata.symbol),
+            pending_direction: if same_direction { "same" } else { "opposite" }.to_string(),
+            pending_output: format_balance(pending_out, pending_out_decimals),
+            expected_output: format_balance(expected_out, to_metadata.decimals),
+            output_after_pending: format_balance(amount_out, to_metadata.decimals),
+            output_after_pending_raw: amount_out.to_string(),
+            output_change_percentage: format_decimal(change.round_dp(4), None),
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_recent_transactions_impl(
+        &self,
+        req: GetRecentTransactionsRequest,
+    ) -> ServiceResult<GetRecentTransactionsResponse> {
+        let address = self.parse_wallet_address(&req.wallet_address).await?;
+
+        let requested = req.blocks.unwrap_or(DEFAULT_RECENT_TX_BLOCKS);
+        if requested == 0 {
+            return Err(ServiceError::InvalidAmount(
+                "blocks must be greater than 0".to_string(),
+            ));
+        }
+        let blocks = requested.min(MAX_RECENT_TX_BLOCKS);
+        let warning = (requested > blocks).then(|| {
+            format!("Requested {requested} blocks; scanned the maximum of {MAX_RECENT_TX_BLOCKS}")
+        });
+
+        let to_block = self.repository.get_block_number().await?;
+        let from_block = to_block.saturating_sub(blocks - 1);
+
+        let wallet_transactions = self
+            .repository
+            .get_wallet_transactions(address, from_block, to_block)
+            .await?;
+
+        let mut transactions = Vec::with_capacity(wallet_transactions.len());
+        for tx in wallet_transactions.into_iter().rev() {
+            let swap = match &tx.swap {
+                Some(intent) => Some(self.describe_swap(intent).await),
+                None => None,
+            };
+            transactions.push(RecentTransaction {
+                hash: tx.hash.to_string(),
+                block_number: tx.block_number,
+                direction: if tx.from == address { "out" } else { "in" }.to_string(),
+                from: tx.from.to_string(),
+                to: tx.to.map(|a| a.to_string()),
+                value_eth: format_balance(tx.value, ETH_DECIMALS),
+                swap,
+            });
+        }
+
+        tracing::info!(
+            "Found {} transactions for {} in blocks {}..={}",
+            transactions.len(),
+            address,
+            from_block,
+            to_block
+        );
+
+        Ok(GetRecentTransactionsResponse {
+            wallet_address: address.to_string(),
+            from_block,
+            to_block,
+            transactions,
+            warning,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn estimate_round_trip_impl(
+        &self,
+        req: EstimateRoundTripRequest,
+    ) -> ServiceResult<EstimateRoundTripResponse> {
+        let from_token = self.parse_token_address_or_symbol(&req.from_token).await?;
+        let to_token = self.parse_token_address_or_symbol(&req.to_token).await?;
+        let from_metadata = self.repository.get_token_metadata(from_token).await?;
+        let to_metadata = self.repository.get_token_metadata(to_token).await?;
+
+        let amount_in = parse_amount(&req.amount, from_metadata.decimals)
+            .map_err(ServiceError::InvalidAmount)?;
+        if amount_in.is_zero() {
+            return Err(ServiceError::InvalidAmount(
+                "Amount must be greater than zero".to_string(),
+            ));
+        }
+
+        let (reserve_in, reserve_out, _, _) = self
+            .repository
+            .get_uniswap_pair_reserves(from_token, to_token, None)
+            .await?;
+        let (bought, returned) = calculate_round_trip(amount_in, reserve_in, reserve_out)?;
+
+        let total_cost = -calculate_quote_change(amount_in, returned)?;
+        let price_impact = total_cost - V2_ROUND_TRIP_FEE_PERCENT;
+        let effective_rate = u256_to_decimal(returned, 0)? / u256_to_decimal(amount_in, 0)?;
+
+        tracing::info!(
+            "{}/{} round trip of {} returns {} ({}% cost)",
+            from_metadata.symbol,
+            to_metadata.symbol,
+            amount_in,
+            returned,
+            total_cost.round_dp(4)
+        );
+
+        Ok(EstimateRoundTripResponse {
+            pair: format!("{}/{}", from_metadata.symbol, to_metadata.symbol),
+            amount_in: format_balance(amount_in, from_metadata.decimals),
+            amount_bought: format_balance(bought, to_metadata.decimals),
+            amount_returned: format_balance(returned, from_metadata.decimals),
+            effective_rate: self.display(effective_rate),
+            total_cost_percentage: format_decimal(total_cost.round_dp(4), None),
+            lp_fee_percentage: format_decimal(V2_ROUND_TRIP_FEE_PERCENT, None),
+            price_impact_percentage: format_decimal(price_impact.round_dp(4), None),
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn estimate_swap_gas_impl(
+        &self,
+        req: EstimateSwapGasRequest,
+    ) -> ServiceResult<EstimateSwapGasResponse> {
+        let from_token = self.parse_token_address_or_symbol(&req.from_token).await?;
+        let to_token = self.parse_token_address_or_symbol(&req.to_token).await?;
+        let from_metadata = self.repository.get_token_metadata(from_token).await?;
+        let amount_in = parse_amount(&req.amount, from_metadata.decimals)
+            .map_err(ServiceError::InvalidAmount)?;
+
+        // No quote is taken, so the simulation accepts any output; the gas barely depends on it
+        let (estimated_gas, estimated_gas_raw, estimated_gas_eth) = self
+            .estimate_v2_swap_gas(
+                V2Dex::UniswapV2,
+                &req.from_address,
+                amount_in,
+                U256::ZERO,
+                vec![from_token, to_token],
+                Self::native_eth(&req.from_token, &req.to_token, false)?,
+            )
+            .await?;
+
+        Ok(EstimateSwapGasResponse {
+            estimated_gas,
+            estimated_gas_raw,
+            gas_buffer_percent: self.gas_buffer_percent,
+            estimated_gas_eth,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn resolve_tokens_impl(
+        &self,
+        req: ResolveTokensRequest,
+    ) -> ServiceResult<ResolveTokensResponse> {
+        if req.tokens.is_empty() {
+            return Err(ServiceError::InvalidAmount(
+                "At least one token must be provided".to_string(),
+            ));
+        }
+        if req.tokens.len() > MAX_BATCH_TOKENS {
+            return Err(ServiceError::InvalidAmount(format!(
+                "Too many tokens: {} (maximum {MAX_BATCH_TOKENS})",
+                req.tokens.len()
+            )));
+        }
+
+        let mut tokens = Vec::with_capacity(req.tokens.len());
+        for input in req.tokens {
+            let input = input.trim().to_string();
+            let source = if Self::is_ens_name(&input) {
+                "ens"
+            } else if Address::from_str(&input).is_ok() {
+                "address"
+            } else {
+                "symbol"
+            };
+
+            let resolved = match self.parse_token_address_or_symbol(&input).await {
+                Ok(address) => self.resolve_token_symbol(address, source).await,
+                Err(e) => Err(e),
+            };
+
+            tokens.push(match resolved {
+                Ok((address, symbol)) => ResolvedToken {
+                    input,
+                    address: Some(address.to_string()),
+                    symbol,
+                    source: source.to_string(),
+                    error: None,
+                },
+                Err(e) => {
+                    tracing::warn!("Failed to resolve {input}: {e}");
+                    ResolvedToken {
+                        input,
+                        address: None,
+                        symbol: None,
+                        source: source.to_string(),
+                        error: Some(e.to_string()),
+                    }
+                }
+            });
+        }
+
+        let all_resolved = tokens.iter().all(|token| token.error.is_none());
+        Ok(ResolveTokensResponse {
+            tokens,
+            all_resolved,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn find_best_route_impl(
+        &self,
+        req: FindBestRouteRequest,
+    ) -> ServiceResult<FindBestRouteResponse> {
+        let from_token = self.parse_token_address_or_symbol(&req.from_token).await?;
+        let to_token = self.parse_token_address_or_symbol(&req.to_token).await?;
+        let from_metadata = self.repository.get_token_metadata(from_token).await?;
+        let to_metadata = self.repository.get_token_metadata(to_token).await?;
+
+        let amount_in = parse_amount(&req.amount, from_metadata.decimals)
+            .map_err(ServiceError::InvalidAmount)?;
+        let retries = req.rpc_retries.unwrap_or(1).min(MAX_ROUTE_RPC_RETRIES);
+
+        let weth_address = self.chain.weth;
+
+        let mut candidates = vec![RouteCandidate::V2(vec![from_token, to_token])];
+        if from_token != weth_address && to_token != weth_address {
+            candidates.push(RouteCandidate::V2(vec![from_token, weth_address, to_token]));
+        }
+        candidates.extend(V3_FEE_TIERS.map(RouteCandidate::V3));
+
+        let mut routes = Vec::new();
+        let mut excluded = Vec::new();
+
+        for candidate in &candidates {
+            let label = self.route_label(candidate, from_token, to_token).await;
+            let mut attempts = 0;
+
+            // A revert or an empty pool excludes the route immediately; RPC failures are retried
+            let failure = loop {
+                attempts += 1;
+                match self
+                    .quote_route(candidate, from_token, to_token, amount_in)
+                    .await
+                {
+                    Ok((amount_out, _)) if amount_out.is_zero() => {
+                        break Some(("no_liquidity", "Quoted output is 0".to_string()));
+                    }
+                    Ok((amount_out, gas_estimate)) => {
+                        let (uniswap_version, fee_tier) = match candidate {
+                            RouteCandidate::V2(_) => ("v2", None),
+                            RouteCandidate::V3(fee) => ("v3", Some(*fee)),
+                        };
+                        routes.push((
+                            amount_out,
+                            RouteQuote {
+                                route: label.clone(),
+                                uniswap_version: uniswap_version.to_string(),
+                                fee_tier,
+                                amount_out: format_balance(amount_out, to_metadata.decimals),
+                                amount_out_raw: amount_out.to_string(),
+                                gas_estimate: gas_estimate.map(|g| g.to_string()),
+                            },
+                        ));
+                        break None;
+                    }
+                    Err(e) if is_revert_error(&e) => break Some(("revert", e.to_string())),
+                    // An open circuit will not close within the retry delay
+                    Err(e)
+                        if attempts > retries || matches!(e, RepositoryError::CircuitOpen(_)) =>
+                    {
+                        break Some(("rpc_error", e.to_string()));
+                    }
+                    Err(e) => {
+                        tracing::warn!("RPC failure quoting {label} (attempt {attempts}): {e}");
+                    }
+                }
+            };
+
+            if let Some((kind, reason)) = failure {
+                tracing::debug!("Excluding route {label}: {kind}: {reason}");
+                excluded.push(ExcludedRoute {
+                    route: label,
+                    kind: kind.to_string(),
+                    reason,
+                    attempts,
+                });
+            }
+        }
+
+        routes.sort_by_key(|(amount_out, _)| std::cmp::Reverse(*amount_out));
+        let routes: Vec<RouteQuote> = routes.into_iter().map(|(_, quote)| quote).collect();
+
+        let Some(best_route) = routes.first().cloned() else {
+            let reasons = excluded
+                .iter()
+                .map(|r| format!("- {}: {} ({})", r.route, r.kind, r.reason))
+                .collect::<Vec<_>>()
+                .join("\n");
+            return Err(ServiceError::SwapSimulationFailed(format!(
+                "No route found for {} -> {}:\n{}",
+                from_metadata.symbol, to_metadata.symbol, reasons
+            )));
+        };
+
+        tracing::info!(
+            "Best route for {} {} -> {}: {} ({} ranked, {} excluded)",
+            req.amount,
+            from_metadata.symbol,
+            to_metadata.symbol,
+            best_route.route,
+            routes.len(),
+            excluded.len()
+        );
+
+        Ok(FindBestRouteResponse {
+            best_route,
+            routes,
+            excluded,
+        })
+    }
+
+    /// Quote a single route candidate, returning the output and the quoter gas estimate (V3 only)
+    async fn quote_route(
+        &self,
+        candidate: &RouteCandidate,
+        from_token: Address,
+        to_token: Address,
+        amount_in: U256,
+    ) -> RepoResult<(U256, Option<u64>)> {
+        match candidate {
+            RouteCandidate::V2(path) => {
+                let amounts = self
+                    .repository
+                    .get_swap_amounts_out(amount_in, path.clone())
+                    .await?;
+                Ok((amounts.last().copied().unwrap_or_default(), None))
+            }
+            RouteCandidate::V3(fee) => {
+                let (amount_out, gas) = self
+                    .repository
+                    .get_v3_quote(from_token, to_token, amount_in, *fee)
+                    .await?;
+                Ok((amount_out, Some(gas)))
+            }
+        }
+    }
+
+    /// Human-readable label for a route candidate (e.g., "V3 0.3% USDC -> WETH")
+    async fn route_label(
+        &self,
+        candidate: &RouteCandidate,
+        from_token: Address,
+        to_token: Address,
+    ) -> String {
+        let path = match candidate {
+            RouteCandidate::V2(path) => self.path_label(path).await,
+            RouteCandidate::V3(_) => self.path_label(&[from_token, to_token]).await,
+        };
+
+        match candidate {
+            RouteCandidate::V2(_) => format!("V2 {path}"),
+            RouteCandidate::V3(fee) => {
+                format!("V3 {}% {path}", Decimal::from(*fee) / Decimal::from(10_000))
+            }
+        }
+    }
+
+    /// Quote an execute_swap request on the simulation path without signing or sending it
+    #[instrument(skip(self), err)]
+    async fn dry_run_swap_impl(
+        &self,
+        mut req: SwapTokensRequest,
+    ) -> ServiceResult<SwapTokensResponse> {
+        // Preview only what execute_swap would accept
+        if Self::is_exact_output(req.swap_mode.as_deref())? {
+            return Err(ServiceError::InvalidAmount(
+                "execute_swap only supports exact_input swaps".to_string(),
+            ));
+        }
+
+        // Simulate from the wallet that would send the swap, so gas is estimated for it
+        if req.from_address.is_none() {
+            req.from_address = self
+                .repository
+                .wallet_address()
+                .map(|address| address.to_string());
+        }
+
+        tracing::info!(
+            "Dry run: simulating {} {} -> {} without sending",
+            req.amount,
+            req.from_token,
+            req.to_token
+        );
+        self.swap_tokens_impl(req).await
+    }
+
+    #[instrument(skip(self), err)]
+    async fn execute_swap_impl(
+        &self,
+        req: SwapTokensRequest,
+    ) -> ServiceResult<ExecuteSwapResponse> {
+        if !self.allow_execution {
+            return Err(ServiceError::ExecutionNotAllowed(
+                "swap execution is disabled; set wallet.allow_execution to true to enable it"
+                    .to_string(),
+            ));
+        }
+
+        if Self::is_exact_output(req.swap_mode.as_deref())? {
+            return Err(ServiceError::InvalidAmount(
+                "execute_swap only supports exact_input swaps".to_string(),
+            ));
+        }
+        validate_positive_amount(&req.amount).map_err(ServiceError::InvalidAmount)?;
+
+        let recipient = self.repository.wallet_address().ok_or_else(|| {
+            ServiceError::ExecutionNotAllowed("no wallet private key is configured".to_string())
+        })?;
+
+        let from_token = self.parse_token_address_or_symbol(&req.from_token).await?;
+        let to_token = self.parse_token_address_or_symbol(&req.to_token).await?;
+        let native = Self::native_eth(
+            &req.from_token,
+            &req.to_token,
+            req.unwrap_weth.unwrap_or(false),
+        )?;
+        if native == NativeEth::Unwrap && to_token != self.chain.weth {
+            return Err(ServiceError::InvalidAmount(format!(
+                "unwrap_weth requires WETH ({}) as to_token",
+                self.chain.weth
+            )));
+        }
+        let from_metadata = self.repository.get_token_metadata(from_token).await?;
+        let to_metadata = self.repository.get_token_metadata(to_token).await?;
+        let input_symbol = if native == NativeEth::Wrap {
+            "ETH"
+        } else {
+            from_metadata.symbol.as_str()
+        };
+        let output_symbol = if native == NativeEth::Unwrap {
+            "ETH"
+        } else {
+            to_metadata.symbol.as_str()
+        };
+
+        let amount_in = parse_amount(&req.amount, from_metadata.decimals)
+            .map_err(ServiceError::InvalidAmount)?;
+        let slippage = self.slippage(req.slippage_tolerance.as_deref())?;
+        let deadline = self.deadline();
+
+        let uniswap_version = req
+            .uniswap_version
+            .as_deref()
+            .unwrap_or("v2")
+            .to_lowercase();
+        let dex = Self::parse_v2_dex(req.dex.as_deref(), &uniswap_version)?;
+        let fee_tiers = Self::parse_fee_tier(req.fee_tier, &uniswap_version)?;
+
+        let (amount_out, minimum_output, fee_tier, execution) = match uniswap_version.as_str() {
+            "v2" => {
+                let repository = self.v2_repository(dex)?;
+                let (path, amounts) = self
+                    .find_best_path(dex, from_token, to_token, amount_in)
+                    .await?;
+                let amount_out = amounts.last().copied().unwrap_or_default();
+                if amount_out.is_zero() {
+                    return Err(ServiceError::SwapSimulationFailed(format!(
+                        "Estimated output is 0 {} for {} {}; refusing to send the swap",
+                        to_metadata.symbol, req.amount, from_metadata.symbol
+                    )));
+                }
+                let minimum_output = calculate_minimum_output(amount_out, slippage)?;
+
+                // Pre-flight the exact transaction so reverts (e.g. a missing approval) never cost gas
+                repository
+                    .simulate_swap(
+                        recipient,
+                        amount_in,
+                        minimum_output,
+                        path.clone(),
+                        deadline,
+                        native,
+                    )
+                    .await
+                    .map_err(|e| Self::preflight_error(&e.to_string()))?;
+
+                let execution = repository
+                    .execute_swap(amount_in, minimum_output, path, deadline, native)
+                    .await?;
+                (amount_out, minimum_output, None, execution)
+            }
+            "v3" => {
+                let scan = self
+                    .scan_v3_fee_tiers(from_token, to_token, amount_in, fee_tiers)
+                    .await;
+                let (amount_out, _, fee) =
+                    scan.selected(&from_metadata.symbol, &to_metadata.symbol)?;
+                let minimum_output = calculate_minimum_output(amount_out, slippage)?;
+
+                self.repository
+                    .simulate_v3_swap(
+                        recipient,
+                        from_token,
+                        to_token,
+                        amount_in,
+                        minimum_output,
+                        fee,
+                        deadline,
+                        native,
+                    )
+                    .await
+                    .map_err(|e| Self::preflight_error(&e.to_string()))?;
+
+                let execution = self
+                    .repository
+                    .execute_v3_swap(
+                        from_token,
+                        to_token,
+                        amount_in,
+                        minimum_output,
+                        fee,
+                        deadline,
+                        native,
+                    )
+                    .await?;
+                (amount_out, minimum_output, Some(fee), execution)
+            }
+            _ => {
+                return Err(ServiceError::InvalidAmount(format!(
+                    "Invalid Uniswap version: {}. Must be 'v2' or 'v3'",
+                    uniswap_version
+                )));
+            }
+        };
+
+        let gas_cost_wei =
+            U256::from(execution.gas_used) * U256::from(execution.effective_gas_price);
+        let gas_cost_eth = format_balance(gas_cost_wei, ETH_DECIMALS);
+
+        tracing::info!(
+            "Swap executed: tx={}, success={}, gas_used={}",
+            execution.transaction_hash,
+            execution.success,
+            execution.gas_used
+        );
+
+        let summary = if req.summarize.unwrap_or(false) {
+            Some(format!(
+                "Swapped {} {} → ~{} {} (at least {}) on {}, {}, {} gas, tx {}",
+                format_balance(amount_in, from_metadata.decimals),
+                input_symbol,
+                format_balance(amount_out, to_metadata.decimals),
+                output_symbol,
+                format_balance(minimum_output, to_metadata.decimals),
+                match uniswap_version.as_str() {
+                    "v2" => dex.label().to_string(),
+                    version => format!("Uniswap {}", version.to_uppercase()),
+                },
+                match Self::execution_status(&execution) {
+                    "success" => "confirmed",
+                    status => status,
+                },
+                self.gas_cost_label(&gas_cost_eth).await,
+                execution.transaction_hash
+            ))
+        } else {
+            None
+        };
+
+        Ok(ExecuteSwapResponse {
+            transaction_hash: execution.transaction_hash.to_string(),
+            status: Self::execution_status(&execution).to_string(),
+            block_number: execution.block_number,
+            receipt_error: execution.receipt_error.clone(),
+            uniswap_version,
+            fee_tier,
+            amount_in: format_balance(amount_in, from_metadata.decimals),
+            expected_output: format_balance(amount_out, to_metadata.decimals),
+            minimum_output: format_balance(minimum_output, to_metadata.decimals),
+            minimum_output_raw: minimum_output.to_string(),
+            deadline: deadline.to_string(),
+            recipient: recipient.to_string(),
+            wrapped: native == NativeEth::Wrap,
+            unwrapped: native == NativeEth::Unwrap,
+            gas_used: execution.gas_used.to_string(),
+            effective_gas_price: execution.effective_gas_price.to_string(),
+            gas_cost_eth,
+            summary,
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn detect_rebasing_impl(
+        &self,
+        req: DetectRebasingRequest,
+    ) -> ServiceResult<DetectRebasingResponse> {
+        let token = self.parse_token_address_or_symbol(&req.token).await?;
+        let metadata = self.repository.get_token_metadata(token).await?;
+        let mechanism = self.repository.detect_rebasing(token).await?;
+
+        Ok(DetectRebasingResponse {
+            symbol: metadata.symbol,
+            token_address: token.to_string(),
+            rebasing: mechanism.is_some(),
+            mechanism: mechanism.map(str::to_string),
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_token_info_impl(
+        &self,
+        req: GetTokenInfoRequest,
+    ) -> ServiceResult<GetTokenInfoResponse> {
+        let token = self.parse_token_address_or_symbol(&req.token).await?;
+        let info = self.repository.get_token_info(token).await?;
+
+        Ok(GetTokenInfoResponse {
+            total_supply: format_balance(info.total_supply, info.decimals),
+            total_supply_raw: info.total_supply.to_string(),
+            name: info.name,
+            symbol: info.symbol,
+            decimals: info.decimals,
+            token_address: token.to_string(),
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn convert_units_impl(
+        &self,
+        req: ConvertUnitsRequest,
+    ) -> ServiceResult<ConvertUnitsResponse> {
+        let (from_unit, from_decimals, from_token) = self.resolve_unit(&req.from_unit).await?;
+        let (to_unit, to_decimals, to_token) = self.resolve_unit(&req.to_unit).await?;
+
+        // Only the smallest unit ("wei") is shared between ETH and tokens; anything else would
+        // need a price
+        let same_asset = match (from_token, to_token) {
+            (Some(from), Some(to)) => from == to,
+            (Some(_), None) => to_decimals == 0,
+            (None, Some(_)) => from_decimals == 0,
+            (None, None) => true,
+        };
+        if !same_asset {
+            return Err(ServiceError::InvalidAmount(format!(
+                "Cannot convert {from_unit} to {to_unit}: they are different assets. Use \
+                 get_token_price for exchange rates"
+            )));
+        }
+
+        let (raw, value) = convert_amount(&req.value, from_decimals, to_decimals)?;
+
+        Ok(ConvertUnitsResponse {
+            value: format_decimal(value, None),
+            raw: raw.to_string(),
+            from_unit,
+            to_unit,
+            from_decimals,
+            to_decimals,
+        })
+    }
+
+    /// Label, decimals and token address of a `convert_units` unit; wei, gwei and ether need no
+    /// RPC call
+    async fn resolve_unit(&self, unit: &str) -> ServiceResult<(String, u8, Option<Address>)> {
+        match unit.trim().to_lowercase().as_str() {
+            "wei" => Ok(("wei".to_string(), 0, None)),
+            "gwei" => Ok(("gwei".to_string(), GWEI_DECIMALS, None)),
+            "ether" | "eth" => Ok(("ether".to_string(), ETH_DECIMALS, None)),
+            _ => {
+                let token = self.parse_token_address_or_symbol(unit).await?;
+                let metadata = self.repository.get_token_metadata(token).await?;
+                Ok((metadata.symbol, metadata.decimals, Some(token)))
+            }
+        }
+    }
+
+    #[instrument(skip(self), err)]
+    async fn approve_token_impl(
+        &self,
+        req: ApproveTokenRequest,
+    ) -> ServiceResult<ApproveTokenResponse> {
+        if !self.allow_execution {
+            return Err(ServiceError::ExecutionNotAllowed(
+                "token approval is disabled; set wallet.allow_execution to true to enable it"
+                    .to_string(),
+            ));
+        }
+
+        let owner = self.repository.wallet_address().ok_or_else(|| {
+            ServiceError::ExecutionNotAllowed("no wallet private key is configured".to_string())
+        })?;
+
+        // Only the routers the swap tools use may be approved
+        let spender = self.resolve_spender(req.spender.as_deref())?;
+        if spender != self.chain.uniswap_v2_router
+            && Some(spender) != self.chain.uniswap_v3_swap_router
+        {
+            return Err(ServiceError::InvalidWalletAddress(format!(
+                "approve_token only approves the Uniswap routers (\"v2\" or \"v3\"), got {spender}"
+            )));
+        }
+
+        let token = self.parse_token_address_or_symbol(&req.token).await?;
+        let metadata = self.repository.get_token_metadata(token).await?;
+
+        let unlimited = req.amount.trim().eq_ignore_ascii_case("max");
+        let amount = if unlimited {
+            U256::MAX
+        } else {
+            parse_amount(&req.amount, metadata.decimals).map_err(ServiceError::InvalidAmount)?
+        };
+
+        let execution = self
+            .repository
+            .approve_token(token, spender, amount)
+            .await?;
+        let gas_cost_wei =
+            U256::from(execution.gas_used) * U256::from(execution.effective_gas_price);
+
+        tracing::info!(
+            "Approval sent: tx={}, success={}, token={}, spender={}",
+            execution.transaction_hash,
+            execution.success,
+            metadata.symbol,
+            spender
+        );
+
+        Ok(ApproveTokenResponse {
+            transaction_hash: execution.transaction_hash.to_string(),
+            status: Self::execution_status(&execution).to_string(),
+            block_number: execution.block_number,
+            receipt_error: execution.receipt_error.clone(),
+            symbol: metadata.symbol,
+            token_address: token.to_string(),
+            spender: spender.to_string(),
+            amount: if unlimited {
+                "unlimited".to_string()
+            } else {
+                format_balance(amount, metadata.decimals)
+            },
+            amount_raw: amount.to_string(),
+            owner: owner.to_string(),
+            gas_used: execution.gas_used.to_string(),
+            gas_cost_eth: format_balance(gas_cost_wei, ETH_DECIMALS),
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn build_permit_impl(
+        &self,
+        req: BuildPermitRequest,
+    ) -> ServiceResult<BuildPermitResponse> {
+        let wallet = self.repository.wallet_address();
+        let owner = match req.owner.as_deref() {
+            Some(owner) => self.parse_wallet_address(owner).await?,
+            None => wallet.ok_or_else(|| {
+                ServiceError::InvalidWalletAddress(
+                    "no wallet is configured; pass the owner address".to_string(),
+                )
+            })?,
+        };
+
+        let spender = if req.spender.trim().eq_ignore_ascii_case("permit2") {
+            PERMIT2_ADDRESS
+        } else {
+            self.resolve_spender(Some(req.spender.trim()))?
+        };
+
+        let now = chrono::Utc::now().timestamp().max(0) as u64;
+        let deadline = req.deadline.unwrap_or(now + self.deadline_secs);
+        if deadline <= now {
+            return Err(ServiceError::InvalidAmount(format!(
+                "deadline {deadline} is not in the future (now {now})"
+            )));
+        }
+
+        let token = self.parse_token_address_or_symbol(&req.token).await?;
+        // Fails before anything is signed when the token has no permit support
+        let state = self
+            .repository
+            .get_permit_state(token, owner)
+            .await
+            .map_err(|e| match e {
+                RepositoryError::ContractError(msg) => ServiceError::TokenNotFound(msg),
+                e => e.into(),
+            })?;
+        let metadata = self.repository.get_token_metadata(token).await?;
+
+        let unlimited = req.amount.trim().eq_ignore_ascii_case("max");
+        let value = if unlimited {
+            U256::MAX
+        } else {
+            parse_amount(&req.amount, metadata.decimals).map_err(ServiceError::InvalidAmount)?
+        };
+
+        let digest = permit_digest(
+            state.domain_separator,
+            owner,
+            spender,
+            value,
+            state.nonce,
+            U256::from(deadline),
+        );
+
+        // A signed permit is as good as an approval, so it is only produced under the same
+        // conditions as approve_token, with Permit2 also accepted as the spender
+        let known_spender = spender == PERMIT2_ADDRESS
+            || spender == self.chain.uniswap_v2_router
+            || Some(spender) == self.chain.uniswap_v3_swap_router
+            || Some(spender) == self.chain.sushiswap_router;
+        let unsigned_reason = if wallet != Some(owner) {
+            Some(
+                "the owner is not the configured wallet, so the digest must be signed by the owner",
+            )
+        } else if !self.allow_execution {
+            Some("signing is disabled; set wallet.allow_execution to true to enable it")
+        } else if !known_spender {
+            Some("only permits for Permit2 or the DEX routers are signed")
+        } else {
+            None
+        };
+
+        let signature = match unsigned_reason {
+            Some(_) => None,
+            None => Some(self.repository.sign_hash(digest)?),
+        };
+
+        tracing::info!(
+            "Permit built: token={}, owner={}, spender={}, nonce={}, signed={}",
+            metadata.symbol,
+            owner,
+            spender,
+            state.nonce,
+            signature.is_some()
+        );
+
+        Ok(BuildPermitResponse {
+            symbol: metadata.symbol,
+            token_address: token.to_string(),
+            owner: owner.to_string(),
+            spender: spender.to_string(),
+            value: if unlimited {
+                "unlimited".to_string()
+            } else {
+                format_balance(value, metadata.decimals)
+            },
+            value_raw: value.to_string(),
+            nonce: state.nonce.to_string(),
+            deadline,
+            domain_separator: state.domain_separator.to_string(),
+            digest: digest.to_string(),
+            signed: signature.is_some(),
+            signature: signature.map(|sig| sig.to_string()),
+            v: signature.map(|sig| 27 + u8::from(sig.v())),
+            r: signature.map(|sig| B256::from(sig.r()).to_string()),
+            s: signature.map(|sig| B256::from(sig.s()).to_string()),
+            note: unsigned_reason.map(str::to_string),
+        })
+    }
+
+    #[instrument(skip(self), err)]
+    async fn quote_path_impl(&self, req: QuotePathRequest) -> ServiceResult<QuotePathResponse> {
+        let dex = Self::parse_v2_dex(req.dex.as_deref(), "v2")?;
+        if !(2..=MAX_QUOTE_PATH_TOKENS).contains(&req.path.len()) {
+            return Err(ServiceError::InvalidAmount(format!(
+                "path must list 2 to {MAX_QUOTE_PATH_TOKENS} tokens, got {}",
+                req.path.len()
+            )));
+        }
+        validate_positive_amount(&req.amount).map_err(ServiceError::InvalidAmount)?;
+
+        let mut path = Vec::with_capacity(req.path.len());
+        let mut metadata = Vec::with_capacity(req.path.len());
+        for token in &req.path {
+            let address = self.parse_token_address_or_symbol(token).await?;
+            if path.last() == Some(&address) {
+                return Err(ServiceError::InvalidAmount(format!(
+                    "path lists {token} twice in a row; each hop needs two different tokens"
+                )));
+            }
+            path.push(address);
+            metadata.push(self.repository.get_token_metadata(address).await?);
+        }
+
+        let amount_in =
+            parse_amount(&req.amount, metadata[0].decimals).map_err(ServiceError::InvalidAmount)?;
+        let amounts = self
+            .v2_repository(dex)?
+            .get_swap_amounts_out(amount_in, path.clone())
+            .await?;
+
+        let formatted: Vec<String> = amounts
+            .iter()
+            .zip(&metadata)
+            .map(|(amount, token)| format_balance(*amount, token.decimals))
+            .collect();
+        Ok(QuotePathResponse {
+            dex: dex.label().to_string(),
+            path: path.iter().map(Address::to_string).collect(),
+            symbols: metadata.into_iter().map(|token| token.symbol).collect(),
+            amount_out: formatted.last().cloned().unwrap_or_default(),
+            amounts: formatted,
+            amounts_raw: amounts.iter().map(U256::to_string).collect(),
+        })
+    }
+
+    /// Quote the given Uniswap V3 fee tiers and keep the one with the highest output
+    async fn scan_v3_fee_tiers(
+        &self,
+        from_token: Address,
+        to_token: Address,
+        amount_in: U256,
+        fee_tiers: &[u32],
+    ) -> V3TierScan {
+        self.scan_v3_quotes(
+            from_token,
+            to_token,
+            V3QuoteAmount::ExactInput(amount_in),
+            fee_tiers,
+        )
+        .await
+    }
+
+    /// Quote the given Uniswap V3 fee tiers and keep the best one for the fixed side of the trade
+    #[instrument(skip(self))]
+    async fn scan_v3_quotes(
+        &self,
+        from_token: Address,
+        to_token: Address,
+        amount: V3QuoteAmount,
+        fee_tiers: &[u32],
+    ) -> V3TierScan {
+        let mut best_quote: Option<(U256, u64, u32)> = None;
+        let mut tier_results: Vec<(u32, Option<U256>)> = Vec::with_capacity(fee_tiers.len());
+        let mut rpc_error = None;
+
+        for &fee in fee_tiers {
+            let mut attempts = 0;
+            let quote = loop {
+                attempts += 1;
+                let result = match amount {
+                    V3QuoteAmount::ExactInput(amount_in) => {
+                        self.repository
+                            .get_v3_quote(from_token, to_token, amount_in, fee)
+                            .await
+                    }
+                    V3QuoteAmount::ExactOutput(amount_out) => {
+                        self.repository
+                            .get_v3_quote_exact_output(from_token, to_token, amount_out, fee)
+                            .await
+                    }
+                };
+                match result {
+                    // A revert means the pool is missing or cannot fill the trade, and an open
+                    // circuit fails fast until its cooldown ends; retrying won't help either way
+                    Err(e)
+                        if !is_revert_error(&e)
+                            && !matches!(e, RepositoryError::CircuitOpen(_))
+                            && attempts <= self.v3_scan_retries =>
+                    {
+                        tracing::warn!(
+                            "V3 quote for fee tier {} failed at the RPC level (attempt {}): {}",
+                            fee,
+                            attempts,
+                            e
+                        );
+                        tokio::time::sleep(V3_SCAN_RETRY_DELAY * attempts).await;
+                    }
+                    result => break result,
+                }
+            };
+
+            match quote {
+                Ok((quoted, gas_estimate)) => {
+                    tracing::info!(
+                        "V3 quote for fee tier {}: {:?} -> {}, gas={}",
+                        fee,
+                        amount,
+                        quoted,
+                        gas_estimate
+                    );
+                    tier_results.push((fee, Some(quoted)));
+
+                    if !quoted.is_zero() {
+                        // Keep track of the best quote: highest output, or lowest required input
+                        let better = best_quote.is_none_or(|(best, _, _)| match amount {
+                            V3QuoteAmount::ExactInput(_) => quoted > best,
+                            V3QuoteAmount::ExactOutput(_) => quoted < best,
+                        });
+                        if better {
+                            best_quote = Some((quoted, gas_estimate, fee));
+                        }
+                    }
+                }
+                Err(e) if is_revert_error(&e) => {
+                    tracing::debug!("V3 quote reverted for fee tier {}: {}", fee, e);
+                    tier_results.push((fee, None));
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "V3 quote for fee tier {} failed after {} attempts: {}",
+                        fee,
+                        attempts,
+                        e
+                    );
+                    tier_results.push((fee, None));
+                    rpc_error = Some(e);
+                }
+            }
+        }
+
+        V3TierScan {
+            best: best_quote,
+            results: tier_results,
+            rpc_error,
+        }
+    }
+
+    /// Error returned when none of the scanned V3 fee tiers has liquidity for a pair
+    fn no_v3_pool_error(from_symbol: &str, to_symbol: &str, fee_tiers: &[u32]) -> ServiceError {
+        let percent = |fee: &u32| format!("{}%", Decimal::new(i64::from(*fee), 4).normalize());
+        let scanned = match fee_tiers {
+            [fee] => format!("in the {} pool", percent(fee)),
+            _ => {
+                let tiers: Vec<String> = fee_tiers.iter().map(percent).collect();
+                format!("across the fee tiers {}", tiers.join(", "))
+            }
+        };
+        ServiceError::SwapSimulationFailed(format!(
+            "No V3 liquidity pool found for {}/{} pair {scanned}.\n\
+             \n\
+             Suggestions:\n\
+             - Try using V2 instead (set uniswap_version to 'v2')\n\
+             - Use a different token pair\n\
+             - Try routing through WETH (e.g., {} -> WETH -> {})",
+            from_symbol, to_symbol, from_symbol, to_symbol
+        ))
+    }
+
+    /// Error returned when the eth_call pre-flight of a real swap fails
+    fn preflight_error(reason: &str) -> ServiceError {
+        ServiceError::SwapSimulationFailed(format!(
+            "Pre-flight simulation failed, the transaction was not sent: {reason}\n\
+             \n\
+             Check that the wallet holds enough tokens and that the router is approved (see check_allowance)."
+        ))
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_price_from_uniswap(
+        &self,
+        token: Address,
+        weth: Address,
+        block: Option<u64>,
+    ) -> ServiceResult<(Decimal, Option<Decimal>, u64)> {
+        // Get ETH/USD price from USDC/WETH Uniswap pair; the ETH price stands without it
+        let eth_usd = self.eth_usd_price(block).await;
+        let (price_eth, updated_at) = self
+            .token_price_in_eth(token, weth, eth_usd.as_ref().ok().copied(), block)
+            .await?;
+
+        let price_usd = match eth_usd {
+            Ok(eth_price_usd) => Some(price_eth * eth_price_usd),
+            Err(e) => {
+                tracing::warn!("ETH/USD unavailable, pricing {token} in ETH only: {e}");
+                None
+            }
+        };
+
+        Ok((price_eth, price_usd, updated_at))
+    }
+
+    /// Address and symbol of the token a price is quoted in
+    async fn resolve_quote_token(&self, quote: &str) -> ServiceResult<(Address, String)> {
+        let address = self.parse_token_address_or_symbol(quote.trim()).await?;
+        if address == self.chain.weth && Self::is_native_eth(quote) {
+            return Ok((address, "ETH".to_string()));
+        }
+        let symbol = match self.token_registry.lookup_symbol(&address.to_string()) {
+            Some(symbol) => symbol.to_string(),
+            None => self.repository.get_token_metadata(address).await?.symbol,
+        };
+        Ok((address, symbol))
+    }
+
+    /// Price of a quote token in ETH on `source`, or which pool it lacks to be priced
+    async fn quote_price_in_eth(
+        &self,
+        quote: Address,
+        quote_symbol: &str,
+        source: PriceSource,
+        block: Option<u64>,
+    ) -> ServiceResult<Decimal> {
+        let weth = self.chain.weth;
+        if quote == weth {
+            return Ok(Decimal::ONE);
+        }
+        let price = match source {
+            PriceSource::V3 => self
+                .source_quote(PriceSource::V3, quote)
+                .await
+                .map(|quote| quote.price_eth),
+            PriceSource::Chainlink => self
+                .chainlink_token_price(quote, quote_symbol, block)
+                .await
+                .map(|(price_eth, _, _)| price_eth),
+            _ => self
+                .token_price_in_eth(quote, weth, None, block)
+                .await
+                .map(|(price, _)| price),
+        };
+        price.map_err(|e| match e {
+            ServiceError::BlockchainError(msg) if msg.contains("No Uniswap V2 pair") => {
+                ServiceError::LiquidityPoolNotFound {
+                    token0: quote_symbol.to_string(),
+                    token1: "WETH".to_string(),
+                }
+            }
+            ServiceError::SwapSimulationFailed(_) if source == PriceSource::V3 => {
+                ServiceError::LiquidityPoolNotFound {
+                    token0: quote_symbol.to_string(),
+                    token1: "WETH (Uniswap V3)".to_string(),
+                }
+            }
+            e => e,
+        })
+    }
+
+    /// Feeds from `chainlink.feeds` by token address; entries that do not resolve are skipped
+    fn resolve_chainlink_feeds(
+        &self,
+        feeds: &HashMap<String, String>,
+    ) -> HashMap<Address, Address> {
+        feeds
+            .iter()
+            .filter_map(|(token, feed)| {
+                let address = Address::from_str(token.trim()).ok().or_else(|| {
+                    self.token_registry
+                        .lookup(token.trim())
+                        .and_then(|address| Address::from_str(address).ok())
+                });
+                match (address, Address::from_str(feed.trim())) {
+                    (Some(address), Ok(feed)) => Some((address, feed)),
+                    (None, _) => {
+                        tracing::warn!(
+                            "Ignoring Chainlink feed: {token} is neither an address nor a known \
+                             symbol"
+                        );
+                        None
+                    }
+                    (_, Err(e)) => {
+                        tracing::warn!("Ignoring Chainlink feed {feed} for {token}: {e}");
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Latest answer of a Chainlink feed and when it was updated; current rounds older than
+    /// `chainlink.max_age_secs` are rejected as stale
+    async fn chainlink_price(
+        &self,
+        feed: Address,
+        pair: &str,
+        block: Option<u64>,
+    ) -> ServiceResult<(Decimal, u64)> {
+        let (price, updated_at) = self.repository.get_chainlink_price(feed, block).await?;
+
+        // A round read at a past block is judged by that block's time, which is not known here
+        let age = chrono::Utc::now().timestamp() - updated_at as i64;
+        if block.is_none() && age > self.chainlink_max_age_secs as i64 {
+            return Err(ServiceError::StalePrice(format!(
+                "the Chainlink {pair} feed last updated {age}s ago, more than \
+                 chainlink.max_age_secs ({}s)",
+                self.chainlink_max_age_secs
+            )));
+        }
+        Ok((price, updated_at))
+    }
+
+    /// ETH/USD from the network's Chainlink feed, with when it was updated
+    async fn chainlink_eth_usd(&self, block: Option<u64>) -> ServiceResult<(Decimal, u64)> {
+        let feed = self.chain.chainlink_eth_usd.ok_or_else(|| {
+            ServiceError::InternalError(format!(
+                "No Chainlink ETH/USD feed is known on {}",
+                self.network.as_str()
+            ))
+        })?;
+        self.chainlink_price(feed, "ETH/USD", block).await
+    }
+
+    /// ETH and USD price of a token from Chainlink, with when its USD feed was updated
+    ///
+    /// The token's USD feed comes from `chainlink.feeds`; WETH uses the ETH/USD feed.
+    async fn chainlink_token_price(
+        &self,
+        token: Address,
+        symbol: &str,
+        block: Option<u64>,
+    ) -> ServiceResult<(Decimal, Decimal, u64)> {
+        if token == self.chain.weth && !self.chainlink_feeds.contains_key(&token) {
+            let (eth_usd, updated_at) = self.chainlink_eth_usd(block).await?;
+            return Ok((Decimal::ONE, eth_usd, updated_at));
+        }
+
+        let feed = self.chainlink_feeds.get(&token).ok_or_else(|| {
+            ServiceError::TokenNotFound(format!(
+                "{symbol} has no Chainlink feed; add its USD feed to chainlink.feeds"
+            ))
+        })?;
+        let (price_usd, updated_at) = self
+            .chainlink_price(*feed, &format!("{symbol}/USD"), block)
+            .await?;
+        let (eth_usd, _) = self.chainlink_eth_usd(block).await?;
+        let price_eth = price_usd.checked_div(eth_usd).ok_or_else(|| {
+            ServiceError::InternalError("Chainlink ETH/USD answered zero".to_string())
+        })?;
+        Ok((price_eth, price_usd, updated_at))
+    }
+
+    /// Where the configured `eth_price_source` reads ETH/USD, for explanations
+    fn eth_usd_source_label(&self) -> &'static str {
+        match self.eth_price_source {
+            EthPriceSource::UniswapV2 => "the Uniswap V2 USDC/WETH pair reserves",
+            EthPriceSource::UniswapV3 => "the Uniswap V3 USDC/WETH 0.05% pool",
+            EthPriceSource::Chainlink => "the Chainlink ETH/USD feed",
+        }
+    }
+
+    /// ETH/USD from the configured `eth_price_source`
+    async fn eth_usd_price(&self, block: Option<u64>) -> ServiceResult<Decimal> {
+        match self.eth_price_source {
+            EthPriceSource::UniswapV2 => Ok(self.repository.get_eth_usd_price(block).await?),
+            _ => Ok(self.eth_usd_price_with_update(block).await?.0),
+        }
+    }
+
+    /// ETH/USD from the configured `eth_price_source`, with when the Uniswap V2 USDC/WETH
+    /// reserves last changed if they are the source
+    async fn eth_usd_price_with_update(
+        &self,
+        block: Option<u64>,
+    ) -> ServiceResult<(Decimal, Option<u64>)> {
+        match self.eth_price_source {
+            EthPriceSource::UniswapV2 => {}
+            EthPriceSource::UniswapV3 => {
+                if block.is_some() {
+                    return Err(ServiceError::InvalidAmount(
+                        "eth_price_source uniswap_v3 only prices the latest block; historical \
+                         USD prices need uniswap_v2 or chainlink"
+                            .to_string(),
+                    ));
+                }
+                let pool = self
+                    .repository
+                    .get_v3_pool_state(self.chain.usdc, self.chain.weth, ETH_USD_V3_FEE)
+                    .await?;
+                let price = v3_spot_price(
+                    pool.sqrt_price_x96,
+                    pool.token0 == self.chain.weth,
+                    ETH_DECIMALS,
+                    USDC_DECIMALS,
+                )?;
+                return Ok((price, None));
+            }
+            EthPriceSource::Chainlink => {
+                let (price, _) = self.chainlink_eth_usd(block).await?;
+                return Ok((price, None));
+            }
+        }
+
+        let reserves = self
+            .repository
+            .get_uniswap_pair_state(self.chain.usdc, self.chain.weth, block)
+            .await?;
+        if reserves.reserve_a.is_zero() || reserves.reserve_b.is_zero() {
+            return Err(ServiceError::InsufficientLiquidity(
+                "No liquidity in USDC/WETH pair".to_string(),
+            ));
+        }
+
+        let price = calculate_price(
+            reserves.reserve_a,
+            reserves.reserve_b,
+            USDC_DECIMALS,
+            ETH_DECIMALS,
+        )?;
+        Ok((price, Some(reserves.updated_at)))
+    }
+
+    /// Price of a token in ETH from its Uniswap V2 WETH pair reserves, with when they last changed
+    ///
+    /// Pairs shallower than `trading.min_liquidity_usd` are rejected; `eth_usd` is the ETH/USD
+    /// price to measure them with when the caller already has it.
+    async fn token_price_in_eth(
+        &self,
+        token: Address,
+        weth: Address,
+        eth_usd: Option<Decimal>,
+        block: Option<u64>,
+    ) -> ServiceResult<(Decimal, u64)> {
+        // Token decimals, from the token list when known, otherwise from the contract
+        let token_decimals = match self.token_registry.decimals(&token.to_string()) {
+            Some(decimals) => decimals,
+            None => self.repository.get_token_metadata(token).await?.decimals,
+        };
+
+        // Query Uniswap V2 Factory to get the pair address and reserves
+        let reserves = self
+            .repository
+            .get_uniswap_pair_state(token, weth, block)
+            .await?;
+        let (reserve_token, reserve_weth) = (reserves.reserve_a, reserves.reserve_b);
+
+        // Check if reserves are valid
+        if reserve_token.is_zero() || reserve_weth.is_zero() {
+            return Err(ServiceError::InsufficientLiquidity(format!(
+                "No liquidity in Uniswap pair for token {token} and WETH"
+            )));
+        }
+
+        // Calculate price in ETH using precise decimal arithmetic
+        // Use actual token decimals (e.g., 6 for USDC, 18 for most others)
+        let price = calculate_price(reserve_weth, reserve_token, 18, token_decimals)?;
+        self.check_min_liquidity(token, reserve_weth, eth_usd, block)
+            .await?;
+        Ok((price, reserves.updated_at))
+    }
+
+    /// Rejects a WETH pair holding less than `trading.min_liquidity_usd`, whose price is too easy
+    /// to manipulate
+    ///
+    /// ETH/USD is read when `eth_usd` is `None`; if it cannot be, the pair is rejected as well,
+    /// since its depth cannot be checked.
+    async fn check_min_liquidity(
+        &self,
+        token: Address,
+        reserve_weth: U256,
+        eth_usd: Option<Decimal>,
+        block: Option<u64>,
+    ) -> ServiceResult<()> {
+        let Some(min_liquidity) = self.min_liquidity_usd else {
+            return Ok(());
+        };
+        let eth_usd = match eth_usd {
+            Some(eth_usd) => eth_usd,
+            None => self.eth_usd_price(block).await.map_err(|e| {
+                ServiceError::InsufficientLiquidity(format!(
+                    "Cannot check the Uniswap V2 pair of {token} and WETH against \
+                     trading.min_liquidity_usd (${min_liquidity}): ETH/USD is unavailable ({e})"
+                ))
+            })?,
+        };
 
-        // Try different fee tiers for V3 (0.05%, 0.3%, 1%)
-        // Most common is 0.3% (3000), but we'll try all three
-        let fee_tiers = [3000u32, 500u32, 10000u32];
-        let mut best_quote: Option<(U256, u64, u32)> = None;
+        let liquidity = calculate_pool_liquidity_usd(reserve_weth, eth_usd)?;
+        if liquidity < min_liquidity {
+            return Err(ServiceError::InsufficientLiquidity(format!(
+                "Uniswap V2 pair of {token} and WETH holds about ${} of liquidity, below \
+                 trading.min_liquidity_usd (${min_liquidity}); its price is too easy to manipulate",
+                liquidity.round_dp(2)
+            )));
+        }
+        Ok(())
+    }
 
-        for fee in fee_tiers {
-            match self
-                .repository
-                .get_v3_quote(from_token, to_token, amount_in, fee)
-                .await
-            {
-                Ok((amount_out, gas_estimate)) => {
-                    tracing::info!(
-                        "V3 quote for fee tier {}: amount_out={}, gas={}",
-                        fee,
-                        amount_out,
-                        gas_estimate
-                    );
+    /// Current price of a token on one Uniswap version, with the depth of the pool behind it
+    ///
+    /// WETH's depth is measured on its USDC pool, whose price also gives ETH/USD.
+    async fn source_quote(
+        &self,
+        source: PriceSource,
+        token: Address,
+    ) -> ServiceResult<SourceQuote> {
+        let weth = self.chain.weth;
+        let usdc = self.chain.usdc;
+        let reference = if token == weth { usdc } else { token };
+        let (price_eth, fee_tier, probe_slippage) = self.pool_depth(source, reference).await?;
 
-                    if !amount_out.is_zero() {
-                        // Keep track of the best quote (highest output)
-                        if best_quote.is_none() || amount_out > best_quote.as_ref().unwrap().0 {
-                            best_quote = Some((amount_out, gas_estimate, fee));
-                        }
-                    }
-                }
-                Err(e) => {
-                    tracing::debug!("V3 quote failed for fee tier {}: {}", fee, e);
-                }
-            }
+        if token == weth {
+            return Ok(SourceQuote {
+                price_eth: Decimal::ONE,
+                price_usd: Decimal::ONE / price_eth,
+                fee_tier,
+                probe_slippage,
+            });
         }
 
-        // Check if we got any valid quote
-        let (amount_out, gas_estimate, selected_fee) = best_quote.ok_or_else(|| {
-            ServiceError::SwapSimulationFailed(format!(
-                "No V3 liquidity pool found for {}/{} pair across all fee tiers (0.05%, 0.3%, 1%).\n\
-                 \n\
-                 Suggestions:\n\
-                 - Try using V2 instead (set uniswap_version to 'v2')\n\
-                 - Use a different token pair\n\
-                 - Try routing through WETH (e.g., {} -> WETH -> {})",
-                from_metadata.symbol,
-                to_metadata.symbol,
-                from_metadata.symbol,
-                to_metadata.symbol
-            ))
-        })?;
+        let eth_usd = match source {
+            PriceSource::V3 => Decimal::ONE / self.pool_depth(PriceSource::V3, usdc).await?.0,
+            _ => self.repository.get_eth_usd_price(None).await?,
+        };
+        Ok(SourceQuote {
+            price_eth,
+            price_usd: price_eth * eth_usd,
+            fee_tier,
+            probe_slippage,
+        })
+    }
 
-        tracing::info!(
-            "Selected V3 pool with fee tier {} ({}%)",
-            selected_fee,
-            selected_fee as f64 / 10000.0
-        );
+    /// Spot price in ETH of a token's WETH pool on one Uniswap version, the V3 fee tier used,
+    /// and how much worse a 1 WETH buy fills than a 0.01 WETH one
+    async fn pool_depth(
+        &self,
+        source: PriceSource,
+        token: Address,
+    ) -> ServiceResult<(Decimal, Option<u32>, Decimal)> {
+        let weth = self.chain.weth;
+        let decimals = match self.token_registry.decimals(&token.to_string()) {
+            Some(decimals) => decimals,
+            None => self.repository.get_token_metadata(token).await?.decimals,
+        };
+        let small_in = U256::from(PRICE_QUOTE_WEI);
+        let large_in = U256::from(DEPTH_PROBE_WEI);
 
-        let minimum_output = calculate_minimum_output(amount_out, slippage);
+        if source == PriceSource::V3 {
+            let scan = self
+                .scan_v3_fee_tiers(weth, token, small_in, &V3_FEE_TIERS)
+                .await;
+            let (small_out, _, fee) = scan.selected("WETH", &token.to_string())?;
+            let (large_out, _) = self
+                .repository
+                .get_v3_quote(weth, token, large_in, fee)
+                .await?;
 
-        // For V3, we can't easily get reserves for price impact calculation
-        // So we'll estimate it based on the output amount vs ideal constant product formula
-        // For now, we'll use a simplified calculation or mark it as "N/A"
-        let price_impact = "N/A (V3)".to_string();
+            // Quotes are net of the pool fee; add it back so the price compares to V2's reserve ratio
+            let fee_share = Decimal::from(fee) / Decimal::from(1_000_000);
+            let price_eth = calculate_price(small_in, small_out, ETH_DECIMALS, decimals)?
+                * (Decimal::ONE - fee_share);
+            let slippage = calculate_probe_slippage(small_in, small_out, large_in, large_out)?;
+            return Ok((price_eth, Some(fee), slippage));
+        }
 
-        // Estimate gas cost
-        let (estimated_gas, gas_cost_eth) = if let Some(addr_str) = &req.from_address {
-            let from_address = Address::from_str(addr_str)
-                .map_err(|e| ServiceError::InvalidWalletAddress(e.to_string()))?;
-            let deadline = U256::from(chrono::Utc::now().timestamp() + 3600);
+        let (reserve_token, reserve_weth, _, _) = self
+            .repository
+            .get_uniswap_pair_reserves(token, weth, None)
+            .await?;
+        if reserve_token.is_zero() || reserve_weth.is_zero() {
+            return Err(ServiceError::InsufficientLiquidity(format!(
+                "No liquidity in Uniswap pair for token {token} and WETH"
+            )));
+        }
 
-            match self
-                .repository
-                .simulate_v3_swap(
-                    from_address,
-                    from_token,
-                    to_token,
-                    amount_in,
-                    minimum_output,
-                    selected_fee,
-                    deadline,
-                )
-                .await
-            {
-                Ok(gas) => self.format_gas_cost(gas).await?,
-                Err(_) => {
-                    // Use the gas estimate from the quote
-                    self.format_gas_cost(gas_estimate).await?
+        let price_eth = calculate_price(reserve_weth, reserve_token, ETH_DECIMALS, decimals)?;
+        let (small_out, _, _) = simulate_v2_swap(small_in, reserve_weth, reserve_token)?;
+        let (large_out, _, _) = simulate_v2_swap(large_in, reserve_weth, reserve_token)?;
+        let slippage = calculate_probe_slippage(small_in, small_out, large_in, large_out)?;
+        Ok((price_eth, None, slippage))
+    }
+
+    /// Price a token on both Uniswap versions and pick the one with the lower probe slippage
+    async fn compare_price_sources(
+        &self,
+        token: Address,
+    ) -> ServiceResult<(SourceQuote, PriceSource, PriceSourceComparison)> {
+        let mut quotes = vec![];
+        let mut unavailable = vec![];
+        let mut last_error = None;
+        for source in [PriceSource::V2, PriceSource::V3] {
+            match self.source_quote(source, token).await {
+                Ok(quote) => quotes.push((source, quote)),
+                Err(e) => {
+                    tracing::warn!("{} price unavailable for {token}: {e}", source.label());
+                    unavailable.push(format!("{}: {e}", source.label()));
+                    last_error = Some(e);
                 }
             }
-        } else {
-            // Use the gas estimate from the quote
-            self.format_gas_cost(gas_estimate).await?
-        };
+        }
 
-        let exchange_rate = calculate_exchange_rate(
-            amount_in,
-            amount_out,
-            from_metadata.decimals,
-            to_metadata.decimals,
-        );
+        let spread_percent = match quotes.as_slice() {
+            [(_, v2), (_, v3)] if !v2.price_eth.is_zero() => Some(format_decimal(
+                (v3.price_eth - v2.price_eth) / v2.price_eth * Decimal::from(100),
+                Some(4),
+            )),
+            _ => None,
+        };
+        let sources = quotes
+            .iter()
+            .map(|(source, quote)| SourcePrice {
+                source: source.label().to_string(),
+                price_eth: self.display(quote.price_eth),
+                price_usd: self.display(quote.price_usd),
+                fee_tier: quote.fee_tier,
+                probe_slippage: format_decimal(quote.probe_slippage, Some(4)),
+            })
+            .collect();
 
-        tracing::info!(
-            "V3 swap simulation complete: fee={}%, output={}, gas={}",
-            selected_fee as f64 / 10000.0,
-            format_balance(amount_out, to_metadata.decimals),
-            estimated_gas
-        );
+        let Some((chosen, quote)) = quotes
+            .into_iter()
+            .min_by(|(_, a), (_, b)| a.probe_slippage.cmp(&b.probe_slippage))
+        else {
+            return Err(last_error.unwrap_or_else(|| {
+                ServiceError::InsufficientLiquidity(format!("No Uniswap price for {token}"))
+            }));
+        };
 
-        Ok(SwapTokensResponse {
-            estimated_output: format_balance(amount_out, to_metadata.decimals),
-            estimated_output_raw: amount_out.to_string(),
-            minimum_output: format_balance(minimum_output, to_metadata.decimals),
-            estimated_gas,
-            estimated_gas_eth: gas_cost_eth,
-            price_impact,
-            exchange_rate,
-            transaction_data: format!(
-                "Swap simulation (V3, fee={}): {from_token} -> {to_token}",
-                selected_fee
-            ),
-        })
+        Ok((
+            quote,
+            chosen,
+            PriceSourceComparison {
+                sources,
+                unavailable,
+                more_liquid: chosen.label().to_string(),
+                spread_percent,
+            },
+        ))
     }
 
-    #[instrument(skip(self), err)]
-    async fn get_price_from_uniswap(
-        &self,
-        token: Address,
-        weth: Address,
-    ) -> ServiceResult<(String, String)> {
-        // Get token metadata to know its decimals
-        let token_metadata = self.repository.get_token_metadata(token).await?;
+    /// Price of 1 ETH in `token`, from the reserves of its Uniswap V2 WETH pair
+    async fn eth_price_in(&self, token: Address) -> ServiceResult<Decimal> {
+        let decimals = match self.token_registry.decimals(&token.to_string()) {
+            Some(decimals) => decimals,
+            None => self.repository.get_token_metadata(token).await?.decimals,
+        };
 
-        // Query Uniswap V2 Factory to get the pair address and reserves
         let (reserve_token, reserve_weth, _, _) = self
             .repository
-            .get_uniswap_pair_reserves(token, weth)
+            .get_uniswap_pair_reserves(token, self.chain.weth, None)
             .await?;
-
-        // Check if reserves are valid
         if reserve_token.is_zero() || reserve_weth.is_zero() {
             return Err(ServiceError::InsufficientLiquidity(format!(
                 "No liquidity in Uniswap pair for token {token} and WETH"
             )));
         }
 
-        // Calculate price in ETH using precise decimal arithmetic
-        // Use actual token decimals (e.g., 6 for USDC, 18 for most others)
-        let price_eth = calculate_price(reserve_weth, reserve_token, 18, token_metadata.decimals)?;
+        calculate_price(reserve_token, reserve_weth, decimals, ETH_DECIMALS)
+    }
+
+    /// Current USD price of a token, via its Uniswap V2 WETH pair
+    #[instrument(skip(self), err)]
+    async fn get_token_usd_price(&self, token: Address) -> ServiceResult<Decimal> {
+        let weth_address = self.chain.weth;
+
+        if token == weth_address {
+            return self.eth_usd_price(None).await;
+        }
+
+        let eth_price_usd = self.eth_usd_price(None).await?;
+        let (price_eth, _) = self
+            .token_price_in_eth(token, weth_address, Some(eth_price_usd), None)
+            .await?;
+        Ok(price_eth * eth_price_usd)
+    }
+
+    /// Format a price, rate or USD value for responses using the configured display precision
+    fn display(&self, value: Decimal) -> String {
+        format_decimal(value, self.display_precision)
+    }
 
-        // Get ETH/USD price from USDC/WETH Uniswap pair
-        let eth_price_usd = self.repository.get_eth_usd_price().await?;
-        let price_usd = price_eth * eth_price_usd;
+    /// Slippage tolerance of a swap request, falling back to `trading.default_slippage`
+    fn slippage(&self, requested: Option<&str>) -> ServiceResult<Decimal> {
+        parse_slippage(requested.unwrap_or(&self.default_slippage))
+            .map_err(ServiceError::InvalidAmount)
+    }
 
-        Ok((price_eth.to_string(), price_usd.to_string()))
+    /// Deadline for a swap submitted now, `trading.deadline_secs` ahead
+    fn deadline(&self) -> U256 {
+        U256::from(chrono::Utc::now().timestamp()) + U256::from(self.deadline_secs)
     }
 
     /// Parse token address or symbol (supports both addresses and token symbols like "USDT", "ETH", etc.)
     #[instrument(skip(self), err)]
     async fn parse_token_address_or_symbol(&self, token: &str) -> ServiceResult<Address> {
-        // First try to parse as an address
+        // First try to parse as an address; the native ETH placeholder trades as WETH
         if let Ok(addr) = Address::from_str(token) {
+            if addr == NATIVE_ETH_ADDRESS {
+                return Ok(self.chain.weth);
+            }
             return Ok(addr);
         }
 
+        // ENS names resolve on-chain
+        if Self::is_ens_name(token) {
+            return Ok(self.repository.resolve_ens(token).await?);
+        }
+
         // If not a valid address, try to lookup as a symbol
         let address_str = self.lookup_token_address(token)?;
         Address::from_str(&address_str)
             .map_err(|e| ServiceError::InvalidWalletAddress(e.to_string()))
     }
 
+    /// Parse a wallet address, resolving ENS names (e.g. "vitalik.eth") on-chain
+    async fn parse_wallet_address(&self, wallet: &str) -> ServiceResult<Address> {
+        match Address::from_str(wallet) {
+            Ok(address) => Ok(address),
+            Err(_) if Self::is_ens_name(wallet) => {
+                self.repository.resolve_ens(wallet).await.map_err(|e| {
+                    ServiceError::InvalidWalletAddress(format!(
+                        "Could not resolve ENS name {wallet}: {e}"
+                    ))
+                })
+            }
+            Err(e) => Err(ServiceError::InvalidWalletAddress(e.to_string())),
+        }
+    }
+
+    /// Whether a swap token refers to native ETH rather than an ERC20: "ETH" or the placeholder
+    /// address. Both resolve to WETH, which the pools trade.
+    fn is_native_eth(token: &str) -> bool {
+        let token = token.trim();
+        token.eq_ignore_ascii_case("ETH")
+            || Address::from_str(token).is_ok_and(|addr| addr == NATIVE_ETH_ADDRESS)
+    }
+
+    /// How native ETH enters or leaves a swap: a native `from_token` is sent as value and
+    /// wrapped by the router, a native `to_token` (or `unwrap_weth`) is unwrapped on delivery
+    fn native_eth(from_token: &str, to_token: &str, unwrap_weth: bool) -> ServiceResult<NativeEth> {
+        let wrap = Self::is_native_eth(from_token);
+        let unwrap = Self::is_native_eth(to_token) || unwrap_weth;
+        match (wrap, unwrap) {
+            (true, true) => Err(ServiceError::InvalidAmount(
+                "Cannot swap native ETH for native ETH".to_string(),
+            )),
+            (true, false) => Ok(NativeEth::Wrap),
+            (false, true) => Ok(NativeEth::Unwrap),
+            (false, false) => Ok(NativeEth::None),
+        }
+    }
+
+    /// Native ETH balance of a wallet
+    async fn eth_balance(&self, owner: Address) -> ServiceResult<GetBalanceResponse> {
+        let balance = self.repository.get_eth_balance(owner).await?;
+
+        Ok(GetBalanceResponse {
+            balance: balance.to_string(),
+            formatted_balance: format_balance_rounded(balance, ETH_DECIMALS, self.balance_decimals),
+            decimals: ETH_DECIMALS,
+            symbol: "ETH".to_string(),
+            summary: None,
+        })
+    }
+
+    /// ERC20 balance of a wallet, labelled with the registry symbol for known tokens
+    async fn erc20_balance(
+        &self,
+        token: Address,
+        owner: Address,
+    ) -> ServiceResult<GetBalanceResponse> {
+        let token_balance = self.repository.get_erc20_balance(token, owner).await?;
+        Ok(self.token_balance_response(token, token_balance))
+    }
+
+    /// Format an ERC20 balance, preferring the registry symbol for known tokens
+    fn token_balance_response(
+        &self,
+        token: Address,
+        token_balance: TokenBalance,
+    ) -> GetBalanceResponse {
+        let symbol = self
+            .token_registry
+            .lookup_symbol(&token.to_string())
+            .map(str::to_string)
+            .unwrap_or(token_balance.symbol);
+
+        GetBalanceResponse {
+            balance: token_balance.balance.to_string(),
+            formatted_balance: format_balance_rounded(
+                token_balance.balance,
+                token_balance.decimals,
+                self.balance_decimals,
+            ),
+            decimals: token_balance.decimals,
+            symbol,
+            summary: None,
+        }
+    }
+
+    /// Label a decoded router swap with token symbols and formatted amounts
+    async fn describe_swap(&self, intent: &SwapIntent) -> DecodedSwap {
+        let (from_token, amount_in) = self
+            .label_token_amount(intent.token_in, intent.amount_in)
+            .await;
+        let (to_token, amount_out) = self
+            .label_token_amount(intent.token_out, intent.amount_out)
+            .await;
+
+        DecodedSwap {
+            uniswap_version: intent.uniswap_version.to_string(),
+            from_token,
+            to_token,
+            amount_in,
+            amount_out,
+            exact_output: intent.exact_output,
+        }
+    }
+
+    /// Symbol and formatted amount of a token, falling back to its address and the raw
+    /// amount when the contract has no readable metadata
+    async fn label_token_amount(&self, token: Address, amount: U256) -> (String, String) {
+        match self.repository.get_token_metadata(token).await {
+            Ok(metadata) => (
+                self.token_registry
+                    .lookup_symbol(&token.to_string())
+                    .map(str::to_string)
+                    .unwrap_or(metadata.symbol),
+                format_balance(amount, metadata.decimals),
+            ),
+            Err(e) => {
+                tracing::debug!("No metadata for {token}: {e}");
+                (token.to_string(), amount.to_string())
+            }
+        }
+    }
+
+    /// Whether the input looks like an ENS name rather than a token symbol
+    fn is_ens_name(token: &str) -> bool {
+        token.contains('.') && token.to_lowercase().ends_with(".eth")
+    }
+
+    /// Look up the symbol of a resolved address: registry first, then on-chain metadata.
+    /// Tokens must expose ERC20 metadata; ENS names often point to wallets, so a miss is not an error there.
+    async fn resolve_token_symbol(
+        &self,
+        address: Address,
+        source: &str,
+    ) -> ServiceResult<(Address, Option<String>)> {
+        if let Some(symbol) = self.token_registry.lookup_symbol(&address.to_string()) {
+            return Ok((address, Some(symbol.to_string())));
+        }
+
+        match self.repository.get_token_metadata(address).await {
+            Ok(metadata) => Ok((address, Some(metadata.symbol))),
+            Err(_) if source == "ens" => Ok((address, None)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Status of a broadcast transaction: "success" or "reverted" from its receipt, or "pending"
+    /// when the receipt could not be read and the transaction may still be mined
+    fn execution_status(execution: &SwapExecution) -> &'static str {
+        if execution.is_pending() {
+            "pending"
+        } else if execution.success {
+            "success"
+        } else {
+            "reverted"
+        }
+    }
+
+    /// Parse the swap mode ("exact_input" by default, or "exact_output")
+    fn is_exact_output(swap_mode: Option<&str>) -> ServiceResult<bool> {
+        match swap_mode.map(str::to_lowercase).as_deref() {
+            None | Some("exact_input") => Ok(false),
+            Some("exact_output") => Ok(true),
+            Some(other) => Err(ServiceError::InvalidAmount(format!(
+                "Invalid swap mode: {other}. Must be 'exact_input' or 'exact_output'"
+            ))),
+        }
+    }
+
+    /// Parse a request's V2 DEX (Uniswap V2 by default); a fork only applies to V2 swaps
+    fn parse_v2_dex(dex: Option<&str>, uniswap_version: &str) -> ServiceResult<V2Dex> {
+        let Some(name) = dex else {
+            return Ok(V2Dex::UniswapV2);
+        };
+        let dex = V2Dex::from_name(name).ok_or_else(|| {
+            ServiceError::InvalidAmount(format!(
+                "Invalid dex: {name}. Must be 'uniswap_v2' or 'sushiswap'"
+            ))
+        })?;
+        if dex != V2Dex::UniswapV2 && !uniswap_version.eq_ignore_ascii_case("v2") {
+            return Err(ServiceError::InvalidAmount(format!(
+                "dex {name} only applies to V2 swaps; leave uniswap_version unset or \"v2\""
+            )));
+        }
+        Ok(dex)
+    }
+
+    /// Fee tiers to quote for a request's `fee_tier`: only that one, or every tier when unset
+    fn parse_fee_tier(fee_tier: Option<u32>, uniswap_version: &str) -> ServiceResult<&[u32]> {
+        let Some(fee) = fee_tier else {
+            return Ok(&V3_FEE_TIERS);
+        };
+        let tier = V3_FEE_TIERS
+            .iter()
+            .find(|&&tier| tier == fee)
+            .ok_or_else(|| {
+                ServiceError::InvalidAmount(format!(
+                    "Invalid fee_tier: {fee}. Must be 100, 500, 3000 or 10000"
+                ))
+            })?;
+        if !uniswap_version.eq_ignore_ascii_case("v3") {
+            return Err(ServiceError::InvalidAmount(
+                "fee_tier only applies to V3 swaps; set uniswap_version to \"v3\"".to_string(),
+            ));
+        }
+        Ok(std::slice::from_ref(tier))
+    }
+
+    /// Repository whose V2 factory and router belong to `dex`
+    fn v2_repository(&self, dex: V2Dex) -> ServiceResult<&dyn EthereumRepository> {
+        if dex == V2Dex::UniswapV2 {
+            return Ok(self.repository.as_ref());
+        }
+        self.v2_forks
+            .iter()
+            .find(|(fork, _)| *fork == dex)
+            .map(|(_, repository)| repository.as_ref())
+            .ok_or_else(|| {
+                ServiceError::InvalidAmount(format!(
+                    "{} is not available on {}",
+                    dex.label(),
+                    self.network.as_str()
+                ))
+            })
+    }
+
+    /// Resolve a spender argument to an address ("v2"/"v3" map to the Uniswap routers,
+    /// "sushiswap" to the SushiSwap router)
+    fn resolve_spender(&self, spender: Option<&str>) -> ServiceResult<Address> {
+        match spender.map(str::to_lowercase).as_deref() {
+            None | Some("v2") => Ok(self.chain.uniswap_v2_router),
+            Some("sushiswap") => self.chain.sushiswap_router.ok_or_else(|| {
+                ServiceError::InvalidWalletAddress(
+                    "SushiSwap is not available on this network".to_string(),
+                )
+            }),
+            Some("v3") => self.chain.uniswap_v3_swap_router.ok_or_else(|| {
+                ServiceError::InvalidWalletAddress(
+                    "Uniswap V3 SwapRouter is not available on this network".to_string(),
+                )
+            }),
+            Some(other) => Address::from_str(other)
+                .map_err(|e| ServiceError::InvalidWalletAddress(e.to_string())),
+        }
+    }
+
     /// Get expected output amount from Uniswap Router
     #[instrument(skip(self), err)]
     async fn get_swap_output_amount(
@@ -554,23 +5453,95 @@ impl EthereumTradingService {
         })
     }
 
+    /// Pick the V2 path on `dex` with the largest output: direct, or through WETH
+    ///
+    /// Returns the path and the amounts along it. Fails only when no path can be quoted.
+    #[instrument(skip(self), err)]
+    async fn find_best_path(
+        &self,
+        dex: V2Dex,
+        from_token: Address,
+        to_token: Address,
+        amount_in: U256,
+    ) -> ServiceResult<(Vec<Address>, Vec<U256>)> {
+        let repository = self.v2_repository(dex)?;
+        let weth = self.chain.weth;
+        let mut candidates = vec![vec![from_token, to_token]];
+        if from_token != weth && to_token != weth {
+            candidates.push(vec![from_token, weth, to_token]);
+        }
+
+        let mut best: Option<(Vec<Address>, Vec<U256>)> = None;
+        let mut first_error = None;
+        for path in candidates {
+            match repository
+                .get_swap_amounts_out(amount_in, path.clone())
+                .await
+            {
+                Ok(amounts) => {
+                    let amount_out = amounts.last().copied().unwrap_or_default();
+                    let best_out = best
+                        .as_ref()
+                        .and_then(|(_, amounts)| amounts.last().copied());
+                    if best_out.is_none_or(|best_out| amount_out > best_out) {
+                        best = Some((path, amounts));
+                    }
+                }
+                Err(e) => {
+                    tracing::debug!("V2 path of {} tokens failed to quote: {e}", path.len());
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match (best, first_error) {
+            (Some(best), _) => Ok(best),
+            (None, Some(e)) => Err(e.into()),
+            (None, None) => Err(ServiceError::SwapSimulationFailed(
+                "No output amount returned".to_string(),
+            )),
+        }
+    }
+
+    /// Symbols along a token path (e.g., "USDC -> WETH -> DAI")
+    async fn path_label(&self, path: &[Address]) -> String {
+        let mut symbols = Vec::with_capacity(path.len());
+        for token in path {
+            let symbol = match self.repository.get_token_metadata(*token).await {
+                Ok(metadata) => metadata.symbol,
+                Err(_) => token.to_string(),
+            };
+            symbols.push(symbol);
+        }
+        symbols.join(" -> ")
+    }
+
     /// Estimate gas cost for swap transaction
     #[instrument(skip(self), err)]
-    async fn estimate_swap_gas(
+    async fn estimate_v2_swap_gas(
         &self,
+        dex: V2Dex,
         from_address: &Option<String>,
         amount_in: U256,
         minimum_output: U256,
         path: Vec<Address>,
-    ) -> ServiceResult<(String, String)> {
+        native: NativeEth,
+    ) -> ServiceResult<(String, String, String)> {
         if let Some(addr_str) = from_address {
             let from_address = Address::from_str(addr_str)
                 .map_err(|e| ServiceError::InvalidWalletAddress(e.to_string()))?;
-            let deadline = U256::from(chrono::Utc::now().timestamp() + 3600);
+            let deadline = self.deadline();
 
             match self
-                .repository
-                .simulate_swap(from_address, amount_in, minimum_output, path, deadline)
+                .v2_repository(dex)?
+                .simulate_swap(
+                    from_address,
+                    amount_in,
+                    minimum_output,
+                    path,
+                    deadline,
+                    native,
+                )
                 .await
             {
                 Ok(gas) => Ok(self.format_gas_cost(gas).await?),
@@ -581,18 +5552,51 @@ impl EthereumTradingService {
         }
     }
 
-    /// Format gas cost with current gas price
+    /// Apply the gas buffer to a node's estimate and price it at the current fee per gas
+    ///
+    /// Returns the buffered gas, the node's gas and the cost of the buffered gas in ETH.
     #[instrument(skip(self), err)]
-    async fn format_gas_cost(&self, gas: u64) -> ServiceResult<(String, String)> {
-        let gas_price = self.repository.get_gas_price().await?;
-        let gas_cost_wei = U256::from(gas) * U256::from(gas_price);
+    async fn format_gas_cost(&self, gas: u64) -> ServiceResult<(String, String, String)> {
+        let buffered = apply_gas_buffer(gas, self.gas_buffer_percent);
+        let gas_price = self.fee_per_gas().await?;
+        let gas_cost_wei = U256::from(buffered) * U256::from(gas_price);
         let gas_cost = format_balance(gas_cost_wei, ETH_DECIMALS);
-        Ok((gas.to_string(), gas_cost))
+        Ok((buffered.to_string(), gas.to_string(), gas_cost))
+    }
+
+    /// Gas cost for summaries: "~$2.10" when the ETH price is available, otherwise in ETH
+    async fn gas_cost_label(&self, gas_cost_eth: &str) -> String {
+        let usd = match (
+            Decimal::from_str(gas_cost_eth),
+            self.eth_usd_price(None).await,
+        ) {
+            (Ok(eth), Ok(eth_usd)) => Some(eth * eth_usd),
+            _ => None,
+        };
+        match usd {
+            Some(usd) => format!("~${usd:.2}"),
+            None => format!("~{gas_cost_eth} ETH"),
+        }
+    }
+
+    /// Fee per gas used for cost estimates.
+    ///
+    /// Prefers the EIP-1559 max fee per gas, which reflects congestion better than the
+    /// legacy gas price, and falls back to the legacy gas price if fee history is unavailable.
+    #[instrument(skip(self), err)]
+    async fn fee_per_gas(&self) -> ServiceResult<u128> {
+        match self.repository.get_fee_estimates().await {
+            Ok(fees) => Ok(fees.max_fee_per_gas),
+            Err(e) => {
+                tracing::debug!("EIP-1559 fee estimates unavailable, using legacy gas price: {e}");
+                Ok(self.repository.get_gas_price().await?)
+            }
+        }
     }
 
     /// Get typical Uniswap V2 swap gas estimate
     #[instrument(skip(self), err)]
-    async fn get_typical_gas_cost(&self) -> ServiceResult<(String, String)> {
+    async fn get_typical_gas_cost(&self) -> ServiceResult<(String, String, String)> {
         const TYPICAL_GAS: u64 = 150000;
         self.format_gas_cost(TYPICAL_GAS).await
     }
@@ -614,5 +5618,94 @@ impl EthereumTradingService {
     }
 }
 
-#[tool_handler]
-impl ServerHandler for EthereumTradingService {}
+impl ServerHandler for EthereumTradingService {
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let tool = request.name.clone();
+        // Held until the result is ready, so shutdown waits for this call
+        let _call = match &self.drain {
+            Some(drain) => match drain.track() {
+                Some(call) => Some(call),
+                None => {
+                    record_tool_call(&tool, Some("ShuttingDown"));
+                    return Err(ErrorData::internal_error(
+                        "Server is shutting down, retry after reconnecting",
+                        None,
+                    ));
+                }
+            },
+            None => None,
+        };
+        let hint_context = self.argument_hints.then(|| {
+            (
+                request.name.clone(),
+                request.arguments.clone().unwrap_or_default(),
+            )
+        });
+        let tcc = ToolCallContext::new(self, request, context);
+        let mut result = match self.tool_router.call(tcc).await {
+            Ok(result) => result,
+            Err(e)
+                if e.code == ErrorCode::INVALID_PARAMS
+                    && e.message.starts_with(DESERIALIZE_ERROR_PREFIX) =>
+            {
+                record_tool_call(&tool, Some("InvalidParams"));
+                let Some((name, arguments)) = hint_context else {
+                    return Err(e);
+                };
+                let Some(tool) = self
+                    .tool_router
+                    .list_all()
+                    .into_iter()
+                    .find(|t| t.name == name)
+                else {
+                    return Err(e);
+                };
+                let message =
+                    describe_argument_error(&name, &tool.input_schema, &arguments, &e.message);
+                tracing::warn!("{message}");
+                return Err(ErrorData::invalid_params(message, None));
+            }
+            Err(e) => {
+                record_tool_call(&tool, Some("ProtocolError"));
+                return Err(e);
+            }
+        };
+        let error_type = result
+            .structured_content
+            .as_ref()
+            .and_then(|content| content.get("error")?.get("type")?.as_str());
+        record_tool_call(&tool, error_type);
+        if self.number_format == NumberFormat::Number
+            && let Some(content) = result.structured_content.as_mut()
+        {
+            numeric_strings_to_numbers(content);
+            result.content = vec![Content::text(content.to_string())];
+        }
+        Ok(result)
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, ErrorData> {
+        let mut tools = self.tool_router.list_all();
+        if self.number_format == NumberFormat::Number {
+            for schema in tools
+                .iter_mut()
+                .filter_map(|tool| tool.output_schema.as_mut())
+            {
+                let mut widened = Value::Object(schema.as_ref().clone());
+                allow_numbers_in_schema(&mut widened);
+                if let Value::Object(widened) = widened {
+                    *schema = Arc::new(widened);
+                }
+            }
+        }
+        Ok(ListToolsResult::with_all_items(tools))
+    }
+}
@@ -1,3 +1,4 @@
+use alloy::primitives::Address;
 use rmcp::schemars::{self, JsonSchema};
 use serde::{Deserialize, Serialize};
 
@@ -14,24 +15,266 @@ pub enum GetBalanceResult {
 #[derive(Debug, JsonSchema, Serialize)]
 #[serde(untagged)]
 pub enum GetTokenPriceResult {
-    Success(GetTokenPriceResponse),
+    Success(Box<GetTokenPriceResponse>),
     Error { error: ServiceError },
 }
 
 #[derive(Debug, JsonSchema, Serialize)]
 #[serde(untagged)]
 pub enum SwapTokensResult {
-    Success(SwapTokensResponse),
+    Success(Box<SwapTokensResponse>),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum CheckAllowanceResult {
+    Success(CheckAllowanceResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum StopLossPlanResult {
+    Success(StopLossPlanResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum ExecuteSwapResult {
+    Success(Box<ExecuteSwapResponse>),
+    /// Quote returned instead of sending the swap, for requests with `dry_run` set
+    Simulated(Box<SwapTokensResponse>),
+    Error {
+        error: ServiceError,
+    },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum GetPoolVolumeResult {
+    Success(GetPoolVolumeResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum FindBestRouteResult {
+    Success(FindBestRouteResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum GetPoolCapacityResult {
+    Success(GetPoolCapacityResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum ResolveTokensResult {
+    Success(ResolveTokensResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum GetBalancesResult {
+    Success(GetBalancesResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum EstimateNetYieldResult {
+    Success(Box<EstimateNetYieldResponse>),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum GetPortfolioValueResult {
+    Success(GetPortfolioValueResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum VerifyQuoteResult {
+    Success(VerifyQuoteResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum GetWalletSnapshotResult {
+    Success(GetWalletSnapshotResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum CompareGasTimingResult {
+    Success(CompareGasTimingResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum ApproveTokenResult {
+    Success(Box<ApproveTokenResponse>),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum BuildPermitResult {
+    Success(Box<BuildPermitResponse>),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum QuotePathResult {
+    Success(QuotePathResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum ConvertUnitsResult {
+    Success(ConvertUnitsResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum DetectRebasingResult {
+    Success(DetectRebasingResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum SimulatePendingSwapResult {
+    Success(SimulatePendingSwapResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum GetRecentTransactionsResult {
+    Success(GetRecentTransactionsResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum EstimateRoundTripResult {
+    Success(EstimateRoundTripResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum EstimateSwapGasResult {
+    Success(EstimateSwapGasResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum GetTokenInfoResult {
+    Success(GetTokenInfoResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum GetMyApprovalResult {
+    Success(GetMyApprovalResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum GetEthStablecoinPricesResult {
+    Success(GetEthStablecoinPricesResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum ImpliedSlippageResult {
+    Success(ImpliedSlippageResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum GetBestSwapResult {
+    Success(Box<GetBestSwapResponse>),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum GetSwapStatusResult {
+    Success(GetSwapStatusResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum GetEthPriceResult {
+    Success(GetEthPriceResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum GetPoolInfoResult {
+    Success(GetPoolInfoResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum GetTokenPricesResult {
+    Success(GetTokenPricesResponse),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum GetTransactionResult {
+    Success(Box<GetTransactionResponse>),
+    Error { error: ServiceError },
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum GetChainStatusResult {
+    Success(GetChainStatusResponse),
     Error { error: ServiceError },
 }
 
 #[derive(Debug, JsonSchema, Serialize, Deserialize)]
 pub struct GetBalanceRequest {
-    /// Wallet address to query balance for
+    /// Wallet address or ENS name (e.g., "vitalik.eth") to query balance for
     pub wallet_address: String,
     /// Optional ERC20 token contract address. If not provided, returns ETH balance
     #[serde(skip_serializing_if = "Option::is_none")]
     pub token_contract_address: Option<String>,
+    /// Optional: Include a one-line human-readable summary of the result (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summarize: Option<bool>,
+    /// Optional: RPC endpoint to query instead of the configured one. Must be listed in the
+    /// server's `rpc.allowed_urls`, which also sets the network it serves
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<String>,
 }
 
 #[derive(Debug, JsonSchema, Serialize)]
@@ -44,29 +287,126 @@ pub struct GetBalanceResponse {
     pub decimals: u8,
     /// Token symbol (ETH or token symbol)
     pub symbol: String,
+    /// One-line human-readable summary (only present when `summarize` is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
 }
 
 #[derive(Debug, JsonSchema, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum GetTokenPriceRequest {
     /// Query by token symbol (e.g., "ETH", "USDT", "BTC")
-    Symbol { symbol: String },
+    Symbol {
+        symbol: String,
+        #[serde(flatten)]
+        options: PriceQueryOptions,
+    },
     /// Query by token contract address (e.g., "0xdac17f958d2ee523a2206206994597c13d831ec7")
-    ContractAddress { contract_address: String },
+    ContractAddress {
+        contract_address: String,
+        #[serde(flatten)]
+        options: PriceQueryOptions,
+    },
 }
 
 impl GetTokenPriceRequest {
     pub fn symbol(symbol: impl ToString) -> Self {
         let symbol = symbol.to_string();
-        Self::Symbol { symbol }
+        Self::Symbol {
+            symbol,
+            options: PriceQueryOptions::default(),
+        }
     }
 
     pub fn contract_address(address: impl ToString) -> Self {
         let contract_address = address.to_string();
-        Self::ContractAddress { contract_address }
+        Self::ContractAddress {
+            contract_address,
+            options: PriceQueryOptions::default(),
+        }
+    }
+
+    /// Options shared by both query variants
+    pub fn options(&self) -> &PriceQueryOptions {
+        match self {
+            Self::Symbol { options, .. } | Self::ContractAddress { options, .. } => options,
+        }
+    }
+
+    pub(crate) fn options_mut(&mut self) -> &mut PriceQueryOptions {
+        match self {
+            Self::Symbol { options, .. } | Self::ContractAddress { options, .. } => options,
+        }
+    }
+
+    pub fn with_explain(mut self, explain: bool) -> Self {
+        self.options_mut().explain = Some(explain);
+        self
+    }
+
+    pub fn at_block(mut self, block_number: u64) -> Self {
+        self.options_mut().block_number = Some(block_number);
+        self
+    }
+
+    pub fn with_summary(mut self, summarize: bool) -> Self {
+        self.options_mut().summarize = Some(summarize);
+        self
+    }
+
+    pub fn with_source(mut self, source: impl ToString) -> Self {
+        self.options_mut().source = Some(source.to_string());
+        self
+    }
+
+    pub fn with_max_staleness(mut self, secs: u64) -> Self {
+        self.options_mut().max_staleness_secs = Some(secs);
+        self
+    }
+
+    pub fn with_quote(mut self, quote: impl ToString) -> Self {
+        self.options_mut().quote = Some(quote.to_string());
+        self
     }
 }
 
+/// Optional settings accepted by both `GetTokenPriceRequest` variants
+#[derive(Debug, Default, Clone, JsonSchema, Serialize, Deserialize)]
+pub struct PriceQueryOptions {
+    /// Optional: Include a human-readable explanation of how the price was derived (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explain: Option<bool>,
+
+    /// Optional: Block number to price at, from on-chain reserves only (defaults to the latest block)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+
+    /// Optional: Include a one-line human-readable summary of the result (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summarize: Option<bool>,
+
+    /// Optional: On-chain price source: "v2" (default, pair reserves), "v3" (QuoterV2 quotes),
+    /// "best" (both, reporting the more liquid one) or "chainlink" (the token's configured
+    /// Chainlink USD feed). "v3" and "best" only price the latest block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Optional: RPC endpoint to query instead of the configured one. Must be listed in the
+    /// server's `rpc.allowed_urls`, which also sets the network it serves
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpc_url: Option<String>,
+
+    /// Optional: Fail with a StalePrice error when the V2 pair's reserves last changed more than
+    /// this many seconds ago (e.g., 3600). Only for the latest block and source "v2"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_staleness_secs: Option<u64>,
+
+    /// Optional: Also price the token in this quote token, symbol or address (e.g., "WBTC"),
+    /// from both tokens' prices in ETH. The quote token needs a WETH pool on the same source
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote: Option<String>,
+}
+
 #[allow(dead_code)]
 #[derive(Debug, JsonSchema, Serialize)]
 pub struct GetTokenPriceResponse {
@@ -74,16 +414,51 @@ pub struct GetTokenPriceResponse {
     pub symbol: String,
     /// Token contract address
     pub address: String,
-    /// Price in USD
-    pub price_usd: String,
+    /// Price in USD (null when the ETH/USD price could not be read)
+    pub price_usd: Option<String>,
     /// Price in ETH
     pub price_eth: String,
+    /// Symbol of the requested quote token (only present when `quote` is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote_symbol: Option<String>,
+    /// Price in units of the quote token (only present when `quote` is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_in_quote: Option<String>,
+    /// Whether price_usd is set; price_eth is reported even when ETH/USD is unavailable
+    pub usd_price_available: bool,
+    /// Where price_usd came from ("onchain" or "coingecko")
+    pub price_source: String,
+    /// Where the on-chain price was read from ("v2", "v3" or "chainlink")
+    pub onchain_source: String,
+    /// Prices from both Uniswap versions side by side (only present for `source: "best"`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comparison: Option<PriceSourceComparison>,
+    /// Why the external price API was not used, when it is enabled but unavailable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback_reason: Option<String>,
+    /// Block the price was read at (only present for historical lookups)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// Unix timestamp (seconds) when the V2 pair behind price_eth last traded or changed
+    /// liquidity; USDC/WETH for ETH itself (absent for V3 prices)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reserves_updated_at: Option<u64>,
+    /// Unix timestamp (seconds) of the Chainlink round behind price_usd (only for source
+    /// "chainlink")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feed_updated_at: Option<u64>,
     /// Timestamp of the price data
     pub timestamp: i64,
+    /// Human-readable explanation of how the price was derived (only present when `explain` is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    /// One-line human-readable summary (only present when `summarize` is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
 }
 
 #[allow(dead_code)]
-#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+#[derive(Debug, Default, JsonSchema, Serialize, Deserialize)]
 pub struct SwapTokensRequest {
     /// Source token symbol or address (e.g., "ETH", "WETH", or "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2")
     pub from_token: String,
@@ -95,8 +470,10 @@ pub struct SwapTokensRequest {
     /// This will be automatically converted to the token's smallest unit based on its decimals
     pub amount: String,
 
-    /// Slippage tolerance in percentage (e.g., "0.5" for 0.5%, "2" for 2%)
-    pub slippage_tolerance: String,
+    /// Optional: Slippage tolerance in percentage (e.g., "0.5" for 0.5%, "2" for 2%), between 0
+    /// and 50. Defaults to the server's configured default slippage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slippage_tolerance: Option<String>,
 
     /// Optional: Uniswap version to use ("v2" or "v3", defaults to "v2")
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -105,6 +482,46 @@ pub struct SwapTokensRequest {
     /// Optional: Wallet address for simulation (defaults to a standard address)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub from_address: Option<String>,
+
+    /// Optional: Include a human-readable explanation of how the route and quote were chosen (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explain: Option<bool>,
+
+    /// Optional: "exact_input" (default) or "exact_output". With "exact_output", `amount` is the
+    /// desired amount of `to_token` and the required input is quoted instead
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swap_mode: Option<String>,
+
+    /// Optional: Include a one-line human-readable summary of the result (defaults to false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summarize: Option<bool>,
+
+    /// Optional: Maximum acceptable price impact in percentage (e.g., "2" for 2%). Quotes above it
+    /// are rejected with PriceImpactTooHigh. V3 quotes whose impact could not be computed pass
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_price_impact: Option<String>,
+
+    /// Optional: Deliver WETH output as native ETH (execute_swap only, defaults to false). Requires
+    /// `to_token` to be WETH; V2 uses swapExactTokensForETH, V3 appends an unwrapWETH9 call.
+    /// Implied when `to_token` is "ETH"; a `from_token` of "ETH" is likewise sent as native ETH
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unwrap_weth: Option<bool>,
+
+    /// Optional: V2 DEX to quote and trade on ("uniswap_v2" or "sushiswap", defaults to
+    /// "uniswap_v2"). Forks share the V2 code paths with their own factory and router; V2 only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dex: Option<String>,
+
+    /// Optional: Only simulate, even on execute_swap with a wallet configured (defaults to
+    /// false). execute_swap then returns the swap_tokens quote, simulated from the wallet, and
+    /// signs and sends nothing; swap_tokens always simulates
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+
+    /// Optional: V3 fee tier to trade on (100, 500, 3000 or 10000). Only that pool is quoted;
+    /// without it every tier is and the best output wins. V3 only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_tier: Option<u32>,
 }
 
 #[allow(dead_code)]
@@ -116,13 +533,28 @@ pub struct SwapTokensResponse {
     /// Estimated output amount (raw)
     pub estimated_output_raw: String,
 
-    /// Minimum output amount after slippage (formatted)
+    /// Minimum output amount after slippage (formatted). Equals the requested output for exact-output swaps
     pub minimum_output: String,
 
-    /// Estimated gas cost in wei
+    /// Estimated input amount (formatted, only for exact-output swaps)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_input: Option<String>,
+
+    /// Maximum input amount after slippage (formatted, only for exact-output swaps)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum_input: Option<String>,
+
+    /// Estimated gas units including the `gas_buffer_percent` safety margin; usable as the gas
+    /// limit as is, without adding another buffer
     pub estimated_gas: String,
 
-    /// Estimated gas cost in ETH
+    /// Gas units as estimated by the node, before the buffer
+    pub estimated_gas_raw: String,
+
+    /// Safety margin included in `estimated_gas`, in percentage
+    pub gas_buffer_percent: u32,
+
+    /// Estimated gas cost in ETH of `estimated_gas` (an upper bound, as it includes the buffer)
     pub estimated_gas_eth: String,
 
     /// Price impact percentage
@@ -133,4 +565,1591 @@ pub struct SwapTokensResponse {
 
     /// Transaction data (for reference, not for execution)
     pub transaction_data: String,
+
+    /// Token path of the quote (e.g., "USDC -> WETH -> DAI")
+    pub route: String,
+
+    /// Token addresses along the route, in swap order
+    pub path: Vec<String>,
+
+    /// Raw amounts at each token of `path`: the input first, the output last (from the V2
+    /// router's getAmountsOut/getAmountsIn; input and output only for V3)
+    pub amounts: Vec<String>,
+
+    /// Uniswap version that produced the quote ("v2" or "v3")
+    pub uniswap_version: String,
+
+    /// Whether the input is native ETH, sent with the swap and wrapped to WETH by the router
+    pub wraps_eth: bool,
+
+    /// Whether the WETH output is unwrapped and delivered as native ETH
+    pub unwraps_eth: bool,
+
+    /// Human-readable explanation of the routing decision (only present when `explain` is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+
+    /// Caveats about the tokens involved, e.g. a rebasing token whose balance changes over time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+
+    /// One-line human-readable summary (only present when `summarize` is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct ExecuteSwapResponse {
+    /// Hash of the signed and broadcast transaction
+    pub transaction_hash: String,
+
+    /// Transaction status from the receipt ("success" or "reverted"), or "pending" when the
+    /// receipt could not be read; follow up with get_swap_status
+    pub status: String,
+
+    /// Block the transaction was included in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+
+    /// Why the receipt could not be read (only present for status "pending"); gas fields are
+    /// zero until the receipt is known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt_error: Option<String>,
+
+    /// Uniswap version used ("v2" or "v3")
+    pub uniswap_version: String,
+
+    /// V3 pool fee tier used (only for V3 swaps)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_tier: Option<u32>,
+
+    /// Input amount (formatted)
+    pub amount_in: String,
+
+    /// Quoted output amount at submission time (formatted)
+    pub expected_output: String,
+
+    /// Minimum output enforced on-chain as amountOutMin (formatted)
+    pub minimum_output: String,
+
+    /// Minimum output enforced on-chain as amountOutMin (raw)
+    pub minimum_output_raw: String,
+
+    /// Unix timestamp after which the swap reverts
+    pub deadline: String,
+
+    /// Address receiving the output tokens (the configured wallet)
+    pub recipient: String,
+
+    /// Whether the input was sent as native ETH and wrapped to WETH by the router
+    pub wrapped: bool,
+
+    /// Whether the WETH output was unwrapped and delivered as native ETH
+    pub unwrapped: bool,
+
+    /// Gas used by the transaction
+    pub gas_used: String,
+
+    /// Effective gas price paid, in wei
+    pub effective_gas_price: String,
+
+    /// Total gas cost in ETH
+    pub gas_cost_eth: String,
+
+    /// One-line human-readable summary (only present when `summarize` is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct CheckAllowanceRequest {
+    /// Token symbol or contract address (e.g., "USDT" or "0xdac17f958d2ee523a2206206994597c13d831ec7")
+    pub token: String,
+
+    /// Address or ENS name of the token holder whose allowance should be checked
+    pub owner_address: String,
+
+    /// Optional: Spender address, "v2"/"v3" for the Uniswap router or "sushiswap" (defaults to the Uniswap V2 router)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spender: Option<String>,
+
+    /// Amount the spender needs to move, in human-readable format (e.g., "100.5")
+    pub amount: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct CheckAllowanceResponse {
+    /// Token symbol
+    pub symbol: String,
+
+    /// Token contract address
+    pub token_address: String,
+
+    /// Spender address the allowance was checked against
+    pub spender: String,
+
+    /// Raw allowance value
+    pub allowance: String,
+
+    /// Allowance formatted with proper decimals
+    pub formatted_allowance: String,
+
+    /// Requested amount formatted with proper decimals
+    pub required_amount: String,
+
+    /// Whether the allowance covers the requested amount (if false, an approval is needed first)
+    pub sufficient: bool,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct StopLossPlanRequest {
+    /// Held token symbol or contract address (e.g., "UNI" or "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984")
+    pub token: String,
+
+    /// Held amount in human-readable format (e.g., "250.5")
+    pub amount: String,
+
+    /// Stop distance below the current price in percentage (e.g., "10" for a stop 10% below)
+    pub stop_percentage: String,
+
+    /// Optional: Current price in USD. If not provided, the live Uniswap price is used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_price_usd: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct StopLossPlanResponse {
+    /// Token symbol
+    pub symbol: String,
+
+    /// Held amount (formatted)
+    pub amount: String,
+
+    /// Current price in USD used as the reference
+    pub current_price_usd: String,
+
+    /// Price in USD at which the stop triggers
+    pub trigger_price_usd: String,
+
+    /// Value of the holding at the trigger price, before costs
+    pub gross_proceeds_usd: String,
+
+    /// Estimated price impact of selling the full holding, in percentage
+    pub estimated_price_impact: String,
+
+    /// Estimated gas cost of the sell in USD
+    pub estimated_gas_usd: String,
+
+    /// Expected proceeds in USD after price impact and gas
+    pub net_proceeds_usd: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct GetPoolVolumeRequest {
+    /// First token symbol or contract address (volume is priced from this side when possible)
+    pub token_a: String,
+
+    /// Second token symbol or contract address
+    pub token_b: String,
+
+    /// Optional: number of recent blocks to scan (default and maximum about 24 hours of blocks,
+    /// e.g. 7200 on mainnet)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocks: Option<u64>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetPoolVolumeResponse {
+    /// Pair label (e.g., "WETH/USDC")
+    pub pair: String,
+
+    /// First block scanned (inclusive)
+    pub from_block: u64,
+
+    /// Last block scanned (inclusive)
+    pub to_block: u64,
+
+    /// Number of swaps found in the range
+    pub swap_count: usize,
+
+    /// Volume of token A (formatted)
+    pub volume_token_a: String,
+
+    /// Volume of token B (formatted)
+    pub volume_token_b: String,
+
+    /// Approximate volume in USD at the current price
+    pub volume_usd: String,
+
+    /// Optional: note when the requested range was adjusted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct FindBestRouteRequest {
+    /// Source token symbol or contract address
+    pub from_token: String,
+
+    /// Destination token symbol or contract address
+    pub to_token: String,
+
+    /// Input amount in human-readable format
+    pub amount: String,
+
+    /// Optional: retries per route for RPC-level failures (not reverts), default 1, maximum 3
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpc_retries: Option<u32>,
+}
+
+#[derive(Debug, Clone, JsonSchema, Serialize)]
+pub struct RouteQuote {
+    /// Route label (e.g., "V2 USDC -> WETH -> UNI", "V3 0.3% USDC -> WETH")
+    pub route: String,
+
+    /// Uniswap version ("v2" or "v3")
+    pub uniswap_version: String,
+
+    /// V3 pool fee tier (only for V3 routes)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_tier: Option<u32>,
+
+    /// Expected output amount (formatted)
+    pub amount_out: String,
+
+    /// Expected output amount (raw)
+    pub amount_out_raw: String,
+
+    /// Gas estimate reported by the quoter (only for V3 routes)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_estimate: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct ExcludedRoute {
+    /// Route label
+    pub route: String,
+
+    /// Why the route was excluded: "revert", "no_liquidity" or "rpc_error"
+    pub kind: String,
+
+    /// Error message or explanation
+    pub reason: String,
+
+    /// Number of quote attempts made
+    pub attempts: u32,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct FindBestRouteResponse {
+    /// Route with the highest output
+    pub best_route: RouteQuote,
+
+    /// All successfully quoted routes, best first
+    pub routes: Vec<RouteQuote>,
+
+    /// Routes excluded from ranking and why
+    pub excluded: Vec<ExcludedRoute>,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct GetPoolCapacityRequest {
+    /// Token sold into the pool (symbol or contract address)
+    pub from_token: String,
+
+    /// Token bought from the pool (symbol or contract address)
+    pub to_token: String,
+
+    /// Optional: share of the output reserve the trade may drain, in percentage (defaults to "50")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drain_percentage: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetPoolCapacityResponse {
+    /// Pair label (e.g., "USDC/WETH")
+    pub pair: String,
+
+    /// Input token reserve (formatted)
+    pub reserve_in: String,
+
+    /// Output token reserve (formatted)
+    pub reserve_out: String,
+
+    /// Share of the output reserve drained at the maximum trade size, in percentage
+    pub drain_percentage: String,
+
+    /// Input amount that drains the output reserve by `drain_percentage` (formatted)
+    pub max_input: String,
+
+    /// Input amount that drains the output reserve by `drain_percentage` (raw)
+    pub max_input_raw: String,
+
+    /// Output received at the maximum trade size (formatted)
+    pub output_at_max: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct ResolveTokensRequest {
+    /// Token symbols, contract addresses or ENS names to resolve (at most 50)
+    pub tokens: Vec<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct ResolvedToken {
+    /// The input as provided
+    pub input: String,
+
+    /// Resolved address (absent on error)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+
+    /// Token symbol, when the address is a known or ERC20 token
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+
+    /// How the input was interpreted ("address", "symbol" or "ens")
+    pub source: String,
+
+    /// Why the input could not be resolved
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct ResolveTokensResponse {
+    /// One entry per input, in the same order
+    pub tokens: Vec<ResolvedToken>,
+
+    /// Whether every input resolved successfully
+    pub all_resolved: bool,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct GetBalancesRequest {
+    /// Wallet address or ENS name (e.g., "vitalik.eth") to query balances for
+    pub wallet_address: String,
+    /// ERC20 token symbols or contract addresses (at most 50). Native ETH is always included
+    #[serde(default)]
+    pub tokens: Vec<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct BalanceEntry {
+    /// The token as requested ("ETH" for the native balance)
+    pub token: String,
+
+    /// Token contract address (absent for native ETH and unresolved tokens)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_address: Option<String>,
+
+    /// Balance details (absent on error)
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<GetBalanceResponse>,
+
+    /// Why the balance could not be fetched
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BalanceEntry {
+    pub fn from_result(
+        token: String,
+        contract_address: Option<Address>,
+        result: Result<GetBalanceResponse, ServiceError>,
+    ) -> Self {
+        let contract_address = contract_address.map(|address| address.to_string());
+        match result {
+            Ok(balance) => Self {
+                token,
+                contract_address,
+                balance: Some(balance),
+                error: None,
+            },
+            Err(e) => Self {
+                token,
+                contract_address,
+                balance: None,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetBalancesResponse {
+    /// Wallet address the balances belong to
+    pub wallet_address: String,
+    /// Native ETH first, then each requested token in order
+    pub balances: Vec<BalanceEntry>,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct EstimateNetYieldRequest {
+    /// Yield-bearing token symbol or contract address (e.g., "0xae7ab96520de3a18e5e111b5eaab095312d7fe84" for stETH)
+    pub token: String,
+
+    /// Held amount in human-readable format (e.g., "10")
+    pub amount: String,
+
+    /// Annual yield of the token in percentage (e.g., "3.2" for 3.2%). Not tracked by the service, so it must be supplied
+    pub apy: String,
+
+    /// Holding period in days (e.g., "90")
+    pub holding_days: String,
+
+    /// Optional: Current price in USD. If not provided, the live Uniswap price is used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_price_usd: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct EstimateNetYieldResponse {
+    /// Token symbol
+    pub symbol: String,
+
+    /// Held amount (formatted)
+    pub amount: String,
+
+    /// Current price in USD used for the valuation
+    pub current_price_usd: String,
+
+    /// Value of the position in USD
+    pub position_value_usd: String,
+
+    /// Annual yield used, in percentage
+    pub apy: String,
+
+    /// Holding period in days
+    pub holding_days: String,
+
+    /// Yield earned over the holding period in USD, before costs
+    pub gross_yield_usd: String,
+
+    /// Estimated cost of buying the position (price impact and gas) in USD
+    pub entry_cost_usd: String,
+
+    /// Estimated cost of selling the position (price impact and gas) in USD
+    pub exit_cost_usd: String,
+
+    /// Yield after entry and exit costs in USD (negative when costs exceed the yield)
+    pub net_yield_usd: String,
+
+    /// Net yield annualized over the holding period, in percentage
+    pub net_apy: String,
+
+    /// Days of holding needed for the yield to cover the costs (absent when the APY is zero)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub break_even_days: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetContractAddressesResponse {
+    /// Configured network (e.g., "mainnet")
+    pub network: String,
+
+    /// Chain id of the network
+    pub chain_id: u64,
+
+    /// Uniswap V2 factory
+    pub uniswap_v2_factory: String,
+
+    /// Uniswap V2 router
+    pub uniswap_v2_router: String,
+
+    /// Uniswap V3 quoter
+    pub uniswap_v3_quoter: String,
+
+    /// Uniswap V3 swap router (absent where V3 swaps are unsupported)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uniswap_v3_swap_router: Option<String>,
+
+    /// WETH, which "ETH" resolves to
+    pub weth: String,
+
+    /// USDC, used for ETH/USD pricing
+    pub usdc: String,
+
+    /// ENS registry (absent where ENS is unavailable)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ens_registry: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct GetPortfolioValueRequest {
+    /// Wallet address or ENS name (e.g., "vitalik.eth") to value
+    pub wallet_address: String,
+    /// ERC20 token symbols or contract addresses (at most 50). Native ETH is always included
+    #[serde(default)]
+    pub tokens: Vec<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct PortfolioHolding {
+    /// The token as requested ("ETH" for the native balance)
+    pub token: String,
+
+    /// Token contract address (absent for native ETH and unresolved tokens)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_address: Option<String>,
+
+    /// Token symbol
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+
+    /// Balance formatted with proper decimals
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<String>,
+
+    /// USD price per token (null when the token could not be priced)
+    pub price_usd: Option<String>,
+
+    /// USD value of the holding (null when the token could not be priced)
+    pub value_usd: Option<String>,
+
+    /// Whether the holding is included in the total
+    pub priced: bool,
+
+    /// Why the balance or price could not be fetched
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetPortfolioValueResponse {
+    /// Wallet address the holdings belong to
+    pub wallet_address: String,
+    /// Native ETH first, then each requested token in order
+    pub holdings: Vec<PortfolioHolding>,
+    /// Sum of the priced holdings in USD
+    pub total_value_usd: String,
+    /// Whether every holding was priced (otherwise the total is a lower bound)
+    pub all_priced: bool,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct VerifyQuoteRequest {
+    /// Source token symbol or address, as in the original quote
+    pub from_token: String,
+
+    /// Destination token symbol or address, as in the original quote
+    pub to_token: String,
+
+    /// Input amount in human-readable format, as in the original quote
+    pub amount: String,
+
+    /// Output of the original quote in human-readable format (its `estimated_output`)
+    pub quoted_output: String,
+
+    /// Slippage tolerance in percentage (e.g., "0.5" for 0.5%)
+    pub slippage_tolerance: String,
+
+    /// Optional: Uniswap version of the original quote ("v2" or "v3", defaults to "v2")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uniswap_version: Option<String>,
+
+    /// Optional: V3 fee tier of the original quote (e.g., 3000). The best tier is re-quoted when omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub v3_fee: Option<u32>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct VerifyQuoteResponse {
+    /// Route that was re-quoted (e.g., "V2 WETH -> USDC")
+    pub route: String,
+
+    /// Output of the original quote (formatted)
+    pub quoted_output: String,
+
+    /// Output of the fresh quote (formatted)
+    pub current_output: String,
+
+    /// Output of the fresh quote (raw)
+    pub current_output_raw: String,
+
+    /// Change of the output since the original quote, in percentage (negative when it dropped)
+    pub change_percentage: String,
+
+    /// Least output the slippage tolerance accepts, relative to the original quote (formatted)
+    pub minimum_output: String,
+
+    /// Whether the fresh output is still at or above `minimum_output`
+    pub within_tolerance: bool,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetWalletSnapshotResponse {
+    /// Wallet address the balances belong to
+    pub wallet_address: String,
+    /// Block at which every balance was read
+    pub block_number: u64,
+    /// Native ETH first, then each requested token in order
+    pub balances: Vec<BalanceEntry>,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct CompareGasTimingRequest {
+    /// Source token symbol or address (e.g., "ETH" or "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2")
+    pub from_token: String,
+
+    /// Destination token symbol or address (e.g., "USDC")
+    pub to_token: String,
+
+    /// Amount to swap in human-readable format (e.g., "1.5")
+    pub amount: String,
+
+    /// Gas price to compare against, in gwei (e.g., "10")
+    pub target_gas_price_gwei: String,
+
+    /// Optional: Uniswap version to use ("v2" or "v3", defaults to "v2")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uniswap_version: Option<String>,
+
+    /// Optional: Wallet address for gas simulation (defaults to a standard address)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_address: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct CompareGasTimingResponse {
+    /// Estimated gas units for the swap
+    pub estimated_gas: String,
+
+    /// Current fee per gas in gwei
+    pub current_gas_price_gwei: String,
+
+    /// Target fee per gas in gwei
+    pub target_gas_price_gwei: String,
+
+    /// Swap gas cost at the current gas price, in ETH
+    pub current_cost_eth: String,
+
+    /// Swap gas cost at the target gas price, in ETH
+    pub target_cost_eth: String,
+
+    /// Swap gas cost at the current gas price, in USD
+    pub current_cost_usd: String,
+
+    /// Swap gas cost at the target gas price, in USD
+    pub target_cost_usd: String,
+
+    /// USD saved by waiting for the target gas price (zero when gas is already at or below it)
+    pub savings_usd: String,
+
+    /// Savings as a percentage of the current cost
+    pub savings_percentage: String,
+
+    /// Whether the current gas price is already at or below the target
+    pub already_at_target: bool,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct ApproveTokenRequest {
+    /// Token symbol or contract address (e.g., "USDC" or "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")
+    pub token: String,
+
+    /// Amount to approve in human-readable format (e.g., "100.5"), or "max" for an unlimited approval
+    pub amount: String,
+
+    /// Optional: Router to approve, "v2", "v3" or "sushiswap" (defaults to the Uniswap V2 router)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spender: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct ApproveTokenResponse {
+    /// Hash of the signed and broadcast transaction
+    pub transaction_hash: String,
+
+    /// Transaction status from the receipt ("success" or "reverted"), or "pending" when the
+    /// receipt could not be read; follow up with get_swap_status
+    pub status: String,
+
+    /// Block the transaction was included in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+
+    /// Why the receipt could not be read (only present for status "pending"); gas fields are
+    /// zero until the receipt is known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt_error: Option<String>,
+
+    /// Token symbol
+    pub symbol: String,
+
+    /// Token contract address
+    pub token_address: String,
+
+    /// Router granted the allowance
+    pub spender: String,
+
+    /// Approved amount (formatted), or "unlimited"
+    pub amount: String,
+
+    /// Approved amount (raw)
+    pub amount_raw: String,
+
+    /// Address that owns the tokens (the configured wallet)
+    pub owner: String,
+
+    /// Gas used by the transaction
+    pub gas_used: String,
+
+    /// Total gas cost in ETH
+    pub gas_cost_eth: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct DetectRebasingRequest {
+    /// Token symbol or contract address (e.g., "0xd46ba6d942050d489dbd938a2c909a5d5039a161" for AMPL)
+    pub token: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct DetectRebasingResponse {
+    /// Token symbol
+    pub symbol: String,
+
+    /// Token contract address
+    pub token_address: String,
+
+    /// Whether the token changes holder balances without transfers
+    pub rebasing: bool,
+
+    /// Rebasing mechanism that was detected (only present when `rebasing` is true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mechanism: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct SimulatePendingSwapRequest {
+    /// Token the user sells (symbol or contract address)
+    pub from_token: String,
+
+    /// Token the user buys (symbol or contract address)
+    pub to_token: String,
+
+    /// Amount the user sells, in human-readable format
+    pub amount: String,
+
+    /// Input amount of the pending swap, in human-readable units of the token it sells
+    pub pending_amount: String,
+
+    /// Optional: "same" if the pending swap also sells `from_token`, "opposite" if it sells
+    /// `to_token` (defaults to "same")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending_direction: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct SimulatePendingSwapResponse {
+    /// Pair label (e.g., "USDC/WETH")
+    pub pair: String,
+
+    /// Direction of the pending swap relative to the user's ("same" or "opposite")
+    pub pending_direction: String,
+
+    /// Output the pending swap receives (formatted, in the token it buys)
+    pub pending_output: String,
+
+    /// User's output at current reserves (formatted)
+    pub expected_output: String,
+
+    /// User's output if the pending swap lands first (formatted)
+    pub output_after_pending: String,
+
+    /// User's output if the pending swap lands first (raw)
+    pub output_after_pending_raw: String,
+
+    /// Change in the user's output caused by the pending swap, in percentage (negative is worse)
+    pub output_change_percentage: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct GetRecentTransactionsRequest {
+    /// Wallet address or ENS name (e.g., "vitalik.eth")
+    pub wallet_address: String,
+
+    /// Optional: number of recent blocks to scan (default 20, maximum 100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocks: Option<u64>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetRecentTransactionsResponse {
+    /// Resolved wallet address
+    pub wallet_address: String,
+
+    /// First block scanned (inclusive)
+    pub from_block: u64,
+
+    /// Last block scanned (inclusive)
+    pub to_block: u64,
+
+    /// Transactions sent or received by the wallet, newest first
+    pub transactions: Vec<RecentTransaction>,
+
+    /// Warning when the requested range was reduced to the maximum
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct RecentTransaction {
+    /// Transaction hash
+    pub hash: String,
+
+    /// Block the transaction was included in
+    pub block_number: u64,
+
+    /// "out" if the wallet sent the transaction, "in" if it received it
+    pub direction: String,
+
+    /// Sender address
+    pub from: String,
+
+    /// Recipient address (absent for contract creations)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+
+    /// ETH value transferred (formatted)
+    pub value_eth: String,
+
+    /// Decoded Uniswap swap, when the transaction called a V2 or V3 router
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swap: Option<DecodedSwap>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct DecodedSwap {
+    /// Uniswap version used ("v2" or "v3")
+    pub uniswap_version: String,
+
+    /// Token sold (symbol, or address when metadata is unavailable)
+    pub from_token: String,
+
+    /// Token bought (symbol, or address when metadata is unavailable)
+    pub to_token: String,
+
+    /// Exact input amount, or the maximum input for exact-output swaps (formatted)
+    pub amount_in: String,
+
+    /// Minimum output amount, or the exact output for exact-output swaps (formatted)
+    pub amount_out: String,
+
+    /// Whether the swap fixed the output amount rather than the input
+    pub exact_output: bool,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct EstimateRoundTripRequest {
+    /// Token spent on the buy and received back from the sell (symbol or contract address)
+    pub from_token: String,
+
+    /// Token bought and immediately sold back (symbol or contract address)
+    pub to_token: String,
+
+    /// Amount of `from_token` to spend, in human-readable format
+    pub amount: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct EstimateRoundTripResponse {
+    /// Pair label (e.g., "WETH/USDC")
+    pub pair: String,
+
+    /// Amount of `from_token` spent (formatted)
+    pub amount_in: String,
+
+    /// Amount of `to_token` bought (formatted)
+    pub amount_bought: String,
+
+    /// Amount of `from_token` received back from the sell (formatted)
+    pub amount_returned: String,
+
+    /// Amount of `from_token` returned per unit spent
+    pub effective_rate: String,
+
+    /// Share of the input lost over the round trip, in percentage
+    pub total_cost_percentage: String,
+
+    /// Part of the cost paid as LP fees (two 0.3% fees), in percentage
+    pub lp_fee_percentage: String,
+
+    /// Cost beyond the LP fees, in percentage. Near zero, and negative for large trades, since
+    /// the sell recovers the buy's price impact on the same pool
+    pub price_impact_percentage: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct EstimateSwapGasRequest {
+    /// Source token symbol or address (e.g., "WETH" or "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2")
+    pub from_token: String,
+
+    /// Destination token symbol or address (e.g., "USDC")
+    pub to_token: String,
+
+    /// Amount to swap in human-readable format (e.g., "1.5")
+    pub amount: String,
+
+    /// Optional: Wallet address to simulate from. Without it, a typical V2 swap's gas is used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_address: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct EstimateSwapGasResponse {
+    /// Estimated gas units for the swap, including the `gas_buffer_percent` safety margin
+    pub estimated_gas: String,
+
+    /// Gas units as estimated by the node, before the buffer
+    pub estimated_gas_raw: String,
+
+    /// Safety margin included in `estimated_gas`, in percentage
+    pub gas_buffer_percent: u32,
+
+    /// Estimated gas cost in ETH of `estimated_gas` at the current fee per gas
+    pub estimated_gas_eth: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct GetTokenInfoRequest {
+    /// Token symbol or contract address (e.g., "DAI" or "0x6b175474e89094c44da98b954eedeac495271d0f")
+    pub token: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetTokenInfoResponse {
+    /// Token name (e.g., "Dai Stablecoin")
+    pub name: String,
+
+    /// Token symbol as reported by the contract
+    pub symbol: String,
+
+    /// Number of decimals
+    pub decimals: u8,
+
+    /// Total supply (formatted with decimals)
+    pub total_supply: String,
+
+    /// Total supply (raw)
+    pub total_supply_raw: String,
+
+    /// Token contract address
+    pub token_address: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct GetMyApprovalRequest {
+    /// Token symbol or contract address (e.g., "USDC")
+    pub token: String,
+
+    /// Optional: Spender address, or "v2"/"v3" for the Uniswap router (defaults to the Uniswap V2 router)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spender: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetMyApprovalResponse {
+    /// Token symbol
+    pub symbol: String,
+
+    /// Token contract address
+    pub token_address: String,
+
+    /// Configured wallet address (the token holder)
+    pub owner: String,
+
+    /// Spender address the allowance was checked against
+    pub spender: String,
+
+    /// Raw allowance value
+    pub allowance: String,
+
+    /// Allowance formatted with proper decimals, or "unlimited"
+    pub formatted_allowance: String,
+
+    /// Whether the allowance is an unlimited (max uint256) approval
+    pub unlimited: bool,
+}
+
+/// V2 and V3 prices of a token compared by `get_token_price` with `source: "best"`
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct PriceSourceComparison {
+    /// Price from each Uniswap version that could be quoted
+    pub sources: Vec<SourcePrice>,
+    /// Versions that could not be priced, with the reason (e.g. "v3: no pool")
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unavailable: Vec<String>,
+    /// Version with the lower probe slippage ("v2" or "v3"), whose price is reported
+    pub more_liquid: String,
+    /// Difference of the V3 price from the V2 price in percentage (only when both are available)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spread_percent: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct SourcePrice {
+    /// Uniswap version ("v2" or "v3")
+    pub source: String,
+    /// Price in ETH
+    pub price_eth: String,
+    /// Price in USD, using the ETH/USD price from the same version
+    pub price_usd: String,
+    /// V3 pool fee tier quoted (only for V3)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_tier: Option<u32>,
+    /// How much worse a 1 WETH buy fills than the spot price, in percentage; lower means deeper
+    /// liquidity. WETH itself is measured on its USDC pool
+    pub probe_slippage: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetEthStablecoinPricesResponse {
+    /// ETH price against each stablecoin, read from its Uniswap V2 WETH pair
+    pub prices: Vec<StablecoinPrice>,
+
+    /// Lowest ETH price among the stablecoins that could be priced
+    pub min_price: String,
+
+    /// Highest ETH price among the stablecoins that could be priced
+    pub max_price: String,
+
+    /// Spread between the highest and lowest price, as a percentage of the lowest
+    pub spread_percent: String,
+
+    /// Whether the spread is small enough to treat any of the prices as the USD price
+    pub consistent: bool,
+
+    /// Present when the spread suggests a stablecoin has depegged or a pair is thin
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct StablecoinPrice {
+    /// Stablecoin symbol (e.g., "USDC")
+    pub symbol: String,
+
+    /// Stablecoin contract address (absent when the symbol is not registered on this network)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_address: Option<String>,
+
+    /// Price of 1 ETH in this stablecoin (absent on error)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_price: Option<String>,
+
+    /// Why the price could not be read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct ImpliedSlippageRequest {
+    /// Source token symbol or address (e.g., "WETH")
+    pub from_token: String,
+
+    /// Destination token symbol or address (e.g., "USDC")
+    pub to_token: String,
+
+    /// Input amount in human-readable format (e.g., "1")
+    pub amount: String,
+
+    /// Desired minimum output in human-readable format (e.g., "1990")
+    pub minimum_output: String,
+
+    /// Optional: Uniswap version to quote ("v2" or "v3", defaults to "v2")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uniswap_version: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct ImpliedSlippageResponse {
+    /// Route that was quoted (e.g., "V2 WETH -> USDC")
+    pub route: String,
+
+    /// Expected output of the current quote (formatted)
+    pub expected_output: String,
+
+    /// Expected output of the current quote (raw)
+    pub expected_output_raw: String,
+
+    /// The desired minimum output (formatted)
+    pub minimum_output: String,
+
+    /// Slippage tolerance in percentage that yields at most this minimum, truncated to 4 decimal
+    /// places; negative when the minimum exceeds the expected output
+    pub implied_slippage: String,
+
+    /// Whether the current quote meets the minimum and the implied slippage is an accepted
+    /// `slippage_tolerance` (at most 50%)
+    pub achievable: bool,
+
+    /// Why the minimum is not achievable (only present when `achievable` is false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct GetBestSwapRequest {
+    /// Source token symbol or address (e.g., "USDC")
+    pub from_token: String,
+
+    /// Destination token symbol or address (e.g., "WETH")
+    pub to_token: String,
+
+    /// Amount to swap in human-readable format (e.g., "1000")
+    pub amount: String,
+
+    /// Slippage tolerance in percentage (e.g., "0.5" for 0.5%)
+    pub slippage_tolerance: String,
+
+    /// Optional: Wallet address for gas simulation (defaults to typical gas costs)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_address: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetBestSwapResponse {
+    /// Uniswap version with the larger output ("v2" or "v3")
+    pub chosen_version: String,
+
+    /// Estimated output of the chosen quote (formatted)
+    pub estimated_output: String,
+
+    /// Minimum output of the chosen quote after slippage (formatted)
+    pub minimum_output: String,
+
+    /// How much more the chosen quote yields than the other, in percentage (only when both quoted)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub advantage_percent: Option<String>,
+
+    /// Full V2 quote, as returned by swap_tokens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub v2_quote: Option<SwapTokensResponse>,
+
+    /// Full V3 quote (best fee tier), as returned by swap_tokens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub v3_quote: Option<SwapTokensResponse>,
+
+    /// Why the V2 quote failed (only when it did)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub v2_error: Option<String>,
+
+    /// Why the V3 quote failed (only when it did)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub v3_error: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct GetSwapStatusRequest {
+    /// Hash of the transaction to follow up, e.g. the `transaction_hash` returned by execute_swap
+    pub transaction_hash: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetSwapStatusResponse {
+    /// Hash of the transaction
+    pub transaction_hash: String,
+
+    /// "pending" while no receipt is available (not yet mined, or unknown to the node), then
+    /// "success" or "reverted"
+    pub status: String,
+
+    /// Block the transaction was included in (only once mined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+
+    /// Blocks mined on top of the inclusion block, counting it (only once mined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmations: Option<u64>,
+
+    /// Gas used by the transaction (only once mined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<String>,
+
+    /// Effective gas price paid, in wei (only once mined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective_gas_price: Option<String>,
+
+    /// Total gas cost in ETH (only once mined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_cost_eth: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetEthPriceResponse {
+    /// ETH price in USD, from the Uniswap V2 USDC/WETH pair
+    pub price_usd: String,
+
+    /// USDC reserve of the pair the price was read from
+    pub reserve_usdc: String,
+
+    /// WETH reserve of the pair the price was read from
+    pub reserve_weth: String,
+
+    /// Unix timestamp (seconds) when the price was read
+    pub timestamp: i64,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct GetPoolInfoRequest {
+    /// First token symbol or address (e.g., "WETH")
+    pub token_a: String,
+
+    /// Second token symbol or address (e.g., "USDC")
+    pub token_b: String,
+
+    /// Optional: V3 fee tier in hundredths of a bip (100, 500, 3000 or 10000). Defaults to the
+    /// tier with the most in-range liquidity
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_tier: Option<u32>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetPoolInfoResponse {
+    /// Address of the Uniswap V3 pool
+    pub pool_address: String,
+
+    /// Fee tier of the pool in hundredths of a bip (e.g., 3000 for 0.3%)
+    pub fee_tier: u32,
+
+    /// The pool's token0 (the token with the lower address)
+    pub token0: String,
+
+    /// The pool's token1
+    pub token1: String,
+
+    /// Current price of token_a in token_b, adjusted for decimals
+    pub price: String,
+
+    /// Current sqrt(token1/token0) in raw units, as a Q64.96 fixed-point number
+    pub sqrt_price_x96: String,
+
+    /// Current tick
+    pub tick: i32,
+
+    /// Liquidity in range at the current tick (raw)
+    pub liquidity: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct ListSupportedTokensRequest {
+    /// Optional: Part of a symbol to search for, case-insensitive (e.g., "usd"). Lists every
+    /// token when omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct ListSupportedTokensResponse {
+    /// Configured network (e.g., "mainnet")
+    pub network: String,
+
+    /// Matching tokens, closest match first; alphabetical without a query
+    pub tokens: Vec<SupportedToken>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct SupportedToken {
+    /// Token symbol accepted by the other tools (e.g., "USDC")
+    pub symbol: String,
+
+    /// Contract address the symbol resolves to
+    pub contract_address: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct GetTokenPricesRequest {
+    /// Token symbols or contract addresses to price (e.g., ["ETH", "UNI", "LINK"], at most 50)
+    pub tokens: Vec<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetTokenPricesResponse {
+    /// ETH price in USD from the Uniswap V2 USDC/WETH pair, shared by every token
+    pub eth_price_usd: String,
+
+    /// One entry per requested token, in request order
+    pub prices: Vec<TokenPriceEntry>,
+
+    /// Whether every token could be priced
+    pub all_priced: bool,
+
+    /// Unix timestamp of the query
+    pub timestamp: i64,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct TokenPriceEntry {
+    /// The token as requested
+    pub token: String,
+
+    /// Token contract address (absent when the token could not be resolved)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_address: Option<String>,
+
+    /// Token symbol
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+
+    /// Price in USD (null when the token could not be priced)
+    pub price_usd: Option<String>,
+
+    /// Price in ETH (null when the token could not be priced)
+    pub price_eth: Option<String>,
+
+    /// Unix timestamp (seconds) when the token's WETH pair reserves last changed (absent for
+    /// ETH/WETH and unpriced tokens)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reserves_updated_at: Option<u64>,
+
+    /// Whether the token was priced
+    pub priced: bool,
+
+    /// Why the token could not be priced, e.g. no Uniswap V2 WETH pair
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetChainStatusResponse {
+    /// Configured network (e.g., "mainnet")
+    pub network: String,
+
+    /// Chain id reported by the RPC endpoint
+    pub chain_id: u64,
+
+    /// Whether the RPC chain id is the configured network's; trades are unsafe otherwise
+    pub chain_id_matches: bool,
+
+    /// Latest block number
+    pub block_number: u64,
+
+    /// Unix timestamp (seconds) of the latest block; base swap deadlines on this
+    pub block_timestamp: u64,
+
+    /// Seconds since the latest block; a large value means the node is lagging
+    pub block_age_secs: i64,
+
+    /// Base fee of the next block in gwei
+    pub base_fee_gwei: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetWalletAddressResponse {
+    /// Address of the configured signer (null in read-only mode)
+    pub wallet_address: Option<String>,
+
+    /// Whether the server runs without a wallet and can only read and simulate
+    pub read_only: bool,
+
+    /// Whether execute_swap and approve_token may broadcast transactions from this wallet
+    pub execution_allowed: bool,
+
+    /// Human-readable summary of the wallet mode
+    pub message: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct GetTransactionRequest {
+    /// Hash of the transaction to look up (0x-prefixed, 32 bytes)
+    pub transaction_hash: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct GetTransactionResponse {
+    /// Hash of the transaction
+    pub transaction_hash: String,
+
+    /// "not_found" when the node does not know the hash, "pending" until it is mined, then
+    /// "success" or "reverted"
+    pub status: String,
+
+    /// Sender address (absent when not found)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+
+    /// Recipient address (absent when not found and for contract creations)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+
+    /// ETH value sent (formatted)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_eth: Option<String>,
+
+    /// Gas limit set by the sender
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
+
+    /// Sender nonce
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+
+    /// First 4 bytes of the calldata, the called function's selector (absent for plain transfers)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_selector: Option<String>,
+
+    /// Calldata length in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_size: Option<usize>,
+
+    /// Decoded Uniswap swap, when the transaction called a V2 or V3 router
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swap: Option<DecodedSwap>,
+
+    /// Block the transaction was included in (only once mined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+
+    /// Gas used by the transaction (only once mined)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct BuildPermitRequest {
+    /// Token symbol or contract address; the token must implement EIP-2612 (e.g., "USDC" or "UNI")
+    pub token: String,
+
+    /// Spender to authorize: "permit2", "v2", "v3", "sushiswap" or an address
+    pub spender: String,
+
+    /// Allowance in human-readable format (e.g., "100.5"), or "max" for an unlimited allowance
+    pub amount: String,
+
+    /// Optional: Unix timestamp after which the permit is invalid (defaults to now plus the
+    /// configured swap deadline)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<u64>,
+
+    /// Optional: Address or ENS name granting the allowance (defaults to the configured wallet).
+    /// Permits for other owners are returned unsigned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct BuildPermitResponse {
+    /// Token symbol
+    pub symbol: String,
+
+    /// Token contract address
+    pub token_address: String,
+
+    /// Address granting the allowance
+    pub owner: String,
+
+    /// Address allowed to spend
+    pub spender: String,
+
+    /// Allowance (formatted), or "unlimited"
+    pub value: String,
+
+    /// Allowance (raw), the `value` argument of `permit()`
+    pub value_raw: String,
+
+    /// Owner's permit nonce on the token, consumed when the permit is used
+    pub nonce: String,
+
+    /// Unix timestamp after which the permit is invalid
+    pub deadline: u64,
+
+    /// The token's EIP-712 domain separator
+    pub domain_separator: String,
+
+    /// EIP-712 digest of the permit, the hash the owner signs
+    pub digest: String,
+
+    /// Whether the configured wallet signed the permit
+    pub signed: bool,
+
+    /// 65-byte signature (r, s, v), when signed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+
+    /// Recovery id for `permit()` (27 or 28), when signed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub v: Option<u8>,
+
+    /// Signature `r` for `permit()`, when signed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r: Option<String>,
+
+    /// Signature `s` for `permit()`, when signed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub s: Option<String>,
+
+    /// Why the permit was not signed, when it was not
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct QuotePathRequest {
+    /// Token symbols or addresses from the input to the output token, 2 to 5 of them
+    /// (e.g., ["USDC", "WETH", "DAI"])
+    pub path: Vec<String>,
+
+    /// Amount of the first token in human-readable format (e.g., "100")
+    pub amount: String,
+
+    /// V2 DEX to quote on, "uniswap_v2" (default) or "sushiswap"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dex: Option<String>,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct QuotePathResponse {
+    /// DEX the path was quoted on
+    pub dex: String,
+
+    /// Token addresses along the path
+    pub path: Vec<String>,
+
+    /// Token symbols along the path
+    pub symbols: Vec<String>,
+
+    /// Amount of each token along the path (formatted), starting with the input
+    pub amounts: Vec<String>,
+
+    /// Amount of each token along the path (raw), as returned by the router's getAmountsOut
+    pub amounts_raw: Vec<String>,
+
+    /// Amount of the last token received (formatted)
+    pub amount_out: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize)]
+pub struct ConvertUnitsRequest {
+    /// Amount in `from_unit` (e.g., "1.5", "1_000" or "1e6")
+    pub value: String,
+
+    /// Unit of `value`: "wei", "gwei", "ether", or a token symbol or address (e.g., "USDC")
+    pub from_unit: String,
+
+    /// Unit to convert to: "wei", "gwei", "ether", or a token symbol or address. "wei" also
+    /// stands for a token's smallest unit
+    pub to_unit: String,
+}
+
+#[derive(Debug, JsonSchema, Serialize)]
+pub struct ConvertUnitsResponse {
+    /// Converted amount in `to_unit`
+    pub value: String,
+
+    /// Amount in the smallest unit (wei, or the token's base unit)
+    pub raw: String,
+
+    /// Source unit, with a token resolved to its symbol
+    pub from_unit: String,
+
+    /// Target unit, with a token resolved to its symbol
+    pub to_unit: String,
+
+    /// Decimals of the source unit relative to the smallest unit
+    pub from_decimals: u8,
+
+    /// Decimals of the target unit relative to the smallest unit
+    pub to_decimals: u8,
 }
@@ -3,12 +3,34 @@
 //! This module provides conversion between U256 (blockchain integers) and Decimal
 //! for accurate financial calculations without floating-point precision loss.
 
-use alloy::primitives::U256;
+use alloy::primitives::{Address, B256, U256, U512, keccak256};
+use alloy::sol_types::SolValue;
 use rust_decimal::Decimal;
 use std::str::FromStr;
 
 use super::ServiceResult;
 use super::error::ServiceError;
+use crate::repository::{RepositoryError, SwapEventLog};
+
+/// Largest scale a Decimal can hold
+const MAX_DECIMAL_SCALE: u32 = 28;
+
+/// Fraction digits kept when converting a V3 sqrt price to a Decimal
+const SQRT_PRICE_SCALE: u8 = 18;
+
+/// Largest accepted slippage tolerance, in percent
+const MAX_SLIPPAGE_PERCENT: u32 = 50;
+
+/// EIP-2612 `Permit` struct type, hashed into the permit digest
+const PERMIT_TYPE: &str =
+    "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)";
+
+/// 10^exp as a U256, or an error when it does not fit
+fn pow10(exp: u32) -> ServiceResult<U256> {
+    U256::from(10u64)
+        .checked_pow(U256::from(exp))
+        .ok_or_else(|| ServiceError::InvalidAmount(format!("10^{exp} does not fit in U256")))
+}
 
 /// Convert U256 to Decimal with proper decimal scaling
 ///
@@ -19,19 +41,27 @@ use super::error::ServiceError;
 /// # Returns
 /// A Decimal representing the actual value (e.g., 1.5 ETH instead of 1500000000000000000 wei)
 pub fn u256_to_decimal(value: U256, decimals: u8) -> ServiceResult<Decimal> {
-    // Convert U256 to string
-    let value_str = value.to_string();
+    // Split into whole and fractional parts with U256 arithmetic
+    let divisor = pow10(decimals as u32)?;
+    let whole = value / divisor;
+    let mut fraction = value % divisor;
 
-    // Parse to Decimal
-    let mut decimal = Decimal::from_str(&value_str).map_err(|e| {
+    // Parse the whole part to Decimal
+    let whole = Decimal::from_str(&whole.to_string()).map_err(|e| {
         ServiceError::InvalidAmount(format!("Failed to parse U256 to Decimal: {}", e))
     })?;
 
-    // Adjust for decimals by dividing by 10^decimals
-    if decimals > 0 {
-        let divisor = Decimal::from(10u64.pow(decimals as u32));
-        decimal /= divisor;
+    // Keep at most 28 fraction digits, the most a Decimal can hold
+    let mut scale = decimals as u32;
+    if scale > MAX_DECIMAL_SCALE {
+        fraction /= pow10(scale - MAX_DECIMAL_SCALE)?;
+        scale = MAX_DECIMAL_SCALE;
     }
+    let fraction = Decimal::from_i128_with_scale(fraction.to::<u128>() as i128, scale);
+
+    let decimal = whole.checked_add(fraction).ok_or_else(|| {
+        ServiceError::InvalidAmount("Value is too large for a Decimal".to_string())
+    })?;
 
     // Normalize to remove trailing zeros
     Ok(decimal.normalize())
@@ -46,21 +76,24 @@ pub fn u256_to_decimal(value: U256, decimals: u8) -> ServiceResult<Decimal> {
 /// # Returns
 /// A U256 representing the raw blockchain value (e.g., wei instead of ETH)
 pub fn decimal_to_u256(value: Decimal, decimals: u8) -> ServiceResult<U256> {
-    // Scale up by multiplying by 10^decimals
-    let scaled = if decimals > 0 {
-        let multiplier = Decimal::from(10u64.pow(decimals as u32));
-        value * multiplier
-    } else {
-        value
-    };
+    if value.is_sign_negative() && !value.is_zero() {
+        return Err(ServiceError::InvalidAmount(format!(
+            "Failed to parse Decimal to U256: negative value {value}"
+        )));
+    }
 
-    // Convert to string and remove decimal point
-    let scaled_str = scaled.to_string();
-    let integer_str = scaled_str.split('.').next().unwrap_or(&scaled_str);
+    // value = mantissa / 10^scale, so the result is mantissa * 10^(decimals - scale), truncated
+    let mantissa = U256::from(value.mantissa().unsigned_abs());
+    let scale = value.scale();
+    let decimals = decimals as u32;
 
-    // Parse to U256
-    U256::from_str(integer_str)
-        .map_err(|e| ServiceError::InvalidAmount(format!("Failed to parse Decimal to U256: {}", e)))
+    if decimals >= scale {
+        mantissa
+            .checked_mul(pow10(decimals - scale)?)
+            .ok_or_else(|| ServiceError::InvalidAmount(format!("{value} overflows U256")))
+    } else {
+        Ok(mantissa / pow10(scale - decimals)?)
+    }
 }
 
 /// Calculate price with precise decimal arithmetic
@@ -93,6 +126,104 @@ pub fn calculate_price(
     Ok(num_decimal / den_decimal)
 }
 
+/// Price of token0 in token1 from a Uniswap V3 pool's `sqrtPriceX96`
+///
+/// # Arguments
+/// * `sqrt_price_x96` - sqrt(token1/token0) in raw units, as a Q64.96 fixed-point number
+/// * `decimals0` - Decimals of the pool's token0
+/// * `decimals1` - Decimals of the pool's token1
+///
+/// # Returns
+/// How much token1 one token0 is worth, adjusted for decimals
+pub fn sqrt_price_x96_to_price(
+    sqrt_price_x96: U256,
+    decimals0: u8,
+    decimals1: u8,
+) -> ServiceResult<Decimal> {
+    let overflow = || ServiceError::InvalidAmount("V3 pool price is out of range".to_string());
+    let pow10 = |exp: u32| {
+        U512::from(10u64)
+            .checked_pow(U512::from(exp))
+            .ok_or_else(overflow)
+    };
+
+    // price = sqrtPriceX96^2 / 2^192 * 10^(decimals0 - decimals1), kept as an integer scaled by
+    // 10^SQRT_PRICE_SCALE; the square of a 160-bit value needs more than 256 bits
+    let sqrt_price = U512::from(sqrt_price_x96);
+    let numerator = (sqrt_price * sqrt_price)
+        .checked_mul(pow10(SQRT_PRICE_SCALE as u32 + decimals0 as u32)?)
+        .ok_or_else(overflow)?;
+    let denominator = (U512::from(1u8) << 192usize)
+        .checked_mul(pow10(decimals1 as u32)?)
+        .ok_or_else(overflow)?;
+    let quotient = numerator / denominator;
+    if quotient.bit_len() > 256 {
+        return Err(overflow());
+    }
+    let scaled = U256::from_limbs_slice(&quotient.as_limbs()[..4]);
+
+    u256_to_decimal(scaled, SQRT_PRICE_SCALE)
+}
+
+/// Price of one token of a Uniswap V3 pool in the other, from the pool's `sqrtPriceX96`
+///
+/// # Arguments
+/// * `sqrt_price_x96` - sqrt(token1/token0) in raw units, as a Q64.96 fixed-point number
+/// * `base_is_token0` - Whether the priced token is the pool's token0
+/// * `base_decimals` - Decimals of the priced token
+/// * `quote_decimals` - Decimals of the token the price is expressed in
+///
+/// # Returns
+/// How much of the quote token one base token is worth
+pub fn v3_spot_price(
+    sqrt_price_x96: U256,
+    base_is_token0: bool,
+    base_decimals: u8,
+    quote_decimals: u8,
+) -> ServiceResult<Decimal> {
+    if base_is_token0 {
+        return sqrt_price_x96_to_price(sqrt_price_x96, base_decimals, quote_decimals);
+    }
+
+    let price0 = sqrt_price_x96_to_price(sqrt_price_x96, quote_decimals, base_decimals)?;
+    Decimal::ONE.checked_div(price0).ok_or_else(|| {
+        ServiceError::InsufficientLiquidity("V3 pool has no usable price".to_string())
+    })
+}
+
+/// Price impact of a Uniswap V3 swap: how much worse than the pool's spot price it executes
+///
+/// # Arguments
+/// * `spot_price` - Pool price of the input token in the output token (see `v3_spot_price`)
+/// * `amount_in` - Input amount (raw)
+/// * `amount_out` - Quoted output amount (raw)
+/// * `decimals_in` - Decimals of the input token
+/// * `decimals_out` - Decimals of the output token
+/// * `fee` - Pool fee tier in hundredths of a bip
+///
+/// # Returns
+/// Impact as a percentage, never negative. The pool fee is taken out of the input first, so the
+/// figure measures how far the trade moves the price, as the V2 impact does
+pub fn calculate_v3_price_impact(
+    spot_price: Decimal,
+    amount_in: U256,
+    amount_out: U256,
+    decimals_in: u8,
+    decimals_out: u8,
+    fee: u32,
+) -> ServiceResult<Decimal> {
+    if spot_price.is_zero() || amount_in.is_zero() {
+        return Ok(Decimal::ZERO);
+    }
+
+    let execution_price = calculate_price(amount_out, amount_in, decimals_out, decimals_in)?;
+    let fee_share = Decimal::from(fee) / Decimal::from(1_000_000);
+    let net_price = execution_price / (Decimal::ONE - fee_share);
+
+    let impact = (Decimal::ONE - net_price / spot_price) * Decimal::from(100);
+    Ok(impact.max(Decimal::ZERO))
+}
+
 /// Calculate percentage with precise decimal arithmetic
 ///
 /// # Arguments
@@ -102,16 +233,20 @@ pub fn calculate_price(
 /// # Returns
 /// Result value
 pub fn apply_percentage(value: U256, percentage: Decimal) -> ServiceResult<U256> {
-    let value_decimal = Decimal::from_str(&value.to_string())
-        .map_err(|e| ServiceError::InvalidAmount(format!("Failed to parse value: {}", e)))?;
-
-    let result_decimal = value_decimal * percentage / Decimal::from(100);
+    if percentage.is_sign_negative() && !percentage.is_zero() {
+        return Err(ServiceError::InvalidAmount(format!(
+            "Failed to parse result: negative percentage {percentage}"
+        )));
+    }
 
-    let result_str = result_decimal.to_string();
-    let integer_str = result_str.split('.').next().unwrap_or(&result_str);
+    // value * (mantissa / 10^scale) / 100, truncated
+    let mantissa = U256::from(percentage.mantissa().unsigned_abs());
+    let divisor = pow10(percentage.scale() + 2)?;
 
-    U256::from_str(integer_str)
-        .map_err(|e| ServiceError::InvalidAmount(format!("Failed to parse result: {}", e)))
+    value
+        .checked_mul(mantissa)
+        .map(|product| product / divisor)
+        .ok_or_else(|| ServiceError::InvalidAmount("Percentage result overflows U256".to_string()))
 }
 
 /// Parse human-readable amount (e.g., "1.5") to smallest unit based on decimals
@@ -124,29 +259,169 @@ pub fn apply_percentage(value: U256, percentage: Decimal) -> ServiceResult<U256>
 /// - "1" with 18 decimals -> 1000000000000000000 (1 ETH in wei)
 /// - "100" with 6 decimals -> 100000000 (100 USDC in smallest unit)
 ///
+/// - "1_000" with 6 decimals -> 1000000000 (digit separators are ignored)
+/// - "1e6" with 6 decimals -> 1000000000000 (scientific notation is expanded)
+///
 /// # Returns
 /// U256 value in smallest unit
 pub fn parse_amount(amount: &str, decimals: u8) -> Result<U256, String> {
+    let amount = strip_digit_separators(amount)?;
+
     // Try to parse as Decimal first for human-readable amounts
-    if let Ok(decimal_amount) = Decimal::from_str(amount) {
+    if let Some(decimal_amount) = parse_decimal_amount(&amount) {
         // Multiply by 10^decimals to get the smallest unit
-        // Build multiplier: 10^decimals
-        let mut multiplier = Decimal::from(1);
-        for _ in 0..decimals {
-            multiplier *= Decimal::from(10);
+        decimal_to_u256(decimal_amount, decimals)
+            .map_err(|e| format!("Failed to parse amount: {}", e))
+    } else {
+        // If not a decimal, try parsing directly as U256 (assume already in smallest unit)
+        U256::from_str(&amount).map_err(|_| invalid_amount(&amount))
+    }
+}
+
+/// Remove `_` digit separators ("1_000.5"), which must sit between two digits
+///
+/// Also rejects an empty amount, which U256 parsing would otherwise read as zero.
+fn strip_digit_separators(amount: &str) -> Result<String, String> {
+    let amount = amount.trim();
+    if amount.is_empty() {
+        return Err(invalid_amount(amount));
+    }
+    let bytes = amount.as_bytes();
+    let misplaced = bytes.iter().enumerate().any(|(i, b)| {
+        *b == b'_'
+            && !(i > 0
+                && bytes[i - 1].is_ascii_digit()
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit))
+    });
+    if misplaced {
+        return Err(invalid_amount(amount));
+    }
+    Ok(amount.replace('_', ""))
+}
+
+/// Parse a plain ("1.5") or scientific ("1.5e3", "2E-6") decimal amount
+fn parse_decimal_amount(amount: &str) -> Option<Decimal> {
+    if amount.contains(['e', 'E']) {
+        Decimal::from_scientific(amount).ok()
+    } else {
+        Decimal::from_str(amount).ok()
+    }
+}
+
+fn invalid_amount(amount: &str) -> String {
+    format!("Invalid amount \"{amount}\": expected a number such as \"1.5\", \"1_000\" or \"1e6\"")
+}
+
+/// Check that an amount is a strictly positive number before any RPC work is done
+///
+/// # Arguments
+/// * `amount` - Amount as string, human-readable or in the smallest unit
+///
+/// # Returns
+/// An error message for empty, malformed, zero or negative amounts
+pub fn validate_positive_amount(amount: &str) -> Result<(), String> {
+    let amount = amount.trim();
+    if amount.is_empty() {
+        return Err("Amount is required".to_string());
+    }
+
+    let normalized = strip_digit_separators(amount)?;
+    let positive = match parse_decimal_amount(&normalized) {
+        Some(value) => value > Decimal::ZERO,
+        None => !U256::from_str(&normalized)
+            .map_err(|_| invalid_amount(amount))?
+            .is_zero(),
+    };
+    if !positive {
+        return Err(format!("Amount must be greater than zero, got {amount}"));
+    }
+    Ok(())
+}
+
+/// Re-express an amount between units of different decimals (e.g., wei, gwei and ether)
+///
+/// # Arguments
+/// * `amount` - Amount as string in the source unit (e.g., "1.5", "1_000" or "1e6")
+/// * `from_decimals` - Decimals of the source unit relative to the smallest unit (0 for wei)
+/// * `to_decimals` - Decimals of the target unit (18 for ether)
+///
+/// # Returns
+/// The amount in the smallest unit and in the target unit. Amounts finer than the smallest
+/// unit (e.g., "0.5" wei) are rejected rather than truncated.
+pub fn convert_amount(
+    amount: &str,
+    from_decimals: u8,
+    to_decimals: u8,
+) -> ServiceResult<(U256, Decimal)> {
+    let amount = strip_digit_separators(amount).map_err(ServiceError::InvalidAmount)?;
+
+    let raw = match parse_decimal_amount(&amount) {
+        Some(value) => {
+            if value.is_sign_negative() && !value.is_zero() {
+                return Err(ServiceError::InvalidAmount(format!(
+                    "Amount cannot be negative, got {amount}"
+                )));
+            }
+            if value.normalize().scale() > from_decimals as u32 {
+                return Err(ServiceError::InvalidAmount(format!(
+                    "{amount} has more fraction digits than the unit's {from_decimals} decimals"
+                )));
+            }
+            decimal_to_u256(value, from_decimals)?
         }
+        // Integers beyond Decimal's range are still exact in U256
+        None => U256::from_str(&amount)
+            .map_err(|_| ServiceError::InvalidAmount(invalid_amount(&amount)))?
+            .checked_mul(pow10(from_decimals as u32)?)
+            .ok_or_else(|| ServiceError::InvalidAmount(format!("{amount} overflows U256")))?,
+    };
 
-        let smallest_unit = decimal_amount * multiplier;
+    Ok((raw, u256_to_decimal(raw, to_decimals)?))
+}
 
-        // Convert to string and parse as U256 (remove decimal point if any)
-        let amount_str = smallest_unit.to_string();
-        let integer_part = amount_str.split('.').next().unwrap_or("0");
+/// Parse a slippage tolerance percentage, which must lie within 0-50
+///
+/// # Arguments
+/// * `slippage` - Percentage as string (e.g., "0.5" for 0.5%)
+///
+/// # Returns
+/// The tolerance as a Decimal percentage
+pub fn parse_slippage(slippage: &str) -> Result<Decimal, String> {
+    let value = Decimal::from_str(slippage.trim()).map_err(|e| format!("Invalid slippage: {e}"))?;
+    if value.is_sign_negative() && !value.is_zero() {
+        return Err(format!("Slippage cannot be negative: {slippage}%"));
+    }
+    if value > Decimal::from(MAX_SLIPPAGE_PERCENT) {
+        return Err(format!(
+            "Slippage {slippage}% exceeds the maximum of {MAX_SLIPPAGE_PERCENT}%"
+        ));
+    }
+    Ok(value)
+}
 
-        U256::from_str(integer_part).map_err(|e| format!("Failed to parse amount: {}", e))
-    } else {
-        // If not a decimal, try parsing directly as U256 (assume already in smallest unit)
-        U256::from_str(amount).map_err(|e| format!("Invalid amount format: {}", e))
+/// Format a Decimal as a fixed-point string (never scientific notation)
+///
+/// # Arguments
+/// * `value` - The value to format
+/// * `precision` - Optional maximum number of fraction digits (rounded half away from zero)
+///
+/// # Returns
+/// Fixed-point string with trailing zeros removed (e.g., "0.000000000000000001", "2000")
+pub fn format_decimal(value: Decimal, precision: Option<u32>) -> String {
+    let value = match precision {
+        Some(dp) => {
+            value.round_dp_with_strategy(dp, rust_decimal::RoundingStrategy::MidpointAwayFromZero)
+        }
+        None => value,
+    };
+
+    let normalized = value.normalize();
+    if normalized.is_zero() {
+        return "0".to_string();
     }
+
+    // Decimal's Display is fixed-point; `{:e}` would be the only scientific form
+    format!("{normalized}")
 }
 
 /// Format balance from smallest unit to human-readable format
@@ -156,8 +431,33 @@ pub fn parse_amount(amount: &str, decimals: u8) -> Result<U256, String> {
 /// * `decimals` - Number of decimal places for the token
 ///
 /// # Returns
-/// Formatted balance as string with trailing zeros removed
+/// Fixed-point balance string with trailing zeros removed. Works on the integer value
+/// directly, so it never uses scientific notation and is exact for any U256.
 pub fn format_balance(balance: U256, decimals: u8) -> String {
+    format_balance_rounded(balance, decimals, None)
+}
+
+/// Format balance like `format_balance`, rounded to at most `max_decimals` fraction digits
+///
+/// # Arguments
+/// * `balance` - Balance in smallest unit (e.g., wei for ETH)
+/// * `decimals` - Number of decimal places for the token
+/// * `max_decimals` - Fraction digits to keep, rounding half up. Full precision when `None`
+///
+/// # Returns
+/// Fixed-point balance string with trailing zeros removed
+pub fn format_balance_rounded(balance: U256, decimals: u8, max_decimals: Option<u8>) -> String {
+    if let Some(max_decimals) = max_decimals.filter(|max| *max < decimals) {
+        let scale = U256::from(10u64).pow(U256::from(decimals - max_decimals));
+        let dropped = balance % scale;
+        let mut kept = balance / scale;
+        // `dropped >= scale / 2` without rounding down odd scales
+        if dropped >= scale - dropped {
+            kept += U256::from(1u64);
+        }
+        return format_balance_rounded(kept, max_decimals, None);
+    }
+
     let divisor = U256::from(10u64).pow(U256::from(decimals));
     let whole = balance / divisor;
     let remainder = balance % divisor;
@@ -215,7 +515,22 @@ pub fn calculate_price_impact(
     };
 
     let impact = (Decimal::from(1) - (price_after / price_before)).abs() * Decimal::from(100);
-    impact.to_string()
+    format_decimal(impact, None)
+}
+
+/// Combine the price impacts of consecutive hops into the impact of the whole route
+///
+/// # Arguments
+/// * `impacts` - Price impact of each hop as a percentage
+///
+/// # Returns
+/// Total price impact as a percentage: each hop keeps (100 - impact)% of the price
+pub fn combine_price_impacts(impacts: &[Decimal]) -> Decimal {
+    let hundred = Decimal::from(100);
+    let retained = impacts.iter().fold(Decimal::ONE, |retained, impact| {
+        retained * (hundred - impact) / hundred
+    });
+    (Decimal::ONE - retained) * hundred
 }
 
 /// Calculate exchange rate between tokens with different decimals
@@ -225,18 +540,20 @@ pub fn calculate_price_impact(
 /// * `amount_out` - Output amount
 /// * `decimals_in` - Decimals for input token
 /// * `decimals_out` - Decimals for output token
+/// * `precision` - Optional maximum number of fraction digits
 ///
 /// # Returns
-/// Exchange rate as a string
+/// Exchange rate as a fixed-point string
 pub fn calculate_exchange_rate(
     amount_in: U256,
     amount_out: U256,
     decimals_in: u8,
     decimals_out: u8,
+    precision: Option<u32>,
 ) -> String {
     if amount_in > U256::ZERO {
         match calculate_price(amount_out, amount_in, decimals_out, decimals_in) {
-            Ok(rate) => rate.to_string(),
+            Ok(rate) => format_decimal(rate, precision),
             Err(_) => "0".to_string(),
         }
     } else {
@@ -244,6 +561,142 @@ pub fn calculate_exchange_rate(
     }
 }
 
+/// Calculate the input that drains a Uniswap V2 pool's output reserve by a given fraction
+///
+/// Uses the constant-product formula with the 0.3% V2 fee: draining the output reserve to
+/// `remaining` of its size requires `reserve_in * (1 / remaining - 1) / 0.997` input tokens.
+///
+/// # Arguments
+/// * `reserve_in` - Input token reserve in the pool
+/// * `reserve_out` - Output token reserve in the pool
+/// * `drain_percentage` - Share of the output reserve removed, in percent (e.g., 50 for 50%)
+///
+/// # Returns
+/// A tuple of (input amount, output amount) in raw units
+pub fn calculate_pool_capacity(
+    reserve_in: U256,
+    reserve_out: U256,
+    drain_percentage: Decimal,
+) -> ServiceResult<(U256, U256)> {
+    if drain_percentage <= Decimal::ZERO || drain_percentage >= Decimal::from(100) {
+        return Err(ServiceError::InvalidAmount(format!(
+            "Drain percentage must be between 0 and 100, got {drain_percentage}"
+        )));
+    }
+
+    // drained = drain_percentage / 100 as an exact ratio drained_num / scale
+    let ratio = drain_percentage / Decimal::from(100);
+    let scale = U256::from(10u64).pow(U256::from(ratio.scale()));
+    let drained_num = U256::from(ratio.mantissa().unsigned_abs());
+    let remaining_num = scale - drained_num;
+
+    let amount_in =
+        reserve_in * drained_num * U256::from(1000u64) / (remaining_num * U256::from(997u64));
+    let amount_out = reserve_out * drained_num / scale;
+
+    Ok((amount_in, amount_out))
+}
+
+/// Approximate USD value locked in a Uniswap V2 WETH pair
+///
+/// Both sides of a V2 pair hold equal value at its price, so the total is twice the WETH side.
+///
+/// # Arguments
+/// * `reserve_weth` - WETH reserve of the pair (raw, 18 decimals)
+/// * `eth_price_usd` - ETH/USD price
+pub fn calculate_pool_liquidity_usd(
+    reserve_weth: U256,
+    eth_price_usd: Decimal,
+) -> ServiceResult<Decimal> {
+    let weth = u256_to_decimal(reserve_weth, 18)?;
+    weth.checked_mul(eth_price_usd)
+        .and_then(|value| value.checked_mul(Decimal::TWO))
+        .ok_or_else(|| ServiceError::InvalidAmount("Pool liquidity is out of range".to_string()))
+}
+
+/// Simulate a swap against a Uniswap V2 pool's reserves
+///
+/// Uses the constant-product formula with the 0.3% V2 fee, matching the router's `getAmountOut`.
+///
+/// # Arguments
+/// * `amount_in` - Input amount (raw)
+/// * `reserve_in` - Input token reserve in the pool
+/// * `reserve_out` - Output token reserve in the pool
+///
+/// # Returns
+/// A tuple of (output amount, input reserve after the swap, output reserve after the swap)
+pub fn simulate_v2_swap(
+    amount_in: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+) -> ServiceResult<(U256, U256, U256)> {
+    if reserve_in.is_zero() || reserve_out.is_zero() {
+        return Err(ServiceError::InsufficientLiquidity(
+            "Pool has no reserves".to_string(),
+        ));
+    }
+
+    let amount_in_with_fee = amount_in * U256::from(997u64);
+    let amount_out =
+        amount_in_with_fee * reserve_out / (reserve_in * U256::from(1000u64) + amount_in_with_fee);
+
+    Ok((amount_out, reserve_in + amount_in, reserve_out - amount_out))
+}
+
+/// Calculate a swap's output when a pending swap on the same V2 pool executes first
+///
+/// # Arguments
+/// * `amount_in` - Input amount of the user's swap (raw)
+/// * `pending_amount_in` - Input amount of the pending swap (raw, in its own input token)
+/// * `pending_same_direction` - Whether the pending swap sells the same token as the user
+/// * `reserve_in` - Reserve of the user's input token before either swap
+/// * `reserve_out` - Reserve of the user's output token before either swap
+///
+/// # Returns
+/// A tuple of (output at current reserves, pending swap output, output after the pending swap)
+pub fn calculate_output_after_pending_swap(
+    amount_in: U256,
+    pending_amount_in: U256,
+    pending_same_direction: bool,
+    reserve_in: U256,
+    reserve_out: U256,
+) -> ServiceResult<(U256, U256, U256)> {
+    let (baseline_out, _, _) = simulate_v2_swap(amount_in, reserve_in, reserve_out)?;
+
+    let (pending_out, reserve_in, reserve_out) = if pending_same_direction {
+        simulate_v2_swap(pending_amount_in, reserve_in, reserve_out)?
+    } else {
+        let (pending_out, reserve_out, reserve_in) =
+            simulate_v2_swap(pending_amount_in, reserve_out, reserve_in)?;
+        (pending_out, reserve_in, reserve_out)
+    };
+
+    let (amount_out, _, _) = simulate_v2_swap(amount_in, reserve_in, reserve_out)?;
+    Ok((baseline_out, pending_out, amount_out))
+}
+
+/// Simulate buying a token on a V2 pool and selling all of it back in the same block
+///
+/// The sell is priced against the reserves left by the buy, so it recovers most of the buy's
+/// price impact and the loss is dominated by the two LP fees.
+///
+/// # Arguments
+/// * `amount_in` - Amount of the input token spent on the buy (raw)
+/// * `reserve_in` - Input token reserve before the buy
+/// * `reserve_out` - Bought token reserve before the buy
+///
+/// # Returns
+/// A tuple of (amount bought, amount of the input token returned by the sell)
+pub fn calculate_round_trip(
+    amount_in: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+) -> ServiceResult<(U256, U256)> {
+    let (bought, reserve_in, reserve_out) = simulate_v2_swap(amount_in, reserve_in, reserve_out)?;
+    let (returned, _, _) = simulate_v2_swap(bought, reserve_out, reserve_in)?;
+    Ok((bought, returned))
+}
+
 /// Calculate minimum output amount with slippage tolerance using precise decimal arithmetic
 ///
 /// # Arguments
@@ -251,27 +704,328 @@ pub fn calculate_exchange_rate(
 /// * `slippage` - Slippage tolerance as a percentage (e.g., 0.5 for 0.5%)
 ///
 /// # Returns
-/// Minimum acceptable output amount
-pub fn calculate_minimum_output(amount_out: U256, slippage: Decimal) -> U256 {
-    // Calculate (100 - slippage) as a percentage
-    let percentage = Decimal::from(100) - slippage;
+/// Minimum acceptable output amount, rounded down to whole smallest units so it never exceeds
+/// `amount_out * (100 - slippage) / 100`
+///
+/// # Errors
+/// Returns `InternalError` unless the minimum lies in `(0, amount_out]`, so a negative
+/// slippage, a slippage of 100% or more, or a quote too small to survive rounding can never
+/// produce a swap with no effective output protection.
+pub fn calculate_minimum_output(amount_out: U256, slippage: Decimal) -> ServiceResult<U256> {
+    // minimum = floor(amount * (100 - slippage) / 100); integer division of the non-negative
+    // product floors, and unlike Decimal it holds any U256 quote
+    let minimum = apply_percentage(amount_out, Decimal::from(100) - slippage)
+        .map_err(|e| ServiceError::InternalError(format!("Invalid minimum output: {e}")))?;
+
+    if minimum.is_zero() || minimum > amount_out {
+        return Err(ServiceError::InternalError(format!(
+            "Minimum output {minimum} is outside (0, {amount_out}] for slippage {slippage}%"
+        )));
+    }
+
+    Ok(minimum)
+}
+
+/// Calculate the slippage tolerance a minimum output corresponds to (the inverse of
+/// `calculate_minimum_output`)
+///
+/// # Arguments
+/// * `amount_out` - Expected output amount of the current quote
+/// * `minimum_output` - Desired minimum output
+///
+/// # Returns
+/// Slippage as a percentage, truncated to 4 decimal places so the minimum derived from it is
+/// never below `minimum_output`. Negative when the minimum exceeds the expected output
+pub fn calculate_implied_slippage(
+    amount_out: U256,
+    minimum_output: U256,
+) -> ServiceResult<Decimal> {
+    if amount_out.is_zero() {
+        return Err(ServiceError::InsufficientLiquidity(
+            "Expected output is zero".to_string(),
+        ));
+    }
+
+    let expected = u256_to_decimal(amount_out, 0)?;
+    let minimum = u256_to_decimal(minimum_output, 0)?;
+    let slippage = (expected - minimum) / expected * Decimal::from(100);
+    Ok(slippage.round_dp_with_strategy(4, rust_decimal::RoundingStrategy::ToZero))
+}
+
+/// Add a safety buffer to a node's gas estimate
+///
+/// # Arguments
+/// * `gas` - Gas units estimated by the node
+/// * `buffer_percent` - Extra gas as a percentage of the estimate (e.g., 20 for 20%)
+///
+/// # Returns
+/// Buffered gas units, rounded up and saturating at `u64::MAX`
+pub fn apply_gas_buffer(gas: u64, buffer_percent: u32) -> u64 {
+    let buffered = (u128::from(gas) * (100 + u128::from(buffer_percent))).div_ceil(100);
+    u64::try_from(buffered).unwrap_or(u64::MAX)
+}
+
+/// Calculate maximum input amount with slippage tolerance for exact-output swaps
+///
+/// # Arguments
+/// * `amount_in` - Estimated input amount
+/// * `slippage` - Slippage tolerance as a percentage (e.g., 0.5 for 0.5%)
+///
+/// # Returns
+/// Maximum input amount the swap may spend, rounded up
+///
+/// # Errors
+/// Returns `InternalError` for a negative slippage, or when the maximum overflows U256.
+pub fn calculate_maximum_input(amount_in: U256, slippage: Decimal) -> ServiceResult<U256> {
+    if slippage.is_sign_negative() && !slippage.is_zero() {
+        return Err(ServiceError::InternalError(format!(
+            "Invalid maximum input: negative slippage {slippage}%"
+        )));
+    }
+
+    // maximum = ceil(amount * (100 + slippage) / 100), computed in U256 so any quote fits and
+    // the bound is never too tight
+    let percentage = Decimal::from(100) + slippage;
+    let mantissa = U256::from(percentage.mantissa().unsigned_abs());
+    let divisor = pow10(percentage.scale() + 2)
+        .map_err(|e| ServiceError::InternalError(format!("Invalid maximum input: {e}")))?;
+
+    amount_in
+        .checked_mul(mantissa)
+        .and_then(|product| product.checked_add(divisor - U256::from(1u64)))
+        .map(|product| product / divisor)
+        .ok_or_else(|| {
+            ServiceError::InternalError(format!(
+                "Maximum input for {amount_in} with {slippage}% slippage overflows U256"
+            ))
+        })
+}
+
+/// Calculate the expected proceeds of a stop-loss sell after execution costs
+///
+/// # Arguments
+/// * `amount` - Amount of tokens sold (human-readable)
+/// * `trigger_price` - Price per token at which the stop executes
+/// * `price_impact` - Expected price impact of the sell as a percentage (e.g., 0.3 for 0.3%)
+/// * `gas_cost` - Gas cost of the sell, denominated in the same currency as `trigger_price`
+///
+/// # Returns
+/// A tuple of (gross proceeds, net proceeds). Net proceeds never go below zero.
+pub fn calculate_stop_loss_proceeds(
+    amount: Decimal,
+    trigger_price: Decimal,
+    price_impact: Decimal,
+    gas_cost: Decimal,
+) -> (Decimal, Decimal) {
+    let gross = amount * trigger_price;
+    let impact_cost = gross * price_impact / Decimal::from(100);
+    let net = (gross - impact_cost - gas_cost).max(Decimal::ZERO);
+
+    (gross, net)
+}
+
+/// Calculate how far a fresh quote has moved from a previously issued one
+///
+/// # Arguments
+/// * `quoted` - Output amount of the earlier quote (raw)
+/// * `current` - Output amount of the fresh quote (raw)
+///
+/// # Returns
+/// Change as a percentage of the earlier quote; negative when the output has dropped
+pub fn calculate_quote_change(quoted: U256, current: U256) -> ServiceResult<Decimal> {
+    if quoted.is_zero() {
+        return Err(ServiceError::InvalidAmount(
+            "Quoted output must be greater than zero".to_string(),
+        ));
+    }
+
+    let quoted = u256_to_decimal(quoted, 0)?;
+    let current = u256_to_decimal(current, 0)?;
+    Ok((current - quoted) / quoted * Decimal::from(100))
+}
+
+/// Calculate how much worse a larger quote's rate is than a smaller one's on the same pool
+///
+/// # Arguments
+/// * `small_in` - Input amount of the small quote (raw)
+/// * `small_out` - Output amount of the small quote (raw)
+/// * `large_in` - Input amount of the large quote (raw)
+/// * `large_out` - Output amount of the large quote (raw)
+///
+/// # Returns
+/// Slippage as a percentage of the small quote's rate; lower means a deeper pool
+pub fn calculate_probe_slippage(
+    small_in: U256,
+    small_out: U256,
+    large_in: U256,
+    large_out: U256,
+) -> ServiceResult<Decimal> {
+    if small_in.is_zero() || small_out.is_zero() || large_in.is_zero() {
+        return Err(ServiceError::InsufficientLiquidity(
+            "Quote returned no output".to_string(),
+        ));
+    }
 
-    // Convert amount to Decimal
-    let amount_decimal = match Decimal::from_str(&amount_out.to_string()) {
-        Ok(d) => d,
-        Err(_) => return U256::ZERO,
+    let small_rate = u256_to_decimal(small_out, 0)? / u256_to_decimal(small_in, 0)?;
+    let large_rate = u256_to_decimal(large_out, 0)? / u256_to_decimal(large_in, 0)?;
+    Ok((Decimal::ONE - large_rate / small_rate) * Decimal::from(100))
+}
+
+/// Calculate the spread between the highest and lowest of several prices of the same asset
+///
+/// # Arguments
+/// * `prices` - Prices to compare
+///
+/// # Returns
+/// A tuple of (lowest, highest, spread as a percentage of the lowest), or `None` when there
+/// are no prices or the lowest is not positive
+pub fn calculate_price_spread(prices: &[Decimal]) -> Option<(Decimal, Decimal, Decimal)> {
+    let min = prices.iter().copied().min()?;
+    let max = prices.iter().copied().max()?;
+    if min <= Decimal::ZERO {
+        return None;
+    }
+    Some((min, max, (max - min) / min * Decimal::from(100)))
+}
+
+/// Calculate the yield of holding a position for a period, net of entry and exit costs
+///
+/// The yield accrues linearly (simple interest) over the holding period.
+///
+/// # Arguments
+/// * `value` - Value of the position
+/// * `apy` - Annual yield as a percentage (e.g., 3.5 for 3.5%)
+/// * `holding_days` - Holding period in days
+/// * `costs` - Total entry and exit costs, denominated in the same currency as `value`
+///
+/// # Returns
+/// A tuple of (gross yield, net yield, net APY as a percentage). The net values go negative
+/// when costs exceed the yield; the net APY is zero when `value` or `holding_days` is zero.
+pub fn calculate_net_yield(
+    value: Decimal,
+    apy: Decimal,
+    holding_days: Decimal,
+    costs: Decimal,
+) -> (Decimal, Decimal, Decimal) {
+    let days_per_year = Decimal::from(365);
+    let gross = value * apy / Decimal::from(100) * holding_days / days_per_year;
+    let net = gross - costs;
+
+    let net_apy = if value.is_zero() || holding_days.is_zero() {
+        Decimal::ZERO
+    } else {
+        net / value * days_per_year / holding_days * Decimal::from(100)
     };
 
-    // Calculate minimum: amount * (100 - slippage) / 100
-    let minimum = amount_decimal * percentage / Decimal::from(100);
+    (gross, net, net_apy)
+}
+
+/// Cost of a transaction at a given gas price
+///
+/// # Arguments
+/// * `gas` - Gas units the transaction consumes
+/// * `gas_price_wei` - Fee per gas in wei
+/// * `eth_usd` - ETH price in USD
+///
+/// # Returns
+/// A tuple of (cost in ETH, cost in USD)
+pub fn calculate_gas_cost(
+    gas: u64,
+    gas_price_wei: U256,
+    eth_usd: Decimal,
+) -> ServiceResult<(Decimal, Decimal)> {
+    let cost_wei = U256::from(gas)
+        .checked_mul(gas_price_wei)
+        .ok_or_else(|| ServiceError::InvalidAmount("Gas cost overflows".to_string()))?;
+    let cost_eth = u256_to_decimal(cost_wei, 18)?;
+    Ok((cost_eth, cost_eth * eth_usd))
+}
+
+/// Sum the traded volume of a pair from its swap events
+///
+/// Each swap counts the amount that entered or left the pool on each side, so a swap of
+/// 10 A for 20 B adds 10 to the A volume and 20 to the B volume.
+///
+/// # Arguments
+/// * `events` - Swap events oriented to the (token A, token B) pair
+///
+/// # Returns
+/// A tuple of (token A volume, token B volume) in raw units
+pub fn sum_swap_volume(events: &[SwapEventLog]) -> (U256, U256) {
+    events
+        .iter()
+        .fold((U256::ZERO, U256::ZERO), |(a, b), event| {
+            (
+                a.saturating_add(event.amount_a_in.saturating_add(event.amount_a_out)),
+                b.saturating_add(event.amount_b_in.saturating_add(event.amount_b_out)),
+            )
+        })
+}
 
-    // Convert back to U256
-    match U256::from_str(&minimum.to_string().split('.').next().unwrap_or("0")) {
-        Ok(result) => result,
-        Err(_) => U256::ZERO,
+/// Whether a failed quote was rejected by the contracts rather than the RPC transport
+///
+/// Reverts (missing pool, no liquidity, invalid path) are deterministic and not worth retrying,
+/// while RPC failures (timeouts, rate limits, connection errors) may succeed on a retry.
+///
+/// # Arguments
+/// * `err` - The repository error returned by the quote
+///
+/// # Returns
+/// `true` if the error is a contract revert, `false` for RPC-level failures
+pub fn is_revert_error(err: &RepositoryError) -> bool {
+    match err {
+        RepositoryError::Reverted { .. } => true,
+        RepositoryError::ContractError(msg) => {
+            let msg = msg.to_lowercase();
+            msg.contains("revert") || msg.contains("no uniswap v2 pair")
+        }
+        _ => false,
     }
 }
 
+/// EIP-712 digest an owner signs to grant an EIP-2612 permit
+///
+/// keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(Permit)), the hash `permit()` recovers
+/// the signer from. The domain separator is read from the token, so name, version, chain id and
+/// verifying contract are whatever the token committed to.
+///
+/// # Arguments
+/// * `domain_separator` - The token's `DOMAIN_SEPARATOR()`
+/// * `owner` - The address granting the allowance
+/// * `spender` - The address allowed to spend
+/// * `value` - The allowance in raw units
+/// * `nonce` - The owner's current `nonces(owner)` on the token
+/// * `deadline` - Unix timestamp after which the permit is rejected
+///
+/// # Returns
+/// The 32-byte digest to sign
+pub fn permit_digest(
+    domain_separator: B256,
+    owner: Address,
+    spender: Address,
+    value: U256,
+    nonce: U256,
+    deadline: U256,
+) -> B256 {
+    let struct_hash = keccak256(
+        (
+            keccak256(PERMIT_TYPE),
+            owner,
+            spender,
+            value,
+            nonce,
+            deadline,
+        )
+            .abi_encode(),
+    );
+
+    let mut message = Vec::with_capacity(66);
+    message.extend_from_slice(b"\x19\x01");
+    message.extend_from_slice(domain_separator.as_slice());
+    message.extend_from_slice(struct_hash.as_slice());
+    keccak256(message)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -292,6 +1046,73 @@ mod tests {
         assert_eq!(usdc.to_string(), "1000.5"); // Decimal removes trailing zeros
     }
 
+    #[test]
+    fn test_sqrt_price_x96_to_price_should_work() {
+        let q96 = U256::from(1u8) << 96;
+
+        // sqrtPriceX96 = 2^96 is a raw price of 1, and doubling the sqrt price quadruples it
+        assert_eq!(sqrt_price_x96_to_price(q96, 18, 18).unwrap(), Decimal::ONE);
+        assert_eq!(
+            sqrt_price_x96_to_price(q96 * U256::from(2u8), 18, 18).unwrap(),
+            Decimal::from(4)
+        );
+
+        // A raw price of 10^12 between a 6- and an 18-decimal token is 1 in whole tokens
+        assert_eq!(
+            sqrt_price_x96_to_price(q96 * U256::from(1_000_000u64), 6, 18).unwrap(),
+            Decimal::ONE
+        );
+
+        // USDC/WETH with ETH at 2000 USDC: one USDC is worth 0.0005 WETH
+        let sqrt_price = U256::from_str("1771595571142957102961017161607260").unwrap();
+        let price = sqrt_price_x96_to_price(sqrt_price, 6, 18).unwrap();
+        assert!((price - Decimal::from_str("0.0005").unwrap()).abs() < Decimal::new(1, 12));
+    }
+
+    #[test]
+    fn test_v3_spot_price_inverts_for_token1() {
+        // USDC (token0) / WETH (token1) pool with ETH at 2000 USDC
+        let sqrt_price = U256::from_str("1771595571142957102961017161607260").unwrap();
+
+        let usdc_in_weth = v3_spot_price(sqrt_price, true, 6, 18).unwrap();
+        let weth_in_usdc = v3_spot_price(sqrt_price, false, 18, 6).unwrap();
+
+        assert!((usdc_in_weth - Decimal::from_str("0.0005").unwrap()).abs() < Decimal::new(1, 12));
+        assert!((weth_in_usdc - Decimal::from(2000)).abs() < Decimal::new(1, 6));
+    }
+
+    #[test]
+    fn test_calculate_v3_price_impact_should_work() {
+        let sqrt_price = U256::from_str("1771595571142957102961017161607260").unwrap();
+        let spot = v3_spot_price(sqrt_price, false, 18, 6).unwrap();
+
+        // Selling 1 WETH for 1990 USDC in the 0.3% pool: 1990 / 0.997 = 1995.99 before the fee,
+        // about 0.2% below the 2000 spot price
+        let impact = calculate_v3_price_impact(
+            spot,
+            U256::from(10u64).pow(U256::from(18)),
+            U256::from(1_990_000_000u64),
+            18,
+            6,
+            3000,
+        )
+        .unwrap();
+        assert_eq!(impact.round_dp(4), Decimal::from_str("0.2006").unwrap());
+
+        // A fill exactly at spot minus the fee has no impact, and better fills never go negative
+        let at_spot = calculate_v3_price_impact(
+            spot,
+            U256::from(10u64).pow(U256::from(18)),
+            U256::from(1_994_000_000u64),
+            18,
+            6,
+            3000,
+        )
+        .unwrap();
+        assert!(at_spot < Decimal::new(1, 6));
+        assert!(at_spot >= Decimal::ZERO);
+    }
+
     #[test]
     fn test_decimal_to_u256_eth_should_work() {
         let eth = Decimal::from_str("1.5").unwrap();
@@ -299,6 +1120,64 @@ mod tests {
         assert_eq!(wei, U256::from_str("1500000000000000000").unwrap());
     }
 
+    #[test]
+    fn test_decimal_scaling_should_handle_large_decimals() {
+        let amount = Decimal::from_str("1.5").unwrap();
+
+        for (decimals, raw) in [
+            (0u8, "1"),
+            (18, "1500000000000000000"),
+            (24, "1500000000000000000000000"),
+            (30, "1500000000000000000000000000000"),
+        ] {
+            let raw = U256::from_str(raw).unwrap();
+            assert_eq!(decimal_to_u256(amount, decimals).unwrap(), raw);
+            assert_eq!(parse_amount("1.5", decimals).unwrap(), raw);
+
+            let expected = if decimals == 0 { "1" } else { "1.5" };
+            assert_eq!(
+                u256_to_decimal(raw, decimals).unwrap().to_string(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_u256_to_decimal_truncates_beyond_decimal_scale() {
+        // 1 + 10^-30 keeps 28 fraction digits
+        let raw = U256::from(10u64).pow(U256::from(30)) + U256::from(1);
+        assert_eq!(u256_to_decimal(raw, 30).unwrap(), Decimal::ONE);
+
+        // Tiny amounts with many decimals stay exact up to 28 digits
+        let raw = U256::from_str("123").unwrap();
+        assert_eq!(
+            u256_to_decimal(raw, 24).unwrap().to_string(),
+            "0.000000000000000000000123"
+        );
+    }
+
+    #[test]
+    fn test_decimal_to_u256_rejects_negative_and_overflow() {
+        assert!(decimal_to_u256(Decimal::from(-1), 18).is_err());
+        assert!(decimal_to_u256(Decimal::ONE, 78).is_err());
+        assert!(parse_amount("1", 255).is_err());
+    }
+
+    #[test]
+    fn test_apply_percentage_with_large_values() {
+        let percentage = Decimal::from_str("0.5").unwrap();
+        for decimals in [0u32, 18, 24, 30] {
+            let value = U256::from(1000u64) * U256::from(10u64).pow(U256::from(decimals));
+            let expected = U256::from(5u64) * U256::from(10u64).pow(U256::from(decimals));
+            assert_eq!(apply_percentage(value, percentage).unwrap(), expected);
+        }
+
+        // Beyond Decimal range, still exact
+        let value = U256::MAX / U256::from(1_000u64);
+        assert_eq!(apply_percentage(value, Decimal::from(100)).unwrap(), value);
+        assert!(apply_percentage(U256::MAX, Decimal::from(200)).is_err());
+    }
+
     #[test]
     fn test_calculate_price_should_work() {
         // Price: 2000 USDC / 1 WETH = 2000 USD per ETH
@@ -331,6 +1210,134 @@ mod tests {
         assert_eq!(amount, U256::from(100500000u64));
     }
 
+    #[test]
+    fn test_parse_amount_accepts_separators_and_scientific_notation() {
+        assert_eq!(parse_amount("1e6", 0).unwrap(), U256::from(1_000_000u64));
+        assert_eq!(parse_amount("1E6", 6).unwrap(), U256::from(10u64.pow(12)));
+        assert_eq!(
+            parse_amount("2.5e-3", 18).unwrap(),
+            U256::from(25 * 10u64.pow(14))
+        );
+        assert_eq!(
+            parse_amount("1_000.5", 6).unwrap(),
+            U256::from(1_000_500_000u64)
+        );
+        assert_eq!(parse_amount(" 1_000 ", 0).unwrap(), U256::from(1000u64));
+        assert_eq!(parse_amount("0.000001", 6).unwrap(), U256::from(1u64));
+        assert!(validate_positive_amount("1e-6").is_ok());
+        assert!(validate_positive_amount("1_000").is_ok());
+    }
+
+    #[test]
+    fn test_convert_amount_between_units() {
+        let (raw, gwei) = convert_amount("1.5", 18, 9).unwrap();
+        assert_eq!(raw, U256::from(15 * 10u64.pow(17)));
+        assert_eq!(gwei, Decimal::from(1_500_000_000));
+
+        let (raw, ether) = convert_amount("1", 0, 18).unwrap();
+        assert_eq!(raw, U256::from(1u64));
+        assert_eq!(ether.to_string(), "0.000000000000000001");
+
+        let (_, wei) = convert_amount("2.5e-3", 18, 0).unwrap();
+        assert_eq!(wei, Decimal::from(2_500_000_000_000_000u64));
+
+        // Finer than a wei, negative or malformed
+        for amount in ["0.5", "-1", "abc"] {
+            assert!(
+                matches!(
+                    convert_amount(amount, 0, 18),
+                    Err(ServiceError::InvalidAmount(_))
+                ),
+                "expected {amount:?} to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_amount_rejects_invalid_input_with_clear_message() {
+        for amount in ["abc", "1_", "_1", "1__0", "1._5", "1e", "e6", "1.2.3", ""] {
+            let error = parse_amount(amount, 18).unwrap_err();
+            assert!(
+                error.starts_with("Invalid amount") && error.contains("\"1e6\""),
+                "unexpected error for {amount:?}: {error}"
+            );
+        }
+        assert!(validate_positive_amount("1e").is_err());
+    }
+
+    #[test]
+    fn test_calculate_implied_slippage_inverts_minimum_output() {
+        let amount_out = U256::from(1_000_000u64);
+        let slippage = calculate_implied_slippage(amount_out, U256::from(995_000u64)).unwrap();
+        assert_eq!(slippage, Decimal::new(5, 1));
+        assert_eq!(
+            calculate_minimum_output(amount_out, slippage).unwrap(),
+            U256::from(995_000u64)
+        );
+
+        // Truncation keeps the derived minimum at or above the requested floor
+        let slippage = calculate_implied_slippage(U256::from(3u64), U256::from(2u64)).unwrap();
+        assert_eq!(slippage, Decimal::new(333_333, 4));
+        assert!(calculate_minimum_output(U256::from(3u64), slippage).unwrap() >= U256::from(2u64));
+
+        let slippage = calculate_implied_slippage(amount_out, U256::from(1_010_000u64)).unwrap();
+        assert_eq!(slippage, Decimal::from(-1));
+
+        assert!(calculate_implied_slippage(U256::ZERO, U256::from(1u64)).is_err());
+    }
+
+    #[test]
+    fn test_validate_positive_amount() {
+        assert!(validate_positive_amount("1.5").is_ok());
+        assert!(validate_positive_amount("0.000001").is_ok());
+        // Raw amounts beyond Decimal's range are still accepted
+        assert!(validate_positive_amount("100000000000000000000000000000000").is_ok());
+
+        assert!(
+            validate_positive_amount("0")
+                .unwrap_err()
+                .contains("greater than zero")
+        );
+        assert!(
+            validate_positive_amount("0.0")
+                .unwrap_err()
+                .contains("greater than zero")
+        );
+        assert!(
+            validate_positive_amount("-1")
+                .unwrap_err()
+                .contains("greater than zero")
+        );
+        assert!(
+            validate_positive_amount("")
+                .unwrap_err()
+                .contains("required")
+        );
+        assert!(
+            validate_positive_amount("abc")
+                .unwrap_err()
+                .contains("Invalid amount")
+        );
+    }
+
+    #[test]
+    fn test_parse_slippage_should_accept_range() {
+        assert_eq!(parse_slippage("0").unwrap(), Decimal::ZERO);
+        assert_eq!(parse_slippage("0.5").unwrap(), Decimal::new(5, 1));
+        assert_eq!(parse_slippage("50").unwrap(), Decimal::from(50));
+    }
+
+    #[test]
+    fn test_parse_slippage_should_reject_out_of_range() {
+        assert!(parse_slippage("-1").unwrap_err().contains("negative"));
+        assert!(parse_slippage("50.1").unwrap_err().contains("maximum"));
+        assert!(
+            parse_slippage("abc")
+                .unwrap_err()
+                .contains("Invalid slippage")
+        );
+    }
+
     #[test]
     fn test_format_balance_eth_should_work() {
         let wei = U256::from_str("1500000000000000000").unwrap();
@@ -376,23 +1383,519 @@ mod tests {
         assert_ne!(impact, "0");
     }
 
+    #[test]
+    fn test_combine_price_impacts_should_compound() {
+        assert_eq!(combine_price_impacts(&[]), Decimal::ZERO);
+        assert_eq!(
+            combine_price_impacts(&[Decimal::from_str("0.5").unwrap()]),
+            Decimal::from_str("0.5").unwrap()
+        );
+        // 10% then 10% keeps 81% of the price
+        assert_eq!(
+            combine_price_impacts(&[Decimal::from(10), Decimal::from(10)]),
+            Decimal::from(19)
+        );
+    }
+
     #[test]
     fn test_calculate_exchange_rate_should_work() {
         // 1 ETH = 2000 USDC
         let amount_in = U256::from_str("1000000000000000000").unwrap(); // 1 ETH (18 decimals)
         let amount_out = U256::from(2000000000u64); // 2000 USDC (6 decimals)
 
-        let rate = super::calculate_exchange_rate(amount_in, amount_out, 18, 6);
+        let rate = super::calculate_exchange_rate(amount_in, amount_out, 18, 6, None);
         assert_eq!(rate, "2000");
     }
 
+    #[test]
+    fn test_calculate_exchange_rate_with_precision_should_round() {
+        // 1 USDC = 0.0005 ETH at 2000 USDC/ETH; 3 digits rounds to 0.001
+        let amount_in = U256::from(1_000_000u64);
+        let amount_out = U256::from_str("500000000000000").unwrap();
+
+        let rate = super::calculate_exchange_rate(amount_in, amount_out, 6, 18, Some(3));
+        assert_eq!(rate, "0.001");
+    }
+
+    #[test]
+    fn test_format_decimal_tiny_value_should_be_fixed_point() {
+        let tiny = Decimal::from_str("0.000000000000000001").unwrap(); // 1e-18
+        assert_eq!(format_decimal(tiny, None), "0.000000000000000001");
+        assert_eq!(format_decimal(tiny, Some(6)), "0");
+    }
+
+    #[test]
+    fn test_format_decimal_huge_value_should_be_fixed_point() {
+        let huge = Decimal::from_str("1000000000000000000000000000").unwrap(); // 1e27
+        let formatted = format_decimal(huge, None);
+        assert_eq!(formatted, "1000000000000000000000000000");
+        assert!(!formatted.contains(['e', 'E']));
+    }
+
+    #[test]
+    fn test_format_decimal_precision_should_trim_trailing_zeros() {
+        let value = Decimal::from_str("1234.500049").unwrap();
+        assert_eq!(format_decimal(value, Some(2)), "1234.5");
+        assert_eq!(format_decimal(value, Some(4)), "1234.5");
+        assert_eq!(format_decimal(value, None), "1234.500049");
+    }
+
+    #[test]
+    fn test_format_balance_rounded_to_six_places() {
+        // 1234.000000000000000001 rounds away its last digit
+        let dust = U256::from_str("1234000000000000000001").unwrap();
+        assert_eq!(format_balance(dust, 18), "1234.000000000000000001");
+        assert_eq!(format_balance_rounded(dust, 18, Some(6)), "1234");
+
+        // 1.2345675 ETH rounds half up, 1.2345674999 down
+        let up = U256::from_str("1234567500000000000").unwrap();
+        let down = U256::from_str("1234567499999999999").unwrap();
+        assert_eq!(format_balance_rounded(up, 18, Some(6)), "1.234568");
+        assert_eq!(format_balance_rounded(down, 18, Some(6)), "1.234567");
+
+        // Rounding can carry into the whole part
+        let carry = U256::from_str("999999999999999999").unwrap();
+        assert_eq!(format_balance_rounded(carry, 18, Some(6)), "1");
+
+        // Tokens with no more decimals than requested keep full precision
+        assert_eq!(
+            format_balance_rounded(U256::from(100500001u64), 6, Some(6)),
+            "100.500001"
+        );
+        assert_eq!(
+            format_balance_rounded(dust, 18, None),
+            format_balance(dust, 18)
+        );
+    }
+
+    #[test]
+    fn test_format_balance_extreme_values_should_be_fixed_point() {
+        // 1e-18 ETH (1 wei)
+        assert_eq!(format_balance(U256::from(1u64), 18), "0.000000000000000001");
+
+        // 1e30 tokens with 18 decimals, beyond Decimal's range
+        let raw = U256::from(10u64).pow(U256::from(48u64));
+        let formatted = format_balance(raw, 18);
+        assert_eq!(formatted, format!("1{}", "0".repeat(30)));
+        assert!(!formatted.contains(['e', 'E']));
+    }
+
     #[test]
     fn test_calculate_minimum_output_should_work() {
         // 1000 tokens with 0.5% slippage = 995 minimum
         let amount_out = U256::from(1000u64);
         let slippage = Decimal::from_str("0.5").unwrap();
 
-        let minimum = super::calculate_minimum_output(amount_out, slippage);
+        let minimum = super::calculate_minimum_output(amount_out, slippage).unwrap();
+        assert_eq!(minimum, U256::from(995u64));
+
+        // Zero slippage keeps the full quote
+        let minimum = calculate_minimum_output(amount_out, Decimal::ZERO).unwrap();
+        assert_eq!(minimum, amount_out);
+    }
+
+    #[test]
+    fn test_calculate_minimum_output_rounds_down() {
+        // 1001 * 99.5% = 995.995, which must floor to 995 rather than round to 996
+        let slippage = Decimal::from_str("0.5").unwrap();
+        let minimum = calculate_minimum_output(U256::from(1001u64), slippage).unwrap();
         assert_eq!(minimum, U256::from(995u64));
+
+        // Never above the exact product for fractional results, matching Decimal's floor()
+        for slippage in ["0.5", "0.3", "1", "2.75", "0.01", "33.3333"] {
+            let slippage = Decimal::from_str(slippage).unwrap();
+            for amount in (1001u64..1101).chain([999_999, 123_456_789, 10u64.pow(18) + 7]) {
+                let Ok(minimum) = calculate_minimum_output(U256::from(amount), slippage) else {
+                    continue;
+                };
+                let exact =
+                    Decimal::from(amount) * (Decimal::from(100) - slippage) / Decimal::from(100);
+                assert!(Decimal::from_str(&minimum.to_string()).unwrap() <= exact);
+                assert_eq!(minimum.to_string(), exact.floor().to_string());
+            }
+        }
+
+        // Quotes beyond Decimal's range still floor exactly
+        let amount_out = U256::from(10u64).pow(U256::from(40u64)) + U256::from(1u64);
+        let minimum = calculate_minimum_output(amount_out, slippage).unwrap();
+        assert_eq!(
+            minimum,
+            (amount_out * U256::from(995u64)) / U256::from(1000u64)
+        );
+    }
+
+    #[test]
+    fn test_calculate_minimum_output_rejects_out_of_range_minimum() {
+        let amount_out = U256::from(1000u64);
+
+        // Negative slippage would push the minimum above the quote
+        assert!(matches!(
+            calculate_minimum_output(amount_out, Decimal::from(-1)),
+            Err(ServiceError::InternalError(_))
+        ));
+        // 100% slippage leaves no protection
+        assert!(matches!(
+            calculate_minimum_output(amount_out, Decimal::from(100)),
+            Err(ServiceError::InternalError(_))
+        ));
+        // A dust quote truncates to zero
+        assert!(matches!(
+            calculate_minimum_output(U256::from(1u64), Decimal::from_str("0.5").unwrap()),
+            Err(ServiceError::InternalError(_))
+        ));
+        assert!(matches!(
+            calculate_minimum_output(U256::ZERO, Decimal::ZERO),
+            Err(ServiceError::InternalError(_))
+        ));
+    }
+
+    #[test]
+    fn test_calculate_pool_capacity_should_work() {
+        // Draining half of a 1000/1000 pool needs 1000 input before fees, 1003 after the 0.3% fee
+        let (amount_in, amount_out) =
+            calculate_pool_capacity(U256::from(1000u64), U256::from(1000u64), Decimal::from(50))
+                .unwrap();
+        assert_eq!(amount_in, U256::from(1003u64));
+        assert_eq!(amount_out, U256::from(500u64));
+
+        // Draining 75% needs 3x the input reserve before fees
+        let (amount_in, amount_out) = calculate_pool_capacity(
+            U256::from(1_000_000u64),
+            U256::from(2_000u64),
+            Decimal::from(75),
+        )
+        .unwrap();
+        assert_eq!(amount_in, U256::from(3_009_027u64));
+        assert_eq!(amount_out, U256::from(1_500u64));
+    }
+
+    #[test]
+    fn test_calculate_pool_capacity_invalid_percentage_should_fail() {
+        let reserve = U256::from(1000u64);
+        assert!(calculate_pool_capacity(reserve, reserve, Decimal::ZERO).is_err());
+        assert!(calculate_pool_capacity(reserve, reserve, Decimal::from(100)).is_err());
+    }
+
+    #[test]
+    fn test_calculate_pool_liquidity_usd_should_work() {
+        // 10 WETH at $3000 on one side, the same value on the other
+        let reserve_weth = U256::from(10u64) * U256::from(10u64).pow(U256::from(18u64));
+        let liquidity = calculate_pool_liquidity_usd(reserve_weth, Decimal::from(3000)).unwrap();
+        assert_eq!(liquidity, Decimal::from(60_000));
+    }
+
+    #[test]
+    fn test_simulate_v2_swap_should_match_get_amount_out() {
+        let reserve = U256::from(1_000_000u64);
+        let (amount_out, reserve_in, reserve_out) =
+            simulate_v2_swap(U256::from(1000u64), reserve, reserve).unwrap();
+        assert_eq!(amount_out, U256::from(996u64));
+        assert_eq!(reserve_in, U256::from(1_001_000u64));
+        assert_eq!(reserve_out, U256::from(999_004u64));
+
+        assert!(simulate_v2_swap(U256::from(1000u64), U256::ZERO, reserve).is_err());
+    }
+
+    #[test]
+    fn test_calculate_output_after_pending_swap_should_work() {
+        let reserve = U256::from(1_000_000u64);
+        let amount_in = U256::from(1000u64);
+        let pending = U256::from(100_000u64);
+
+        // A pending swap in the same direction moves the price against the user
+        let (baseline, pending_out, after) =
+            calculate_output_after_pending_swap(amount_in, pending, true, reserve, reserve)
+                .unwrap();
+        assert_eq!(baseline, U256::from(996u64));
+        assert_eq!(pending_out, U256::from(90_661u64));
+        assert_eq!(after, U256::from(823u64));
+
+        // One in the opposite direction moves it in the user's favour
+        let (_, _, after) =
+            calculate_output_after_pending_swap(amount_in, pending, false, reserve, reserve)
+                .unwrap();
+        assert_eq!(after, U256::from(1204u64));
+    }
+
+    #[test]
+    fn test_calculate_round_trip_should_lose_fees() {
+        let reserve = U256::from(1_000_000u64);
+
+        let (bought, returned) =
+            calculate_round_trip(U256::from(1000u64), reserve, reserve).unwrap();
+        assert_eq!(bought, U256::from(996u64));
+        assert_eq!(returned, U256::from(994u64));
+
+        // The sell recovers the buy's price impact, so a large trade costs no more than the fees
+        let (bought, returned) =
+            calculate_round_trip(U256::from(100_000u64), reserve, reserve).unwrap();
+        assert_eq!(bought, U256::from(90_661u64));
+        assert_eq!(returned, U256::from(99_454u64));
+    }
+
+    #[test]
+    fn test_apply_gas_buffer_should_round_up() {
+        assert_eq!(apply_gas_buffer(150_000, 20), 180_000);
+        assert_eq!(apply_gas_buffer(100_001, 20), 120_002);
+        assert_eq!(apply_gas_buffer(21_000, 0), 21_000);
+        assert_eq!(apply_gas_buffer(u64::MAX, 20), u64::MAX);
+    }
+
+    #[test]
+    fn test_calculate_maximum_input_should_round_up() {
+        // 1000 tokens with 0.5% slippage = 1005 maximum
+        let maximum =
+            calculate_maximum_input(U256::from(1000u64), Decimal::from_str("0.5").unwrap())
+                .unwrap();
+        assert_eq!(maximum, U256::from(1005u64));
+
+        // 999 * 1.005 = 1003.995 -> 1004
+        let maximum =
+            calculate_maximum_input(U256::from(999u64), Decimal::from_str("0.5").unwrap()).unwrap();
+        assert_eq!(maximum, U256::from(1004u64));
+    }
+
+    #[test]
+    fn test_calculate_maximum_input_should_handle_large_amounts() {
+        // 1e30 is beyond Decimal's range but well within U256
+        let amount = U256::from(10u64).pow(U256::from(30u64));
+        let maximum = calculate_maximum_input(amount, Decimal::from_str("0.5").unwrap()).unwrap();
+        assert_eq!(maximum, amount / U256::from(1000u64) * U256::from(1005u64));
+
+        // Overflow is reported instead of saturating to U256::MAX
+        assert!(calculate_maximum_input(U256::MAX, Decimal::from_str("0.5").unwrap()).is_err());
+
+        // Negative slippage is rejected
+        assert!(
+            calculate_maximum_input(U256::from(1000u64), Decimal::from_str("-1").unwrap()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_calculate_stop_loss_proceeds_should_work() {
+        // 10 tokens at $90 with 1% impact and $5 gas
+        let (gross, net) = calculate_stop_loss_proceeds(
+            Decimal::from(10),
+            Decimal::from(90),
+            Decimal::from(1),
+            Decimal::from(5),
+        );
+        assert_eq!(gross, Decimal::from(900));
+        assert_eq!(net, Decimal::from(886)); // 900 - 9 - 5
+    }
+
+    #[test]
+    fn test_calculate_stop_loss_proceeds_never_negative() {
+        // Gas larger than the position should clamp net proceeds at zero
+        let (_, net) = calculate_stop_loss_proceeds(
+            Decimal::from(1),
+            Decimal::from(1),
+            Decimal::ZERO,
+            Decimal::from(10),
+        );
+        assert_eq!(net, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_calculate_quote_change_should_work() {
+        let quoted = U256::from(1_000u64);
+
+        let change = calculate_quote_change(quoted, U256::from(990u64)).unwrap();
+        assert_eq!(change, Decimal::from(-1));
+
+        let change = calculate_quote_change(quoted, U256::from(1_025u64)).unwrap();
+        assert_eq!(change, Decimal::from_str("2.5").unwrap());
+
+        assert!(calculate_quote_change(U256::ZERO, quoted).is_err());
+    }
+
+    #[test]
+    fn test_calculate_net_yield_should_work() {
+        // $10,000 at 4% for 365 days with $100 of costs
+        let (gross, net, net_apy) = calculate_net_yield(
+            Decimal::from(10_000),
+            Decimal::from(4),
+            Decimal::from(365),
+            Decimal::from(100),
+        );
+        assert_eq!(gross, Decimal::from(400));
+        assert_eq!(net, Decimal::from(300));
+        assert_eq!(net_apy, Decimal::from(3));
+    }
+
+    #[test]
+    fn test_calculate_net_yield_short_holding_can_lose() {
+        // 30 days of 3.65% on $1,000 earns $3, less than $5 of costs
+        let (gross, net, net_apy) = calculate_net_yield(
+            Decimal::from(1_000),
+            Decimal::from_str("3.65").unwrap(),
+            Decimal::from(30),
+            Decimal::from(5),
+        );
+        assert_eq!(gross, Decimal::from(3));
+        assert_eq!(net, Decimal::from(-2));
+        assert!(net_apy < Decimal::ZERO);
+
+        let (_, _, net_apy) = calculate_net_yield(
+            Decimal::ZERO,
+            Decimal::from(4),
+            Decimal::from(30),
+            Decimal::ONE,
+        );
+        assert_eq!(net_apy, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_calculate_gas_cost_should_work() {
+        // 150,000 gas at 20 gwei is 0.003 ETH, $9 at $3,000/ETH
+        let (eth, usd) =
+            calculate_gas_cost(150_000, U256::from(20_000_000_000u64), Decimal::from(3_000))
+                .unwrap();
+        assert_eq!(eth, Decimal::from_str("0.003").unwrap());
+        assert_eq!(usd, Decimal::from(9));
+
+        assert!(calculate_gas_cost(u64::MAX, U256::MAX, Decimal::ONE).is_err());
+    }
+
+    #[test]
+    fn test_sum_swap_volume_should_count_both_directions() {
+        let swap = |a_in: u64, b_in: u64, a_out: u64, b_out: u64| SwapEventLog {
+            block_number: None,
+            amount_a_in: U256::from(a_in),
+            amount_b_in: U256::from(b_in),
+            amount_a_out: U256::from(a_out),
+            amount_b_out: U256::from(b_out),
+        };
+        // A -> B, then B -> A
+        let events = vec![swap(10, 0, 0, 20), swap(0, 40, 19, 0)];
+
+        let (volume_a, volume_b) = sum_swap_volume(&events);
+        assert_eq!(volume_a, U256::from(29));
+        assert_eq!(volume_b, U256::from(60));
+    }
+
+    #[test]
+    fn test_sum_swap_volume_empty_should_be_zero() {
+        assert_eq!(sum_swap_volume(&[]), (U256::ZERO, U256::ZERO));
+    }
+
+    #[test]
+    fn test_is_revert_error_should_distinguish_reverts_from_rpc_failures() {
+        assert!(is_revert_error(&RepositoryError::ContractError(
+            "Failed to get amounts out: server returned an error response: execution reverted"
+                .to_string()
+        )));
+        assert!(is_revert_error(&RepositoryError::ContractError(
+            "No Uniswap V2 pair found for tokens 0x1 and 0x2".to_string()
+        )));
+        assert!(is_revert_error(&RepositoryError::Reverted {
+            reason: "UniswapV2Library: INSUFFICIENT_LIQUIDITY".to_string()
+        }));
+        assert!(!is_revert_error(&RepositoryError::ContractError(
+            "Failed to get amounts out: error sending request".to_string()
+        )));
+        assert!(!is_revert_error(&RepositoryError::RpcError(
+            "execution reverted".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_calculate_probe_slippage() {
+        // Same rate for both sizes: no slippage
+        let slippage = calculate_probe_slippage(
+            U256::from(10u64),
+            U256::from(20u64),
+            U256::from(1000u64),
+            U256::from(2000u64),
+        )
+        .unwrap();
+        assert_eq!(slippage, Decimal::ZERO);
+
+        // The large quote gets 1% less per unit
+        let slippage = calculate_probe_slippage(
+            U256::from(10u64),
+            U256::from(20u64),
+            U256::from(1000u64),
+            U256::from(1980u64),
+        )
+        .unwrap();
+        assert_eq!(slippage, Decimal::from(1));
+
+        assert!(
+            calculate_probe_slippage(U256::from(10u64), U256::ZERO, U256::from(1u64), U256::ZERO)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_calculate_price_spread() {
+        let prices = [
+            Decimal::from(2000),
+            Decimal::from(2010),
+            Decimal::from(2020),
+        ];
+        let (min, max, spread) = calculate_price_spread(&prices).unwrap();
+        assert_eq!(min, Decimal::from(2000));
+        assert_eq!(max, Decimal::from(2020));
+        assert_eq!(spread, Decimal::from(1));
+
+        let (_, _, spread) = calculate_price_spread(&[Decimal::from(2000)]).unwrap();
+        assert_eq!(spread, Decimal::ZERO);
+
+        assert!(calculate_price_spread(&[]).is_none());
+        assert!(calculate_price_spread(&[Decimal::ZERO, Decimal::ONE]).is_none());
+    }
+
+    #[test]
+    fn test_permit_digest_matches_eip712_signing_hash() {
+        use alloy::primitives::address;
+        use alloy::sol_types::{SolStruct, eip712_domain};
+
+        alloy::sol! {
+            struct Permit {
+                address owner;
+                address spender;
+                uint256 value;
+                uint256 nonce;
+                uint256 deadline;
+            }
+        }
+
+        let domain = eip712_domain! {
+            name: "USD Coin",
+            version: "2",
+            chain_id: 1,
+            verifying_contract: address!("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+        };
+        let permit = Permit {
+            owner: address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"),
+            spender: address!("0x000000000022D473030F116dDEE9F6B43aC78BA3"),
+            value: U256::from(1_000_000u64),
+            nonce: U256::from(3),
+            deadline: U256::from(1_900_000_000u64),
+        };
+
+        let digest = permit_digest(
+            domain.separator(),
+            permit.owner,
+            permit.spender,
+            permit.value,
+            permit.nonce,
+            permit.deadline,
+        );
+
+        assert_eq!(digest, permit.eip712_signing_hash(&domain));
+        // Any field change produces a different digest
+        assert_ne!(
+            digest,
+            permit_digest(
+                domain.separator(),
+                permit.owner,
+                permit.spender,
+                permit.value,
+                U256::from(4),
+                permit.deadline,
+            )
+        );
     }
 }
@@ -0,0 +1,127 @@
+//! Draining in-flight tool calls before shutdown
+//!
+//! Cancelling the sessions right away can abort a tool call mid-RPC, e.g. an `execute_swap`
+//! that has broadcast its transaction but not yet reported the hash. On shutdown the server
+//! first drains: new SSE streams and new tool calls are refused, while the calls already
+//! running get `server.shutdown_grace_secs` to finish before the sessions are cancelled.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use tokio::sync::Notify;
+
+/// Counts running tool calls and whether the server is draining
+///
+/// Clones share the same state, so every session's service reports to one drain.
+#[derive(Debug, Clone, Default)]
+pub struct Drain {
+    inner: Arc<DrainState>,
+}
+
+#[derive(Debug, Default)]
+struct DrainState {
+    draining: AtomicBool,
+    active: AtomicUsize,
+    idle: Notify,
+}
+
+impl Drain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether shutdown has started and new work is refused
+    pub fn is_draining(&self) -> bool {
+        self.inner.draining.load(Ordering::SeqCst)
+    }
+
+    /// Number of tool calls currently running
+    pub fn active(&self) -> usize {
+        self.inner.active.load(Ordering::SeqCst)
+    }
+
+    /// Registers a tool call until the guard is dropped, or returns None while draining
+    pub fn track(&self) -> Option<CallGuard> {
+        self.inner.active.fetch_add(1, Ordering::SeqCst);
+        let guard = CallGuard {
+            inner: self.inner.clone(),
+        };
+        // Checked after counting, so a call racing with `drain` is either refused or awaited
+        (!self.is_draining()).then_some(guard)
+    }
+
+    /// Refuses new work, then waits up to `grace` for running tool calls to finish
+    ///
+    /// Returns the number of calls still running when the grace period ran out.
+    pub async fn drain(&self, grace: Duration) -> usize {
+        self.inner.draining.store(true, Ordering::SeqCst);
+
+        let idle = async {
+            loop {
+                let notified = self.inner.idle.notified();
+                tokio::pin!(notified);
+                // Registered before checking the count, so a guard dropped in between still wakes us
+                notified.as_mut().enable();
+                if self.active() == 0 {
+                    return;
+                }
+                notified.await;
+            }
+        };
+        let _ = tokio::time::timeout(grace, idle).await;
+        self.active()
+    }
+}
+
+/// Marks a tool call as running until dropped
+#[derive(Debug)]
+pub struct CallGuard {
+    inner: Arc<DrainState>,
+}
+
+impl Drop for CallGuard {
+    fn drop(&mut self) {
+        if self.inner.active.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.inner.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drain_waits_for_running_calls() {
+        let drain = Drain::new();
+        let call = drain.track().expect("not draining yet");
+
+        let finish = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            drop(call);
+        });
+
+        assert_eq!(drain.drain(Duration::from_secs(5)).await, 0);
+        finish.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_drain_gives_up_after_grace_period() {
+        let drain = Drain::new();
+        let _call = drain.track().expect("not draining yet");
+
+        assert_eq!(drain.drain(Duration::from_millis(20)).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_drain_refuses_new_calls() {
+        let drain = Drain::new();
+        assert!(!drain.is_draining());
+
+        assert_eq!(drain.drain(Duration::from_millis(20)).await, 0);
+        assert!(drain.is_draining());
+        assert!(drain.track().is_none());
+        assert_eq!(drain.active(), 0);
+    }
+}
@@ -0,0 +1,114 @@
+//! Prometheus metrics for tool calls and RPC latency
+//!
+//! Metrics are recorded through the `metrics` facade and rendered at `GET /metrics`:
+//!
+//! * `mcp_tool_calls_total{tool}` - every tool invocation
+//! * `mcp_tool_errors_total{tool, error}` - failed invocations by `ServiceError` variant
+//!   (`InvalidParams` for arguments that did not deserialize, `ProtocolError` for other
+//!   MCP-level failures such as an unknown tool)
+//! * `rpc_call_duration_seconds{method, outcome}` - latency of each repository call, with
+//!   `outcome` one of `ok`, `error` or `timeout`
+//! * `rpc_circuit_breaker_state` - 0 closed, 1 half-open, 2 open
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use axum::http::header;
+use axum::response::IntoResponse;
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+
+use crate::repository::{CircuitState, RepoResult, RepositoryError};
+
+/// Histogram buckets for RPC latency, in seconds
+const RPC_LATENCY_BUCKETS: &[f64] = &[0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Handle to the process-wide Prometheus recorder, installing it on first use
+///
+/// If another global recorder is already installed, metrics go there and `/metrics` stays empty.
+pub fn prometheus_handle() -> &'static PrometheusHandle {
+    static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+    HANDLE.get_or_init(|| {
+        let recorder = PrometheusBuilder::new()
+            .set_buckets_for_metric(
+                Matcher::Full("rpc_call_duration_seconds".to_string()),
+                RPC_LATENCY_BUCKETS,
+            )
+            .expect("RPC latency buckets are not empty")
+            .build_recorder();
+        let handle = recorder.handle();
+        if let Err(e) = metrics::set_global_recorder(recorder) {
+            tracing::warn!("Prometheus recorder not installed: {e}");
+        }
+        handle
+    })
+}
+
+/// `GET /metrics` in the Prometheus text exposition format
+pub async fn metrics_handler() -> impl IntoResponse {
+    let handle = prometheus_handle();
+    handle.run_upkeep();
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        handle.render(),
+    )
+}
+
+/// Count a tool invocation, and its error kind when it failed
+pub fn record_tool_call(tool: &str, error: Option<&str>) {
+    metrics::counter!("mcp_tool_calls_total", "tool" => tool.to_string()).increment(1);
+    if let Some(error) = error {
+        metrics::counter!(
+            "mcp_tool_errors_total",
+            "tool" => tool.to_string(),
+            "error" => error.to_string()
+        )
+        .increment(1);
+    }
+}
+
+/// Record a transition of a circuit breaker, e.g. `rpc_circuit_breaker_state` for "RPC"
+pub fn record_circuit_state(name: &str, state: CircuitState) {
+    let value = match state {
+        CircuitState::Closed => 0.0,
+        CircuitState::HalfOpen => 1.0,
+        CircuitState::Open => 2.0,
+    };
+    metrics::gauge!(format!("{}_circuit_breaker_state", name.to_lowercase())).set(value);
+}
+
+/// Record how long a repository call took and how it ended
+pub fn record_rpc_call<T>(method: &'static str, elapsed: Duration, result: &RepoResult<T>) {
+    let outcome = match result {
+        Ok(_) => "ok",
+        Err(RepositoryError::Timeout(_)) => "timeout",
+        Err(_) => "error",
+    };
+    metrics::histogram!("rpc_call_duration_seconds", "method" => method, "outcome" => outcome)
+        .record(elapsed.as_secs_f64());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorded_metrics_are_rendered() {
+        let handle = prometheus_handle();
+
+        record_tool_call("test_tool", Some("TokenNotFound"));
+        record_rpc_call::<()>(
+            "test_method",
+            Duration::from_millis(30),
+            &Err(RepositoryError::Timeout("slow".to_string())),
+        );
+        let rendered = handle.render();
+
+        assert!(rendered.contains(r#"mcp_tool_calls_total{tool="test_tool"} 1"#));
+        assert!(
+            rendered.contains(r#"mcp_tool_errors_total{tool="test_tool",error="TokenNotFound"} 1"#)
+        );
+        assert!(rendered.contains(
+            r#"rpc_call_duration_seconds_bucket{method="test_method",outcome="timeout",le="0.05"} 1"#
+        ));
+    }
+}